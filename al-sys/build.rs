@@ -0,0 +1,69 @@
+#[cfg(feature = "bundled")]
+extern crate cmake;
+
+
+fn main() {
+	bundled::build();
+}
+
+
+#[cfg(feature = "bundled")]
+mod bundled {
+	use std::env;
+	use std::path::PathBuf;
+	use cmake;
+
+
+	/// Build openal-soft from source and link it statically.
+	/// The source tree is taken from `ALSOFT_SOURCE_DIR` if set, or from the `openal-soft` submodule of this crate,
+	/// which is included in the published package.
+	pub fn build() {
+		println!("cargo:rerun-if-env-changed=ALSOFT_SOURCE_DIR");
+		let src = env::var_os("ALSOFT_SOURCE_DIR")
+			.map(PathBuf::from)
+			.unwrap_or_else(|| PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap()).join("openal-soft"));
+		if !src.join("CMakeLists.txt").exists() {
+			panic!("The `bundled` feature requires the openal-soft sources in `{}`; run `git submodule update --init` or set ALSOFT_SOURCE_DIR", src.display());
+		}
+
+		let dst = cmake::Config::new(&src)
+			.define("LIBTYPE", "STATIC")
+			.define("ALSOFT_UTILS", "OFF")
+			.define("ALSOFT_EXAMPLES", "OFF")
+			.define("ALSOFT_TESTS", "OFF")
+			.define("ALSOFT_INSTALL_CONFIG", "OFF")
+			.define("ALSOFT_INSTALL_HRTF_DATA", "OFF")
+			.define("ALSOFT_INSTALL_AMBDEC_PRESETS", "OFF")
+			.define("ALSOFT_INSTALL_EXAMPLES", "OFF")
+			.define("ALSOFT_INSTALL_UTILS", "OFF")
+			.build();
+
+		println!("cargo:rustc-link-search=native={}", dst.join("lib").display());
+		println!("cargo:rustc-link-search=native={}", dst.join("lib64").display());
+
+		let target = env::var("TARGET").unwrap();
+		if target.contains("windows") {
+			println!("cargo:rustc-link-lib=static=OpenAL32");
+			for lib in &["winmm", "ole32", "user32", "shell32", "avrt"] {
+				println!("cargo:rustc-link-lib={}", lib);
+			}
+		} else if target.contains("apple") {
+			println!("cargo:rustc-link-lib=static=openal");
+			println!("cargo:rustc-link-lib=c++");
+			for framework in &["CoreAudio", "AudioToolbox", "AudioUnit", "CoreFoundation"] {
+				println!("cargo:rustc-link-lib=framework={}", framework);
+			}
+		} else {
+			println!("cargo:rustc-link-lib=static=openal");
+			for lib in &["stdc++", "pthread", "dl", "m"] {
+				println!("cargo:rustc-link-lib={}", lib);
+			}
+		}
+	}
+}
+
+
+#[cfg(not(feature = "bundled"))]
+mod bundled {
+	pub fn build() { }
+}
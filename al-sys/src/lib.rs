@@ -1,8 +1,12 @@
-#[cfg(not(target_os = "emscripten"))]
+#[cfg(all(feature = "dynamic", not(any(target_os = "emscripten", feature = "bundled"))))]
 #[macro_use]
 extern crate rental;
 
 
+#[cfg(not(any(target_os = "emscripten", feature = "dynamic", feature = "bundled")))]
+compile_error!("al-sys needs either the `dynamic` or the `bundled` feature");
+
+
 mod alc;
 mod al;
 mod efx;
@@ -14,7 +18,18 @@ pub use efx::*;
 pub use efx_presets::*;
 
 
-#[cfg(not(target_os = "emscripten"))]
+/// Library names tried, in order, by `AlApi::load_default()`.
+pub const DEFAULT_LIBRARY_NAMES: &'static [&'static str] = &[
+	"libopenal.so",
+	"libopenal.so.1",
+	"libopenal.dylib",
+	"OpenAL.framework/OpenAL",
+	"soft_oal.dll",
+	"OpenAL32.dll",
+];
+
+
+#[cfg(all(feature = "dynamic", not(any(target_os = "emscripten", feature = "bundled"))))]
 macro_rules! al_api {
 	{
 		$($sym:ident: ($sym_ty:ty),)*
@@ -48,17 +63,34 @@ macro_rules! al_api {
 			use self::rent::RentSymbols;
 
 
-			pub struct AlApi(rent::RentSymbols);
+			/// A table of entry points supplied directly rather than loaded from a library,
+			/// such as a fake implementation for tests.
+			#[allow(non_snake_case)]
+			#[derive(Copy, Clone)]
+			pub struct AlFns {
+				$(pub $sym: $sym_ty,)*
+			}
+
+
+			enum AlApiInner {
+				Lib(rent::RentSymbols),
+				Fns(AlFns),
+			}
+
+
+			pub struct AlApi(AlApiInner);
 
 
 			impl AlApi {
 				pub fn load_default() -> io::Result<AlApi> {
-					AlApi::from_lib(libloading::Library::new("libopenal.so")
-						.or_else(|_| libloading::Library::new("libopenal.dylib"))
-						.or_else(|_| libloading::Library::new("OpenAL.framework/OpenAL"))
-						.or_else(|_| libloading::Library::new("soft_oal.dll"))
-						.or_else(|_| libloading::Library::new("OpenAL32.dll"))
-					?)
+					let mut err = None;
+					for name in DEFAULT_LIBRARY_NAMES {
+						match libloading::Library::new(name) {
+							Ok(lib) => return AlApi::from_lib(lib),
+							Err(e) => err = Some(e),
+						}
+					}
+					Err(err.unwrap())
 				}
 
 
@@ -71,25 +103,34 @@ macro_rules! al_api {
 					match RentSymbols::try_new(Box::new(lib), |lib| Ok(AlSymbols{
 						$($sym: unsafe { lib.get(stringify!($sym).as_bytes())? },)*
 					})) {
-						Ok(syms) => Ok(AlApi(syms)),
+						Ok(syms) => Ok(AlApi(AlApiInner::Lib(syms))),
 						Err(rental::TryNewError(e, _)) => return Err(e),
 					}
 				}
 
 
+				pub fn from_fns(fns: AlFns) -> AlApi {
+					AlApi(AlApiInner::Fns(fns))
+				}
+
+
 				$(#[allow(non_snake_case)]
 				pub unsafe fn $sym(&self) -> $sym_ty {
-					self.0.rent(|s| *s.$sym)
+					match self.0 {
+						AlApiInner::Lib(ref syms) => syms.rent(|s| *s.$sym),
+						AlApiInner::Fns(ref fns) => fns.$sym,
+					}
 				})*
 			}
 		}
 
-		pub use al_api::AlApi;
+		pub use al_api::{AlApi, AlFns};
 	};
 }
 
 
-#[cfg(target_os = "emscripten")]
+// With a statically linked implementation, the symbols are resolved at link time and the path arguments are ignored.
+#[cfg(any(target_os = "emscripten", feature = "bundled"))]
 macro_rules! al_api {
 	{
 		$($sym:ident: (unsafe extern "C" fn $($sym_sig:tt)*),)*
@@ -110,28 +151,45 @@ macro_rules! al_api {
 			}
 
 
-			pub struct AlApi;
+			/// A table of entry points supplied directly rather than linked statically,
+			/// such as a fake implementation for tests.
+			#[allow(non_snake_case)]
+			#[derive(Copy, Clone)]
+			pub struct AlFns {
+				$(pub $sym: unsafe extern "C" fn $($sym_sig)*,)*
+			}
+
+
+			pub struct AlApi(Option<AlFns>);
 
 
 			impl AlApi {
 				pub fn load_default() -> io::Result<AlApi> {
-					Ok(AlApi)
+					Ok(AlApi(None))
 				}
 
 
 				pub fn load<P: AsRef<Path>>(_path: P) -> io::Result<AlApi> {
-					Ok(AlApi)
+					Ok(AlApi(None))
+				}
+
+
+				pub fn from_fns(fns: AlFns) -> AlApi {
+					AlApi(Some(fns))
 				}
 
 
 				$(#[allow(non_snake_case)]
 				pub unsafe fn $sym(&self) -> unsafe extern "C" fn $($sym_sig)* {
-					al_symbols::$sym
+					match self.0 {
+						Some(ref fns) => fns.$sym,
+						None => al_symbols::$sym,
+					}
 				})*
 			}
 		}
 
-		pub use al_api::AlApi;
+		pub use al_api::{AlApi, AlFns};
 	};
 }
 
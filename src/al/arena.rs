@@ -0,0 +1,151 @@
+use std::iter::Enumerate;
+use std::slice;
+
+
+/// A small copyable index into an `Arena`. The generation tells it apart from handles to earlier entries
+/// that lived in the same slot, so a stale handle finds nothing rather than whatever replaced its entry.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Handle {
+	index: u32,
+	generation: u32,
+}
+
+
+/// Entries stored contiguously in one allocation, with the slots of removed entries reused by later ones.
+pub struct Arena<T> {
+	slots: Vec<Slot<T>>,
+	free: Vec<u32>,
+}
+
+
+struct Slot<T> {
+	generation: u32,
+	value: Option<T>,
+}
+
+
+/// The live entries of an `Arena`, in slot order.
+pub struct Iter<'a, T: 'a>(Enumerate<slice::Iter<'a, Slot<T>>>);
+
+
+/// The live entries of an `Arena`, mutably and in slot order.
+pub struct ValuesMut<'a, T: 'a>(slice::IterMut<'a, Slot<T>>);
+
+
+impl<T> Arena<T> {
+	pub fn new() -> Arena<T> {
+		Arena{slots: Vec::new(), free: Vec::new()}
+	}
+
+
+	pub fn insert(&mut self, value: T) -> Handle {
+		match self.free.pop() {
+			Some(index) => {
+				let slot = &mut self.slots[index as usize];
+				slot.value = Some(value);
+				Handle{index: index, generation: slot.generation}
+			},
+			None => {
+				self.slots.push(Slot{generation: 0, value: Some(value)});
+				Handle{index: (self.slots.len() - 1) as u32, generation: 0}
+			},
+		}
+	}
+
+
+	pub fn remove(&mut self, handle: Handle) -> Option<T> {
+		let slot = match self.slots.get_mut(handle.index as usize) {
+			Some(slot) if slot.generation == handle.generation => slot,
+			_ => return None,
+		};
+		let value = slot.value.take()?;
+		slot.generation = slot.generation.wrapping_add(1);
+		self.free.push(handle.index);
+		Some(value)
+	}
+
+
+	pub fn get_mut(&mut self, handle: Handle) -> Option<&mut T> {
+		match self.slots.get_mut(handle.index as usize) {
+			Some(slot) if slot.generation == handle.generation => slot.value.as_mut(),
+			_ => None,
+		}
+	}
+
+
+	pub fn iter(&self) -> Iter<T> {
+		Iter(self.slots.iter().enumerate())
+	}
+
+
+	pub fn values_mut(&mut self) -> ValuesMut<T> {
+		ValuesMut(self.slots.iter_mut())
+	}
+}
+
+
+impl<'a, T> Iterator for Iter<'a, T> {
+	type Item = (Handle, &'a T);
+
+	fn next(&mut self) -> Option<(Handle, &'a T)> {
+		while let Some((index, slot)) = self.0.next() {
+			if let Some(ref value) = slot.value {
+				return Some((Handle{index: index as u32, generation: slot.generation}, value));
+			}
+		}
+		None
+	}
+}
+
+
+impl<'a, T> Iterator for ValuesMut<'a, T> {
+	type Item = &'a mut T;
+
+	fn next(&mut self) -> Option<&'a mut T> {
+		while let Some(slot) = self.0.next() {
+			if let Some(ref mut value) = slot.value {
+				return Some(value);
+			}
+		}
+		None
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+
+	#[test]
+	fn reused_slot_rejects_stale_handle() {
+		let mut arena = Arena::new();
+		let a = arena.insert("a");
+		let b = arena.insert("b");
+		assert_eq!(arena.remove(a), Some("a"));
+		assert_eq!(arena.remove(a), None);
+
+		let c = arena.insert("c");
+		assert_eq!(c.index, a.index);
+		assert!(c != a);
+		assert_eq!(arena.get_mut(a), None);
+		assert_eq!(arena.remove(a), None);
+		assert_eq!(arena.get_mut(c).map(|v| *v), Some("c"));
+		assert_eq!(arena.get_mut(b).map(|v| *v), Some("b"));
+		assert_eq!(arena.slots.len(), 2);
+	}
+
+
+	#[test]
+	fn iter_skips_removed() {
+		let mut arena = Arena::new();
+		let handles: Vec<_> = (0..5).map(|i| arena.insert(i)).collect();
+		arena.remove(handles[1]);
+		arena.remove(handles[3]);
+		for v in arena.values_mut() {
+			*v *= 10;
+		}
+		let live: Vec<_> = arena.iter().map(|(h, &v)| (h, v)).collect();
+		assert_eq!(live, vec![(handles[0], 0), (handles[2], 20), (handles[4], 40)]);
+	}
+}
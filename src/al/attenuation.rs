@@ -0,0 +1,152 @@
+use ::AltoResult;
+use al::*;
+
+
+/// The parameters that determine how loud a source is heard at a distance from the listener.
+/// This mirrors the gain calculation of the OpenAL specification, so curves can be previewed offline
+/// and sources culled by loudness without disagreeing with what the listener actually hears.
+/// Cone attenuation and EFX air absorption are not included.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Attenuation {
+	pub model: DistanceModel,
+	/// `AL_REFERENCE_DISTANCE`
+	pub reference_distance: f32,
+	/// `AL_ROLLOFF_FACTOR`
+	pub rolloff_factor: f32,
+	/// `AL_MAX_DISTANCE`
+	pub max_distance: f32,
+	/// `AL_GAIN`
+	pub gain: f32,
+	/// `AL_MIN_GAIN`
+	pub min_gain: f32,
+	/// `AL_MAX_GAIN`
+	pub max_gain: f32,
+}
+
+
+impl Attenuation {
+	/// Read the parameters of a source, using the distance model that applies to it:
+	/// its own if the context uses per-source distance models, or the context's otherwise.
+	pub fn of_source<'d: 'c, 'c, S: SourceTrait<'d, 'c> + ?Sized>(src: &S) -> AltoResult<Attenuation> {
+		let ctx = src.context();
+		let _lock = ctx.make_current(true)?;
+		let model = if ctx.using_source_distance_model().unwrap_or(false) { src.distance_model()? } else { ctx.distance_model()? };
+
+		Ok(Attenuation{
+			model: model,
+			reference_distance: src.reference_distance()?,
+			rolloff_factor: src.rolloff_factor()?,
+			max_distance: src.max_distance()?,
+			gain: src.gain()?,
+			min_gain: src.min_gain()?,
+			max_gain: src.max_gain()?,
+		})
+	}
+
+
+	/// The attenuation due to distance alone, from 0 to 1 for the usual parameters.
+	pub fn distance_gain(&self, distance: f32) -> f32 {
+		let (rd, rf, md) = (self.reference_distance, self.rolloff_factor, self.max_distance);
+		let clamped = |d: f32| if rd <= md { d.max(rd).min(md) } else { d };
+
+		let gain = match self.model {
+			DistanceModel::None => 1.0,
+			DistanceModel::Inverse | DistanceModel::InverseClamped => {
+				let d = if self.model == DistanceModel::InverseClamped { clamped(distance) } else { distance };
+				let denom = rd + rf * (d - rd);
+				if denom > 0.0 { rd / denom } else { 1.0 }
+			},
+			DistanceModel::Linear | DistanceModel::LinearClamped => {
+				let d = if self.model == DistanceModel::LinearClamped { clamped(distance) } else { distance.min(md) };
+				if md != rd { 1.0 - rf * (d - rd) / (md - rd) } else { 1.0 }
+			},
+			DistanceModel::Exponent | DistanceModel::ExponentClamped => {
+				let d = if self.model == DistanceModel::ExponentClamped { clamped(distance) } else { distance };
+				if d > 0.0 && rd > 0.0 { (d / rd).powf(-rf) } else { 1.0 }
+			},
+		};
+		gain.max(0.0)
+	}
+
+
+	/// The effective gain at `distance`: the distance attenuation scaled by `gain` and clamped to `[min_gain, max_gain]`.
+	pub fn gain_at(&self, distance: f32) -> f32 {
+		(self.gain * self.distance_gain(distance)).max(self.min_gain).min(self.max_gain)
+	}
+
+
+	/// Whether a source at `distance` would be heard at or above `threshold`, for culling inaudible sources.
+	#[inline]
+	pub fn is_audible_at(&self, distance: f32, threshold: f32) -> bool {
+		self.gain_at(distance) >= threshold
+	}
+}
+
+
+impl Default for Attenuation {
+	/// The initial parameters of a source under the default distance model.
+	fn default() -> Attenuation {
+		Attenuation{
+			model: DistanceModel::InverseClamped,
+			reference_distance: 1.0,
+			rolloff_factor: 1.0,
+			max_distance: ::std::f32::MAX,
+			gain: 1.0,
+			min_gain: 0.0,
+			max_gain: 1.0,
+		}
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+
+	fn with(model: DistanceModel, reference_distance: f32, rolloff_factor: f32, max_distance: f32) -> Attenuation {
+		Attenuation{model: model, reference_distance: reference_distance, rolloff_factor: rolloff_factor, max_distance: max_distance, ..Attenuation::default()}
+	}
+
+
+	#[test]
+	fn distance_models() {
+		let inv = Attenuation::default();
+		assert_eq!(inv.distance_gain(0.5), 1.0);
+		assert_eq!(inv.distance_gain(2.0), 0.5);
+		assert_eq!(inv.distance_gain(4.0), 0.25);
+		// Only the clamped model keeps sources closer than the reference distance from getting louder.
+		assert_eq!(with(DistanceModel::Inverse, 1.0, 1.0, 10.0).distance_gain(0.5), 2.0);
+		assert_eq!(with(DistanceModel::InverseClamped, 1.0, 1.0, 2.0).distance_gain(4.0), 0.5);
+
+		let lin = with(DistanceModel::Linear, 1.0, 1.0, 11.0);
+		assert_eq!(lin.distance_gain(6.0), 0.5);
+		assert_eq!(lin.distance_gain(20.0), 0.0);
+		assert_eq!(with(DistanceModel::LinearClamped, 1.0, 1.0, 11.0).distance_gain(0.0), 1.0);
+
+		assert_eq!(with(DistanceModel::Exponent, 1.0, 2.0, 10.0).distance_gain(2.0), 0.25);
+		assert_eq!(with(DistanceModel::ExponentClamped, 1.0, 2.0, 10.0).distance_gain(0.5), 1.0);
+		assert_eq!(with(DistanceModel::None, 1.0, 1.0, 10.0).distance_gain(100.0), 1.0);
+	}
+
+
+	#[test]
+	fn degenerate_parameters() {
+		// Linear with max_distance equal to the reference distance has nothing to interpolate over.
+		assert_eq!(with(DistanceModel::Linear, 1.0, 1.0, 1.0).distance_gain(5.0), 1.0);
+		assert_eq!(with(DistanceModel::Exponent, 0.0, 1.0, 10.0).distance_gain(5.0), 1.0);
+		// Never negative, even with a rolloff that overshoots.
+		assert_eq!(with(DistanceModel::LinearClamped, 1.0, 4.0, 11.0).distance_gain(6.0), 0.0);
+	}
+
+
+	#[test]
+	fn gain_clamps() {
+		let att = Attenuation{gain: 2.0, min_gain: 0.1, ..Attenuation::default()};
+		assert_eq!(att.gain_at(1.0), 1.0);
+		assert_eq!(att.gain_at(4.0), 0.5);
+		assert_eq!(att.gain_at(1000.0), 0.1);
+		assert!(att.is_audible_at(1000.0, 0.1));
+		assert!(!att.is_audible_at(1000.0, 0.2));
+	}
+}
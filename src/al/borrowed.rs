@@ -0,0 +1,235 @@
+use std::ops::Deref;
+use std::mem;
+use std::ptr;
+use std::fmt;
+use std::thread;
+
+use super::*;
+
+
+/// An object owned by code outside of alto, as returned by `borrow_raw()`.
+/// It can be used like the object itself, but is released rather than deleted when dropped,
+/// leaving its raw handle to whoever lent it. The object can't be moved out, so it is never deleted by alto:
+/// it dereferences immutably, borrowed sources implement `SourceTrait`, and the other methods taking
+/// `&mut self` are forwarded.
+pub struct Borrowed<T: Release>(mem::ManuallyDrop<T>);
+
+
+/// An object that can give up its raw handle without deleting it.
+pub trait Release {
+	/// Stop tracking the object and forget it without deleting it.
+	fn release(self);
+}
+
+
+impl<T: Release> Borrowed<T> {
+	#[doc(hidden)]
+	pub fn new(obj: T) -> Borrowed<T> { Borrowed(mem::ManuallyDrop::new(obj)) }
+}
+
+
+impl<T: Release> Deref for Borrowed<T> {
+	type Target = T;
+
+	fn deref(&self) -> &T { &self.0 }
+}
+
+
+impl<T: Release> Drop for Borrowed<T> {
+	fn drop(&mut self) {
+		// The object is not touched again after being moved out.
+		unsafe { ptr::read(&*self.0) }.release();
+	}
+}
+
+
+impl<T: Release + fmt::Debug> fmt::Debug for Borrowed<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_tuple("Borrowed").field(&*self.0).finish()
+	}
+}
+
+
+impl<'d: 'c, 'c> Release for Buffer<'d, 'c> {
+	fn release(self) { self.into_raw(); }
+}
+
+
+impl<'d: 'c, 'c> Release for StaticSource<'d, 'c> {
+	fn release(self) {
+		// Only an effect slot being dropped on another thread shares the handle, and only for a moment.
+		let mut src = self;
+		while let Err((_, shared)) = src.into_raw() {
+			thread::yield_now();
+			src = shared;
+		}
+	}
+}
+
+
+impl<'d: 'c, 'c> Release for StreamingSource<'d, 'c> {
+	fn release(self) {
+		// Only an effect slot being dropped on another thread shares the handle, and only for a moment.
+		let mut src = self;
+		while let Err((_, shared)) = src.into_raw() {
+			thread::yield_now();
+			src = shared;
+		}
+	}
+}
+
+
+impl<'d: 'c, 'c> Borrowed<Buffer<'d, 'c>> {
+	/// `alBufferData()`
+	pub fn set_data<F: SampleFrame, R: AsBufferData<F>>(&mut self, data: R, freq: i32) -> AltoResult<()> { self.0.set_data(data, freq) }
+	/// `alBufferData()` with a format resolved by `Context::resolve_format()`.
+	pub fn set_data_raw(&mut self, format: ResolvedFormat, data: &[u8], freq: i32) -> AltoResult<()> { self.0.set_data_raw(format, data, freq) }
+}
+
+
+impl<'d: 'c, 'c> Borrowed<StaticSource<'d, 'c>> {
+	/// `alSourcei(AL_BUFFER)`
+	pub fn set_buffer(&mut self, buf: Arc<Buffer<'d, 'c>>) -> AltoResult<()> { self.0.set_buffer(buf) }
+	/// `alSourcei(AL_BUFFER)`
+	pub fn clear_buffer(&mut self) -> AltoResult<()> { self.0.clear_buffer() }
+	/// Play the sample frames from `start` up to `end` of the attached buffer.
+	pub fn play_range(&mut self, start: sys::ALint, end: sys::ALint) -> AltoResult<()> { self.0.play_range(start, end) }
+	/// `alSourcei(AL_LOOPING)`
+	pub fn set_looping(&mut self, value: bool) -> AltoResult<()> { self.0.set_looping(value) }
+}
+
+
+impl<'d: 'c, 'c> Borrowed<StreamingSource<'d, 'c>> {
+	/// `alSourceQueueBuffers()`
+	pub fn queue_buffer(&mut self, buf: Buffer<'d, 'c>) -> Result<(), (AltoError, Buffer<'d, 'c>)> { self.0.queue_buffer(buf) }
+	/// `alSourceUnqueueBuffers()`
+	pub fn unqueue_buffer(&mut self) -> AltoResult<Buffer<'d, 'c>> { self.0.unqueue_buffer() }
+	/// `alSourceQueueBuffers()`
+	pub fn queue_buffers(&mut self, bufs: Vec<Buffer<'d, 'c>>) -> Result<(), (AltoError, Vec<Buffer<'d, 'c>>)> { self.0.queue_buffers(bufs) }
+	/// `alSourceUnqueueBuffers()`
+	pub fn unqueue_buffers(&mut self, n: usize) -> AltoResult<Vec<Buffer<'d, 'c>>> { self.0.unqueue_buffers(n) }
+	/// `alSourceUnqueueBuffers()` for every processed buffer.
+	pub fn unqueue_processed(&mut self) -> AltoResult<Vec<Buffer<'d, 'c>>> { self.0.unqueue_processed() }
+}
+
+
+unsafe impl<'d: 'c, 'c, S: SourceTrait<'d, 'c> + Release> SourceTrait<'d, 'c> for Borrowed<S> {
+	fn context(&self) -> &Context<'d> { self.0.context() }
+	fn as_raw(&self) -> sys::ALuint { self.0.as_raw() }
+
+	fn state(&self) -> AltoResult<SourceState> { self.0.state() }
+	fn play(&mut self) -> AltoResult<()> { self.0.play() }
+	fn pause(&mut self) -> AltoResult<()> { self.0.pause() }
+	fn stop(&mut self) -> AltoResult<()> { self.0.stop() }
+	fn rewind(&mut self) -> AltoResult<()> { self.0.rewind() }
+
+	fn relative(&self) -> AltoResult<bool> { self.0.relative() }
+	fn set_relative(&mut self, value: bool) -> AltoResult<()> { self.0.set_relative(value) }
+
+	fn gain(&self) -> AltoResult<f32> { self.0.gain() }
+	fn set_gain(&mut self, value: f32) -> AltoResult<()> { self.0.set_gain(value) }
+
+	fn min_gain(&self) -> AltoResult<f32> { self.0.min_gain() }
+	fn set_min_gain(&mut self, value: f32) -> AltoResult<()> { self.0.set_min_gain(value) }
+
+	fn max_gain(&self) -> AltoResult<f32> { self.0.max_gain() }
+	fn set_max_gain(&mut self, value: f32) -> AltoResult<()> { self.0.set_max_gain(value) }
+
+	fn reference_distance(&self) -> AltoResult<f32> { self.0.reference_distance() }
+	fn set_reference_distance(&mut self, value: f32) -> AltoResult<()> { self.0.set_reference_distance(value) }
+
+	fn rolloff_factor(&self) -> AltoResult<f32> { self.0.rolloff_factor() }
+	fn set_rolloff_factor(&mut self, value: f32) -> AltoResult<()> { self.0.set_rolloff_factor(value) }
+
+	fn max_distance(&self) -> AltoResult<f32> { self.0.max_distance() }
+	fn set_max_distance(&mut self, value: f32) -> AltoResult<()> { self.0.set_max_distance(value) }
+
+	fn pitch(&self) -> AltoResult<f32> { self.0.pitch() }
+	fn set_pitch(&mut self, value: f32) -> AltoResult<()> { self.0.set_pitch(value) }
+
+	fn position<V: From<[f32; 3]>>(&self) -> AltoResult<V> { self.0.position() }
+	fn set_position<V: Into<[f32; 3]>>(&mut self, value: V) -> AltoResult<()> { self.0.set_position(value) }
+
+	fn velocity<V: From<[f32; 3]>>(&self) -> AltoResult<V> { self.0.velocity() }
+	fn set_velocity<V: Into<[f32; 3]>>(&mut self, value: V) -> AltoResult<()> { self.0.set_velocity(value) }
+	fn auto_velocity(&self) -> Option<f32> { self.0.auto_velocity() }
+	fn set_auto_velocity(&mut self, smoothing: Option<f32>) -> AltoResult<()> { self.0.set_auto_velocity(smoothing) }
+
+	fn direction<V: From<[f32; 3]>>(&self) -> AltoResult<V> { self.0.direction() }
+	fn set_direction<V: Into<[f32; 3]>>(&mut self, value: V) -> AltoResult<()> { self.0.set_direction(value) }
+
+	fn orientation<V: From<[f32; 3]>>(&self) -> AltoResult<(V, V)> { self.0.orientation() }
+	fn set_orientation<V: Into<[f32; 3]>>(&mut self, value: (V, V)) -> AltoResult<()> { self.0.set_orientation(value) }
+
+	fn cone_inner_angle(&self) -> AltoResult<f32> { self.0.cone_inner_angle() }
+	fn set_cone_inner_angle(&mut self, value: f32) -> AltoResult<()> { self.0.set_cone_inner_angle(value) }
+
+	fn cone_outer_angle(&self) -> AltoResult<f32> { self.0.cone_outer_angle() }
+	fn set_cone_outer_angle(&mut self, value: f32) -> AltoResult<()> { self.0.set_cone_outer_angle(value) }
+
+	fn cone_outer_gain(&self) -> AltoResult<f32> { self.0.cone_outer_gain() }
+	fn set_cone_outer_gain(&mut self, value: f32) -> AltoResult<()> { self.0.set_cone_outer_gain(value) }
+	fn set_cone(&mut self, cone: SourceCone) -> AltoResult<()> { self.0.set_cone(cone) }
+
+	fn sec_offset(&self) -> AltoResult<f32> { self.0.sec_offset() }
+	fn set_sec_offset(&mut self, value: f32) -> AltoResult<()> { self.0.set_sec_offset(value) }
+
+	fn sample_offset(&self) -> AltoResult<sys::ALint> { self.0.sample_offset() }
+	fn set_sample_offset(&mut self, value: sys::ALint) -> AltoResult<()> { self.0.set_sample_offset(value) }
+
+	fn byte_offset(&self) -> AltoResult<sys::ALint> { self.0.byte_offset() }
+	fn set_byte_offset(&mut self, value: sys::ALint) -> AltoResult<()> { self.0.set_byte_offset(value) }
+
+	fn soft_sec_offset_latency(&self) -> AltoResult<(f64, f64)> { self.0.soft_sec_offset_latency() }
+
+	fn soft_sample_frac_offset_latency(&self) -> AltoResult<(i32, i32, i64)> { self.0.soft_sample_frac_offset_latency() }
+
+	fn soft_sec_length(&self) -> AltoResult<f32> { self.0.soft_sec_length() }
+
+	fn soft_sample_length(&self) -> AltoResult<sys::ALint> { self.0.soft_sample_length() }
+
+	fn soft_byte_length(&self) -> AltoResult<sys::ALint> { self.0.soft_byte_length() }
+
+	fn soft_direct_channels(&self) -> AltoResult<bool> { self.0.soft_direct_channels() }
+	fn set_soft_direct_channels(&mut self, value: bool) -> AltoResult<()> { self.0.set_soft_direct_channels(value) }
+
+	fn distance_model(&self) -> AltoResult<DistanceModel> { self.0.distance_model() }
+	fn set_distance_model(&mut self, value: DistanceModel) -> AltoResult<()> { self.0.set_distance_model(value) }
+
+	fn state_snapshot(&self) -> AltoResult<SourceProps> { self.0.state_snapshot() }
+	fn apply(&mut self, props: &SourceProps) -> AltoResult<()> { self.0.apply(props) }
+	fn apply_changed(&mut self, old: &SourceProps, new: &SourceProps) -> AltoResult<()> { self.0.apply_changed(old, new) }
+	fn get_many(&self, props: &[SourceProp]) -> AltoResult<PropValues> { self.0.get_many(props) }
+
+	#[cfg(feature = "efx")]
+	fn set_direct_filter<F: FilterTrait<'d, 'c>>(&mut self, value: &F) -> AltoResult<()> { self.0.set_direct_filter(value) }
+	#[cfg(feature = "efx")]
+	fn clear_direct_filter(&mut self) -> AltoResult<()> { self.0.clear_direct_filter() }
+
+	#[cfg(feature = "efx")]
+	fn set_auxiliary_send(&mut self, send: sys::ALint, slot: &mut AuxEffectSlot<'d, 'c>) -> AltoResult<()> { self.0.set_auxiliary_send(send, slot) }
+	#[cfg(feature = "efx")]
+	fn set_auxiliary_send_filter<F: FilterTrait<'d, 'c>>(&mut self, send: sys::ALint, slot: &mut AuxEffectSlot<'d, 'c>, filter: &F) -> AltoResult<()> { self.0.set_auxiliary_send_filter(send, slot, filter) }
+	#[cfg(feature = "efx")]
+	fn clear_auxiliary_send(&mut self, send: sys::ALint) -> AltoResult<()> { self.0.clear_auxiliary_send(send) }
+
+	#[cfg(feature = "efx")]
+	fn air_absorption_factor(&self) -> AltoResult<f32> { self.0.air_absorption_factor() }
+	#[cfg(feature = "efx")]
+	fn set_air_absorption_factor(&mut self, value: f32) -> AltoResult<()> { self.0.set_air_absorption_factor(value) }
+
+	#[cfg(feature = "efx")]
+	fn room_rolloff_factor(&self) -> AltoResult<f32> { self.0.room_rolloff_factor() }
+	#[cfg(feature = "efx")]
+	fn set_room_rolloff_factor(&mut self, value: f32) -> AltoResult<()> { self.0.set_room_rolloff_factor(value) }
+
+	#[cfg(feature = "efx")]
+	fn cone_outer_gainhf(&self) -> AltoResult<f32> { self.0.cone_outer_gainhf() }
+	#[cfg(feature = "efx")]
+	fn set_cone_outer_gainhf(&mut self, value: f32) -> AltoResult<()> { self.0.set_cone_outer_gainhf(value) }
+
+	#[cfg(feature = "efx")]
+	fn direct_filter_gainhf_auto(&self) -> AltoResult<bool> { self.0.direct_filter_gainhf_auto() }
+	#[cfg(feature = "efx")]
+	fn set_direct_filter_gainhf_auto(&mut self, value: bool) -> AltoResult<()> { self.0.set_direct_filter_gainhf_auto(value) }
+}
@@ -0,0 +1,85 @@
+use ::AltoResult;
+use al::*;
+
+
+/// A source whose properties are cached on the Rust side. Getters return the last value set without calling
+/// into OpenAL, and setters only record the new value until `flush()` writes whatever changed since the last flush.
+/// This suits engines that set the position and gain of every source every frame, many of which don't move.
+///
+/// Flushing many sources while holding `Context::suspended()` applies all of their changes together.
+/// Properties changed through `source_mut()` or by the range policy are not seen until `refresh()`.
+pub struct CachedSource<S> {
+	src: S,
+	/// The properties as last read from or written to OpenAL.
+	written: SourceProps,
+	pending: SourceProps,
+}
+
+
+impl<'d: 'c, 'c, S: SourceTrait<'d, 'c>> CachedSource<S> {
+	/// Wrap a source, reading all of its current properties.
+	pub fn new(src: S) -> AltoResult<CachedSource<S>> {
+		let props = src.state_snapshot()?;
+		Ok(CachedSource{src: src, written: props, pending: props})
+	}
+
+
+	#[inline]
+	pub fn source(&self) -> &S { &self.src }
+	/// The wrapped source, such as to play it or change properties that aren't cached.
+	#[inline]
+	pub fn source_mut(&mut self) -> &mut S { &mut self.src }
+	/// Unwrap the source, discarding changes that haven't been flushed.
+	pub fn into_inner(self) -> S { self.src }
+
+
+	/// The cached properties, including changes that haven't been flushed.
+	#[inline]
+	pub fn props(&self) -> &SourceProps { &self.pending }
+	/// Change any of the cached properties, to be written by the next `flush()`.
+	#[inline]
+	pub fn props_mut(&mut self) -> &mut SourceProps { &mut self.pending }
+	/// Whether any property was changed since the last flush.
+	pub fn is_dirty(&self) -> bool { self.pending != self.written }
+
+
+	#[inline]
+	pub fn gain(&self) -> f32 { self.pending.gain }
+	#[inline]
+	pub fn set_gain(&mut self, value: f32) { self.pending.gain = value; }
+	#[inline]
+	pub fn pitch(&self) -> f32 { self.pending.pitch }
+	#[inline]
+	pub fn set_pitch(&mut self, value: f32) { self.pending.pitch = value; }
+	#[inline]
+	pub fn position<V: From<[f32; 3]>>(&self) -> V { self.pending.position.into() }
+	#[inline]
+	pub fn set_position<V: Into<[f32; 3]>>(&mut self, value: V) { self.pending.position = value.into(); }
+	#[inline]
+	pub fn velocity<V: From<[f32; 3]>>(&self) -> V { self.pending.velocity.into() }
+	#[inline]
+	pub fn set_velocity<V: Into<[f32; 3]>>(&mut self, value: V) { self.pending.velocity = value.into(); }
+	#[inline]
+	pub fn direction<V: From<[f32; 3]>>(&self) -> V { self.pending.direction.into() }
+	#[inline]
+	pub fn set_direction<V: Into<[f32; 3]>>(&mut self, value: V) { self.pending.direction = value.into(); }
+
+
+	/// Write the properties changed since the last flush, deferring updates so they apply together.
+	/// If this fails, the changes stay pending and are written again by the next flush.
+	pub fn flush(&mut self) -> AltoResult<()> {
+		if !self.is_dirty() { return Ok(()) }
+
+		self.src.apply_changed(&self.written, &self.pending)?;
+		self.written = self.pending;
+		Ok(())
+	}
+
+
+	/// Discard pending changes and read every property from OpenAL again.
+	pub fn refresh(&mut self) -> AltoResult<()> {
+		self.written = self.src.state_snapshot()?;
+		self.pending = self.written;
+		Ok(())
+	}
+}
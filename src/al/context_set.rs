@@ -0,0 +1,118 @@
+use std::sync::Mutex;
+
+use ::{AltoError, AltoResult, ObjectKind};
+use sys;
+use super::*;
+
+
+/// A group of contexts, each named by its role, such as the game world, the UI, or a cutscene.
+/// Separate contexts keep their listeners, global properties, and sources isolated from one another.
+/// One context can be soloed, pausing everything playing in the others until they are resumed.
+pub struct ContextSet<'d> {
+	ctxs: Vec<(String, Context<'d>)>,
+	/// Sources paused by `solo()`, by the raw handle of their context.
+	paused: Mutex<Vec<(usize, Vec<sys::ALuint>)>>,
+}
+
+
+impl<'d> ContextSet<'d> {
+	pub fn new() -> ContextSet<'d> {
+		ContextSet{ctxs: Vec::new(), paused: Mutex::new(Vec::new())}
+	}
+
+
+	/// Add a context under `name`, returning the context it replaces, if any.
+	pub fn insert(&mut self, name: &str, ctx: Context<'d>) -> Option<Context<'d>> {
+		match self.ctxs.iter().position(|&(ref n, _)| n == name) {
+			Some(i) => {
+				let old = ::std::mem::replace(&mut self.ctxs[i].1, ctx);
+				self.forget_paused(&old);
+				Some(old)
+			},
+			None => {
+				self.ctxs.push((name.to_owned(), ctx));
+				None
+			},
+		}
+	}
+
+
+	/// Take the context named `name` out of the set. Any of its sources paused by `solo()` stay paused.
+	pub fn remove(&mut self, name: &str) -> Option<Context<'d>> {
+		let i = self.ctxs.iter().position(|&(ref n, _)| n == name)?;
+		let (_, ctx) = self.ctxs.remove(i);
+		self.forget_paused(&ctx);
+		Some(ctx)
+	}
+
+
+	#[inline]
+	pub fn get(&self, name: &str) -> Option<&Context<'d>> {
+		self.ctxs.iter().find(|&&(ref n, _)| n == name).map(|&(_, ref ctx)| ctx)
+	}
+
+
+	/// Names of the contexts in the order they were added.
+	pub fn names(&self) -> Vec<&str> {
+		self.ctxs.iter().map(|&(ref n, _)| &**n).collect()
+	}
+
+
+	/// Call `f` with the context named `name` current and locked on this thread,
+	/// so that a sequence of calls on it can't be interleaved with calls from other threads.
+	/// `f` can use the other contexts of the set as well, with the same restrictions as `Context::exclusive()`.
+	/// Fails with `AlcInvalidContext` if there is no such context.
+	pub fn with<R, F: FnOnce(&Context<'d>) -> R>(&self, name: &str, f: F) -> AltoResult<R> {
+		self.get(name).ok_or(AltoError::AlcInvalidContext)?.exclusive(f)
+	}
+
+
+	/// Pause every playing source in all contexts but `name`, such as to play a cutscene on its own.
+	/// Sources paused by an earlier solo are resumed first.
+	/// Fails with `AlcInvalidContext` if there is no such context.
+	pub fn solo(&self, name: &str) -> AltoResult<()> {
+		let solo = self.get(name).ok_or(AltoError::AlcInvalidContext)?;
+		self.resume_all()?;
+
+		let mut paused = self.paused.lock().unwrap_or_else(|e| e.into_inner());
+		for &(_, ref ctx) in self.ctxs.iter().filter(|&&(_, ref ctx)| ctx != solo) {
+			let _lock = ctx.make_current(true)?;
+			let playing: Vec<_> = ctx.sources()?.into_iter().filter(|s| s.state == SourceState::Playing).map(|s| s.raw).collect();
+			if playing.is_empty() { continue }
+
+			unsafe { ctx.api.head().alSourcePausev()(playing.len() as sys::ALsizei, playing.as_ptr()); }
+			ctx.get_error().map_err(|e| e.at("alSourcePausev", ObjectKind::Source, Some(("n", playing.len()).into())))?;
+			paused.push((ctx.as_raw() as usize, playing));
+		}
+		Ok(())
+	}
+
+
+	/// Resume the sources paused by `solo()` that are still paused.
+	pub fn resume_all(&self) -> AltoResult<()> {
+		let mut paused = self.paused.lock().unwrap_or_else(|e| e.into_inner());
+		while let Some((raw, srcs)) = paused.pop() {
+			let ctx = match self.ctxs.iter().find(|&&(_, ref ctx)| ctx.as_raw() as usize == raw) {
+				Some(&(_, ref ctx)) => ctx,
+				None => continue,
+			};
+			let _lock = ctx.make_current(true)?;
+			let resume: Vec<_> = ctx.sources()?.into_iter().filter(|s| s.state == SourceState::Paused && srcs.contains(&s.raw)).map(|s| s.raw).collect();
+			if resume.is_empty() { continue }
+
+			unsafe { ctx.api.head().alSourcePlayv()(resume.len() as sys::ALsizei, resume.as_ptr()); }
+			ctx.get_error().map_err(|e| e.at("alSourcePlayv", ObjectKind::Source, Some(("n", resume.len()).into())))?;
+		}
+		Ok(())
+	}
+
+
+	fn forget_paused(&self, ctx: &Context<'d>) {
+		self.paused.lock().unwrap_or_else(|e| e.into_inner()).retain(|&(raw, _)| raw != ctx.as_raw() as usize);
+	}
+}
+
+
+impl<'d> Default for ContextSet<'d> {
+	fn default() -> ContextSet<'d> { ContextSet::new() }
+}
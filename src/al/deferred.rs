@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::mem;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use ::{AltoResult, ObjectKind};
+use sys;
+use super::*;
+
+
+/// The object a held-back write is made to.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum DeferredTarget {
+	Listener,
+	Source(sys::ALuint),
+}
+
+
+/// A property value held back until the end of a deferred batch.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum DeferredValue {
+	Float(f32),
+	Vector([f32; 3]),
+}
+
+
+/// A held-back write to a property, named for error reports.
+pub type DeferredWrite = (DeferredTarget, sys::ALenum, &'static str, DeferredValue);
+
+
+/// Property writes made while a `SuspendLock` is held, keeping only the last value written to each property.
+/// They are made when the outermost lock is dropped, just before the deferred updates are applied.
+pub struct DeferredWrites(Mutex<Pending>);
+
+
+#[derive(Default)]
+struct Pending {
+	writes: Vec<DeferredWrite>,
+	/// Position of each property's write, so a repeated write replaces it in place.
+	index: HashMap<(DeferredTarget, sys::ALenum), usize>,
+}
+
+
+impl DeferredWrites {
+	pub fn new() -> DeferredWrites {
+		DeferredWrites(Mutex::new(Pending::default()))
+	}
+
+
+	/// Hold back a write while `batches` is nonzero, replacing any earlier write to the same property.
+	/// Returns `false` if no batch is active, in which case the write must be made now.
+	pub fn defer(&self, batches: &AtomicUsize, target: DeferredTarget, param: sys::ALenum, name: &'static str, value: DeferredValue) -> bool {
+		let mut pending = self.0.lock().unwrap_or_else(|e| e.into_inner());
+		if batches.load(Ordering::SeqCst) == 0 { return false }
+
+		let Pending{ref mut writes, ref mut index} = *pending;
+		match index.entry((target, param)) {
+			Entry::Occupied(e) => writes[*e.get()].3 = value,
+			Entry::Vacant(e) => {
+				e.insert(writes.len());
+				writes.push((target, param, name, value));
+			},
+		}
+		true
+	}
+
+
+	/// The value held back for a property, if any.
+	pub fn get(&self, target: DeferredTarget, param: sys::ALenum) -> Option<DeferredValue> {
+		let pending = self.0.lock().unwrap_or_else(|e| e.into_inner());
+		pending.index.get(&(target, param)).map(|&i| pending.writes[i].3)
+	}
+
+
+	/// Forget the writes held back for an object that is about to be deleted.
+	pub fn discard(&self, target: DeferredTarget) {
+		let mut pending = self.0.lock().unwrap_or_else(|e| e.into_inner());
+		if !pending.writes.iter().any(|&(t, _, _, _)| t == target) { return }
+
+		let Pending{ref mut writes, ref mut index} = *pending;
+		writes.retain(|&(t, _, _, _)| t != target);
+		index.clear();
+		index.extend(writes.iter().enumerate().map(|(i, &(t, param, _, _))| ((t, param), i)));
+	}
+
+
+	/// End a batch, returning the held-back writes if it was the outermost one.
+	pub fn end_batch(&self, batches: &AtomicUsize) -> Option<Vec<DeferredWrite>> {
+		let mut pending = self.0.lock().unwrap_or_else(|e| e.into_inner());
+		if batches.fetch_sub(1, Ordering::SeqCst) != 1 { return None }
+
+		pending.index.clear();
+		Some(mem::replace(&mut pending.writes, Vec::new()))
+	}
+}
+
+
+/// Make the writes held back by a batch, returning the first error. The context must be current.
+/// A failed write doesn't stop the ones after it.
+pub fn flush_deferred(ctx: &Context, writes: Vec<DeferredWrite>) -> AltoResult<()> {
+	let head = ctx.api.head();
+	let mut result = Ok(());
+	for (target, param, name, value) in writes {
+		let (function, object) = unsafe {
+			match (target, value) {
+				(DeferredTarget::Listener, DeferredValue::Float(value)) => { head.alListenerf()(param, value); ("alListenerf", ObjectKind::Listener) },
+				(DeferredTarget::Listener, DeferredValue::Vector(value)) => { head.alListenerfv()(param, value.as_ptr()); ("alListenerfv", ObjectKind::Listener) },
+				(DeferredTarget::Source(src), DeferredValue::Float(value)) => { head.alSourcef()(src, param, value); ("alSourcef", ObjectKind::Source) },
+				(DeferredTarget::Source(src), DeferredValue::Vector(value)) => { head.alSourcefv()(src, param, value.as_ptr()); ("alSourcefv", ObjectKind::Source) },
+			}
+		};
+		if let Err(e) = ctx.get_error() {
+			if result.is_ok() {
+				let param = match value {
+					DeferredValue::Float(value) => (name, value).into(),
+					DeferredValue::Vector(value) => (name, value).into(),
+				};
+				result = Err(e.at(function, object, Some(param)));
+			}
+		}
+	}
+	result
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+
+	#[test]
+	fn repeated_writes_coalesce() {
+		let (writes, batches) = (DeferredWrites::new(), AtomicUsize::new(0));
+		let src = DeferredTarget::Source(7);
+		assert!(!writes.defer(&batches, src, sys::AL_GAIN, "AL_GAIN", DeferredValue::Float(0.5)));
+
+		batches.store(2, Ordering::SeqCst);
+		assert!(writes.defer(&batches, src, sys::AL_GAIN, "AL_GAIN", DeferredValue::Float(0.5)));
+		assert!(writes.defer(&batches, DeferredTarget::Listener, sys::AL_POSITION, "AL_POSITION", DeferredValue::Vector([1.0, 2.0, 3.0])));
+		assert!(writes.defer(&batches, src, sys::AL_GAIN, "AL_GAIN", DeferredValue::Float(0.25)));
+		assert_eq!(writes.get(src, sys::AL_GAIN), Some(DeferredValue::Float(0.25)));
+		assert_eq!(writes.get(src, sys::AL_PITCH), None);
+
+		// Only the outermost batch hands the writes back, in the order each property was first written.
+		assert_eq!(writes.end_batch(&batches), None);
+		assert_eq!(writes.end_batch(&batches), Some(vec![
+			(src, sys::AL_GAIN, "AL_GAIN", DeferredValue::Float(0.25)),
+			(DeferredTarget::Listener, sys::AL_POSITION, "AL_POSITION", DeferredValue::Vector([1.0, 2.0, 3.0])),
+		]));
+		assert_eq!(writes.get(src, sys::AL_GAIN), None);
+	}
+
+
+	#[test]
+	fn discard_reindexes() {
+		let (writes, batches) = (DeferredWrites::new(), AtomicUsize::new(1));
+		let (a, b) = (DeferredTarget::Source(1), DeferredTarget::Source(2));
+		writes.defer(&batches, a, sys::AL_GAIN, "AL_GAIN", DeferredValue::Float(0.1));
+		writes.defer(&batches, b, sys::AL_GAIN, "AL_GAIN", DeferredValue::Float(0.2));
+		writes.defer(&batches, a, sys::AL_PITCH, "AL_PITCH", DeferredValue::Float(1.5));
+		writes.discard(a);
+		assert_eq!(writes.get(a, sys::AL_GAIN), None);
+
+		// A later write to the remaining source still replaces its entry.
+		writes.defer(&batches, b, sys::AL_GAIN, "AL_GAIN", DeferredValue::Float(0.3));
+		assert_eq!(writes.end_batch(&batches), Some(vec![(b, sys::AL_GAIN, "AL_GAIN", DeferredValue::Float(0.3))]));
+	}
+}
@@ -0,0 +1,370 @@
+use std::cell::UnsafeCell;
+#[cfg(feature = "capture")]
+use std::ffi::CString;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicUsize, Ordering};
+
+use ::{AltoError, AltoResult, ObjectKind};
+use ::catch_panic;
+use sys;
+use super::*;
+
+
+/// Events held between polls before new ones are discarded.
+const CAPACITY: usize = 1024;
+
+
+/// A notification from a context, its device, or the streams playing in it, as returned by `Context::poll_events()`.
+#[derive(Clone, PartialEq, Debug)]
+pub enum AudioEvent {
+	/// A source changed state on its own, such as by reaching the end of its buffer.
+	/// Requires `AL_SOFT_events`
+	SourceStateChanged{source: sys::ALuint, state: SourceState},
+	/// Buffers queued on a streaming source finished playing.
+	/// Requires `AL_SOFT_events`
+	BuffersCompleted{source: sys::ALuint, count: u32},
+	/// The device was disconnected, such as by being unplugged. Only reported once.
+	Disconnected,
+	/// A `Stream` played through all of its buffers before they could be refilled.
+	Underrun(sys::ALuint),
+	/// A `Stream` played everything its decoder produced.
+	StreamEnded(sys::ALuint),
+	#[cfg(feature = "capture")]
+	/// A capture device has at least as many sample frames available as the watermark set with
+	/// `CaptureDevice::set_watermark()`. Reported again once the available frames drop below the watermark.
+	CaptureWatermark{specifier: CString, available: usize},
+	/// This many events were discarded since the last poll because too many were waiting.
+	Overflowed(usize),
+}
+
+
+/// Events waiting to be polled, shared with the `AL_SOFT_events` callback.
+/// The callback never waits on a lock, so it never waits on a thread that is polling.
+pub struct EventQueue {
+	ring: EventRing,
+	/// Buffer completed, source state changed, and disconnected event types, or 0 until the callback is installed.
+	types: [AtomicI32; 3],
+	disconnected: AtomicBool,
+	overflowed: AtomicUsize,
+	/// A panic caught in the callback, to be reported by the next poll.
+	panicked: Mutex<Option<AltoError>>,
+}
+
+
+/// A bounded queue that any number of threads can push to and pop from without locking.
+/// Each slot's sequence number tells whether it is ready to be written or read for a given position.
+struct EventRing {
+	slots: Box<[Slot]>,
+	push_pos: AtomicUsize,
+	pop_pos: AtomicUsize,
+}
+
+
+struct Slot {
+	seq: AtomicUsize,
+	event: UnsafeCell<MaybeUninit<AudioEvent>>,
+}
+
+
+unsafe impl Send for EventRing { }
+unsafe impl Sync for EventRing { }
+
+
+impl EventRing {
+	fn new(capacity: usize) -> EventRing {
+		let capacity = capacity.next_power_of_two();
+		EventRing{
+			slots: (0..capacity).map(|i| Slot{seq: AtomicUsize::new(i), event: UnsafeCell::new(MaybeUninit::uninit())}).collect(),
+			push_pos: AtomicUsize::new(0),
+			pop_pos: AtomicUsize::new(0),
+		}
+	}
+
+
+	fn push(&self, event: AudioEvent) -> Result<(), AudioEvent> {
+		let mask = self.slots.len() - 1;
+		let mut pos = self.push_pos.load(Ordering::Relaxed);
+		loop {
+			let slot = &self.slots[pos & mask];
+			let seq = slot.seq.load(Ordering::Acquire);
+			if seq == pos {
+				match self.push_pos.compare_exchange_weak(pos, pos.wrapping_add(1), Ordering::Relaxed, Ordering::Relaxed) {
+					Ok(_) => {
+						unsafe { (*slot.event.get()).as_mut_ptr().write(event); }
+						slot.seq.store(pos.wrapping_add(1), Ordering::Release);
+						return Ok(());
+					},
+					Err(cur) => pos = cur,
+				}
+			} else if (seq.wrapping_sub(pos) as isize) < 0 {
+				return Err(event);
+			} else {
+				pos = self.push_pos.load(Ordering::Relaxed);
+			}
+		}
+	}
+
+
+	fn pop(&self) -> Option<AudioEvent> {
+		let mask = self.slots.len() - 1;
+		let mut pos = self.pop_pos.load(Ordering::Relaxed);
+		loop {
+			let slot = &self.slots[pos & mask];
+			let seq = slot.seq.load(Ordering::Acquire);
+			let ready = pos.wrapping_add(1);
+			if seq == ready {
+				match self.pop_pos.compare_exchange_weak(pos, ready, Ordering::Relaxed, Ordering::Relaxed) {
+					Ok(_) => {
+						let event = unsafe { (*slot.event.get()).as_ptr().read() };
+						slot.seq.store(pos.wrapping_add(self.slots.len()), Ordering::Release);
+						return Some(event);
+					},
+					Err(cur) => pos = cur,
+				}
+			} else if (seq.wrapping_sub(ready) as isize) < 0 {
+				return None;
+			} else {
+				pos = self.pop_pos.load(Ordering::Relaxed);
+			}
+		}
+	}
+}
+
+
+impl Drop for EventRing {
+	fn drop(&mut self) {
+		while let Some(_) = self.pop() { }
+	}
+}
+
+
+impl EventQueue {
+	pub fn new() -> EventQueue {
+		EventQueue{
+			ring: EventRing::new(CAPACITY),
+			types: [AtomicI32::new(0), AtomicI32::new(0), AtomicI32::new(0)],
+			disconnected: AtomicBool::new(false),
+			overflowed: AtomicUsize::new(0),
+			panicked: Mutex::new(None),
+		}
+	}
+
+
+	fn push(&self, event: AudioEvent) {
+		if let Err(_) = self.ring.push(event) {
+			self.overflowed.fetch_add(1, Ordering::Relaxed);
+		}
+	}
+
+
+	fn installed(&self) -> bool {
+		self.types[0].load(Ordering::Acquire) != 0
+	}
+
+
+	fn set_types(&self, types: [sys::ALenum; 3]) {
+		for (t, &ty) in self.types.iter().zip(&types).rev() {
+			t.store(ty, Ordering::Release);
+		}
+	}
+
+
+	fn disconnected(&self) {
+		if !self.disconnected.swap(true, Ordering::AcqRel) {
+			self.push(AudioEvent::Disconnected);
+		}
+	}
+}
+
+
+/// Called by the implementation from its own thread.
+unsafe extern "C" fn event_callback(ty: sys::ALenum, object: sys::ALuint, param: sys::ALuint, _: sys::ALsizei, _: *const sys::ALchar, user: *mut sys::ALvoid) {
+	let events = &*(user as *const EventQueue);
+	if !events.installed() { return }
+
+	let handled = catch_panic(|| {
+		if ty == events.types[0].load(Ordering::Acquire) {
+			events.push(AudioEvent::BuffersCompleted{source: object, count: param});
+		} else if ty == events.types[1].load(Ordering::Acquire) {
+			events.push(AudioEvent::SourceStateChanged{source: object, state: SourceState::from_raw(param as sys::ALint)});
+		} else if ty == events.types[2].load(Ordering::Acquire) {
+			events.disconnected();
+		}
+	});
+	if let Err(e) = handled {
+		if let Ok(mut panicked) = events.panicked.try_lock() {
+			*panicked = Some(e);
+		}
+	}
+}
+
+
+/// Remove the `AL_SOFT_events` callback, if it was installed, before the event queue is freed.
+pub fn remove_event_callback(ctx: &Context) {
+	if !ctx.events.installed() { return }
+	if let (Ok(ase), Ok(_lock)) = (ctx.exts.AL_SOFT_events(), ctx.make_current_unchecked(true)) {
+		if let Ok(cb) = ase.alEventCallbackSOFT {
+			unsafe { cb(None, ptr::null_mut()); }
+		}
+	}
+}
+
+
+impl<'d> Context<'d> {
+	/// Take every event that happened since the last call, oldest first. This merges `AL_SOFT_events`
+	/// notifications, disconnection of the device, events from `Stream`s, and capture watermarks into one
+	/// polling point. `AL_SOFT_events` notifications are only collected from the first call onward,
+	/// and are skipped if the extension is unavailable.
+	///
+	/// Once `Disconnected` is returned, the device is marked as disconnected, and this and every other
+	/// call on it fails with `AlcDeviceDisconnected`. See `Device::reopen()` for recovering.
+	///
+	/// Fails once with `CallbackPanicked` if handling an `AL_SOFT_events` notification panicked.
+	/// Events queued before then are returned by the next call.
+	pub fn poll_events(&self) -> AltoResult<Vec<AudioEvent>> {
+		self.install_event_callback()?;
+		if let Some(e) = self.events.panicked.lock().unwrap_or_else(|e| e.into_inner()).take() {
+			return Err(e);
+		}
+		if let Ok(false) = self.dev.connected() {
+			self.events.disconnected();
+		}
+		if self.events.disconnected.load(Ordering::Acquire) {
+			self.dev.set_disconnected();
+		}
+
+		#[cfg(feature = "capture")]
+		{
+			for (spec, available) in self.dev.alto().capture_watermarks_reached() {
+				self.events.push(AudioEvent::CaptureWatermark{specifier: spec, available: available});
+			}
+		}
+		let mut events: Vec<_> = iter::from_fn(|| self.events.ring.pop()).collect();
+		match self.events.overflowed.swap(0, Ordering::Relaxed) {
+			0 => (),
+			n => events.push(AudioEvent::Overflowed(n)),
+		}
+		Ok(events)
+	}
+
+
+	/// Add an event to be returned by the next `poll_events()`.
+	#[doc(hidden)]
+	pub fn push_event(&self, event: AudioEvent) {
+		self.events.push(event);
+	}
+
+
+	fn install_event_callback(&self) -> AltoResult<()> {
+		let ase = match self.exts.AL_SOFT_events() {
+			Ok(ase) => ase,
+			Err(_) => return Ok(()),
+		};
+		// Serializes installation across threads.
+		let _lock = self.make_current(true)?;
+		if self.events.installed() { return Ok(()) }
+
+		let types = [ase.AL_EVENT_TYPE_BUFFER_COMPLETED_SOFT?, ase.AL_EVENT_TYPE_SOURCE_STATE_CHANGED_SOFT?, ase.AL_EVENT_TYPE_DISCONNECTED_SOFT?];
+		let (callback, control) = (ase.alEventCallbackSOFT?, ase.alEventControlSOFT?);
+		self.events.set_types(types);
+		unsafe {
+			callback(Some(event_callback), &*self.events as *const EventQueue as *mut sys::ALvoid);
+			control(types.len() as sys::ALsizei, types.as_ptr(), sys::AL_TRUE);
+		}
+		if let Err(e) = self.get_error() {
+			self.events.set_types([0; 3]);
+			return Err(e.at("alEventControlSOFT", ObjectKind::Context, None));
+		}
+		Ok(())
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+	use std::thread;
+
+	use super::*;
+
+
+	#[test]
+	fn ring_order_and_overflow() {
+		let ring = EventRing::new(3);
+		assert_eq!(ring.slots.len(), 4);
+		for i in 0..4 {
+			ring.push(AudioEvent::Underrun(i)).unwrap();
+		}
+		assert_eq!(ring.push(AudioEvent::Underrun(4)), Err(AudioEvent::Underrun(4)));
+
+		assert_eq!(ring.pop(), Some(AudioEvent::Underrun(0)));
+		ring.push(AudioEvent::Underrun(5)).unwrap();
+		let rest: Vec<_> = (0..4).filter_map(|_| ring.pop()).collect();
+		assert_eq!(rest, vec![AudioEvent::Underrun(1), AudioEvent::Underrun(2), AudioEvent::Underrun(3), AudioEvent::Underrun(5)]);
+		assert_eq!(ring.pop(), None);
+	}
+
+
+	#[test]
+	fn ring_wraps_around() {
+		let ring = EventRing::new(4);
+		for i in 0..100 {
+			ring.push(AudioEvent::StreamEnded(i)).unwrap();
+			ring.push(AudioEvent::Underrun(i)).unwrap();
+			assert_eq!(ring.pop(), Some(AudioEvent::StreamEnded(i)));
+			assert_eq!(ring.pop(), Some(AudioEvent::Underrun(i)));
+		}
+		assert_eq!(ring.pop(), None);
+	}
+
+
+	#[test]
+	fn queue_counts_overflow() {
+		let queue = EventQueue::new();
+		for i in 0..CAPACITY as u32 + 10 {
+			queue.push(AudioEvent::Underrun(i));
+		}
+		assert_eq!(queue.overflowed.load(Ordering::Relaxed), 10);
+		// A disconnection is only reported once, even when it doesn't fit.
+		queue.disconnected();
+		queue.disconnected();
+		assert_eq!(queue.overflowed.load(Ordering::Relaxed), 11);
+	}
+
+
+	#[test]
+	fn ring_concurrent_push_pop() {
+		let ring = Arc::new(EventRing::new(64));
+		let producers: Vec<_> = (0..4).map(|t| {
+			let ring = ring.clone();
+			thread::spawn(move || {
+				for i in 0..1000 {
+					let mut event = AudioEvent::BuffersCompleted{source: t, count: i};
+					while let Err(e) = ring.push(event) {
+						event = e;
+						thread::yield_now();
+					}
+				}
+			})
+		}).collect();
+
+		// Each producer's events come out in the order it pushed them.
+		let mut next = [0; 4];
+		while next.iter().any(|&n| n < 1000) {
+			match ring.pop() {
+				Some(AudioEvent::BuffersCompleted{source, count}) => {
+					assert_eq!(count, next[source as usize]);
+					next[source as usize] += 1;
+				},
+				Some(e) => panic!("unexpected {:?}", e),
+				None => thread::yield_now(),
+			}
+		}
+		for p in producers {
+			p.join().unwrap();
+		}
+		assert_eq!(ring.pop(), None);
+	}
+}
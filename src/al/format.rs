@@ -1,10 +1,13 @@
 use std::ops::{Deref, DerefMut};
 use std::slice;
+use std::fmt;
 
 use ::{AltoError, AltoResult};
 use sys;
+#[cfg(feature = "loopback")]
 use alc::*;
 use al::*;
+#[cfg(feature = "loopback")]
 use ext;
 
 
@@ -12,15 +15,22 @@ use ext;
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum Format {
 	Standard(StandardFormat),
+	#[cfg(feature = "exotic-formats")]
 	ExtALaw(ExtALawFormat),
 	ExtBFormat(ExtBFormat),
+	#[cfg(feature = "exotic-formats")]
 	ExtDouble(ExtDoubleFormat),
 	ExtFloat32(ExtFloat32Format),
+	#[cfg(feature = "exotic-formats")]
 	ExtIma4(ExtIma4Format),
 	ExtMcFormats(ExtMcFormat),
+	#[cfg(feature = "exotic-formats")]
 	ExtMuLaw(ExtMuLawFormat),
+	#[cfg(feature = "exotic-formats")]
 	ExtMuLawBFormat(ExtMuLawBFormat),
+	#[cfg(feature = "exotic-formats")]
 	ExtMuLawMcFormats(ExtMuLawMcFormat),
+	#[cfg(feature = "exotic-formats")]
 	SoftMsadpcm(SoftMsadpcmFormat),
 }
 
@@ -39,6 +49,7 @@ pub enum StandardFormat {
 }
 
 
+#[cfg(feature = "exotic-formats")]
 /// Formats provided by `AL_EXT_ALAW`.
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum ExtALawFormat {
@@ -67,6 +78,7 @@ pub enum ExtBFormat {
 }
 
 
+#[cfg(feature = "exotic-formats")]
 /// Formats provided by `AL_EXT_double`.
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum ExtDoubleFormat {
@@ -87,6 +99,7 @@ pub enum ExtFloat32Format {
 }
 
 
+#[cfg(feature = "exotic-formats")]
 /// Formats provided by `AL_EXT_IMA4`.
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum ExtIma4Format {
@@ -133,6 +146,7 @@ pub enum ExtMcFormat {
 }
 
 
+#[cfg(feature = "exotic-formats")]
 /// Formats provided by `AL_EXT_MULAW`.
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum ExtMuLawFormat {
@@ -143,6 +157,7 @@ pub enum ExtMuLawFormat {
 }
 
 
+#[cfg(feature = "exotic-formats")]
 /// Formats provided by `AL_EXT_MULAW_BFORMAT`.
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum ExtMuLawBFormat {
@@ -153,6 +168,7 @@ pub enum ExtMuLawBFormat {
 }
 
 
+#[cfg(feature = "exotic-formats")]
 /// Formats provided by `AL_EXT_MULAW_MCFORMATS`.
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum ExtMuLawMcFormat {
@@ -173,6 +189,7 @@ pub enum ExtMuLawMcFormat {
 }
 
 
+#[cfg(feature = "exotic-formats")]
 /// Formats provided by `AL_SOFT_MSADPCM`.
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum SoftMsadpcmFormat {
@@ -183,6 +200,29 @@ pub enum SoftMsadpcmFormat {
 }
 
 
+/// A format whose OpenAL enum was looked up ahead of time with `Context::resolve_format()`, so uploads
+/// through `Buffer::set_data_raw()` need no extension checks and can't fail with `AlExtensionNotPresent`.
+/// Only valid for the context that resolved it.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ResolvedFormat {
+	format: Format,
+	raw: sys::ALint,
+	ctx: usize,
+}
+
+
+/// How the samples of a format are stored.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Encoding {
+	/// Samples of this many bytes each.
+	Bytes(usize),
+	#[cfg(feature = "exotic-formats")]
+	Ima4,
+	#[cfg(feature = "exotic-formats")]
+	Msadpcm,
+}
+
+
 /// Implemented by structs that represent a frame of audio samples.
 /// A sample frame is a grouping of audio samples from each channel
 /// of an output format.
@@ -202,6 +242,13 @@ pub unsafe trait SampleFrame: Copy {
 pub unsafe trait StandardFrame: SampleFrame { }
 
 
+/// Sample types that can make up a `Frame`.
+pub unsafe trait FrameSample: Copy {
+	/// The format of an interleaved frame of this sample type, indexed by channel count, or `None` if there is none.
+	const FORMATS: [Option<Format>; 9];
+}
+
+
 pub trait AsBufferData<F: SampleFrame> {
 	fn as_buffer_data(&self) -> &[F];
 }
@@ -212,9 +259,11 @@ pub trait AsBufferDataMut<F: SampleFrame> {
 }
 
 
+#[cfg(feature = "exotic-formats")]
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 #[repr(C)]
 pub struct ALawSample(pub u8);
+#[cfg(feature = "exotic-formats")]
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 #[repr(C)]
 pub struct MuLawSample(pub u8);
@@ -310,22 +359,176 @@ pub struct BFormat3D<S: Copy> {
 }
 
 
+/// An interleaved frame of `N` channels, for code that is generic over the channel count.
+/// Has the same layout and format as the named frame of the speaker layout with that many channels:
+/// `Mono`, `Stereo`, `McQuad`, `Mc51Chn`, `Mc61Chn`, or `Mc71Chn`, in that channel order.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[repr(transparent)]
+pub struct Frame<S: Copy, const N: usize>(pub [S; N]);
+
+
 impl Format {
+	/// The OpenAL enum for this format. Extension formats need a context, and are read from the values it
+	/// resolved through `alGetEnumValue()` when the extension was loaded, so this makes no OpenAL calls.
 	pub fn into_raw<'d>(self, ctx: Option<&Context<'d>>) -> AltoResult<sys::ALint> {
 		match self {
 			Format::Standard(f) => Ok(f.into_raw()),
+			#[cfg(feature = "exotic-formats")]
 			Format::ExtALaw(f) => f.into_raw(ctx),
 			Format::ExtBFormat(f) => f.into_raw(ctx),
+			#[cfg(feature = "exotic-formats")]
 			Format::ExtDouble(f) => f.into_raw(ctx),
 			Format::ExtFloat32(f) => f.into_raw(ctx),
+			#[cfg(feature = "exotic-formats")]
 			Format::ExtIma4(f) => f.into_raw(ctx),
 			Format::ExtMcFormats(f) => f.into_raw(ctx),
+			#[cfg(feature = "exotic-formats")]
 			Format::ExtMuLaw(f) => f.into_raw(ctx),
+			#[cfg(feature = "exotic-formats")]
 			Format::ExtMuLawBFormat(f) => f.into_raw(ctx),
+			#[cfg(feature = "exotic-formats")]
 			Format::ExtMuLawMcFormats(f) => f.into_raw(ctx),
+			#[cfg(feature = "exotic-formats")]
 			Format::SoftMsadpcm(f) => f.into_raw(ctx),
 		}
 	}
+
+
+	/// Number of channels in a sample frame.
+	pub fn channels(&self) -> usize { self.layout().0 }
+
+
+	/// Bytes in a sample frame, or `None` for ADPCM formats, which are stored in blocks of several frames instead.
+	pub fn frame_size(&self) -> Option<usize> {
+		match self.layout() {
+			(channels, Encoding::Bytes(bytes)) => Some(channels * bytes),
+			#[cfg(feature = "exotic-formats")]
+			_ => None,
+		}
+	}
+
+
+	/// Sample frames in the smallest unit data of this format can be uploaded in. This is 1, or for ADPCM formats
+	/// `alignment`, where 0 selects the default of 65 frames for IMA4 and 64 for MSADPCM.
+	/// `alignment` is ignored for other formats. Returns `None` if it isn't a valid block alignment for the format.
+	#[cfg_attr(not(feature = "exotic-formats"), allow(unused_variables))]
+	pub fn block_frames(&self, alignment: usize) -> Option<usize> {
+		match self.layout().1 {
+			Encoding::Bytes(_) => Some(1),
+			// The first sample is held in the header, and the rest are packed in groups of 8.
+			#[cfg(feature = "exotic-formats")]
+			Encoding::Ima4 => match if alignment == 0 { 65 } else { alignment } {
+				frames if (frames - 1) % 8 == 0 => Some(frames),
+				_ => None,
+			},
+			// The first two samples are held in the header, and the rest are packed in pairs.
+			#[cfg(feature = "exotic-formats")]
+			Encoding::Msadpcm => match if alignment == 0 { 64 } else { alignment } {
+				frames if frames >= 2 && frames % 2 == 0 => Some(frames),
+				_ => None,
+			},
+		}
+	}
+
+
+	/// Bytes in the smallest unit data of this format can be uploaded in, a block of `block_frames(alignment)` frames.
+	#[cfg_attr(not(feature = "exotic-formats"), allow(unused_variables))]
+	pub fn block_size(&self, alignment: usize) -> Option<usize> {
+		let (channels, encoding) = self.layout();
+		let frames = self.block_frames(alignment)?;
+		Some(channels * match encoding {
+			Encoding::Bytes(bytes) => bytes,
+			// A 4 byte header per channel, then 4 bits for each remaining sample.
+			#[cfg(feature = "exotic-formats")]
+			Encoding::Ima4 => 4 + (frames - 1) / 2,
+			// A 7 byte header per channel, then 4 bits for each remaining sample.
+			#[cfg(feature = "exotic-formats")]
+			Encoding::Msadpcm => 7 + (frames - 2) / 2,
+		})
+	}
+
+
+	/// Number of channels, and how each sample is stored.
+	fn layout(&self) -> (usize, Encoding) {
+		match *self {
+			Format::Standard(f) => match f {
+				StandardFormat::MonoU8 => (1, Encoding::Bytes(1)),
+				StandardFormat::MonoI16 => (1, Encoding::Bytes(2)),
+				StandardFormat::StereoU8 => (2, Encoding::Bytes(1)),
+				StandardFormat::StereoI16 => (2, Encoding::Bytes(2)),
+			},
+			#[cfg(feature = "exotic-formats")]
+			Format::ExtALaw(f) => (if f == ExtALawFormat::Mono { 1 } else { 2 }, Encoding::Bytes(1)),
+			Format::ExtBFormat(f) => match f {
+				ExtBFormat::B2DU8 => (3, Encoding::Bytes(1)),
+				ExtBFormat::B2DI16 => (3, Encoding::Bytes(2)),
+				ExtBFormat::B2DF32 => (3, Encoding::Bytes(4)),
+				ExtBFormat::B3DU8 => (4, Encoding::Bytes(1)),
+				ExtBFormat::B3DI16 => (4, Encoding::Bytes(2)),
+				ExtBFormat::B3DF32 => (4, Encoding::Bytes(4)),
+			},
+			#[cfg(feature = "exotic-formats")]
+			Format::ExtDouble(f) => (if f == ExtDoubleFormat::Mono { 1 } else { 2 }, Encoding::Bytes(8)),
+			Format::ExtFloat32(f) => (if f == ExtFloat32Format::Mono { 1 } else { 2 }, Encoding::Bytes(4)),
+			#[cfg(feature = "exotic-formats")]
+			Format::ExtIma4(f) => (if f == ExtIma4Format::Mono { 1 } else { 2 }, Encoding::Ima4),
+			Format::ExtMcFormats(f) => match f {
+				ExtMcFormat::QuadU8 => (4, Encoding::Bytes(1)),
+				ExtMcFormat::QuadI16 => (4, Encoding::Bytes(2)),
+				ExtMcFormat::QuadF32 => (4, Encoding::Bytes(4)),
+				ExtMcFormat::RearU8 => (2, Encoding::Bytes(1)),
+				ExtMcFormat::RearI16 => (2, Encoding::Bytes(2)),
+				ExtMcFormat::RearF32 => (2, Encoding::Bytes(4)),
+				ExtMcFormat::Mc51ChnU8 => (6, Encoding::Bytes(1)),
+				ExtMcFormat::Mc51ChnI16 => (6, Encoding::Bytes(2)),
+				ExtMcFormat::Mc51ChnF32 => (6, Encoding::Bytes(4)),
+				ExtMcFormat::Mc61ChnU8 => (7, Encoding::Bytes(1)),
+				ExtMcFormat::Mc61ChnI16 => (7, Encoding::Bytes(2)),
+				ExtMcFormat::Mc61ChnF32 => (7, Encoding::Bytes(4)),
+				ExtMcFormat::Mc71ChnU8 => (8, Encoding::Bytes(1)),
+				ExtMcFormat::Mc71ChnI16 => (8, Encoding::Bytes(2)),
+				ExtMcFormat::Mc71ChnF32 => (8, Encoding::Bytes(4)),
+			},
+			#[cfg(feature = "exotic-formats")]
+			Format::ExtMuLaw(f) => (if f == ExtMuLawFormat::Mono { 1 } else { 2 }, Encoding::Bytes(1)),
+			#[cfg(feature = "exotic-formats")]
+			Format::ExtMuLawBFormat(f) => (if f == ExtMuLawBFormat::B2D { 3 } else { 4 }, Encoding::Bytes(1)),
+			#[cfg(feature = "exotic-formats")]
+			Format::ExtMuLawMcFormats(f) => (match f {
+				ExtMuLawMcFormat::Mono => 1,
+				ExtMuLawMcFormat::Stereo | ExtMuLawMcFormat::Rear => 2,
+				ExtMuLawMcFormat::Quad => 4,
+				ExtMuLawMcFormat::Mc51Chn => 6,
+				ExtMuLawMcFormat::Mc61Chn => 7,
+				ExtMuLawMcFormat::Mc71Chn => 8,
+			}, Encoding::Bytes(1)),
+			#[cfg(feature = "exotic-formats")]
+			Format::SoftMsadpcm(f) => (if f == SoftMsadpcmFormat::Mono { 1 } else { 2 }, Encoding::Msadpcm),
+		}
+	}
+}
+
+
+impl ResolvedFormat {
+	/// The format that was resolved.
+	#[inline]
+	pub fn format(&self) -> Format { self.format }
+	/// The OpenAL enum for the format.
+	#[inline]
+	pub fn as_raw(&self) -> sys::ALint { self.raw }
+
+
+	#[doc(hidden)]
+	pub fn belongs_to(&self, ctx: &Context) -> bool { self.ctx == ctx.as_raw() as usize }
+}
+
+
+impl<'d> Context<'d> {
+	/// Check that `format` is supported by this context and look up its OpenAL enum once,
+	/// failing with `AlExtensionNotPresent` now rather than on some later upload.
+	pub fn resolve_format(&self, format: Format) -> AltoResult<ResolvedFormat> {
+		Ok(ResolvedFormat{format: format, raw: format.into_raw(Some(self))?, ctx: self.as_raw() as usize})
+	}
 }
 
 
@@ -341,6 +544,7 @@ impl StandardFormat {
 }
 
 
+#[cfg(feature = "exotic-formats")]
 impl ExtALawFormat {
 	pub fn into_raw<'d>(self, ctx: Option<&Context<'d>>) -> AltoResult<sys::ALint> {
 		ctx.ok_or(AltoError::AlExtensionNotPresent).and_then(|ctx| match self {
@@ -365,6 +569,7 @@ impl ExtBFormat {
 }
 
 
+#[cfg(feature = "exotic-formats")]
 impl ExtDoubleFormat {
 	pub fn into_raw<'d>(self, ctx: Option<&Context<'d>>) -> AltoResult<sys::ALint> {
 		ctx.ok_or(AltoError::AlExtensionNotPresent).and_then(|ctx| match self {
@@ -385,6 +590,7 @@ impl ExtFloat32Format {
 }
 
 
+#[cfg(feature = "exotic-formats")]
 impl ExtIma4Format {
 	pub fn into_raw<'d>(self, ctx: Option<&Context<'d>>) -> AltoResult<sys::ALint> {
 		ctx.ok_or(AltoError::AlExtensionNotPresent).and_then(|ctx| match self {
@@ -418,6 +624,7 @@ impl ExtMcFormat {
 }
 
 
+#[cfg(feature = "exotic-formats")]
 impl ExtMuLawFormat {
 	pub fn into_raw<'d>(self, ctx: Option<&Context<'d>>) -> AltoResult<sys::ALint> {
 		ctx.ok_or(AltoError::AlExtensionNotPresent).and_then(|ctx| match self {
@@ -428,6 +635,7 @@ impl ExtMuLawFormat {
 }
 
 
+#[cfg(feature = "exotic-formats")]
 impl ExtMuLawBFormat {
 	pub fn into_raw<'d>(self, ctx: Option<&Context<'d>>) -> AltoResult<sys::ALint> {
 		ctx.ok_or(AltoError::AlExtensionNotPresent).and_then(|ctx| match self {
@@ -438,6 +646,7 @@ impl ExtMuLawBFormat {
 }
 
 
+#[cfg(feature = "exotic-formats")]
 impl ExtMuLawMcFormat {
 	pub fn into_raw<'d>(self, ctx: Option<&Context<'d>>) -> AltoResult<sys::ALint> {
 		ctx.ok_or(AltoError::AlExtensionNotPresent).and_then(|ctx| match self {
@@ -453,6 +662,7 @@ impl ExtMuLawMcFormat {
 }
 
 
+#[cfg(feature = "exotic-formats")]
 impl SoftMsadpcmFormat {
 	pub fn into_raw<'d>(self, ctx: Option<&Context<'d>>) -> AltoResult<sys::ALint> {
 		ctx.ok_or(AltoError::AlExtensionNotPresent).and_then(|ctx| match self {
@@ -463,6 +673,174 @@ impl SoftMsadpcmFormat {
 }
 
 
+/// A short description such as `16-bit stereo`, for display in UIs and logs.
+impl fmt::Display for Format {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			Format::Standard(ref inner) => fmt::Display::fmt(inner, f),
+			#[cfg(feature = "exotic-formats")]
+			Format::ExtALaw(ref inner) => fmt::Display::fmt(inner, f),
+			Format::ExtBFormat(ref inner) => fmt::Display::fmt(inner, f),
+			#[cfg(feature = "exotic-formats")]
+			Format::ExtDouble(ref inner) => fmt::Display::fmt(inner, f),
+			Format::ExtFloat32(ref inner) => fmt::Display::fmt(inner, f),
+			#[cfg(feature = "exotic-formats")]
+			Format::ExtIma4(ref inner) => fmt::Display::fmt(inner, f),
+			Format::ExtMcFormats(ref inner) => fmt::Display::fmt(inner, f),
+			#[cfg(feature = "exotic-formats")]
+			Format::ExtMuLaw(ref inner) => fmt::Display::fmt(inner, f),
+			#[cfg(feature = "exotic-formats")]
+			Format::ExtMuLawBFormat(ref inner) => fmt::Display::fmt(inner, f),
+			#[cfg(feature = "exotic-formats")]
+			Format::ExtMuLawMcFormats(ref inner) => fmt::Display::fmt(inner, f),
+			#[cfg(feature = "exotic-formats")]
+			Format::SoftMsadpcm(ref inner) => fmt::Display::fmt(inner, f),
+		}
+	}
+}
+
+
+impl fmt::Display for StandardFormat {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.pad(match *self {
+			StandardFormat::MonoU8 => "8-bit mono",
+			StandardFormat::MonoI16 => "16-bit mono",
+			StandardFormat::StereoU8 => "8-bit stereo",
+			StandardFormat::StereoI16 => "16-bit stereo",
+		})
+	}
+}
+
+
+#[cfg(feature = "exotic-formats")]
+impl fmt::Display for ExtALawFormat {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.pad(match *self {
+			ExtALawFormat::Mono => "A-law mono",
+			ExtALawFormat::Stereo => "A-law stereo",
+		})
+	}
+}
+
+
+impl fmt::Display for ExtBFormat {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.pad(match *self {
+			ExtBFormat::B2DU8 => "8-bit 2D B-format",
+			ExtBFormat::B2DI16 => "16-bit 2D B-format",
+			ExtBFormat::B2DF32 => "32-bit float 2D B-format",
+			ExtBFormat::B3DU8 => "8-bit 3D B-format",
+			ExtBFormat::B3DI16 => "16-bit 3D B-format",
+			ExtBFormat::B3DF32 => "32-bit float 3D B-format",
+		})
+	}
+}
+
+
+#[cfg(feature = "exotic-formats")]
+impl fmt::Display for ExtDoubleFormat {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.pad(match *self {
+			ExtDoubleFormat::Mono => "64-bit float mono",
+			ExtDoubleFormat::Stereo => "64-bit float stereo",
+		})
+	}
+}
+
+
+impl fmt::Display for ExtFloat32Format {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.pad(match *self {
+			ExtFloat32Format::Mono => "32-bit float mono",
+			ExtFloat32Format::Stereo => "32-bit float stereo",
+		})
+	}
+}
+
+
+#[cfg(feature = "exotic-formats")]
+impl fmt::Display for ExtIma4Format {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.pad(match *self {
+			ExtIma4Format::Mono => "IMA4 ADPCM mono",
+			ExtIma4Format::Stereo => "IMA4 ADPCM stereo",
+		})
+	}
+}
+
+
+impl fmt::Display for ExtMcFormat {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.pad(match *self {
+			ExtMcFormat::QuadU8 => "8-bit quad",
+			ExtMcFormat::QuadI16 => "16-bit quad",
+			ExtMcFormat::QuadF32 => "32-bit float quad",
+			ExtMcFormat::RearU8 => "8-bit rear",
+			ExtMcFormat::RearI16 => "16-bit rear",
+			ExtMcFormat::RearF32 => "32-bit float rear",
+			ExtMcFormat::Mc51ChnU8 => "8-bit 5.1",
+			ExtMcFormat::Mc51ChnI16 => "16-bit 5.1",
+			ExtMcFormat::Mc51ChnF32 => "32-bit float 5.1",
+			ExtMcFormat::Mc61ChnU8 => "8-bit 6.1",
+			ExtMcFormat::Mc61ChnI16 => "16-bit 6.1",
+			ExtMcFormat::Mc61ChnF32 => "32-bit float 6.1",
+			ExtMcFormat::Mc71ChnU8 => "8-bit 7.1",
+			ExtMcFormat::Mc71ChnI16 => "16-bit 7.1",
+			ExtMcFormat::Mc71ChnF32 => "32-bit float 7.1",
+		})
+	}
+}
+
+
+#[cfg(feature = "exotic-formats")]
+impl fmt::Display for ExtMuLawFormat {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.pad(match *self {
+			ExtMuLawFormat::Mono => "mu-law mono",
+			ExtMuLawFormat::Stereo => "mu-law stereo",
+		})
+	}
+}
+
+
+#[cfg(feature = "exotic-formats")]
+impl fmt::Display for ExtMuLawBFormat {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.pad(match *self {
+			ExtMuLawBFormat::B2D => "mu-law 2D B-format",
+			ExtMuLawBFormat::B3D => "mu-law 3D B-format",
+		})
+	}
+}
+
+
+#[cfg(feature = "exotic-formats")]
+impl fmt::Display for ExtMuLawMcFormat {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.pad(match *self {
+			ExtMuLawMcFormat::Mono => "mu-law mono",
+			ExtMuLawMcFormat::Stereo => "mu-law stereo",
+			ExtMuLawMcFormat::Quad => "mu-law quad",
+			ExtMuLawMcFormat::Rear => "mu-law rear",
+			ExtMuLawMcFormat::Mc51Chn => "mu-law 5.1",
+			ExtMuLawMcFormat::Mc61Chn => "mu-law 6.1",
+			ExtMuLawMcFormat::Mc71Chn => "mu-law 7.1",
+		})
+	}
+}
+
+
+#[cfg(feature = "exotic-formats")]
+impl fmt::Display for SoftMsadpcmFormat {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.pad(match *self {
+			SoftMsadpcmFormat::Mono => "MS ADPCM mono",
+			SoftMsadpcmFormat::Stereo => "MS ADPCM stereo",
+		})
+	}
+}
+
+
 unsafe impl SampleFrame for Mono<u8> {
 	type Sample = u8;
 
@@ -481,18 +859,21 @@ unsafe impl SampleFrame for Mono<f32> {
 	#[inline(always)] fn len() -> usize { 1 }
 	#[inline(always)] fn format() -> Format { Format::ExtFloat32(ExtFloat32Format::Mono) }
 }
+#[cfg(feature = "exotic-formats")]
 unsafe impl SampleFrame for Mono<f64> {
 	type Sample = f64;
 
 	#[inline(always)] fn len() -> usize { 1 }
 	#[inline(always)] fn format() -> Format { Format::ExtDouble(ExtDoubleFormat::Mono) }
 }
+#[cfg(feature = "exotic-formats")]
 unsafe impl SampleFrame for Mono<ALawSample> {
 	type Sample = ALawSample;
 
 	#[inline(always)] fn len() -> usize { 1 }
 	#[inline(always)] fn format() -> Format { Format::ExtALaw(ExtALawFormat::Mono) }
 }
+#[cfg(feature = "exotic-formats")]
 unsafe impl SampleFrame for Mono<MuLawSample> {
 	type Sample = MuLawSample;
 
@@ -519,18 +900,21 @@ unsafe impl SampleFrame for Stereo<f32> {
 	#[inline(always)] fn len() -> usize { 2 }
 	#[inline(always)] fn format() -> Format { Format::ExtFloat32(ExtFloat32Format::Stereo) }
 }
+#[cfg(feature = "exotic-formats")]
 unsafe impl SampleFrame for Stereo<f64> {
 	type Sample = f64;
 
 	#[inline(always)] fn len() -> usize { 2 }
 	#[inline(always)] fn format() -> Format { Format::ExtDouble(ExtDoubleFormat::Stereo) }
 }
+#[cfg(feature = "exotic-formats")]
 unsafe impl SampleFrame for Stereo<ALawSample> {
 	type Sample = ALawSample;
 
 	#[inline(always)] fn len() -> usize { 2 }
 	#[inline(always)] fn format() -> Format { Format::ExtALaw(ExtALawFormat::Stereo) }
 }
+#[cfg(feature = "exotic-formats")]
 unsafe impl SampleFrame for Stereo<MuLawSample> {
 	type Sample = MuLawSample;
 
@@ -557,6 +941,7 @@ unsafe impl SampleFrame for McRear<f32> {
 	#[inline(always)] fn len() -> usize { 1 }
 	#[inline(always)] fn format() -> Format { Format::ExtMcFormats(ExtMcFormat::RearF32) }
 }
+#[cfg(feature = "exotic-formats")]
 unsafe impl SampleFrame for McRear<MuLawSample> {
 	type Sample = MuLawSample;
 
@@ -583,6 +968,7 @@ unsafe impl SampleFrame for McQuad<f32> {
 	#[inline(always)] fn len() -> usize { 4 }
 	#[inline(always)] fn format() -> Format { Format::ExtMcFormats(ExtMcFormat::QuadF32) }
 }
+#[cfg(feature = "exotic-formats")]
 unsafe impl SampleFrame for McQuad<MuLawSample> {
 	type Sample = MuLawSample;
 
@@ -609,6 +995,7 @@ unsafe impl SampleFrame for Mc51Chn<f32> {
 	#[inline(always)] fn len() -> usize { 6 }
 	#[inline(always)] fn format() -> Format { Format::ExtMcFormats(ExtMcFormat::Mc51ChnF32) }
 }
+#[cfg(feature = "exotic-formats")]
 unsafe impl SampleFrame for Mc51Chn<MuLawSample> {
 	type Sample = MuLawSample;
 
@@ -635,6 +1022,7 @@ unsafe impl SampleFrame for Mc61Chn<f32> {
 	#[inline(always)] fn len() -> usize { 7 }
 	#[inline(always)] fn format() -> Format { Format::ExtMcFormats(ExtMcFormat::Mc61ChnF32) }
 }
+#[cfg(feature = "exotic-formats")]
 unsafe impl SampleFrame for Mc61Chn<MuLawSample> {
 	type Sample = MuLawSample;
 
@@ -661,6 +1049,7 @@ unsafe impl SampleFrame for Mc71Chn<f32> {
 	#[inline(always)] fn len() -> usize { 8 }
 	#[inline(always)] fn format() -> Format { Format::ExtMcFormats(ExtMcFormat::Mc71ChnF32) }
 }
+#[cfg(feature = "exotic-formats")]
 unsafe impl SampleFrame for Mc71Chn<MuLawSample> {
 	type Sample = MuLawSample;
 
@@ -687,6 +1076,7 @@ unsafe impl SampleFrame for BFormat2D<f32> {
 	#[inline(always)] fn len() -> usize { 3 }
 	#[inline(always)] fn format() -> Format { Format::ExtBFormat(ExtBFormat::B2DF32) }
 }
+#[cfg(feature = "exotic-formats")]
 unsafe impl SampleFrame for BFormat2D<MuLawSample> {
 	type Sample = MuLawSample;
 
@@ -713,6 +1103,7 @@ unsafe impl SampleFrame for BFormat3D<f32> {
 	#[inline(always)] fn len() -> usize { 4 }
 	#[inline(always)] fn format() -> Format { Format::ExtBFormat(ExtBFormat::B3DF32) }
 }
+#[cfg(feature = "exotic-formats")]
 unsafe impl SampleFrame for BFormat3D<MuLawSample> {
 	type Sample = MuLawSample;
 
@@ -721,22 +1112,104 @@ unsafe impl SampleFrame for BFormat3D<MuLawSample> {
 }
 
 
+macro_rules! impl_frame_sample {
+	($sample:ty, $($len:expr => $format:expr),+) => {
+		unsafe impl FrameSample for $sample {
+			const FORMATS: [Option<Format>; 9] = {
+				let mut formats = [None; 9];
+				$(formats[$len] = Some($format);)+
+				formats
+			};
+		}
+	};
+}
+
+
+impl_frame_sample!(u8,
+	1 => Format::Standard(StandardFormat::MonoU8),
+	2 => Format::Standard(StandardFormat::StereoU8),
+	4 => Format::ExtMcFormats(ExtMcFormat::QuadU8),
+	6 => Format::ExtMcFormats(ExtMcFormat::Mc51ChnU8),
+	7 => Format::ExtMcFormats(ExtMcFormat::Mc61ChnU8),
+	8 => Format::ExtMcFormats(ExtMcFormat::Mc71ChnU8)
+);
+impl_frame_sample!(i16,
+	1 => Format::Standard(StandardFormat::MonoI16),
+	2 => Format::Standard(StandardFormat::StereoI16),
+	4 => Format::ExtMcFormats(ExtMcFormat::QuadI16),
+	6 => Format::ExtMcFormats(ExtMcFormat::Mc51ChnI16),
+	7 => Format::ExtMcFormats(ExtMcFormat::Mc61ChnI16),
+	8 => Format::ExtMcFormats(ExtMcFormat::Mc71ChnI16)
+);
+impl_frame_sample!(f32,
+	1 => Format::ExtFloat32(ExtFloat32Format::Mono),
+	2 => Format::ExtFloat32(ExtFloat32Format::Stereo),
+	4 => Format::ExtMcFormats(ExtMcFormat::QuadF32),
+	6 => Format::ExtMcFormats(ExtMcFormat::Mc51ChnF32),
+	7 => Format::ExtMcFormats(ExtMcFormat::Mc61ChnF32),
+	8 => Format::ExtMcFormats(ExtMcFormat::Mc71ChnF32)
+);
+#[cfg(feature = "exotic-formats")]
+impl_frame_sample!(f64,
+	1 => Format::ExtDouble(ExtDoubleFormat::Mono),
+	2 => Format::ExtDouble(ExtDoubleFormat::Stereo)
+);
+#[cfg(feature = "exotic-formats")]
+impl_frame_sample!(ALawSample,
+	1 => Format::ExtALaw(ExtALawFormat::Mono),
+	2 => Format::ExtALaw(ExtALawFormat::Stereo)
+);
+#[cfg(feature = "exotic-formats")]
+impl_frame_sample!(MuLawSample,
+	1 => Format::ExtMuLaw(ExtMuLawFormat::Mono),
+	2 => Format::ExtMuLaw(ExtMuLawFormat::Stereo),
+	4 => Format::ExtMuLawMcFormats(ExtMuLawMcFormat::Quad),
+	6 => Format::ExtMuLawMcFormats(ExtMuLawMcFormat::Mc51Chn),
+	7 => Format::ExtMuLawMcFormats(ExtMuLawMcFormat::Mc61Chn),
+	8 => Format::ExtMuLawMcFormats(ExtMuLawMcFormat::Mc71Chn)
+);
+
+
+impl<S: FrameSample, const N: usize> Frame<S, N> {
+	/// Evaluated at compile time, so a channel count without a format is rejected when the frame is used.
+	const FORMAT: Format = match S::FORMATS[N] {
+		Some(format) => format,
+		None => panic!("no format has this many channels of this sample type"),
+	};
+}
+
+
+unsafe impl<S: FrameSample, const N: usize> SampleFrame for Frame<S, N> {
+	type Sample = S;
+
+	#[inline(always)] fn len() -> usize { N }
+	#[inline(always)] fn format() -> Format { Self::FORMAT }
+}
+
+
 unsafe impl StandardFrame for Mono<u8> { }
 unsafe impl StandardFrame for Mono<i16> { }
 unsafe impl StandardFrame for Stereo<u8> { }
 unsafe impl StandardFrame for Stereo<i16> { }
+unsafe impl StandardFrame for Frame<u8, 1> { }
+unsafe impl StandardFrame for Frame<i16, 1> { }
+unsafe impl StandardFrame for Frame<u8, 2> { }
+unsafe impl StandardFrame for Frame<i16, 2> { }
 
 
+#[cfg(feature = "loopback")]
 unsafe impl LoopbackFrame for Mono<u8>
 {
 	fn channels(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_MONO_SOFT?) }
 	fn sample_ty(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_UNSIGNED_BYTE_SOFT?) }
 }
+#[cfg(feature = "loopback")]
 unsafe impl LoopbackFrame for Mono<i16>
 {
 	fn channels(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_MONO_SOFT?) }
 	fn sample_ty(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_SHORT_SOFT?) }
 }
+#[cfg(feature = "loopback")]
 unsafe impl LoopbackFrame for Mono<f32>
 {
 	fn channels(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_MONO_SOFT?) }
@@ -744,16 +1217,19 @@ unsafe impl LoopbackFrame for Mono<f32>
 }
 
 
+#[cfg(feature = "loopback")]
 unsafe impl LoopbackFrame for Stereo<u8>
 {
 	fn channels(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_STEREO_SOFT?) }
 	fn sample_ty(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_UNSIGNED_BYTE_SOFT?) }
 }
+#[cfg(feature = "loopback")]
 unsafe impl LoopbackFrame for Stereo<i16>
 {
 	fn channels(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_STEREO_SOFT?) }
 	fn sample_ty(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_SHORT_SOFT?) }
 }
+#[cfg(feature = "loopback")]
 unsafe impl LoopbackFrame for Stereo<f32>
 {
 	fn channels(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_STEREO_SOFT?) }
@@ -761,16 +1237,19 @@ unsafe impl LoopbackFrame for Stereo<f32>
 }
 
 
+#[cfg(feature = "loopback")]
 unsafe impl LoopbackFrame for McQuad<u8>
 {
 	fn channels(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_QUAD_SOFT?) }
 	fn sample_ty(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_UNSIGNED_BYTE_SOFT?) }
 }
+#[cfg(feature = "loopback")]
 unsafe impl LoopbackFrame for McQuad<i16>
 {
 	fn channels(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_QUAD_SOFT?) }
 	fn sample_ty(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_SHORT_SOFT?) }
 }
+#[cfg(feature = "loopback")]
 unsafe impl LoopbackFrame for McQuad<f32>
 {
 	fn channels(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_QUAD_SOFT?) }
@@ -778,16 +1257,19 @@ unsafe impl LoopbackFrame for McQuad<f32>
 }
 
 
+#[cfg(feature = "loopback")]
 unsafe impl LoopbackFrame for Mc51Chn<u8>
 {
 	fn channels(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_5POINT1_SOFT?) }
 	fn sample_ty(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_UNSIGNED_BYTE_SOFT?) }
 }
+#[cfg(feature = "loopback")]
 unsafe impl LoopbackFrame for Mc51Chn<i16>
 {
 	fn channels(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_5POINT1_SOFT?) }
 	fn sample_ty(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_SHORT_SOFT?) }
 }
+#[cfg(feature = "loopback")]
 unsafe impl LoopbackFrame for Mc51Chn<f32>
 {
 	fn channels(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_5POINT1_SOFT?) }
@@ -795,16 +1277,19 @@ unsafe impl LoopbackFrame for Mc51Chn<f32>
 }
 
 
+#[cfg(feature = "loopback")]
 unsafe impl LoopbackFrame for Mc61Chn<u8>
 {
 	fn channels(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_6POINT1_SOFT?) }
 	fn sample_ty(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_UNSIGNED_BYTE_SOFT?) }
 }
+#[cfg(feature = "loopback")]
 unsafe impl LoopbackFrame for Mc61Chn<i16>
 {
 	fn channels(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_6POINT1_SOFT?) }
 	fn sample_ty(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_SHORT_SOFT?) }
 }
+#[cfg(feature = "loopback")]
 unsafe impl LoopbackFrame for Mc61Chn<f32>
 {
 	fn channels(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_6POINT1_SOFT?) }
@@ -812,16 +1297,19 @@ unsafe impl LoopbackFrame for Mc61Chn<f32>
 }
 
 
+#[cfg(feature = "loopback")]
 unsafe impl LoopbackFrame for Mc71Chn<u8>
 {
 	fn channels(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_7POINT1_SOFT?) }
 	fn sample_ty(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_UNSIGNED_BYTE_SOFT?) }
 }
+#[cfg(feature = "loopback")]
 unsafe impl LoopbackFrame for Mc71Chn<i16>
 {
 	fn channels(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_7POINT1_SOFT?) }
 	fn sample_ty(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_SHORT_SOFT?) }
 }
+#[cfg(feature = "loopback")]
 unsafe impl LoopbackFrame for Mc71Chn<f32>
 {
 	fn channels(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Ok(sl.ALC_7POINT1_SOFT?) }
@@ -829,6 +1317,26 @@ unsafe impl LoopbackFrame for Mc71Chn<f32>
 }
 
 
+#[cfg(feature = "loopback")]
+/// The sample type is taken from the mono frame of `S`, which loopback devices support for every sample type they support.
+unsafe impl<S: FrameSample, const N: usize> LoopbackFrame for Frame<S, N> where
+	Mono<S>: LoopbackFrame,
+{
+	fn channels(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> {
+		match Self::FORMAT.channels() {
+			1 => Ok(sl.ALC_MONO_SOFT?),
+			2 => Ok(sl.ALC_STEREO_SOFT?),
+			4 => Ok(sl.ALC_QUAD_SOFT?),
+			6 => Ok(sl.ALC_5POINT1_SOFT?),
+			7 => Ok(sl.ALC_6POINT1_SOFT?),
+			8 => Ok(sl.ALC_7POINT1_SOFT?),
+			_ => Err(AltoError::AlcInvalidValue),
+		}
+	}
+	fn sample_ty(sl: &ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint> { Mono::<S>::sample_ty(sl) }
+}
+
+
 impl<S> AsBufferData<Mono<S>> for [Mono<S>] where S: Copy, Mono<S>: SampleFrame { fn as_buffer_data(&self) -> &[Mono<S>] { self } }
 impl<S> AsBufferData<Mono<S>> for [S] where
 	S: Copy,
@@ -911,6 +1419,16 @@ impl<S> AsBufferData<BFormat3D<S>> for [S] where
 	}
 }
 
+impl<S, const N: usize> AsBufferData<Frame<S, N>> for [Frame<S, N>] where S: Copy, Frame<S, N>: SampleFrame { fn as_buffer_data(&self) -> &[Frame<S, N>] { self } }
+impl<S, const N: usize> AsBufferData<Frame<S, N>> for [S] where
+	S: Copy,
+	Frame<S, N>: SampleFrame,
+{
+	fn as_buffer_data(&self) -> &[Frame<S, N>] {
+		unsafe { slice::from_raw_parts(self.as_ptr() as *const _, self.len() / N) }
+	}
+}
+
 
 impl<F, T> AsBufferData<F> for T where
 	F: SampleFrame,
@@ -1003,6 +1521,16 @@ impl<S> AsBufferDataMut<BFormat3D<S>> for [S] where
 	}
 }
 
+impl<S, const N: usize> AsBufferDataMut<Frame<S, N>> for [Frame<S, N>] where S: Copy, Frame<S, N>: SampleFrame { fn as_buffer_data_mut(&mut self) -> &mut [Frame<S, N>] { self } }
+impl<S, const N: usize> AsBufferDataMut<Frame<S, N>> for [S] where
+	S: Copy,
+	Frame<S, N>: SampleFrame,
+{
+	fn as_buffer_data_mut(&mut self) -> &mut [Frame<S, N>] {
+		unsafe { slice::from_raw_parts_mut(self.as_mut_ptr() as *mut _, self.len() / N) }
+	}
+}
+
 
 impl<F, T> AsBufferDataMut<F> for T where
 	F: SampleFrame,
@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+use std::cmp;
+
+use sys;
+use super::*;
+
+
+/// How much sample data the buffers of a context hold, read with `Context::memory_stats()`.
+/// Sizes are of the data as uploaded; the implementation may store it in another format internally.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct MemoryStats {
+	/// Bytes held by buffers that haven't been deleted.
+	pub buffer_bytes: usize,
+	/// The largest `buffer_bytes` has been since the context was created.
+	pub peak_buffer_bytes: usize,
+	/// Buffers holding sample data.
+	pub buffers: usize,
+	/// Bytes uploaded over the life of the context, including data since replaced or deleted.
+	pub uploaded_bytes: u64,
+}
+
+
+/// The size and block length of the data last uploaded into each buffer of a context.
+pub struct MemoryTracker {
+	sizes: HashMap<sys::ALuint, usize>,
+	/// Sample frames per block of buffers holding ADPCM data.
+	block_frames: HashMap<sys::ALuint, usize>,
+	stats: MemoryStats,
+}
+
+
+impl MemoryTracker {
+	pub fn new() -> MemoryTracker {
+		MemoryTracker{sizes: HashMap::new(), block_frames: HashMap::new(), stats: MemoryStats::default()}
+	}
+
+
+	/// `bytes` were uploaded into `buf` in blocks of `block_frames` sample frames, replacing whatever it held.
+	pub fn uploaded(&mut self, buf: sys::ALuint, bytes: usize, block_frames: usize) {
+		if block_frames > 1 {
+			self.block_frames.insert(buf, block_frames);
+		} else {
+			self.block_frames.remove(&buf);
+		}
+		let old = self.sizes.insert(buf, bytes);
+		self.stats.buffer_bytes = self.stats.buffer_bytes - old.unwrap_or(0) + bytes;
+		self.stats.peak_buffer_bytes = cmp::max(self.stats.peak_buffer_bytes, self.stats.buffer_bytes);
+		self.stats.buffers = self.sizes.len();
+		self.stats.uploaded_bytes += bytes as u64;
+	}
+
+
+	/// Sample frames per block of the data last uploaded into `buf`, which is 1 unless it is ADPCM.
+	pub fn block_frames(&self, buf: sys::ALuint) -> usize {
+		self.block_frames.get(&buf).cloned().unwrap_or(1)
+	}
+
+
+	/// `buf` was deleted.
+	pub fn deleted(&mut self, buf: sys::ALuint) {
+		self.block_frames.remove(&buf);
+		if let Some(old) = self.sizes.remove(&buf) {
+			self.stats.buffer_bytes -= old;
+			self.stats.buffers = self.sizes.len();
+		}
+	}
+}
+
+
+impl<'d> Context<'d> {
+	/// The sample data held by this context's buffers, for enforcing a memory budget or spotting leaked buffers.
+	/// Buffers are counted from their first upload until alto deletes them, including buffers owned by the registry.
+	/// Buffers released with `into_raw()` stay counted, and data uploaded outside of alto is not seen.
+	pub fn memory_stats(&self) -> MemoryStats {
+		self.memory.lock().unwrap_or_else(|e| e.into_inner()).stats
+	}
+
+
+	/// Forget the sizes of buffers that were deleted, and that alto held them.
+	#[doc(hidden)]
+	pub fn buffers_deleted(&self, bufs: &[sys::ALuint]) {
+		let mut memory = self.memory.lock().unwrap_or_else(|e| e.into_inner());
+		let mut held = self.held_buffers.lock().unwrap_or_else(|e| e.into_inner());
+		for &buf in bufs {
+			memory.deleted(buf);
+			held.remove(&buf);
+		}
+	}
+}
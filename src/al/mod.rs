@@ -2,15 +2,24 @@ use std::ops::Deref;
 use std::iter;
 use std::sync::{Arc, Mutex, MutexGuard};
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::hash_map::Entry;
 use std::io::{self, Write};
 use std::mem;
 use std::ptr;
 use std::hash::{Hash, Hasher};
-
-use ::{AltoError, AltoResult};
+use std::cell::{Cell, RefCell};
+use std::cmp;
+use std::thread;
+use std::time::{Duration, Instant};
+use std::fmt;
+use std::any::Any;
+use std::slice;
+
+use ::{AltoError, AltoResult, ObjectKind};
 use sys;
 use alc::*;
+#[cfg(feature = "efx")]
 use efx::*;
 use ext;
 
@@ -19,6 +28,63 @@ mod format;
 pub use self::format::*;
 
 
+mod registry;
+pub use self::registry::*;
+
+
+mod orientation;
+pub use self::orientation::*;
+
+
+mod units;
+pub use self::units::*;
+
+
+mod attenuation;
+pub use self::attenuation::*;
+
+
+mod context_set;
+pub use self::context_set::*;
+
+
+mod range;
+pub use self::range::{RangeHook, RangePolicy, RangeViolation};
+use self::range::{NON_NEGATIVE, UNIT, ANGLE, POSITIVE, METERS_PER_UNIT};
+#[cfg(feature = "efx")]
+use self::range::EFX_FACTOR;
+
+
+mod events;
+pub use self::events::AudioEvent;
+use self::events::{EventQueue, remove_event_callback};
+
+
+mod deferred;
+use self::deferred::{DeferredWrites, DeferredTarget, DeferredValue, flush_deferred};
+
+
+mod velocity;
+use self::velocity::VelocityTracker;
+
+
+mod arena;
+use self::arena::{Arena, Handle};
+
+
+mod cached;
+pub use self::cached::CachedSource;
+
+
+mod memory;
+pub use self::memory::MemoryStats;
+use self::memory::MemoryTracker;
+
+
+mod borrowed;
+pub use self::borrowed::{Borrowed, Release};
+
+
 lazy_static! {
 	#[doc(hidden)]
 	#[no_mangle]
@@ -26,6 +92,17 @@ lazy_static! {
 }
 
 
+thread_local! {
+	/// Contexts locked by this thread, so that nested calls on the same context don't deadlock.
+	static HELD_CONTEXTS: RefCell<Vec<usize>> = RefCell::new(Vec::new());
+	/// The context last made current on this thread with `alcSetThreadContext()`, or 0.
+	static THREAD_CONTEXT: Cell<usize> = Cell::new(0);
+	/// How many locks of this thread need `ALTO_CTX_LOCK__`, and its guard while there are any,
+	/// so that a thread working with several contexts only takes it once.
+	static GLOBAL_HELD: RefCell<(usize, Option<MutexGuard<'static, ()>>)> = RefCell::new((0, None));
+}
+
+
 /// The gain curve of sources as a function of distance to the listener.
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum DistanceModel {
@@ -47,12 +124,89 @@ pub enum DistanceModel {
 
 
 /// A listener context.
+///
+/// A context borrows its device, and the buffers, sources, and effect objects created from it borrow the
+/// context, so the borrow checker ensures each is dropped before what it was created from. Sources and
+/// buffers moved into the registry are stopped and deleted when the context is dropped, before it is destroyed.
+/// The handles in `owned` keep their parents alive instead, and can be dropped in any order.
+///
+/// A context, and the buffers, sources, and effect objects created from it, can be shared between
+/// threads. Each call into OpenAL holds a lock on the context for its duration, so calls from different
+/// threads on objects of the same context are serialized, while objects of different contexts can be
+/// used in parallel. If the implementation lacks `ALC_EXT_thread_local_context`, calls on all contexts
+/// are serialized by a single global lock instead, since only one context can be current per process.
+///
+/// The lock costs an uncontended mutex acquisition per call, which is small next to the call itself, but
+/// threads making many calls on one context contend for it. There is no need to wrap a context in a `Mutex`
+/// of your own; to make several calls without calls from other threads interleaving, use `exclusive()`.
 pub struct Context<'d> {
 	dev: &'d DeviceTrait,
 	api: &'d AlApi,
 	ctx: *mut sys::ALCcontext,
+	/// Whether the context and the objects in its registry are destroyed on drop; cleared by `into_raw()`.
+	owned: bool,
 	exts: ext::AlCache<'d>,
+	/// `alcSetThreadContext()`, looked up once since it is needed for every call.
+	set_thread_context: Option<unsafe extern "C" fn(*mut sys::ALCcontext) -> sys::ALCboolean>,
 	defer_rc: Arc<AtomicUsize>,
+	deferred: DeferredWrites,
+	registry: Mutex<Registry>,
+	lock: Mutex<()>,
+	listener_velocity: Mutex<Option<VelocityTracker>>,
+	source_defaults: Mutex<SourceDefaults>,
+	range_policy: Mutex<RangePolicy>,
+	position_epsilon: Mutex<Option<f32>>,
+	sources: Mutex<SourceTable>,
+	/// Names of dropped buffers, deleted by the next `maintain()`.
+	dropped_buffers: Mutex<Vec<sys::ALuint>>,
+	/// Names of the buffers alto holds until they are deleted or released, so that none is adopted twice.
+	held_buffers: Mutex<HashSet<sys::ALuint>>,
+	/// Listener properties waiting for `Listener::commit_staged()`.
+	staged_listener: Mutex<ListenerUpdate>,
+	memory: Mutex<MemoryTracker>,
+	events: Box<EventQueue>,
+}
+
+
+/// Bookkeeping for every source created through a context, stored contiguously so that passes over
+/// hundreds of sources each frame stay cache friendly. A source reaches its own entry through the slot it
+/// was given; only names that come from elsewhere, such as in a `SourceId`, are looked up by name.
+struct SourceTable {
+	live: Arena<LiveSource>,
+	slots: HashMap<sys::ALuint, Handle>,
+	/// How many sources each buffer is attached to or queued on, so that a dropped buffer isn't deleted while in use.
+	users: HashMap<sys::ALuint, usize>,
+}
+
+
+/// Bookkeeping for a source created through a context, reported by `Context::sources()`.
+struct LiveSource {
+	src: sys::ALuint,
+	streaming: bool,
+	registered: bool,
+	tag: Option<String>,
+	priority: u8,
+	/// Sample frames played by `StaticSource::play_range()`, until the source stops.
+	range: Option<(sys::ALint, sys::ALint)>,
+	user_data: Option<Box<dyn Any + Send>>,
+	#[cfg(feature = "efx")]
+	/// The effect slot attached to each auxiliary send, or 0.
+	sends: Vec<sys::ALuint>,
+	velocity: Option<VelocityTracker>,
+	/// The last position and velocity written while `Context::set_position_epsilon()` is enabled.
+	last_position: Option<[f32; 3]>,
+	last_velocity: Option<[f32; 3]>,
+	/// The buffer attached to the source, or the buffers queued on it in queue order.
+	buffers: Vec<sys::ALuint>,
+}
+
+
+/// Keeps a context current on this thread and locked against other threads until dropped.
+#[doc(hidden)]
+pub struct ContextLock<'c> {
+	ctx: usize,
+	/// The guard of the context's own lock, and whether the lock counts towards `GLOBAL_HELD`.
+	guards: Option<(MutexGuard<'c, ()>, bool)>,
 }
 
 
@@ -61,13 +215,106 @@ pub struct Context<'d> {
 pub struct SuspendLock<'d: 'c, 'c>(&'c Context<'d>);
 
 
+/// The sources paused by `Context::pause_all_playing()`, to be resumed when an interruption ends.
+#[must_use]
+pub struct PausedSources<'d: 'c, 'c> {
+	ctx: &'c Context<'d>,
+	srcs: Vec<sys::ALuint>,
+}
+
+
+/// The listener of a context, grouping the `alListener*` properties.
+#[derive(Copy, Clone)]
+pub struct Listener<'d: 'c, 'c>(&'c Context<'d>);
+
+
+/// A snapshot of every listener property, for reading or applying them all at once.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ListenerState {
+	/// `AL_GAIN`
+	pub gain: f32,
+	/// `AL_POSITION`
+	pub position: [f32; 3],
+	/// `AL_VELOCITY`
+	pub velocity: [f32; 3],
+	/// `AL_ORIENTATION` as `(forward, up)`
+	pub orientation: ([f32; 3], [f32; 3]),
+	/// `AL_METERS_PER_UNIT`, or `None` if `ALC_EXT_EFX` is unavailable.
+	/// Left unchanged by `Listener::set_state()` if `None`.
+	pub meters_per_unit: Option<f32>,
+}
+
+
+/// Listener properties staged with `Listener::stage_*()`, waiting for `Listener::commit_staged()`.
+/// Properties left `None` are not changed by the commit.
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct ListenerUpdate {
+	/// `AL_GAIN`
+	pub gain: Option<f32>,
+	/// `AL_POSITION`
+	pub position: Option<[f32; 3]>,
+	/// `AL_VELOCITY`
+	pub velocity: Option<[f32; 3]>,
+	/// `AL_ORIENTATION` as `(forward, up)`
+	pub orientation: Option<([f32; 3], [f32; 3])>,
+}
+
+
+/// The global properties that determine how sound travels in a context, read and applied with
+/// `Context::acoustics()` and `Context::set_acoustics()`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Acoustics {
+	/// `AL_DISTANCE_MODEL`
+	pub distance_model: DistanceModel,
+	/// `AL_SPEED_OF_SOUND`
+	pub speed_of_sound: f32,
+	/// `AL_DOPPLER_FACTOR`
+	pub doppler_factor: f32,
+	/// `AL_METERS_PER_UNIT`, or `None` if `ALC_EXT_EFX` is unavailable.
+	/// Left unchanged by `Context::set_acoustics()` if `None`.
+	pub meters_per_unit: Option<f32>,
+}
+
+
+/// Properties that every source created through a context starts with, set with `Context::set_source_defaults()`.
+/// Properties left as `None` keep their OpenAL defaults. Sources adopted with `from_raw()` are left as they are.
+#[derive(Copy, Clone, PartialEq, Default, Debug)]
+pub struct SourceDefaults {
+	/// `AL_SOURCE_RELATIVE`
+	pub relative: Option<bool>,
+	/// `AL_REFERENCE_DISTANCE`
+	pub reference_distance: Option<f32>,
+	/// `AL_ROLLOFF_FACTOR`
+	pub rolloff_factor: Option<f32>,
+	/// `AL_MAX_DISTANCE`
+	pub max_distance: Option<f32>,
+	/// `AL_CONE_INNER_ANGLE`, `AL_CONE_OUTER_ANGLE`, and `AL_CONE_OUTER_GAIN`
+	pub cone: Option<(f32, f32, f32)>,
+}
+
+
 /// An audio buffer of any format.
+/// Dropping a buffer doesn't take the context lock; it is deleted by the next `Context::maintain()`,
+/// or when the context is dropped. A buffer still attached to or queued on a source is only deleted
+/// by a `maintain()` after the source stops using it.
 pub struct Buffer<'d: 'c, 'c> {
 	ctx: &'c Context<'d>,
 	buf: sys::ALuint, 
 }
 
 
+/// What `Buffer::replace_data()` does with the sources that were using the buffer.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum ReloadMode {
+	/// Leave them stopped.
+	Stop,
+	/// Play the new data from the beginning on sources that were playing.
+	Restart,
+	/// Put sources back in the state they were in, at the same sample offset if it is still within the new data.
+	Resume,
+}
+
+
 /// Capabilities common to both static and streaming sources.
 pub unsafe trait SourceTrait<'d: 'c, 'c> {
 	/// The context from which this source was created.
@@ -95,6 +342,10 @@ pub unsafe trait SourceTrait<'d: 'c, 'c> {
 	fn gain(&self) -> AltoResult<f32>;
 	/// `alSourcef(AL_GAIN)`
 	fn set_gain(&mut self, f32) -> AltoResult<()>;
+	/// `alGetSourcef(AL_GAIN)` in decibels, where silence is `-inf`.
+	fn gain_db(&self) -> AltoResult<f32> { self.gain().map(gain_to_db) }
+	/// `alSourcef(AL_GAIN)` in decibels, where `-inf` mutes the source.
+	fn set_gain_db(&mut self, db: f32) -> AltoResult<()> { self.set_gain(db_to_gain(db)) }
 
 	/// `alGetSourcef(AL_MIN_GAIN)`
 	fn min_gain(&self) -> AltoResult<f32>;
@@ -105,6 +356,16 @@ pub unsafe trait SourceTrait<'d: 'c, 'c> {
 	fn max_gain(&self) -> AltoResult<f32>;
 	/// `alSourcef(AL_MAX_GAIN)`
 	fn set_max_gain(&mut self, f32) -> AltoResult<()>;
+	/// `AL_MIN_GAIN` and `AL_MAX_GAIN` at once.
+	/// Fails with `AlInvalidRange` if a gain is outside 0-1 or `min_gain` exceeds `max_gain`, in which case nothing is changed.
+	fn set_gain_range(&mut self, min_gain: f32, max_gain: f32) -> AltoResult<()> {
+		if !(min_gain >= 0.0 && min_gain <= 1.0) { return Err(AltoError::AlInvalidRange("min_gain must be between 0 and 1")) }
+		if !(max_gain >= 0.0 && max_gain <= 1.0) { return Err(AltoError::AlInvalidRange("max_gain must be between 0 and 1")) }
+		if min_gain > max_gain { return Err(AltoError::AlInvalidRange("min_gain must not exceed max_gain")) }
+
+		self.set_min_gain(min_gain)?;
+		self.set_max_gain(max_gain)
+	}
 
 	/// `alGetSourcef(AL_REFERENCE_DISTANCE)`
 	fn reference_distance(&self) -> AltoResult<f32>;
@@ -120,27 +381,97 @@ pub unsafe trait SourceTrait<'d: 'c, 'c> {
 	fn max_distance(&self) -> AltoResult<f32>;
 	/// `alSourcef(AL_MAX_DISTANCE)`
 	fn set_max_distance(&mut self, f32) -> AltoResult<()>;
+	/// `AL_REFERENCE_DISTANCE`, `AL_MAX_DISTANCE`, and `AL_ROLLOFF_FACTOR` at once.
+	/// Fails with `AlInvalidRange` if a distance isn't positive, `reference_distance` exceeds `max_distance`,
+	/// or `rolloff_factor` is negative, in which case nothing is changed.
+	fn set_distance_range(&mut self, reference_distance: f32, max_distance: f32, rolloff_factor: f32) -> AltoResult<()> {
+		if !(reference_distance > 0.0) { return Err(AltoError::AlInvalidRange("reference_distance must be positive")) }
+		if !(max_distance > 0.0) { return Err(AltoError::AlInvalidRange("max_distance must be positive")) }
+		if reference_distance > max_distance { return Err(AltoError::AlInvalidRange("reference_distance must not exceed max_distance")) }
+		if !(rolloff_factor >= 0.0) || rolloff_factor.is_infinite() { return Err(AltoError::AlInvalidRange("rolloff_factor must be a non-negative number")) }
+
+		self.set_reference_distance(reference_distance)?;
+		self.set_max_distance(max_distance)?;
+		self.set_rolloff_factor(rolloff_factor)
+	}
 
 	/// `alGetSourcef(AL_PITCH)`
 	fn pitch(&self) -> AltoResult<f32>;
 	/// `alSourcef(AL_PITCH)`
 	fn set_pitch(&mut self, f32) -> AltoResult<()>;
+	/// `alGetSourcef(AL_PITCH)` as an interval in semitones.
+	fn pitch_semitones(&self) -> AltoResult<f32> { self.pitch().map(pitch_to_semitones) }
+	/// `alSourcef(AL_PITCH)` from an interval in semitones, clamped to the valid pitch range.
+	fn set_pitch_semitones(&mut self, semitones: f32) -> AltoResult<()> {
+		if semitones.is_nan() { return Err(AltoError::AlInvalidValue) }
+		self.set_pitch(semitones_to_pitch(semitones))
+	}
+	/// `alGetSourcef(AL_PITCH)` as an interval in cents.
+	fn pitch_cents(&self) -> AltoResult<f32> { self.pitch_semitones().map(|st| st * 100.0) }
+	/// `alSourcef(AL_PITCH)` from an interval in cents, clamped to the valid pitch range.
+	fn set_pitch_cents(&mut self, cents: f32) -> AltoResult<()> { self.set_pitch_semitones(cents / 100.0) }
 
 	/// `alGetSourcefv(AL_POSITION)`
 	fn position<V: From<[f32; 3]>>(&self) -> AltoResult<V>;
 	/// `alSourcefv(AL_POSITION)`
+	/// Also sets `AL_VELOCITY` if automatic velocity is enabled.
 	fn set_position<V: Into<[f32; 3]>>(&mut self, V) -> AltoResult<()>;
 
 	/// `alGetSourcefv(AL_VELOCITY)`
 	fn velocity<V: From<[f32; 3]>>(&self) -> AltoResult<V>;
 	/// `alSourcefv(AL_VELOCITY)`
 	fn set_velocity<V: Into<[f32; 3]>>(&mut self, V) -> AltoResult<()>;
+	/// The smoothing time constant, in seconds, of automatic velocity, or `None` if it is disabled.
+	fn auto_velocity(&self) -> Option<f32>;
+	/// Derive the velocity from successive calls to `set_position()`, so that Doppler shift works
+	/// without tracking velocity by hand. Velocities are smoothed over `smoothing` seconds; `0.0` disables smoothing.
+	/// Passing `None` disables tracking and resets the velocity to zero.
+	fn set_auto_velocity(&mut self, smoothing: Option<f32>) -> AltoResult<()>;
+
+	/// A label for debugging, as reported by `Context::sources()`.
+	fn tag(&self) -> Option<String> { self.context().source_tag(self.as_raw()) }
+	/// Label the source for debugging, or remove its label with `None`.
+	fn set_tag(&mut self, tag: Option<&str>) { self.context().set_source_tag(self.as_raw(), tag) }
+
+	/// How important the source is when deciding which voice to give up when sources run out, where higher is more
+	/// important. Stored by alto rather than OpenAL, and defaults to `DEFAULT_PRIORITY`.
+	fn priority(&self) -> u8 { self.context().source_priority(self.as_raw()) }
+	/// Change the priority of the source.
+	fn set_priority(&mut self, priority: u8) { self.context().set_source_priority(self.as_raw(), priority) }
+
+	/// A copy of the value attached with `set_user_data()`, if there is one and it is a `T`.
+	fn user_data<T: Any + Send + Clone>(&self) -> Option<T> {
+		self.context().source_user_data(self.as_raw(), |data| data.as_ref().and_then(|d| d.downcast_ref::<T>()).cloned()).and_then(|d| d)
+	}
+	/// Call `f` with the attached value, if there is one and it is a `T`.
+	/// The context's source bookkeeping is locked for the duration of the closure, so `f` must not touch other sources.
+	fn with_user_data<T: Any + Send, R, F: FnOnce(&mut T) -> R>(&self, f: F) -> Option<R> {
+		self.context().source_user_data(self.as_raw(), |data| data.as_mut().and_then(|d| d.downcast_mut::<T>()).map(f)).and_then(|r| r)
+	}
+	/// Attach an arbitrary value to the source, such as the ID of the entity it belongs to,
+	/// replacing any value attached before. It is dropped along with the source.
+	fn set_user_data<T: Any + Send>(&mut self, data: T) {
+		// The old value is dropped after the lock is released, in case its destructor touches this source.
+		let _old = self.context().source_user_data(self.as_raw(), |d| mem::replace(d, Some(Box::new(data) as Box<dyn Any + Send>)));
+	}
+	/// Remove the attached value, returning it if it is a `T`.
+	fn take_user_data<T: Any + Send>(&mut self) -> Option<T> {
+		self.context().source_user_data(self.as_raw(), |d| d.take().and_then(|d| d.downcast::<T>().ok()).map(|d| *d)).and_then(|d| d)
+	}
 
 	/// `alGetSourcefv(AL_DIRECTION)`
 	fn direction<V: From<[f32; 3]>>(&self) -> AltoResult<V>;
 	/// `alSourcefv(AL_DIRECTION)`
 	fn set_direction<V: Into<[f32; 3]>>(&mut self, V) -> AltoResult<()>;
 
+	/// `alGetSourcefv(AL_ORIENTATION)`
+	/// Rotates the sound field of a B-format source, as `(forward, up)` vectors.
+	/// Requires `AL_EXT_BFORMAT`
+	fn orientation<V: From<[f32; 3]>>(&self) -> AltoResult<(V, V)>;
+	/// `alSourcefv(AL_ORIENTATION)`
+	/// Requires `AL_EXT_BFORMAT`
+	fn set_orientation<V: Into<[f32; 3]>>(&mut self, (V, V)) -> AltoResult<()>;
+
 	/// `alGetSourcef(AL_CONE_INNER_ANGLE)`
 	fn cone_inner_angle(&self) -> AltoResult<f32>;
 	/// `alSourcef(AL_CONE_INNER_ANGLE)`
@@ -155,6 +486,24 @@ pub unsafe trait SourceTrait<'d: 'c, 'c> {
 	fn cone_outer_gain(&self) -> AltoResult<f32>;
 	/// `alSourcef(AL_CONE_OUTER_GAIN)`
 	fn set_cone_outer_gain(&mut self, f32) -> AltoResult<()>;
+	/// Every cone parameter at once. `outer_gain_hf` is `None` if `ALC_EXT_EFX` is unavailable.
+	fn cone(&self) -> AltoResult<SourceCone> {
+		#[cfg(feature = "efx")]
+		let outer_gain_hf = if self.context().device().extensions().ALC_EXT_EFX().is_ok() { Some(self.cone_outer_gainhf()?) } else { None };
+		#[cfg(not(feature = "efx"))]
+		let outer_gain_hf = None;
+		Ok(SourceCone{
+			inner_angle: self.cone_inner_angle()?,
+			outer_angle: self.cone_outer_angle()?,
+			outer_gain: self.cone_outer_gain()?,
+			outer_gain_hf: outer_gain_hf,
+		})
+	}
+	/// Set every cone parameter at once, applying them together under one `SuspendLock`.
+	/// Fails with `AlInvalidRange` if an angle is outside 0-360 degrees, the inner angle is wider than the outer one,
+	/// or a gain is outside 0-1, in which case nothing is changed.
+	/// An `outer_gain_hf` requires `ALC_EXT_EFX`.
+	fn set_cone(&mut self, cone: SourceCone) -> AltoResult<()>;
 
 	/// `alGetSourcef(AL_SEC_OFFSET)`
 	fn sec_offset(&self) -> AltoResult<f32>;
@@ -179,6 +528,25 @@ pub unsafe trait SourceTrait<'d: 'c, 'c> {
 	/// Requires `AL_SOFT_source_latency`
 	fn soft_sample_frac_offset_latency(&self) -> AltoResult<(i32, i32, i64)>;
 
+	/// The playback position as both a time and a sample frame count, with sub-frame precision
+	/// if `AL_SOFT_source_latency` is available. For streaming sources, the position is within the queue.
+	fn playback_position(&self) -> AltoResult<PlaybackPosition> {
+		let (frames, frame_fraction) = match self.soft_sample_frac_offset_latency() {
+			Ok((int, frac, _)) => (int, frac as u32 as f64 / (1u64 << 32) as f64),
+			Err(_) => (self.sample_offset()?, 0.0),
+		};
+		let secs = match self.soft_sec_offset_latency() {
+			Ok((secs, _)) => secs,
+			Err(_) => self.sec_offset()? as f64,
+		};
+
+		Ok(PlaybackPosition{
+			time: Duration::from_nanos((secs.max(0.0) * 1_000_000_000.0) as u64),
+			frames: cmp::max(frames, 0) as u64,
+			frame_fraction: frame_fraction,
+		})
+	}
+
 	/// `alGetSourcef(AL_SEC_LENGTH_SOFT)`
 	/// Requires `AL_SOFT_source_length`
 	fn soft_sec_length(&self) -> AltoResult<f32>;
@@ -204,51 +572,138 @@ pub unsafe trait SourceTrait<'d: 'c, 'c> {
 	/// `alSourcei(AL_DISTANCE_MODEL)`
 	/// Requires `AL_EXT_source_distance_model`
 	fn set_distance_model(&mut self, DistanceModel) -> AltoResult<()>;
-
+	/// The distance attenuation parameters currently in effect for this source.
+	fn attenuation(&self) -> AltoResult<Attenuation> { Attenuation::of_source(self) }
+	/// Read every positional and gain-related property while holding the context, so no other thread can change them in between.
+	fn state_snapshot(&self) -> AltoResult<SourceProps>;
+	/// Apply every property of a snapshot at once, such as to restore it or copy it from another source.
+	/// Updates are deferred until all properties are set if `AL_SOFT_deferred_updates` is available.
+	fn apply(&mut self, &SourceProps) -> AltoResult<()>;
+	/// Apply only the properties of `new` that differ from `old`, such as the values last applied.
+	/// Updates are deferred until all properties are set if `AL_SOFT_deferred_updates` is available.
+	fn apply_changed(&mut self, old: &SourceProps, new: &SourceProps) -> AltoResult<()>;
+	/// Read several properties with one acquisition of the context, such as to poll many sources every frame.
+	/// Fails as a whole if any property can't be read, naming the first one that failed.
+	fn get_many(&self, &[SourceProp]) -> AltoResult<PropValues>;
+
+	#[cfg(feature = "efx")]
 	/// `alSourcei(AL_DIRECT_FILTER)`
 	/// Requires `ALC_EXT_EFX`
 	fn set_direct_filter<F: FilterTrait<'d, 'c>>(&mut self, value: &F) -> AltoResult<()>;
+	#[cfg(feature = "efx")]
 	/// `alSourcei(AL_DIRECT_FILTER)`
 	/// Requires `ALC_EXT_EFX`
 	fn clear_direct_filter(&mut self) -> AltoResult<()>;
 
+	#[cfg(feature = "efx")]
 	/// `alSourceiv(AL_AUXILIARY_SEND_FILTER)`
 	/// Requires `ALC_EXT_EFX`
 	fn set_auxiliary_send(&mut self, send: sys::ALint, value: &mut AuxEffectSlot<'d, 'c>) -> AltoResult<()>;
+	#[cfg(feature = "efx")]
 	/// `alSourceiv(AL_AUXILIARY_SEND_FILTER)`
 	/// Requires `ALC_EXT_EFX`
 	fn set_auxiliary_send_filter<F: FilterTrait<'d, 'c>>(&mut self, send: sys::ALint, slot: &mut AuxEffectSlot<'d, 'c>, filter: &F) -> AltoResult<()>;
+	#[cfg(feature = "efx")]
 	/// `alSourceiv(AL_AUXILIARY_SEND_FILTER)`
 	/// Requires `ALC_EXT_EFX`
 	fn clear_auxiliary_send(&mut self, send: sys::ALint) -> AltoResult<()>;
 
+	#[cfg(feature = "efx")]
 	/// `alGetSourcef(AL_AIR_ABSORPTION_FACTOR)`
 	/// Requires `ALC_EXT_EFX`
 	fn air_absorption_factor(&self) -> AltoResult<f32>;
+	#[cfg(feature = "efx")]
 	/// `alSourcef(AL_AIR_ABSORPTION_FACTOR)`
 	/// Requires `ALC_EXT_EFX`
 	fn set_air_absorption_factor(&mut self, f32) -> AltoResult<()>;
 
+	#[cfg(feature = "efx")]
 	/// `alGetSourcef(AL_ROOM_ROLLOFF_FACTOR)`
 	/// Requires `ALC_EXT_EFX`
 	fn room_rolloff_factor(&self) -> AltoResult<f32>;
+	#[cfg(feature = "efx")]
 	/// `alSourcef(AL_ROOM_ROLLOFF_FACTOR)`
 	/// Requires `ALC_EXT_EFX`
 	fn set_room_rolloff_factor(&mut self, f32) -> AltoResult<()>;
 
+	#[cfg(feature = "efx")]
 	/// `alGetSourcef(AL_CONE_OUTER_GAINHF)`
 	/// Requires `ALC_EXT_EFX`
 	fn cone_outer_gainhf(&self) -> AltoResult<f32>;
+	#[cfg(feature = "efx")]
 	/// `alSourcef(AL_CONE_OUTER_GAINHF)`
 	/// Requires `ALC_EXT_EFX`
 	fn set_cone_outer_gainhf(&mut self, f32) -> AltoResult<()>;
 
+	#[cfg(feature = "efx")]
 	/// `alGetSourcei(AL_DIRECT_FILTER_GAINHF_AUTO)`
 	/// Requires `ALC_EXT_EFX`
 	fn direct_filter_gainhf_auto(&self) -> AltoResult<bool>;
+	#[cfg(feature = "efx")]
 	/// `alSourcei(AL_DIRECT_FILTER_GAINHF_AUTO)`
 	/// Requires `ALC_EXT_EFX`
 	fn set_direct_filter_gainhf_auto(&mut self, bool) -> AltoResult<()>;
+
+	/// `alSourcefv(AL_ORIENTATION)` from a rotation quaternion in `(x, y, z, w)` order.
+	/// See `orientation_from_quat()`.
+	/// Requires `AL_EXT_BFORMAT`
+	fn set_orientation_quat<Q: Into<[f32; 4]>>(&mut self, value: Q) -> AltoResult<()> where Self: Sized {
+		let value = orientation_from_quat(value)?;
+		self.set_orientation(value)
+	}
+	/// `alSourcefv(AL_ORIENTATION)` facing `target` from the source's current position.
+	/// This rotates the sound field of a B-format source; use `set_direction()` to aim a sound cone.
+	/// See `orientation_look_at()`.
+	/// Requires `AL_EXT_BFORMAT`
+	fn look_at<V: Into<[f32; 3]>>(&mut self, target: V, up: V) -> AltoResult<()> where Self: Sized {
+		let eye: [f32; 3] = self.position()?;
+		let value = orientation_look_at(eye, target.into(), up.into())?;
+		self.set_orientation(value)
+	}
+	/// Make the source play from the listener's position, as for UI sounds and music.
+	/// Sets it relative with zero position and velocity, turns off automatic velocity,
+	/// and disables distance attenuation with a zero rolloff factor and the cone with a zero direction.
+	fn attach_to_listener(&mut self) -> AltoResult<()> {
+		self.set_auto_velocity(None)?;
+		self.set_relative(true)?;
+		self.set_position([0.0, 0.0, 0.0])?;
+		self.set_velocity([0.0, 0.0, 0.0])?;
+		self.set_direction([0.0, 0.0, 0.0])?;
+		self.set_rolloff_factor(0.0)
+	}
+
+	/// Block until the source reaches `state`, returning `false` if `timeout` elapses first.
+	/// The state is polled at an interval derived from the length of the buffer being played.
+	fn wait_until(&self, state: SourceState, timeout: Option<Duration>) -> AltoResult<bool> {
+		poll_state(self, |s| s == state, timeout, None)
+	}
+	/// Block until the source reaches `state`, returning `false` if `timeout` elapses first.
+	/// The state is polled every `poll_interval`, or at an interval derived from the length of the buffer if `None`.
+	fn wait_for_state(&self, state: SourceState, timeout: Option<Duration>, poll_interval: Option<Duration>) -> AltoResult<bool> {
+		poll_state(self, |s| s == state, timeout, poll_interval)
+	}
+	/// The current state, if it differs from `last`. Doesn't block.
+	fn changed_since(&self, last: SourceState) -> AltoResult<Option<SourceState>> {
+		self.state().map(|s| if s != last { Some(s) } else { None })
+	}
+	/// `alSourcePlay()`, then block until the source is no longer playing.
+	/// A looping source only returns once it is stopped or paused from another thread.
+	fn play_blocking(&mut self) -> AltoResult<()> {
+		self.play()?;
+		poll_state(self, |s| s != SourceState::Playing, None, None).map(|_| ())
+	}
+}
+
+
+/// Playback position of a source, as reported by `SourceTrait::playback_position()`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct PlaybackPosition {
+	/// `AL_SEC_OFFSET`, or `AL_SEC_OFFSET_LATENCY_SOFT` if available.
+	pub time: Duration,
+	/// Whole sample frames played.
+	pub frames: u64,
+	/// Fraction of the next frame played, in `[0, 1)`. Always 0 without `AL_SOFT_source_latency`.
+	pub frame_fraction: f64,
 }
 
 
@@ -263,6 +718,340 @@ pub enum SourceState {
 	Paused,
 	/// `AL_STOPPED`
 	Stopped,
+
+	/// A state this crate doesn't know, such as one added by an extension.
+	Unknown(sys::ALint),
+}
+
+
+/// The sound cone of a directional source, read and set with `SourceTrait::cone()` and `set_cone()`.
+/// Angles are in degrees. Listeners inside the inner angle hear the full gain, which fades toward the outer gains
+/// between the inner and outer angles. A source only has a cone if its direction is nonzero.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct SourceCone {
+	/// `AL_CONE_INNER_ANGLE`
+	pub inner_angle: f32,
+	/// `AL_CONE_OUTER_ANGLE`
+	pub outer_angle: f32,
+	/// `AL_CONE_OUTER_GAIN`
+	pub outer_gain: f32,
+	/// `AL_CONE_OUTER_GAINHF`, or `None` to leave it unchanged.
+	/// Requires `ALC_EXT_EFX`
+	pub outer_gain_hf: Option<f32>,
+}
+
+
+/// Every positional and gain-related property of a source, read with `SourceTrait::state_snapshot()`.
+/// Playback state, offsets, and the attached buffer are not included.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct SourceProps {
+	/// `AL_SOURCE_RELATIVE`
+	pub relative: bool,
+	/// `AL_GAIN`
+	pub gain: f32,
+	/// `AL_MIN_GAIN`
+	pub min_gain: f32,
+	/// `AL_MAX_GAIN`
+	pub max_gain: f32,
+	/// `AL_PITCH`
+	pub pitch: f32,
+	/// `AL_POSITION`
+	pub position: [f32; 3],
+	/// `AL_VELOCITY`
+	pub velocity: [f32; 3],
+	/// `AL_DIRECTION`
+	pub direction: [f32; 3],
+	/// `AL_REFERENCE_DISTANCE`
+	pub reference_distance: f32,
+	/// `AL_ROLLOFF_FACTOR`
+	pub rolloff_factor: f32,
+	/// `AL_MAX_DISTANCE`
+	pub max_distance: f32,
+	/// `AL_CONE_INNER_ANGLE`
+	pub cone_inner_angle: f32,
+	/// `AL_CONE_OUTER_ANGLE`
+	pub cone_outer_angle: f32,
+	/// `AL_CONE_OUTER_GAIN`
+	pub cone_outer_gain: f32,
+	/// `AL_DISTANCE_MODEL`, or `None` if `AL_EXT_source_distance_model` is unavailable.
+	/// Left unchanged by `SourceTrait::apply()` if `None`.
+	pub distance_model: Option<DistanceModel>,
+	#[cfg(feature = "efx")]
+	/// `AL_AIR_ABSORPTION_FACTOR`, or `None` if `ALC_EXT_EFX` is unavailable.
+	/// Left unchanged by `SourceTrait::apply()` if `None`.
+	pub air_absorption_factor: Option<f32>,
+	#[cfg(feature = "efx")]
+	/// `AL_ROOM_ROLLOFF_FACTOR`, or `None` if `ALC_EXT_EFX` is unavailable.
+	/// Left unchanged by `SourceTrait::apply()` if `None`.
+	pub room_rolloff_factor: Option<f32>,
+	#[cfg(feature = "efx")]
+	/// `AL_CONE_OUTER_GAINHF`, or `None` if `ALC_EXT_EFX` is unavailable.
+	/// Left unchanged by `SourceTrait::apply()` if `None`.
+	pub cone_outer_gainhf: Option<f32>,
+}
+
+
+/// A source property that can be read with `SourceTrait::get_many()`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum SourceProp {
+	/// `AL_SOURCE_STATE`
+	State,
+	/// `AL_SOURCE_RELATIVE`
+	Relative,
+	/// `AL_LOOPING`
+	Looping,
+	/// `AL_GAIN`
+	Gain,
+	/// `AL_MIN_GAIN`
+	MinGain,
+	/// `AL_MAX_GAIN`
+	MaxGain,
+	/// `AL_PITCH`
+	Pitch,
+	/// `AL_POSITION`
+	Position,
+	/// `AL_VELOCITY`
+	Velocity,
+	/// `AL_DIRECTION`
+	Direction,
+	/// `AL_REFERENCE_DISTANCE`
+	ReferenceDistance,
+	/// `AL_ROLLOFF_FACTOR`
+	RolloffFactor,
+	/// `AL_MAX_DISTANCE`
+	MaxDistance,
+	/// `AL_CONE_INNER_ANGLE`
+	ConeInnerAngle,
+	/// `AL_CONE_OUTER_ANGLE`
+	ConeOuterAngle,
+	/// `AL_CONE_OUTER_GAIN`
+	ConeOuterGain,
+	/// `AL_SEC_OFFSET`
+	SecOffset,
+	/// `AL_SAMPLE_OFFSET`
+	SampleOffset,
+	/// `AL_BYTE_OFFSET`
+	ByteOffset,
+	/// `AL_BUFFERS_QUEUED`
+	BuffersQueued,
+	/// `AL_BUFFERS_PROCESSED`
+	BuffersProcessed,
+}
+
+
+/// The value of a property read with `SourceTrait::get_many()`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum PropValue {
+	State(SourceState),
+	Bool(bool),
+	Float(f32),
+	Int(sys::ALint),
+	Vector([f32; 3]),
+}
+
+
+/// Values read with `SourceTrait::get_many()`, in the order they were requested.
+#[derive(Clone, PartialEq, Debug)]
+pub struct PropValues(Vec<(SourceProp, PropValue)>);
+
+
+impl SourceProp {
+	fn param(self) -> sys::ALenum {
+		match self {
+			SourceProp::State => sys::AL_SOURCE_STATE,
+			SourceProp::Relative => sys::AL_SOURCE_RELATIVE,
+			SourceProp::Looping => sys::AL_LOOPING,
+			SourceProp::Gain => sys::AL_GAIN,
+			SourceProp::MinGain => sys::AL_MIN_GAIN,
+			SourceProp::MaxGain => sys::AL_MAX_GAIN,
+			SourceProp::Pitch => sys::AL_PITCH,
+			SourceProp::Position => sys::AL_POSITION,
+			SourceProp::Velocity => sys::AL_VELOCITY,
+			SourceProp::Direction => sys::AL_DIRECTION,
+			SourceProp::ReferenceDistance => sys::AL_REFERENCE_DISTANCE,
+			SourceProp::RolloffFactor => sys::AL_ROLLOFF_FACTOR,
+			SourceProp::MaxDistance => sys::AL_MAX_DISTANCE,
+			SourceProp::ConeInnerAngle => sys::AL_CONE_INNER_ANGLE,
+			SourceProp::ConeOuterAngle => sys::AL_CONE_OUTER_ANGLE,
+			SourceProp::ConeOuterGain => sys::AL_CONE_OUTER_GAIN,
+			SourceProp::SecOffset => sys::AL_SEC_OFFSET,
+			SourceProp::SampleOffset => sys::AL_SAMPLE_OFFSET,
+			SourceProp::ByteOffset => sys::AL_BYTE_OFFSET,
+			SourceProp::BuffersQueued => sys::AL_BUFFERS_QUEUED,
+			SourceProp::BuffersProcessed => sys::AL_BUFFERS_PROCESSED,
+		}
+	}
+
+
+	fn name(self) -> &'static str {
+		match self {
+			SourceProp::State => "AL_SOURCE_STATE",
+			SourceProp::Relative => "AL_SOURCE_RELATIVE",
+			SourceProp::Looping => "AL_LOOPING",
+			SourceProp::Gain => "AL_GAIN",
+			SourceProp::MinGain => "AL_MIN_GAIN",
+			SourceProp::MaxGain => "AL_MAX_GAIN",
+			SourceProp::Pitch => "AL_PITCH",
+			SourceProp::Position => "AL_POSITION",
+			SourceProp::Velocity => "AL_VELOCITY",
+			SourceProp::Direction => "AL_DIRECTION",
+			SourceProp::ReferenceDistance => "AL_REFERENCE_DISTANCE",
+			SourceProp::RolloffFactor => "AL_ROLLOFF_FACTOR",
+			SourceProp::MaxDistance => "AL_MAX_DISTANCE",
+			SourceProp::ConeInnerAngle => "AL_CONE_INNER_ANGLE",
+			SourceProp::ConeOuterAngle => "AL_CONE_OUTER_ANGLE",
+			SourceProp::ConeOuterGain => "AL_CONE_OUTER_GAIN",
+			SourceProp::SecOffset => "AL_SEC_OFFSET",
+			SourceProp::SampleOffset => "AL_SAMPLE_OFFSET",
+			SourceProp::ByteOffset => "AL_BYTE_OFFSET",
+			SourceProp::BuffersQueued => "AL_BUFFERS_QUEUED",
+			SourceProp::BuffersProcessed => "AL_BUFFERS_PROCESSED",
+		}
+	}
+}
+
+
+impl PropValues {
+	/// The value read for `prop`, or `None` if it wasn't requested.
+	pub fn get(&self, prop: SourceProp) -> Option<PropValue> {
+		self.0.iter().find(|&&(p, _)| p == prop).map(|&(_, v)| v)
+	}
+	/// The value read for `prop` if it is a float, such as `Gain` or `SecOffset`.
+	pub fn float(&self, prop: SourceProp) -> Option<f32> {
+		match self.get(prop) { Some(PropValue::Float(v)) => Some(v), _ => None }
+	}
+	/// The value read for `prop` if it is an integer, such as `SampleOffset` or `BuffersProcessed`.
+	pub fn int(&self, prop: SourceProp) -> Option<sys::ALint> {
+		match self.get(prop) { Some(PropValue::Int(v)) => Some(v), _ => None }
+	}
+	/// The value read for `prop` if it is a flag, such as `Relative` or `Looping`.
+	pub fn bool(&self, prop: SourceProp) -> Option<bool> {
+		match self.get(prop) { Some(PropValue::Bool(v)) => Some(v), _ => None }
+	}
+	/// The value read for `prop` if it is a vector, such as `Position`.
+	pub fn vector<V: From<[f32; 3]>>(&self, prop: SourceProp) -> Option<V> {
+		match self.get(prop) { Some(PropValue::Vector(v)) => Some(v.into()), _ => None }
+	}
+	/// The value read for `SourceProp::State`.
+	pub fn state(&self) -> Option<SourceState> {
+		match self.get(SourceProp::State) { Some(PropValue::State(v)) => Some(v), _ => None }
+	}
+	#[inline]
+	pub fn iter(&self) -> slice::Iter<(SourceProp, PropValue)> { self.0.iter() }
+}
+
+
+/// Priority of sources that haven't been given one, leaving room both above and below.
+pub const DEFAULT_PRIORITY: u8 = 128;
+
+
+/// A snapshot of a source, as reported by `Context::sources()`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SourceInfo {
+	/// Raw handle as provided by OpenAL.
+	pub raw: sys::ALuint,
+	pub streaming: bool,
+	/// Whether the source is owned by the context's registry.
+	pub registered: bool,
+	/// The label given with `SourceTrait::set_tag()`.
+	pub tag: Option<String>,
+	/// The priority given with `SourceTrait::set_priority()`.
+	pub priority: u8,
+	/// `AL_SOURCE_STATE`
+	pub state: SourceState,
+	/// `AL_SOURCE_RELATIVE`
+	pub relative: bool,
+	/// `AL_POSITION`
+	pub position: [f32; 3],
+	/// `AL_GAIN`
+	pub gain: f32,
+}
+
+
+fn poll_state<'d: 'c, 'c, S, F>(src: &S, mut done: F, timeout: Option<Duration>, interval: Option<Duration>) -> AltoResult<bool> where
+	S: SourceTrait<'d, 'c> + ?Sized,
+	F: FnMut(SourceState) -> bool,
+{
+	let start = Instant::now();
+	loop {
+		if done(src.state()?) { return Ok(true) }
+
+		let mut interval = interval.unwrap_or_else(|| poll_interval(src));
+		if let Some(timeout) = timeout {
+			let elapsed = start.elapsed();
+			if elapsed >= timeout { return Ok(false) }
+			interval = cmp::min(interval, timeout - elapsed);
+		}
+		thread::sleep(interval);
+	}
+}
+
+
+/// An eighth of the current buffer's duration, but no longer than the time left to play it, within 1-100ms.
+fn poll_interval<'d: 'c, 'c, S: SourceTrait<'d, 'c> + ?Sized>(src: &S) -> Duration {
+	let secs = match buffer_secs(src.context(), src.as_raw()) {
+		Ok(len) if len > 0.0 => {
+			let remaining = len - src.sec_offset().unwrap_or(0.0);
+			if remaining > 0.0 { remaining.min(len / 8.0) } else { len / 8.0 }
+		},
+		_ => 0.01,
+	};
+	Duration::from_micros((secs.max(0.001).min(0.1) * 1_000_000.0) as u64)
+}
+
+
+/// The result of a query in `Debug` output, with the error shown in place of a value that couldn't be read.
+struct Live<T>(AltoResult<T>);
+
+
+impl<T: fmt::Debug> fmt::Debug for Live<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.0 {
+			Ok(ref value) => value.fmt(f),
+			Err(ref e) => write!(f, "<{}>", e),
+		}
+	}
+}
+
+
+/// Live properties shared by every kind of source.
+fn debug_source<'d: 'c, 'c, S: SourceTrait<'d, 'c>>(src: &S, d: &mut fmt::DebugStruct) {
+	d.field("raw", &src.as_raw())
+		.field("tag", &src.tag())
+		.field("priority", &src.priority())
+		.field("state", &Live(src.state()))
+		.field("gain", &Live(src.gain()))
+		.field("pitch", &Live(src.pitch()))
+		.field("relative", &Live(src.relative()))
+		.field("position", &Live(src.position::<[f32; 3]>()))
+		.field("velocity", &Live(src.velocity::<[f32; 3]>()))
+		.field("sec_offset", &Live(src.sec_offset()))
+		.field("sample_offset", &Live(src.sample_offset()))
+		.field("byte_offset", &Live(src.byte_offset()));
+}
+
+
+/// Duration in seconds of the buffer a source is currently playing.
+fn buffer_secs(ctx: &Context, src: sys::ALuint) -> AltoResult<f32> {
+	let _lock = ctx.make_current(true)?;
+	let (mut buf, mut freq, mut bits, mut chans, mut size) = (0, 0, 0, 0, 0);
+	unsafe {
+		ctx.api.head().alGetSourcei()(src, sys::AL_BUFFER, &mut buf);
+		ctx.get_error().map_err(|e| e.at("alGetSourcei", ObjectKind::Source, Some("AL_BUFFER".into())))?;
+		if buf == 0 { return Ok(0.0) }
+
+		ctx.api.head().alGetBufferi()(buf as sys::ALuint, sys::AL_FREQUENCY, &mut freq);
+		ctx.get_error().map_err(|e| e.at("alGetBufferi", ObjectKind::Buffer, Some("AL_FREQUENCY".into())))?;
+		ctx.api.head().alGetBufferi()(buf as sys::ALuint, sys::AL_BITS, &mut bits);
+		ctx.get_error().map_err(|e| e.at("alGetBufferi", ObjectKind::Buffer, Some("AL_BITS".into())))?;
+		ctx.api.head().alGetBufferi()(buf as sys::ALuint, sys::AL_CHANNELS, &mut chans);
+		ctx.get_error().map_err(|e| e.at("alGetBufferi", ObjectKind::Buffer, Some("AL_CHANNELS".into())))?;
+		ctx.api.head().alGetBufferi()(buf as sys::ALuint, sys::AL_SIZE, &mut size);
+		ctx.get_error().map_err(|e| e.at("alGetBufferi", ObjectKind::Buffer, Some("AL_SIZE".into())))?;
+	}
+
+	if freq <= 0 || bits <= 0 || chans <= 0 { return Ok(0.0) }
+	Ok((size as f32 * 8.0 / (bits * chans) as f32) / freq as f32)
 }
 
 
@@ -270,7 +1059,8 @@ pub enum SourceState {
 pub struct SourceImpl<'d: 'c, 'c> {
 	ctx: &'c Context<'d>,
 	src: sys::ALuint,
-	sends: Mutex<Vec<sys::ALuint>>,
+	/// Entry of the source in the context's `SourceTable`.
+	slot: Handle,
 }
 
 
@@ -288,16 +1078,184 @@ pub struct StreamingSource<'d: 'c, 'c> {
 }
 
 
+/// Builder for a source with its initial properties set, created with `Context::source_builder()`.
+/// Properties that aren't set keep their OpenAL defaults.
+pub struct SourceBuilder<'d: 'c, 'c: 's, 's> {
+	ctx: &'c Context<'d>,
+	buf: Option<Arc<Buffer<'d, 'c>>>,
+	looping: Option<bool>,
+	relative: Option<bool>,
+	gain: Option<f32>,
+	pitch: Option<f32>,
+	position: Option<[f32; 3]>,
+	velocity: Option<[f32; 3]>,
+	direction: Option<[f32; 3]>,
+	cone: Option<(f32, f32, f32)>,
+	#[cfg(feature = "efx")]
+	sends: Vec<(sys::ALint, &'s mut AuxEffectSlot<'d, 'c>)>,
+	#[cfg(not(feature = "efx"))]
+	sends: ::std::marker::PhantomData<&'s ()>,
+}
+
+
+impl SourceState {
+	/// Convert a value of `AL_SOURCE_STATE`. Values other than the four standard states become `Unknown`.
+	pub fn from_raw(value: sys::ALint) -> SourceState {
+		match value {
+			sys::AL_INITIAL => SourceState::Initial,
+			sys::AL_PLAYING => SourceState::Playing,
+			sys::AL_PAUSED => SourceState::Paused,
+			sys::AL_STOPPED => SourceState::Stopped,
+			v => SourceState::Unknown(v),
+		}
+	}
+
+
+	pub fn into_raw(self) -> sys::ALint {
+		match self {
+			SourceState::Initial => sys::AL_INITIAL,
+			SourceState::Playing => sys::AL_PLAYING,
+			SourceState::Paused => sys::AL_PAUSED,
+			SourceState::Stopped => sys::AL_STOPPED,
+			SourceState::Unknown(v) => v,
+		}
+	}
+}
+
+
+impl fmt::Display for SourceState {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			SourceState::Initial => f.pad("initial"),
+			SourceState::Playing => f.pad("playing"),
+			SourceState::Paused => f.pad("paused"),
+			SourceState::Stopped => f.pad("stopped"),
+			SourceState::Unknown(s) => f.pad(&format!("unknown ({})", s)),
+		}
+	}
+}
+
+
+impl fmt::Display for DistanceModel {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.pad(match *self {
+			DistanceModel::None => "none",
+			DistanceModel::Inverse => "inverse",
+			DistanceModel::InverseClamped => "inverse clamped",
+			DistanceModel::Linear => "linear",
+			DistanceModel::LinearClamped => "linear clamped",
+			DistanceModel::Exponent => "exponent",
+			DistanceModel::ExponentClamped => "exponent clamped",
+		})
+	}
+}
+
+
+impl SourceTable {
+	fn new() -> SourceTable {
+		SourceTable{live: Arena::new(), slots: HashMap::new(), users: HashMap::new()}
+	}
+
+
+	fn insert(&mut self, live: LiveSource) -> Handle {
+		let src = live.src;
+		let slot = self.live.insert(live);
+		self.slots.insert(src, slot);
+		slot
+	}
+
+
+	fn remove(&mut self, slot: Handle) -> Option<LiveSource> {
+		let live = self.live.remove(slot)?;
+		self.slots.remove(&live.src);
+		Some(live)
+	}
+
+
+	/// Remove a source, releasing the buffers it used.
+	fn forget(&mut self, slot: Handle) {
+		if let Some(live) = self.remove(slot) {
+			for &buf in &live.buffers {
+				release_user(&mut self.users, buf);
+			}
+		}
+	}
+
+
+	/// Record `bufs` as attached to or queued on a source, after releasing what it used before if `replace` is set.
+	fn attach(&mut self, slot: Handle, bufs: &[sys::ALuint], replace: bool) {
+		let live = match self.live.get_mut(slot) {
+			Some(live) => live,
+			None => return,
+		};
+		if replace {
+			for buf in live.buffers.drain(..) {
+				release_user(&mut self.users, buf);
+			}
+		}
+		for &buf in bufs {
+			live.buffers.push(buf);
+			*self.users.entry(buf).or_insert(0) += 1;
+		}
+	}
+
+
+	/// Record `bufs` as unqueued from a source.
+	fn detach(&mut self, slot: Handle, bufs: &[sys::ALuint]) {
+		let live = match self.live.get_mut(slot) {
+			Some(live) => live,
+			None => return,
+		};
+		for &buf in bufs {
+			if let Some(i) = live.buffers.iter().position(|&b| b == buf) {
+				live.buffers.remove(i);
+				release_user(&mut self.users, buf);
+			}
+		}
+	}
+}
+
+
+fn release_user(users: &mut HashMap<sys::ALuint, usize>, buf: sys::ALuint) {
+	if let Entry::Occupied(mut users) = users.entry(buf) {
+		*users.get_mut() -= 1;
+		if *users.get() == 0 {
+			users.remove();
+		}
+	}
+}
+
+
 impl<'d> Context<'d> {
 	#[doc(hidden)]
 	pub unsafe fn new(dev: &'d DeviceTrait, api: &'d AlApi, ctx: *mut sys::ALCcontext) -> Context<'d> {
-		Context{
+		let ctx = Context{
 			dev: dev,
 			api: api,
 			ctx: ctx,
+			owned: true,
 			exts: ext::AlCache::new(api.head()),
+			set_thread_context: api.rent(|exts| exts.ALC_EXT_thread_local_context().ok().and_then(|tlc| tlc.alcSetThreadContext.ok())),
 			defer_rc: Arc::new(AtomicUsize::new(0)),
+			deferred: DeferredWrites::new(),
+			registry: Mutex::new(Registry::default()),
+			lock: Mutex::new(()),
+			listener_velocity: Mutex::new(None),
+			source_defaults: Mutex::new(SourceDefaults::default()),
+			range_policy: Mutex::new(RangePolicy::default()),
+			position_epsilon: Mutex::new(None),
+			sources: Mutex::new(SourceTable::new()),
+			dropped_buffers: Mutex::new(Vec::new()),
+			held_buffers: Mutex::new(HashSet::new()),
+			staged_listener: Mutex::new(ListenerUpdate::default()),
+			memory: Mutex::new(MemoryTracker::new()),
+			events: Box::new(EventQueue::new()),
+		};
+		// Extension queries answer for whichever context is current, so resolve them all now while it is ours.
+		if let Ok(_lock) = ctx.make_current(true) {
+			ctx.exts.preload();
 		}
+		ctx
 	}
 
 
@@ -307,6 +1265,28 @@ impl<'d> Context<'d> {
 	pub fn as_raw(&self) -> *mut sys::ALCcontext { self.ctx }
 
 
+	/// Take ownership of a context created outside of alto. It will be destroyed when dropped.
+	///
+	/// # Safety
+	/// `ctx` must be a valid context that is not destroyed elsewhere.
+	pub unsafe fn from_raw(dev: &'d DeviceTrait, ctx: *mut sys::ALCcontext) -> AltoResult<Context<'d>> {
+		let api = dev.alto().raw_api();
+		if ctx == ptr::null_mut() || api.head().alcGetContextsDevice()(ctx) != dev.as_raw() {
+			return Err(AltoError::AlcInvalidContext);
+		}
+
+		Ok(Context::new(dev, api, ctx))
+	}
+
+
+	/// Release ownership of the context without destroying it.
+	/// Objects in its registry are left alive, but their handles can no longer be resolved.
+	pub fn into_raw(mut self) -> *mut sys::ALCcontext {
+		self.owned = false;
+		self.ctx
+	}
+
+
 	/// `alIsExtensionPresent()`
 	pub fn is_extension_present(&self, ext: ext::Al) -> bool {
 		match ext {
@@ -324,6 +1304,7 @@ impl<'d> Context<'d> {
 //			ext::Al::SoftBufferSubData => self.ext.AL_SOFT_buffer_sub_data().is_ok(),
 			ext::Al::SoftDeferredUpdates => self.exts.AL_SOFT_deferred_updates().is_ok(),
 			ext::Al::SoftDirectChannels => self.exts.AL_SOFT_direct_channels().is_ok(),
+			ext::Al::SoftEvents => self.exts.AL_SOFT_events().is_ok(),
 			ext::Al::SoftLoopPoints => self.exts.AL_SOFT_loop_points().is_ok(),
 			ext::Al::SoftMsadpcm => self.exts.AL_SOFT_MSADPCM().is_ok(),
 			ext::Al::SoftSourceLatency => self.exts.AL_SOFT_source_latency().is_ok(),
@@ -333,21 +1314,90 @@ impl<'d> Context<'d> {
 	}
 
 
+	/// Enums and functions of an advertised extension that didn't resolve, or `None` if it isn't advertised or nothing is missing.
+	/// Calls needing a missing symbol fail with `AlMissingSymbols` rather than `AlExtensionNotPresent`.
+	pub fn missing_symbols(&self, ext: ext::Al) -> Option<ext::MissingSymbols> {
+		let missing = match ext {
+			ext::Al::ALaw => self.exts.AL_EXT_ALAW().ok().map(|e| e.missing_symbols()),
+			ext::Al::BFormat => self.exts.AL_EXT_BFORMAT().ok().map(|e| e.missing_symbols()),
+			ext::Al::Double => self.exts.AL_EXT_double().ok().map(|e| e.missing_symbols()),
+			ext::Al::Float32 => self.exts.AL_EXT_float32().ok().map(|e| e.missing_symbols()),
+			ext::Al::Ima4 => self.exts.AL_EXT_IMA4().ok().map(|e| e.missing_symbols()),
+			ext::Al::McFormats => self.exts.AL_EXT_MCFORMATS().ok().map(|e| e.missing_symbols()),
+			ext::Al::MuLaw => self.exts.AL_EXT_MULAW().ok().map(|e| e.missing_symbols()),
+			ext::Al::MuLawBFormat => self.exts.AL_EXT_MULAW_BFORMAT().ok().map(|e| e.missing_symbols()),
+			ext::Al::MuLawMcFormats => self.exts.AL_EXT_MULAW_MCFORMATS().ok().map(|e| e.missing_symbols()),
+			ext::Al::SoftBlockAlignment => self.exts.AL_SOFT_block_alignment().ok().map(|e| e.missing_symbols()),
+//			ext::Al::SoftBufferSamples => self.ext.AL_SOFT_buffer_samples().ok().map(|e| e.missing_symbols()),
+//			ext::Al::SoftBufferSubData => self.ext.AL_SOFT_buffer_sub_data().ok().map(|e| e.missing_symbols()),
+			ext::Al::SoftDeferredUpdates => self.exts.AL_SOFT_deferred_updates().ok().map(|e| e.missing_symbols()),
+			ext::Al::SoftDirectChannels => self.exts.AL_SOFT_direct_channels().ok().map(|e| e.missing_symbols()),
+			ext::Al::SoftEvents => self.exts.AL_SOFT_events().ok().map(|e| e.missing_symbols()),
+			ext::Al::SoftLoopPoints => self.exts.AL_SOFT_loop_points().ok().map(|e| e.missing_symbols()),
+			ext::Al::SoftMsadpcm => self.exts.AL_SOFT_MSADPCM().ok().map(|e| e.missing_symbols()),
+			ext::Al::SoftSourceLatency => self.exts.AL_SOFT_source_latency().ok().map(|e| e.missing_symbols()),
+			ext::Al::SoftSourceLength => self.exts.AL_SOFT_source_length().ok().map(|e| e.missing_symbols()),
+			ext::Al::SourceDistanceModel => self.exts.AL_EXT_source_distance_model().ok().map(|e| e.missing_symbols()),
+		};
+		missing.filter(|m| !m.is_empty())
+	}
+
+
 	#[doc(hidden)]
 	pub fn extensions(&self) -> &ext::AlCache { &self.exts }
 
 
+	/// Fails with `AlcDeviceDisconnected` once the device was found to be disconnected.
+	#[doc(hidden)]
+	pub fn make_current(&self, set: bool) -> AltoResult<ContextLock> {
+		if set && self.dev.is_disconnected() { return Err(AltoError::AlcDeviceDisconnected) }
+		self.make_current_unchecked(set)
+	}
+	/// Like `make_current()`, but also after the device was disconnected, so that dropped objects are still deleted.
 	#[doc(hidden)]
-	pub fn make_current(&self, set: bool) -> AltoResult<Option<MutexGuard<()>>> {
-		self.api.rent(|exts| {
-			if let Ok(tlc) = exts.ALC_EXT_thread_local_context() {
-				unsafe { tlc.alcSetThreadContext?(if set { self.ctx } else { ptr::null_mut() }); }
-				self.dev.alto().get_error(self.dev.as_raw()).map(|_| None)
+	pub fn make_current_unchecked(&self, set: bool) -> AltoResult<ContextLock> {
+		let key = self.ctx as usize;
+		let held = HELD_CONTEXTS.with(|h| h.borrow().contains(&key));
+
+		// The locks must be taken before the context is made current, or another thread could replace it in between.
+		// With thread-local contexts, only calls on the same context need to be serialized, since they share its error state.
+		// Otherwise the global lock is taken first and held once per thread, so that a thread already holding it can
+		// lock a second context without waiting on itself or on a thread that is waiting for the global lock.
+		let lock = ContextLock{
+			ctx: key,
+			guards: if held {
+				None
 			} else {
-				unsafe { self.api.head().alcMakeContextCurrent()(if set { self.ctx } else { ptr::null_mut() }); }
-				self.dev.alto().get_error(self.dev.as_raw()).map(|_| Some(ALTO_CTX_LOCK__.lock().unwrap()))
-			}
-		})
+				let global = self.set_thread_context.is_none();
+				if global {
+					GLOBAL_HELD.with(|g| {
+						let mut g = g.borrow_mut();
+						if g.0 == 0 {
+							g.1 = Some(ALTO_CTX_LOCK__.lock().unwrap_or_else(|e| e.into_inner()));
+						}
+						g.0 += 1;
+					});
+				}
+				let ctx_guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+				HELD_CONTEXTS.with(|h| h.borrow_mut().push(key));
+				Some((ctx_guard, global))
+			},
+		};
+
+		if let Some(set_thread_context) = self.set_thread_context {
+			// A thread that keeps working with one context, such as a streaming worker, only switches once.
+			// The implementation keeps a thread's context alive while it is current, so its address can't be reused.
+			let target = if set { key } else { 0 };
+			if THREAD_CONTEXT.with(|c| c.get()) == target { return Ok(lock) }
+
+			unsafe { set_thread_context(target as *mut sys::ALCcontext); }
+			self.dev.alto().get_error(self.dev.as_raw()).map_err(|e| e.at("alcSetThreadContext", ObjectKind::Context, None))?;
+			THREAD_CONTEXT.with(|c| c.set(target));
+		} else {
+			unsafe { self.api.head().alcMakeContextCurrent()(if set { self.ctx } else { ptr::null_mut() }); }
+			self.dev.alto().get_error(self.dev.as_raw()).map_err(|e| e.at("alcMakeContextCurrent", ObjectKind::Context, None))?;
+		}
+		Ok(lock)
 	}
 
 
@@ -355,7 +1405,7 @@ impl<'d> Context<'d> {
 	pub fn distance_model(&self) -> AltoResult<DistanceModel> {
 		let _lock = self.make_current(true)?;
 		let value = unsafe { self.api.head().alGetInteger()(sys::AL_DISTANCE_MODEL) };
-		self.get_error().and_then(|_| match value {
+		self.get_error().map_err(|e| e.at("alGetInteger", ObjectKind::Context, Some("AL_DISTANCE_MODEL".into()))).and_then(|_| match value {
 			sys::AL_NONE => Ok(DistanceModel::None),
 			sys::AL_INVERSE_DISTANCE => Ok(DistanceModel::Inverse),
 			sys::AL_INVERSE_DISTANCE_CLAMPED => Ok(DistanceModel::InverseClamped),
@@ -368,19 +1418,18 @@ impl<'d> Context<'d> {
 	}
 	/// `alDistanceModel()`
 	pub fn set_distance_model(&self, value: DistanceModel) -> AltoResult<()> {
-		let _lock = self.make_current(true)?;
-		unsafe {
-			self.api.head().alDistanceModel()(match value {
-				DistanceModel::None => sys::AL_NONE,
-				DistanceModel::Inverse => sys::AL_INVERSE_DISTANCE,
-				DistanceModel::InverseClamped => sys::AL_INVERSE_DISTANCE_CLAMPED,
-				DistanceModel::Linear => sys::AL_LINEAR_DISTANCE,
-				DistanceModel::LinearClamped => sys::AL_LINEAR_DISTANCE_CLAMPED,
-				DistanceModel::Exponent => sys::AL_EXPONENT_DISTANCE,
-				DistanceModel::ExponentClamped => sys::AL_EXPONENT_DISTANCE_CLAMPED,
-			})
+		let model = match value {
+			DistanceModel::None => sys::AL_NONE,
+			DistanceModel::Inverse => sys::AL_INVERSE_DISTANCE,
+			DistanceModel::InverseClamped => sys::AL_INVERSE_DISTANCE_CLAMPED,
+			DistanceModel::Linear => sys::AL_LINEAR_DISTANCE,
+			DistanceModel::LinearClamped => sys::AL_LINEAR_DISTANCE_CLAMPED,
+			DistanceModel::Exponent => sys::AL_EXPONENT_DISTANCE,
+			DistanceModel::ExponentClamped => sys::AL_EXPONENT_DISTANCE_CLAMPED,
 		};
-		self.get_error()
+		let _lock = self.make_current(true)?;
+		unsafe { self.api.head().alDistanceModel()(model) };
+		self.get_error().map_err(|e| e.at("alDistanceModel", ObjectKind::Context, Some(("AL_DISTANCE_MODEL", model).into())))
 	}
 
 
@@ -389,7 +1438,7 @@ impl<'d> Context<'d> {
 	pub fn using_source_distance_model(&self) -> AltoResult<bool> {
 		let _lock = self.make_current(true)?;
 		let value = unsafe { self.api.head().alIsEnabled()(self.exts.AL_EXT_source_distance_model()?.AL_SOURCE_DISTANCE_MODEL?) };
-		self.get_error().map(|_| value == sys::AL_TRUE)
+		self.get_error().map_err(|e| e.at("alIsEnabled", ObjectKind::Context, Some("AL_SOURCE_DISTANCE_MODEL".into()))).map(|_| value == sys::AL_TRUE)
 	}
 	/// `alEnable/alDisable(AL_SOURCE_DISTANCE_MODEL)`
 	/// Requires `AL_EXT_source_distance_model`
@@ -400,7 +1449,7 @@ impl<'d> Context<'d> {
 		} else {
 			unsafe { self.api.head().alDisable()(self.exts.AL_EXT_source_distance_model()?.AL_SOURCE_DISTANCE_MODEL?); }
 		}
-		self.get_error()
+		self.get_error().map_err(|e| e.at("alDisable", ObjectKind::Context, Some("AL_SOURCE_DISTANCE_MODEL".into())))
 	}
 
 
@@ -408,13 +1457,14 @@ impl<'d> Context<'d> {
 	pub fn doppler_factor(&self) -> AltoResult<f32> {
 		let _lock = self.make_current(true)?;
 		let value = unsafe { self.api.head().alGetFloat()(sys::AL_DOPPLER_FACTOR) };
-		self.get_error().map(|_| value)
+		self.get_error().map_err(|e| e.at("alGetFloat", ObjectKind::Context, Some("AL_DOPPLER_FACTOR".into()))).map(|_| value)
 	}
 	/// `alDopplerFactor()`
 	pub fn set_doppler_factor(&self, value: f32) -> AltoResult<()> {
+		let value = self.check_range("AL_DOPPLER_FACTOR", "doppler factor must not be negative", value, NON_NEGATIVE)?;
 		let _lock = self.make_current(true)?;
 		unsafe { self.api.head().alDopplerFactor()(value); }
-		self.get_error()
+		self.get_error().map_err(|e| e.at("alDopplerFactor", ObjectKind::Context, Some(("AL_DOPPLER_FACTOR", value).into())))
 	}
 
 
@@ -422,60 +1472,97 @@ impl<'d> Context<'d> {
 	pub fn speed_of_sound(&self) -> AltoResult<f32> {
 		let _lock = self.make_current(true)?;
 		let value = unsafe { self.api.head().alGetFloat()(sys::AL_SPEED_OF_SOUND) };
-		self.get_error().map(|_| value)
+		self.get_error().map_err(|e| e.at("alGetFloat", ObjectKind::Context, Some("AL_SPEED_OF_SOUND".into()))).map(|_| value)
 	}
 	/// `alSpeedOfSound()`
 	pub fn set_speed_of_sound(&self, value: f32) -> AltoResult<()> {
+		let value = self.check_range("AL_SPEED_OF_SOUND", "speed of sound must be positive", value, POSITIVE)?;
 		let _lock = self.make_current(true)?;
 		unsafe { self.api.head().alSpeedOfSound()(value); }
-		self.get_error()
+		self.get_error().map_err(|e| e.at("alSpeedOfSound", ObjectKind::Context, Some(("AL_SPEED_OF_SOUND", value).into())))
 	}
 
 
 	/// `alGetListenerv(AL_GAIN)`
 	pub fn gain(&self) -> AltoResult<f32> {
+		if let Some(DeferredValue::Float(value)) = self.deferred.get(DeferredTarget::Listener, sys::AL_GAIN) { return Ok(value) }
+
 		let _lock = self.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { self.api.head().alGetListenerf()(sys::AL_GAIN, &mut value); }
-		self.get_error().map(|_| value)
+		self.get_error().map_err(|e| e.at("alGetListenerf", ObjectKind::Listener, Some("AL_GAIN".into()))).map(|_| value)
 	}
 	/// `alListenerf(AL_GAIN)`
 	pub fn set_gain(&self, value: f32) -> AltoResult<()> {
+		let value = self.check_range("AL_GAIN", "listener gain must not be negative", value, NON_NEGATIVE)?;
+		if self.deferred.defer(&self.defer_rc, DeferredTarget::Listener, sys::AL_GAIN, "AL_GAIN", DeferredValue::Float(value)) { return Ok(()) }
+
 		let _lock = self.make_current(true)?;
 		unsafe { self.api.head().alListenerf()(sys::AL_GAIN, value); }
-		self.get_error()
+		self.get_error().map_err(|e| e.at("alListenerf", ObjectKind::Listener, Some(("AL_GAIN", value).into())))
 	}
 
 
 	/// `alGetListenerfv(AL_POSITION)`
 	pub fn position<V: From<[f32; 3]>>(&self) -> AltoResult<V> {
+		if let Some(DeferredValue::Vector(value)) = self.deferred.get(DeferredTarget::Listener, sys::AL_POSITION) { return Ok(value.into()) }
+
 		let _lock = self.make_current(true)?;
 		let mut value = [0.0, 0.0, 0.0];
 		unsafe { self.api.head().alGetListenerfv()(sys::AL_POSITION, &mut value as *mut [f32; 3] as *mut sys::ALfloat); }
-		self.get_error().map(|_| value.into())
+		self.get_error().map_err(|e| e.at("alGetListenerfv", ObjectKind::Listener, Some("AL_POSITION".into()))).map(|_| value.into())
 	}
 	/// `alListenerfv(AL_POSITION)`
+	/// Also sets `AL_VELOCITY` if automatic velocity is enabled.
 	pub fn set_position<V: Into<[f32; 3]>>(&self, value: V) -> AltoResult<()> {
-		let _lock = self.make_current(true)?;
 		let value = value.into();
-		unsafe { self.api.head().alListenerfv()(sys::AL_POSITION, &value as *const [f32; 3] as *const sys::ALfloat); }
-		self.get_error()
+		if !self.deferred.defer(&self.defer_rc, DeferredTarget::Listener, sys::AL_POSITION, "AL_POSITION", DeferredValue::Vector(value)) {
+			let _lock = self.make_current(true)?;
+			unsafe { self.api.head().alListenerfv()(sys::AL_POSITION, &value as *const [f32; 3] as *const sys::ALfloat); }
+			self.get_error().map_err(|e| e.at("alListenerfv", ObjectKind::Listener, Some(("AL_POSITION", value).into())))?;
+		}
+
+		let vel = self.listener_velocity.lock().unwrap_or_else(|e| e.into_inner()).as_mut().and_then(|t| t.update(value, Instant::now()));
+		if let Some(vel) = vel {
+			self.set_velocity(vel)?;
+		}
+		Ok(())
+	}
+
+
+	/// The smoothing time constant, in seconds, of automatic listener velocity, or `None` if it is disabled.
+	pub fn auto_velocity(&self) -> Option<f32> {
+		self.listener_velocity.lock().unwrap_or_else(|e| e.into_inner()).map(|t| t.smoothing())
+	}
+	/// Derive the listener's velocity from successive calls to `set_position()`, so that Doppler shift works
+	/// without tracking velocity by hand. Velocities are smoothed over `smoothing` seconds; `0.0` disables smoothing.
+	/// Passing `None` disables tracking and resets the velocity to zero.
+	pub fn set_auto_velocity(&self, smoothing: Option<f32>) -> AltoResult<()> {
+		let was = mem::replace(&mut *self.listener_velocity.lock().unwrap_or_else(|e| e.into_inner()), smoothing.map(VelocityTracker::new));
+		if was.is_some() && smoothing.is_none() {
+			self.set_velocity([0.0, 0.0, 0.0])?;
+		}
+		Ok(())
 	}
 
 
 	/// `alGetListenerfv(AL_VELOCITY)`
 	pub fn velocity<V: From<[f32; 3]>>(&self) -> AltoResult<V> {
+		if let Some(DeferredValue::Vector(value)) = self.deferred.get(DeferredTarget::Listener, sys::AL_VELOCITY) { return Ok(value.into()) }
+
 		let _lock = self.make_current(true)?;
 		let mut value = [0.0, 0.0, 0.0];
 		unsafe { self.api.head().alGetListenerfv()(sys::AL_VELOCITY, &mut value as *mut [f32; 3] as *mut sys::ALfloat); }
-		self.get_error().map(|_| value.into())
+		self.get_error().map_err(|e| e.at("alGetListenerfv", ObjectKind::Listener, Some("AL_VELOCITY".into()))).map(|_| value.into())
 	}
 	/// `alListenerfv(AL_VELOCITY)`
 	pub fn set_velocity<V: Into<[f32; 3]>>(&self, value: V) -> AltoResult<()> {
-		let _lock = self.make_current(true)?;
 		let value = value.into();
+		if self.deferred.defer(&self.defer_rc, DeferredTarget::Listener, sys::AL_VELOCITY, "AL_VELOCITY", DeferredValue::Vector(value)) { return Ok(()) }
+
+		let _lock = self.make_current(true)?;
 		unsafe { self.api.head().alListenerfv()(sys::AL_VELOCITY, &value as *const [f32; 3] as *const sys::ALfloat); }
-		self.get_error()
+		self.get_error().map_err(|e| e.at("alListenerfv", ObjectKind::Listener, Some(("AL_VELOCITY", value).into())))
 	}
 
 
@@ -484,14 +1571,14 @@ impl<'d> Context<'d> {
 		let _lock = self.make_current(true)?;
 		let mut value = [[0.0, 0.0, 0.0], [0.0, 0.0, 0.0]];
 		unsafe { self.api.head().alGetListenerfv()(sys::AL_ORIENTATION, &mut value as *mut [[f32; 3]; 2] as *mut sys::ALfloat); }
-		self.get_error().map(|_| (value[0].into(), value[1].into()))
+		self.get_error().map_err(|e| e.at("alGetListenerfv", ObjectKind::Listener, Some("AL_ORIENTATION".into()))).map(|_| (value[0].into(), value[1].into()))
 	}
 	/// `alListenerfv(AL_ORIENTATION)`
 	pub fn set_orientation<V: Into<[f32; 3]>>(&self, value: (V, V)) -> AltoResult<()> {
 		let _lock = self.make_current(true)?;
 		let value = [value.0.into(), value.1.into()];
 		unsafe { self.api.head().alListenerfv()(sys::AL_ORIENTATION, &value as *const [[f32; 3]; 2] as *const sys::ALfloat); }
-		self.get_error()
+		self.get_error().map_err(|e| e.at("alListenerfv", ObjectKind::Listener, Some(("AL_ORIENTATION", value).into())))
 	}
 
 
@@ -502,107 +1589,478 @@ impl<'d> Context<'d> {
 		let _lock = self.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { self.api.head().alGetListenerf()(efx.AL_METERS_PER_UNIT?, &mut value); }
-		self.get_error().map(|_| value)
+		self.get_error().map_err(|e| e.at("alGetListenerf", ObjectKind::Listener, Some("AL_METERS_PER_UNIT".into()))).map(|_| value)
 	}
 	/// `alListenerf(AL_METERS_PER_UNIT)`
 	/// Requires `ALC_EXT_EFX`
 	pub fn set_meters_per_unit(&self, value: f32) -> AltoResult<()> {
 		let efx = self.dev.extensions().ALC_EXT_EFX()?;
+		let value = self.check_range("AL_METERS_PER_UNIT", "meters per unit must be positive", value, METERS_PER_UNIT)?;
 		let _lock = self.make_current(true)?;
 		unsafe { self.api.head().alListenerf()(efx.AL_METERS_PER_UNIT?, value); }
-		self.get_error()
+		self.get_error().map_err(|e| e.at("alListenerf", ObjectKind::Listener, Some(("AL_METERS_PER_UNIT", value).into())))
 	}
 
 
-	/// `alGenBuffers()`
-	pub fn new_buffer<'c>(&'c self) -> AltoResult<Buffer<'d, 'c>> {
-		Buffer::new(self)
+	/// Read every global acoustic property while holding the context, so no other thread can change them in between.
+	pub fn acoustics(&self) -> AltoResult<Acoustics> {
+		let _lock = self.make_current(true)?;
+		Ok(Acoustics{
+			distance_model: self.distance_model()?,
+			speed_of_sound: self.speed_of_sound()?,
+			doppler_factor: self.doppler_factor()?,
+			meters_per_unit: if self.dev.extensions().ALC_EXT_EFX().is_ok() { Some(self.meters_per_unit()?) } else { None },
+		})
+	}
+	/// Apply every global acoustic property at once, such as when changing the scale of the world.
+	/// Updates are deferred until all properties are set if `AL_SOFT_deferred_updates` is available.
+	pub fn set_acoustics(&self, acoustics: &Acoustics) -> AltoResult<()> {
+		let _lock = self.make_current(true)?;
+		let suspend = self.suspended().ok();
+
+		self.set_distance_model(acoustics.distance_model)?;
+		self.set_speed_of_sound(acoustics.speed_of_sound)?;
+		self.set_doppler_factor(acoustics.doppler_factor)?;
+		if let Some(mpu) = acoustics.meters_per_unit {
+			self.set_meters_per_unit(mpu)?;
+		}
+		suspend.map_or(Ok(()), SuspendLock::commit)
+	}
+
+
+	/// `alGenBuffers()`
+	pub fn new_buffer<'c>(&'c self) -> AltoResult<Buffer<'d, 'c>> {
+		Buffer::new(self)
+	}
+
+
+	/// Delete the buffers dropped since the last call. Buffers still attached to or queued on a source are kept
+	/// for a later call, as are buffers the implementation refuses to delete because a source alto doesn't track,
+	/// such as one released with `into_raw()` or one on another context, still uses them. If some can't be deleted
+	/// for another reason, the rest are deleted anyway and the first error is returned.
+	fn delete_dropped_buffers(&self) -> AltoResult<()> {
+		let bufs = mem::replace(&mut *self.dropped_buffers.lock().unwrap_or_else(|e| e.into_inner()), Vec::new());
+		if bufs.is_empty() { return Ok(()) }
+
+		let (mut pending, bufs): (Vec<_>, Vec<_>) = {
+			let table = self.sources.lock().unwrap_or_else(|e| e.into_inner());
+			bufs.into_iter().partition(|buf| table.users.contains_key(buf))
+		};
+		let result = self.delete_unused_buffers(bufs, &mut pending);
+		if !pending.is_empty() {
+			self.dropped_buffers.lock().unwrap_or_else(|e| e.into_inner()).extend(pending);
+		}
+		result
+	}
+
+
+	/// Delete buffers that no tracked source uses, adding those that turn out to be in use anyway to `pending`.
+	fn delete_unused_buffers(&self, bufs: Vec<sys::ALuint>, pending: &mut Vec<sys::ALuint>) -> AltoResult<()> {
+		if bufs.is_empty() { return Ok(()) }
+
+		let _lock = match self.make_current_unchecked(true) {
+			Ok(lock) => lock,
+			Err(e) => {
+				pending.extend(bufs);
+				return Err(e);
+			},
+		};
+		unsafe { self.api.head().alDeleteBuffers()(bufs.len() as sys::ALsizei, bufs.as_ptr()); }
+		if let Ok(()) = self.get_error() {
+			self.buffers_deleted(&bufs);
+			return Ok(())
+		}
+
+		// Nothing is deleted if any name fails, so fall back to deleting them one at a time.
+		let mut result = Ok(());
+		for buf in bufs {
+			unsafe { self.api.head().alDeleteBuffers()(1, &buf); }
+			match self.get_error() {
+				Ok(()) => self.buffers_deleted(&[buf]),
+				Err(AltoError::AlInvalidOperation) => pending.push(buf),
+				Err(e) => {
+					if result.is_ok() {
+						result = Err(e.at("alDeleteBuffers", ObjectKind::Buffer, Some(("buffer", buf).into())));
+					}
+				},
+			}
+		}
+		result
 	}
 
 
 	/// `alGenSources()`
 	pub fn new_static_source<'c>(&'c self) -> AltoResult<StaticSource<'d, 'c>> {
-		StaticSource::new(self)
+		let mut src = StaticSource::new(self)?;
+		self.source_defaults().apply(self, &mut src)?;
+		Ok(src)
 	}
 
 
 	/// `alGenSources()`
 	pub fn new_streaming_source<'c>(&'c self) -> AltoResult<StreamingSource<'d, 'c>> {
-		StreamingSource::new(self)
+		let mut src = StreamingSource::new(self)?;
+		self.source_defaults().apply(self, &mut src)?;
+		Ok(src)
+	}
+
+
+	/// `alGenSources()`
+	/// Generates `n` sources in a single call, such as to allocate a pool of voices up front.
+	/// Either every source is created or none are.
+	pub fn new_static_sources<'c>(&'c self, n: usize) -> AltoResult<Vec<StaticSource<'d, 'c>>> {
+		let mut srcs: Vec<_> = SourceImpl::gen(self, n, false)?.into_iter().map(|src| StaticSource{src: Arc::new(src), buf: None}).collect();
+		let defaults = self.source_defaults();
+		for src in &mut srcs {
+			defaults.apply(self, src)?;
+		}
+		Ok(srcs)
+	}
+
+
+	/// `alGenSources()`
+	/// Generates `n` sources in a single call, such as to allocate a pool of voices up front.
+	/// Either every source is created or none are.
+	pub fn new_streaming_sources<'c>(&'c self, n: usize) -> AltoResult<Vec<StreamingSource<'d, 'c>>> {
+		let mut srcs: Vec<_> = SourceImpl::gen(self, n, true)?.into_iter().map(|src| StreamingSource{src: Arc::new(src), bufs: VecDeque::new()}).collect();
+		let defaults = self.source_defaults();
+		for src in &mut srcs {
+			defaults.apply(self, src)?;
+		}
+		Ok(srcs)
+	}
+
+
+	/// Properties that new sources of this context start with.
+	pub fn source_defaults(&self) -> SourceDefaults {
+		*self.source_defaults.lock().unwrap_or_else(|e| e.into_inner())
+	}
+	/// Set the properties that sources created from now on start with, such as a project-wide attenuation curve.
+	/// Properties set through a `SourceBuilder` take precedence.
+	pub fn set_source_defaults(&self, defaults: SourceDefaults) {
+		*self.source_defaults.lock().unwrap_or_else(|e| e.into_inner()) = defaults;
+	}
+
+
+	/// The distance below which source position and velocity updates are skipped, or `None` if they never are.
+	pub fn position_epsilon(&self) -> Option<f32> {
+		*self.position_epsilon.lock().unwrap_or_else(|e| e.into_inner())
+	}
+	/// Skip `set_position()` and `set_velocity()` on this context's sources when the new value is less than
+	/// `epsilon` away from the last one written, so stationary emitters updated every frame cost no calls into OpenAL.
+	/// Skipped values don't move the reference point, so slow drift is still written once it adds up to `epsilon`.
+	/// Automatic velocity still sees every position. Values written with `as_raw()` are not noticed.
+	pub fn set_position_epsilon(&self, epsilon: Option<f32>) {
+		let mut current = self.position_epsilon.lock().unwrap_or_else(|e| e.into_inner());
+		let mut table = self.sources.lock().unwrap_or_else(|e| e.into_inner());
+		for live in table.live.values_mut() {
+			live.last_position = None;
+			live.last_velocity = None;
+		}
+		*current = epsilon;
+	}
+
+
+	/// Start building a source with its properties set in one expression.
+	pub fn source_builder<'c, 's>(&'c self) -> SourceBuilder<'d, 'c, 's> {
+		SourceBuilder::new(self)
 	}
 
 
 	/// `alSourcePlayv()`
-	pub fn play_all<'c, S, I>(&self, srcs: I) -> AltoResult<()> where
+	/// All sources start at the same sample, so layered stems stay in sync.
+	/// Fails with `AlWrongContext` if any source belongs to another context.
+	pub fn play_all<'c, 's, S, I>(&self, srcs: I) -> AltoResult<()> where
 		'd: 'c,
-		S: SourceTrait<'d, 'c>,
-		I: Iterator,
-		<I as Iterator>::Item: AsRef<S> + AsMut<S>,
+		S: SourceTrait<'d, 'c> + 's,
+		I: IntoIterator<Item = &'s mut S>,
 	{
-		let v: Vec<_> = srcs.filter(|s| s.as_ref().context() == self).map(|s| s.as_ref().as_raw()).collect();
-		if v.len() > sys::ALint::max_value() as usize { return Err(AltoError::AlInvalidValue) }
-
+		let v = self.source_names(srcs)?;
+		alto_event!(debug, sources = v.len(), "play_all");
 		let _lock = self.make_current(true)?;
 		unsafe { self.api.head().alSourcePlayv()(v.len() as i32, v.as_slice().as_ptr()); }
-		self.get_error()
+		self.get_error().map_err(|e| e.at("alSourcePlayv", ObjectKind::Source, Some(("n", v.len()).into())))
 	}
 
 
 	/// `alSourcePausev()`
-	pub fn pause_all<'c, S, I>(&self, srcs: I) -> AltoResult<()> where
+	/// Fails with `AlWrongContext` if any source belongs to another context.
+	pub fn pause_all<'c, 's, S, I>(&self, srcs: I) -> AltoResult<()> where
 		'd: 'c,
-		S: SourceTrait<'d, 'c>,
-		I: Iterator,
-		<I as Iterator>::Item: AsRef<S> + AsMut<S>,
+		S: SourceTrait<'d, 'c> + 's,
+		I: IntoIterator<Item = &'s mut S>,
 	{
-		let v: Vec<_> = srcs.filter(|s| s.as_ref().context() == self).map(|s| s.as_ref().as_raw()).collect();
-		if v.len() > sys::ALint::max_value() as usize { return Err(AltoError::AlInvalidValue) }
-
+		let v = self.source_names(srcs)?;
+		alto_event!(debug, sources = v.len(), "pause_all");
 		let _lock = self.make_current(true)?;
 		unsafe { self.api.head().alSourcePausev()(v.len() as i32, v.as_slice().as_ptr()); }
-		self.get_error()
+		self.get_error().map_err(|e| e.at("alSourcePausev", ObjectKind::Source, Some(("n", v.len()).into())))
 	}
 
 
 	/// `alSourceStopv()`
-	pub fn stop_all<'c, S, I>(&self, srcs: I) -> AltoResult<()> where
+	/// Fails with `AlWrongContext` if any source belongs to another context.
+	pub fn stop_all<'c, 's, S, I>(&self, srcs: I) -> AltoResult<()> where
 		'd: 'c,
-		S: SourceTrait<'d, 'c>,
-		I: Iterator,
-		<I as Iterator>::Item: AsRef<S> + AsMut<S>,
+		S: SourceTrait<'d, 'c> + 's,
+		I: IntoIterator<Item = &'s mut S>,
 	{
-		let v: Vec<_> = srcs.filter(|s| s.as_ref().context() == self).map(|s| s.as_ref().as_raw()).collect();
-		if v.len() > sys::ALint::max_value() as usize { return Err(AltoError::AlInvalidValue) }
-
+		let v = self.source_names(srcs)?;
+		alto_event!(debug, sources = v.len(), "stop_all");
 		let _lock = self.make_current(true)?;
 		unsafe { self.api.head().alSourceStopv()(v.len() as i32, v.as_slice().as_ptr()); }
-		self.get_error()
+		self.get_error().map_err(|e| e.at("alSourceStopv", ObjectKind::Source, Some(("n", v.len()).into())))
 	}
 
 
 	/// `alSourceRewindv()`
-	pub fn rewind_all<'c, S, I>(&self, srcs: I) -> AltoResult<()> where
+	/// Fails with `AlWrongContext` if any source belongs to another context.
+	pub fn rewind_all<'c, 's, S, I>(&self, srcs: I) -> AltoResult<()> where
 		'd: 'c,
-		S: SourceTrait<'d, 'c>,
-		I: Iterator,
-		<I as Iterator>::Item: AsRef<S> + AsMut<S>,
+		S: SourceTrait<'d, 'c> + 's,
+		I: IntoIterator<Item = &'s mut S>,
 	{
-		let v: Vec<_> = srcs.filter(|s| s.as_ref().context() == self).map(|s| s.as_ref().as_raw()).collect();
+		let v = self.source_names(srcs)?;
+		alto_event!(debug, sources = v.len(), "rewind_all");
+		let _lock = self.make_current(true)?;
+		unsafe { self.api.head().alSourceRewindv()(v.len() as i32, v.as_slice().as_ptr()); }
+		self.get_error().map_err(|e| e.at("alSourceRewindv", ObjectKind::Source, Some(("n", v.len()).into())))
+	}
+
+
+	fn source_names<'c, 's, S, I>(&self, srcs: I) -> AltoResult<Vec<sys::ALuint>> where
+		'd: 'c,
+		S: SourceTrait<'d, 'c> + 's,
+		I: IntoIterator<Item = &'s mut S>,
+	{
+		let mut v = Vec::new();
+		for src in srcs {
+			if src.context() != self { return Err(AltoError::AlWrongContext) }
+			v.push(src.as_raw());
+		}
 		if v.len() > sys::ALint::max_value() as usize { return Err(AltoError::AlInvalidValue) }
+		Ok(v)
+	}
+
+
+	/// The listener of this context.
+	#[inline]
+	pub fn listener<'c>(&'c self) -> Listener<'d, 'c> { Listener(self) }
+
 
+	/// A snapshot of every source alive in this context, including registered sources, ordered by handle.
+	/// Intended for debug overlays and diagnostics.
+	pub fn sources(&self) -> AltoResult<Vec<SourceInfo>> {
 		let _lock = self.make_current(true)?;
-		unsafe { self.api.head().alSourceRewindv()(v.len() as i32, v.as_slice().as_ptr()); }
-		self.get_error()
+		let mut live: Vec<_> = self.sources.lock().unwrap_or_else(|e| e.into_inner()).live.iter().map(|(_, live)| (live.src, (live.streaming, live.registered, live.tag.clone(), live.priority))).collect();
+		live.sort_by_key(|&(src, _)| src);
+
+		let mut infos = Vec::with_capacity(live.len());
+		for (src, (streaming, registered, tag, priority)) in live {
+			let (mut state, mut relative, mut position, mut gain) = (0, 0, [0.0, 0.0, 0.0], 0.0);
+			unsafe {
+				self.api.head().alGetSourcei()(src, sys::AL_SOURCE_STATE, &mut state);
+				self.get_error().map_err(|e| e.at("alGetSourcei", ObjectKind::Source, Some("AL_SOURCE_STATE".into())))?;
+				self.api.head().alGetSourcei()(src, sys::AL_SOURCE_RELATIVE, &mut relative);
+				self.get_error().map_err(|e| e.at("alGetSourcei", ObjectKind::Source, Some("AL_SOURCE_RELATIVE".into())))?;
+				self.api.head().alGetSourcefv()(src, sys::AL_POSITION, &mut position as *mut [f32; 3] as *mut sys::ALfloat);
+				self.get_error().map_err(|e| e.at("alGetSourcefv", ObjectKind::Source, Some("AL_POSITION".into())))?;
+				self.api.head().alGetSourcef()(src, sys::AL_GAIN, &mut gain);
+				self.get_error().map_err(|e| e.at("alGetSourcef", ObjectKind::Source, Some("AL_GAIN".into())))?;
+			}
+
+			infos.push(SourceInfo{
+				raw: src,
+				streaming: streaming,
+				registered: registered,
+				tag: tag,
+				priority: priority,
+				state: SourceState::from_raw(state),
+				relative: relative == sys::AL_TRUE as sys::ALint,
+				position: position,
+				gain: gain,
+			});
+		}
+		Ok(infos)
+	}
+
+
+	/// Pause every playing source in this context, including registered sources, such as for an
+	/// audio session interruption or a pause menu. The returned token resumes exactly those sources.
+	pub fn pause_all_playing<'c>(&'c self) -> AltoResult<PausedSources<'d, 'c>> {
+		let _lock = self.make_current(true)?;
+		let playing = self.sources_in_state(self.sources.lock().unwrap_or_else(|e| e.into_inner()).live.iter().map(|(_, live)| live.src), SourceState::Playing)?;
+
+		if !playing.is_empty() {
+			unsafe { self.api.head().alSourcePausev()(playing.len() as sys::ALsizei, playing.as_ptr()); }
+			self.get_error().map_err(|e| e.at("alSourcePausev", ObjectKind::Source, Some(("n", playing.len()).into())))?;
+		}
+		Ok(PausedSources{ctx: self, srcs: playing})
+	}
+
+
+	fn sources_in_state<I: IntoIterator<Item = sys::ALuint>>(&self, srcs: I, state: SourceState) -> AltoResult<Vec<sys::ALuint>> {
+		let mut v = Vec::new();
+		for src in srcs {
+			let mut s = 0;
+			unsafe { self.api.head().alGetSourcei()(src, sys::AL_SOURCE_STATE, &mut s); }
+			self.get_error().map_err(|e| e.at("alGetSourcei", ObjectKind::Source, Some("AL_SOURCE_STATE".into())))?;
+			if SourceState::from_raw(s) == state { v.push(src); }
+		}
+		v.sort();
+		Ok(v)
+	}
+
+
+	fn track_source(&self, src: sys::ALuint, streaming: bool) -> Handle {
+		#[cfg(feature = "efx")]
+		let sends = vec![0; self.dev.max_auxiliary_sends().unwrap_or(0) as usize];
+		self.sources.lock().unwrap_or_else(|e| e.into_inner()).insert(LiveSource{
+			src: src,
+			streaming: streaming,
+			registered: false,
+			tag: None,
+			priority: DEFAULT_PRIORITY,
+			range: None,
+			user_data: None,
+			#[cfg(feature = "efx")]
+			sends: sends,
+			velocity: None,
+			last_position: None,
+			last_velocity: None,
+			buffers: Vec::new(),
+		})
+	}
+	fn untrack_source(&self, slot: Handle) -> Option<LiveSource> {
+		self.sources.lock().unwrap_or_else(|e| e.into_inner()).remove(slot)
+	}
+	/// Stop tracking a source that is deleted or released, so the buffers it uses no longer count as in use.
+	fn forget_source(&self, slot: Handle) {
+		self.sources.lock().unwrap_or_else(|e| e.into_inner()).forget(slot);
+	}
+	fn forget_named_source(&self, src: sys::ALuint) {
+		let mut table = self.sources.lock().unwrap_or_else(|e| e.into_inner());
+		if let Some(slot) = table.slots.get(&src).cloned() {
+			table.forget(slot);
+		}
+	}
+	fn attach_buffers(&self, slot: Handle, bufs: &[sys::ALuint], replace: bool) {
+		self.sources.lock().unwrap_or_else(|e| e.into_inner()).attach(slot, bufs, replace);
+	}
+	fn detach_buffers(&self, slot: Handle, bufs: &[sys::ALuint]) {
+		self.sources.lock().unwrap_or_else(|e| e.into_inner()).detach(slot, bufs);
+	}
+	fn source_slot(&self, src: sys::ALuint) -> Option<Handle> {
+		self.sources.lock().unwrap_or_else(|e| e.into_inner()).slots.get(&src).cloned()
+	}
+	fn with_live_source<R, F: FnOnce(&mut LiveSource) -> R>(&self, slot: Handle, f: F) -> Option<R> {
+		self.sources.lock().unwrap_or_else(|e| e.into_inner()).live.get_mut(slot).map(f)
+	}
+	fn with_named_source<R, F: FnOnce(&mut LiveSource) -> R>(&self, src: sys::ALuint, f: F) -> Option<R> {
+		let mut table = self.sources.lock().unwrap_or_else(|e| e.into_inner());
+		let slot = table.slots.get(&src).cloned()?;
+		table.live.get_mut(slot).map(f)
+	}
+	fn source_tag(&self, src: sys::ALuint) -> Option<String> {
+		self.with_named_source(src, |live| live.tag.clone()).and_then(|t| t)
+	}
+	fn set_source_tag(&self, src: sys::ALuint, tag: Option<&str>) {
+		self.with_named_source(src, |live| live.tag = tag.map(|t| t.to_owned()));
+	}
+	fn source_priority(&self, src: sys::ALuint) -> u8 {
+		self.with_named_source(src, |live| live.priority).unwrap_or(DEFAULT_PRIORITY)
+	}
+	fn set_source_priority(&self, src: sys::ALuint, priority: u8) {
+		self.with_named_source(src, |live| live.priority = priority);
+	}
+	fn set_source_range(&self, slot: Handle, range: Option<(sys::ALint, sys::ALint)>) {
+		self.with_live_source(slot, |live| live.range = range);
+	}
+
+
+	/// Stop sources played with `play_range()` that have passed the end of their range, or seek looping ones back to its start.
+	fn enforce_ranges(&self) -> AltoResult<()> {
+		let ranged: Vec<_> = self.sources.lock().unwrap_or_else(|e| e.into_inner()).live.iter()
+			.filter_map(|(slot, live)| live.range.map(|r| (slot, live.src, r)))
+			.collect();
+		if ranged.is_empty() { return Ok(()) }
+
+		let _lock = self.make_current(true)?;
+		for (slot, src, (start, end)) in ranged {
+			let (mut state, mut offset, mut looping) = (0, 0, 0);
+			unsafe {
+				self.api.head().alGetSourcei()(src, sys::AL_SOURCE_STATE, &mut state);
+				self.api.head().alGetSourcei()(src, sys::AL_SAMPLE_OFFSET, &mut offset);
+				self.api.head().alGetSourcei()(src, sys::AL_LOOPING, &mut looping);
+			}
+			self.get_error().map_err(|e| e.at("alGetSourcei", ObjectKind::Source, Some("AL_SAMPLE_OFFSET".into())))?;
+
+			if state != sys::AL_PLAYING && state != sys::AL_PAUSED {
+				self.set_source_range(slot, None);
+			} else if offset >= end || offset < start {
+				if looping == sys::AL_TRUE as sys::ALint {
+					unsafe { self.api.head().alSourcei()(src, sys::AL_SAMPLE_OFFSET, start); }
+				} else {
+					unsafe { self.api.head().alSourceStop()(src); }
+					self.set_source_range(slot, None);
+				}
+				self.get_error().map_err(|e| e.at("alSourcei", ObjectKind::Source, Some(("AL_SAMPLE_OFFSET", start).into())))?;
+			}
+		}
+		Ok(())
+	}
+	fn source_user_data<R, F: FnOnce(&mut Option<Box<dyn Any + Send>>) -> R>(&self, src: sys::ALuint, f: F) -> Option<R> {
+		self.with_named_source(src, |live| f(&mut live.user_data))
 	}
 
 
 	/// `alcSuspendContext()`
 	/// or `alDeferUpdatesSOFT()` if `AL_SOFT_deferred_updates` is available.
+	/// State changes made while the returned lock is held are applied together when it is dropped,
+	/// including when the holder returns early or unwinds from a panic. Locks can be nested.
+	/// While any lock is held, repeated writes to the same float or vector property of a source or of the
+	/// listener are coalesced, so only the last value is passed to OpenAL when the outermost lock is dropped.
+	/// Reading such a property returns the value written. Errors those writes raise are returned by
+	/// `SuspendLock::commit()`, or logged if the lock is dropped.
+	pub fn suspended<'c>(&'c self) -> AltoResult<SuspendLock<'d, 'c>> {
+		SuspendLock::new(self)
+	}
+
+
+	/// Undo the start of a batch that failed, making any writes held back for it. The context must be current.
+	fn abort_batch(&self) {
+		if let Some(writes) = self.deferred.end_batch(&self.defer_rc) {
+			if let Err(_) = flush_deferred(self, writes) {
+				let _ = writeln!(io::stderr(), "ALTO ERROR: deferred write failed in SuspendLock creation");
+			}
+		}
+	}
+
+
+	#[doc(hidden)]
+	#[deprecated(note = "renamed to `suspended()`")]
 	pub fn suspend<'c>(&'c self) -> AltoResult<SuspendLock<'d, 'c>> {
 		SuspendLock::new(self)
 	}
 
 
+	/// Run `f` with the context locked against other threads, so the calls it makes on the context and its objects
+	/// aren't interleaved with calls from other threads, as if the context were wrapped in a `Mutex`.
+	/// Other threads using the context wait until `f` returns, so it should be short. Calls on other contexts
+	/// from inside `f` lock those contexts as well until they return. Waiting inside `f` on another thread that uses
+	/// this context deadlocks, and with `ALC_EXT_thread_local_context`, so can two threads that each call into the
+	/// other's context from inside `exclusive()`.
+	/// The implementation still applies each change as it is made; use `suspended()` inside `f` to apply them together.
+	///
+	/// Registry methods can be called from inside `f`: alto only ever locks the registry after the context,
+	/// never while holding the registry and waiting for the context.
+	pub fn exclusive<R, F: FnOnce(&Context<'d>) -> R>(&self, f: F) -> AltoResult<R> {
+		let _lock = self.make_current(true)?;
+		Ok(f(self))
+	}
+
+
+	#[cfg(feature = "efx")]
 	/// `alGenAuxiliaryEffectSlots()`
 	/// Requires `ALC_EXT_EFX`
 	pub fn new_aux_effect_slot<'c>(&'c self) -> AltoResult<AuxEffectSlot<'d, 'c>> {
@@ -610,6 +2068,7 @@ impl<'d> Context<'d> {
 	}
 
 
+	#[cfg(feature = "efx")]
 	/// `alGenEffects()`
 	/// Requires `ALC_EXT_EFX`
 	pub fn new_effect<'c, E: EffectTrait<'d, 'c>>(&'c self) -> AltoResult<E> {
@@ -617,6 +2076,7 @@ impl<'d> Context<'d> {
 	}
 
 
+	#[cfg(feature = "efx")]
 	/// `alGenFilters()`
 	/// Requires `ALC_EXT_EFX`
 	pub fn new_filter<'c, F: FilterTrait<'d, 'c>>(&'c self) -> AltoResult<F> {
@@ -628,7 +2088,11 @@ impl<'d> Context<'d> {
 	pub fn get_error(&self) -> AltoResult<()> {
 		match unsafe { self.api.head().alGetError()() } {
 			sys::AL_NO_ERROR => Ok(()),
-			e => Err(AltoError::from_al(e))
+			e => {
+				let e = AltoError::from_al(e);
+				alto_event!(warn, error = %e, "AL error");
+				Err(e)
+			},
 		}
 	}
 }
@@ -642,8 +2106,36 @@ impl<'d> PartialEq for Context<'d> {
 impl<'d> Eq for Context<'d> { }
 
 
+/// Queries the current listener and global state, for logging.
+impl<'d> fmt::Debug for Context<'d> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("Context")
+			.field("raw", &self.ctx)
+			.field("device", &self.dev.specifier())
+			.field("distance_model", &Live(self.distance_model()))
+			.field("doppler_factor", &Live(self.doppler_factor()))
+			.field("speed_of_sound", &Live(self.speed_of_sound()))
+			.field("listener", &Live(self.listener().state()))
+			.field("sources", &Live(self.sources().map(|srcs| srcs.len())))
+			.finish()
+	}
+}
+
+
 impl<'d> Drop for Context<'d> {
 	fn drop(&mut self) {
+		// Removed first, so that the event queue isn't left reachable from the implementation even if
+		// the context can't be destroyed below.
+		remove_event_callback(self);
+		if self.owned {
+			self.clear_registry();
+		}
+		if let Err(_) = self.delete_dropped_buffers() {
+			let _ = writeln!(io::stderr(), "ALTO ERROR: `alDeleteBuffers` failed in Context drop");
+		}
+		if !self.owned {
+			return;
+		}
 		if self.make_current(false).is_ok() {
 			unsafe { self.api.head().alcDestroyContext()(self.ctx); }
 			if let Err(_) = self.dev.alto().get_error(self.dev.as_raw()) {
@@ -660,9 +2152,33 @@ unsafe impl<'d> Send for Context<'d> { }
 unsafe impl<'d> Sync for Context<'d> { }
 
 
+impl<'c> Drop for ContextLock<'c> {
+	fn drop(&mut self) {
+		if let Some((ctx_guard, global)) = self.guards.take() {
+			HELD_CONTEXTS.with(|h| {
+				let mut h = h.borrow_mut();
+				if let Some(i) = h.iter().rposition(|&c| c == self.ctx) {
+					h.remove(i);
+				}
+			});
+			drop(ctx_guard);
+			if global {
+				let global_guard = GLOBAL_HELD.with(|g| {
+					let mut g = g.borrow_mut();
+					g.0 -= 1;
+					if g.0 == 0 { g.1.take() } else { None }
+				});
+				drop(global_guard);
+			}
+		}
+	}
+}
+
+
 impl<'d: 'c, 'c> SuspendLock<'d, 'c> {
 	fn new(ctx: &'c Context<'d>) -> AltoResult<SuspendLock<'d, 'c>> {
-		let adus = ctx.exts.AL_SOFT_deferred_updates()?.alDeferUpdatesSOFT;
+		// Without the extension, fall back to `alcSuspendContext()` rather than failing.
+		let adus = ctx.exts.AL_SOFT_deferred_updates().and_then(|asdu| asdu.alDeferUpdatesSOFT);
 		let _lock = ctx.make_current(true)?;
 
 		let old = ctx.defer_rc.fetch_add(1, Ordering::SeqCst);
@@ -671,14 +2187,14 @@ impl<'d: 'c, 'c> SuspendLock<'d, 'c> {
 				Ok(adus) => {
 					unsafe { adus(); }
 					if let Err(e) = ctx.get_error() {
-						ctx.defer_rc.fetch_sub(1, Ordering::SeqCst);
+						ctx.abort_batch();
 						return Err(e.into());
 					}
 				},
 				Err(_) => {
 					unsafe { ctx.api.head().alcSuspendContext()(ctx.ctx); }
-					if let Err(e) = ctx.dev.alto().get_error(ctx.dev.as_raw()) {
-						ctx.defer_rc.fetch_sub(1, Ordering::SeqCst);
+					if let Err(e) = ctx.dev.alto().get_error(ctx.dev.as_raw()).map_err(|e| e.at("alcSuspendContext", ObjectKind::Context, None)) {
+						ctx.abort_batch();
 						return Err(e);
 					}
 				},
@@ -687,6 +2203,321 @@ impl<'d: 'c, 'c> SuspendLock<'d, 'c> {
 
 		Ok(SuspendLock(ctx))
 	}
+
+
+	/// Release the lock, returning the first error raised by a write it held back.
+	/// If other locks are still held, nothing is applied yet and this succeeds; the outermost lock reports the errors.
+	pub fn commit(self) -> AltoResult<()> {
+		let result = self.end();
+		mem::forget(self);
+		result
+	}
+
+
+	fn end(&self) -> AltoResult<()> {
+		let writes = match self.0.deferred.end_batch(&self.0.defer_rc) {
+			Some(writes) => writes,
+			None => return Ok(()),
+		};
+
+		let (lock, result) = match self.0.make_current(true) {
+			Ok(lock) => {
+				let result = flush_deferred(self.0, writes);
+				(Some(lock), result)
+			},
+			Err(e) => (None, Err(e)),
+		};
+		match self.0.exts.AL_SOFT_deferred_updates().and_then(|asdu| asdu.alProcessUpdatesSOFT) {
+			Ok(apus) => {
+				if lock.is_some() {
+					unsafe { apus(); }
+					if let Err(_) = self.0.get_error() {
+						let _ = writeln!(io::stderr(), "ALTO ERROR: `alProcessUpdatesSOFT` failed in SuspendLock drop");
+					}
+				}
+			},
+			Err(_) => {
+				unsafe { self.0.api.head().alcProcessContext()(self.0.ctx); }
+				if let Err(_) = self.0.dev.alto().get_error(self.0.dev.as_raw()) {
+					let _ = writeln!(io::stderr(), "ALTO ERROR: `alcProcessContext` failed in SuspendLock drop");
+				}
+			},
+		}
+		result
+	}
+}
+
+
+impl<'d: 'c, 'c> Listener<'d, 'c> {
+	/// The context this listener belongs to.
+	#[inline]
+	pub fn context(&self) -> &'c Context<'d> { self.0 }
+
+
+	/// `alGetListenerv(AL_GAIN)`
+	#[inline]
+	pub fn gain(&self) -> AltoResult<f32> { self.0.gain() }
+	/// `alListenerf(AL_GAIN)`
+	#[inline]
+	pub fn set_gain(&self, value: f32) -> AltoResult<()> { self.0.set_gain(value) }
+	/// `alGetListenerf(AL_GAIN)` in decibels, where silence is `-inf`.
+	pub fn gain_db(&self) -> AltoResult<f32> { self.0.gain().map(gain_to_db) }
+	/// `alListenerf(AL_GAIN)` in decibels, where `-inf` mutes the context.
+	pub fn set_gain_db(&self, db: f32) -> AltoResult<()> { self.0.set_gain(db_to_gain(db)) }
+
+
+	/// `alGetListenerfv(AL_POSITION)`
+	#[inline]
+	pub fn position<V: From<[f32; 3]>>(&self) -> AltoResult<V> { self.0.position() }
+	/// `alListenerfv(AL_POSITION)`
+	#[inline]
+	pub fn set_position<V: Into<[f32; 3]>>(&self, value: V) -> AltoResult<()> { self.0.set_position(value) }
+
+
+	/// `alGetListenerfv(AL_VELOCITY)`
+	#[inline]
+	pub fn velocity<V: From<[f32; 3]>>(&self) -> AltoResult<V> { self.0.velocity() }
+	/// `alListenerfv(AL_VELOCITY)`
+	#[inline]
+	pub fn set_velocity<V: Into<[f32; 3]>>(&self, value: V) -> AltoResult<()> { self.0.set_velocity(value) }
+	/// See `Context::auto_velocity()`.
+	#[inline]
+	pub fn auto_velocity(&self) -> Option<f32> { self.0.auto_velocity() }
+	/// See `Context::set_auto_velocity()`.
+	#[inline]
+	pub fn set_auto_velocity(&self, smoothing: Option<f32>) -> AltoResult<()> { self.0.set_auto_velocity(smoothing) }
+
+
+	/// `alGetListenerfv(AL_ORIENTATION)`
+	#[inline]
+	pub fn orientation<V: From<[f32; 3]>>(&self) -> AltoResult<(V, V)> { self.0.orientation() }
+	/// `alListenerfv(AL_ORIENTATION)`
+	#[inline]
+	pub fn set_orientation<V: Into<[f32; 3]>>(&self, value: (V, V)) -> AltoResult<()> { self.0.set_orientation(value) }
+	/// `alListenerfv(AL_ORIENTATION)` from a rotation quaternion in `(x, y, z, w)` order.
+	/// See `orientation_from_quat()`.
+	pub fn set_orientation_quat<Q: Into<[f32; 4]>>(&self, value: Q) -> AltoResult<()> {
+		self.0.set_orientation(orientation_from_quat(value)?)
+	}
+	/// `alListenerfv(AL_ORIENTATION)` facing `target` from the listener's current position.
+	/// See `orientation_look_at()`.
+	pub fn look_at<V: Into<[f32; 3]>>(&self, target: V, up: V) -> AltoResult<()> {
+		let _lock = self.0.make_current(true)?;
+		let eye: [f32; 3] = self.position()?;
+		self.0.set_orientation(orientation_look_at(eye, target.into(), up.into())?)
+	}
+
+
+	/// `alGetListenerf(AL_METERS_PER_UNIT)`
+	/// Requires `ALC_EXT_EFX`
+	#[inline]
+	pub fn meters_per_unit(&self) -> AltoResult<f32> { self.0.meters_per_unit() }
+	/// `alListenerf(AL_METERS_PER_UNIT)`
+	/// Requires `ALC_EXT_EFX`
+	#[inline]
+	pub fn set_meters_per_unit(&self, value: f32) -> AltoResult<()> { self.0.set_meters_per_unit(value) }
+
+
+	/// Read every listener property while holding the context, so no other thread can change them in between.
+	pub fn state(&self) -> AltoResult<ListenerState> {
+		let _lock = self.0.make_current(true)?;
+		Ok(ListenerState{
+			gain: self.gain()?,
+			position: self.position()?,
+			velocity: self.velocity()?,
+			orientation: self.orientation()?,
+			meters_per_unit: if self.0.dev.extensions().ALC_EXT_EFX().is_ok() { Some(self.meters_per_unit()?) } else { None },
+		})
+	}
+
+
+	/// Apply every listener property at once.
+	/// Updates are deferred until all properties are set if `AL_SOFT_deferred_updates` is available.
+	pub fn set_state(&self, state: &ListenerState) -> AltoResult<()> {
+		let _lock = self.0.make_current(true)?;
+		let suspend = self.0.suspended().ok();
+
+		self.set_gain(state.gain)?;
+		self.set_position(state.position)?;
+		self.set_velocity(state.velocity)?;
+		self.set_orientation(state.orientation)?;
+		if let Some(mpu) = state.meters_per_unit {
+			self.set_meters_per_unit(mpu)?;
+		}
+		suspend.map_or(Ok(()), SuspendLock::commit)
+	}
+
+
+	/// Stage `AL_GAIN` for the next `commit_staged()`, without touching the context.
+	pub fn stage_gain(&self, value: f32) { self.staged().gain = Some(value); }
+	/// Stage `AL_POSITION` for the next `commit_staged()`, without touching the context.
+	pub fn stage_position<V: Into<[f32; 3]>>(&self, value: V) { self.staged().position = Some(value.into()); }
+	/// Stage `AL_VELOCITY` for the next `commit_staged()`, without touching the context.
+	pub fn stage_velocity<V: Into<[f32; 3]>>(&self, value: V) { self.staged().velocity = Some(value.into()); }
+	/// Stage `AL_ORIENTATION` for the next `commit_staged()`, without touching the context.
+	pub fn stage_orientation<V: Into<[f32; 3]>>(&self, value: (V, V)) {
+		self.staged().orientation = Some((value.0.into(), value.1.into()));
+	}
+	/// Stage every property that is `Some` in `update`, replacing any staged value.
+	pub fn stage(&self, update: &ListenerUpdate) {
+		let mut staged = self.staged();
+		staged.gain = update.gain.or(staged.gain);
+		staged.position = update.position.or(staged.position);
+		staged.velocity = update.velocity.or(staged.velocity);
+		staged.orientation = update.orientation.or(staged.orientation);
+	}
+
+
+	/// Apply the staged properties while holding the context once, then clear them.
+	/// Staging only takes a small lock of its own, so other threads can write the listener as often as they
+	/// like while the audio thread commits the latest values once per frame.
+	/// The writes are applied as one batch if `AL_SOFT_deferred_updates` is available.
+	/// A staged position still drives `Context::set_auto_velocity()` unless a velocity is staged too.
+	pub fn commit_staged(&self) -> AltoResult<()> {
+		let update = mem::replace(&mut *self.staged(), ListenerUpdate::default());
+		if update == ListenerUpdate::default() { return Ok(()) }
+
+		let _lock = self.0.make_current(true)?;
+		let suspend = self.0.suspended().ok();
+
+		if let Some(gain) = update.gain {
+			self.set_gain(gain)?;
+		}
+		if let Some(position) = update.position {
+			self.set_position(position)?;
+		}
+		if let Some(velocity) = update.velocity {
+			self.set_velocity(velocity)?;
+		}
+		if let Some(orientation) = update.orientation {
+			self.set_orientation(orientation)?;
+		}
+		suspend.map_or(Ok(()), SuspendLock::commit)
+	}
+
+
+	fn staged(&self) -> MutexGuard<ListenerUpdate> {
+		self.0.staged_listener.lock().unwrap_or_else(|e| e.into_inner())
+	}
+}
+
+
+impl<'d: 'c, 'c> PartialEq for Listener<'d, 'c> {
+	fn eq(&self, other: &Listener<'d, 'c>) -> bool {
+		self.0 == other.0
+	}
+}
+impl<'d: 'c, 'c> Eq for Listener<'d, 'c> { }
+
+
+impl Default for ListenerState {
+	/// The initial state of a listener.
+	fn default() -> ListenerState {
+		ListenerState{
+			gain: 1.0,
+			position: [0.0, 0.0, 0.0],
+			velocity: [0.0, 0.0, 0.0],
+			orientation: ([0.0, 0.0, -1.0], [0.0, 1.0, 0.0]),
+			meters_per_unit: None,
+		}
+	}
+}
+
+
+impl SourceDefaults {
+	fn apply<'d: 'c, 'c, S: SourceTrait<'d, 'c>>(&self, ctx: &Context<'d>, src: &mut S) -> AltoResult<()> {
+		if *self == SourceDefaults::default() { return Ok(()) }
+
+		let _lock = ctx.make_current(true)?;
+		if let Some(relative) = self.relative {
+			src.set_relative(relative)?;
+		}
+		if let Some(reference_distance) = self.reference_distance {
+			src.set_reference_distance(reference_distance)?;
+		}
+		if let Some(rolloff_factor) = self.rolloff_factor {
+			src.set_rolloff_factor(rolloff_factor)?;
+		}
+		if let Some(max_distance) = self.max_distance {
+			src.set_max_distance(max_distance)?;
+		}
+		if let Some((inner, outer, outer_gain)) = self.cone {
+			src.set_cone(SourceCone{inner_angle: inner, outer_angle: outer, outer_gain: outer_gain, outer_gain_hf: None})?;
+		}
+		Ok(())
+	}
+}
+
+
+impl Default for Acoustics {
+	/// The initial acoustics of a context.
+	fn default() -> Acoustics {
+		Acoustics{
+			distance_model: DistanceModel::InverseClamped,
+			speed_of_sound: 343.3,
+			doppler_factor: 1.0,
+			meters_per_unit: None,
+		}
+	}
+}
+
+
+impl Default for SourceCone {
+	/// The initial cone of a source, which is omnidirectional.
+	fn default() -> SourceCone {
+		SourceCone{inner_angle: 360.0, outer_angle: 360.0, outer_gain: 0.0, outer_gain_hf: None}
+	}
+}
+
+
+impl Default for SourceProps {
+	/// The initial properties of a source.
+	fn default() -> SourceProps {
+		SourceProps{
+			relative: false,
+			gain: 1.0,
+			min_gain: 0.0,
+			max_gain: 1.0,
+			pitch: 1.0,
+			position: [0.0, 0.0, 0.0],
+			velocity: [0.0, 0.0, 0.0],
+			direction: [0.0, 0.0, 0.0],
+			reference_distance: 1.0,
+			rolloff_factor: 1.0,
+			max_distance: ::std::f32::MAX,
+			cone_inner_angle: 360.0,
+			cone_outer_angle: 360.0,
+			cone_outer_gain: 0.0,
+			distance_model: None,
+			#[cfg(feature = "efx")]
+			air_absorption_factor: None,
+			#[cfg(feature = "efx")]
+			room_rolloff_factor: None,
+			#[cfg(feature = "efx")]
+			cone_outer_gainhf: None,
+		}
+	}
+}
+
+
+impl<'d: 'c, 'c> PausedSources<'d, 'c> {
+	/// Handles of the paused sources.
+	#[inline]
+	pub fn sources(&self) -> &[sys::ALuint] { &self.srcs }
+
+
+	/// Resume the paused sources. Sources that were deleted, stopped, or started again in the meantime are left alone.
+	pub fn resume(self) -> AltoResult<()> {
+		let _lock = self.ctx.make_current(true)?;
+		let resume = {
+			let live = self.ctx.sources.lock().unwrap_or_else(|e| e.into_inner());
+			self.ctx.sources_in_state(self.srcs.iter().cloned().filter(|src| live.slots.contains_key(src)), SourceState::Paused)?
+		};
+		if resume.is_empty() { return Ok(()) }
+
+		unsafe { self.ctx.api.head().alSourcePlayv()(resume.len() as sys::ALsizei, resume.as_ptr()); }
+		self.ctx.get_error().map_err(|e| e.at("alSourcePlayv", ObjectKind::Source, Some(("n", resume.len()).into())))
+	}
 }
 
 
@@ -699,26 +2530,8 @@ impl<'d: 'c, 'c> Deref for SuspendLock<'d, 'c> {
 
 impl<'d: 'c, 'c> Drop for SuspendLock<'d, 'c> {
 	fn drop(&mut self) {
-		let old = self.0.defer_rc.fetch_sub(1, Ordering::SeqCst);
-		if old == 1 {
-			match self.0.exts.AL_SOFT_deferred_updates().and_then(|asdu| asdu.alProcessUpdatesSOFT) {
-				Ok(apus) => {
-					if let Ok(_lock) = self.0.make_current(true) {
-						unsafe { apus(); }
-						if let Err(_) = self.0.get_error() {
-							let _ = writeln!(io::stderr(), "ALTO ERROR: `alProcessUpdatesSOFT` failed in SuspendLock drop");
-						}
-					} else {
-						let _ = writeln!(io::stderr(), "ALTO ERROR: `alcMakeContextCurrent` failed in SuspendLock drop");
-					}
-				},
-				Err(_) => {
-					unsafe { self.0.api.head().alcProcessContext()(self.0.ctx); }
-					if let Err(_) = self.0.dev.alto().get_error(self.0.dev.as_raw()) {
-						let _ = writeln!(io::stderr(), "ALTO ERROR: `alcProcessContext` failed in SuspendLock drop");
-					}
-				},
-			}
+		if let Err(e) = self.end() {
+			let _ = writeln!(io::stderr(), "ALTO ERROR: {} in SuspendLock drop", e);
 		}
 	}
 }
@@ -730,7 +2543,9 @@ impl<'d: 'c, 'c> Buffer<'d, 'c> {
 		let _lock = ctx.make_current(true)?;
 		let mut buf = 0;
 		unsafe { ctx.api.head().alGenBuffers()(1, &mut buf as *mut sys::ALuint); }
-		ctx.get_error().map(|_| Buffer{ctx: ctx, buf: buf})
+		ctx.get_error().map_err(|e| e.at("alGenBuffers", ObjectKind::Buffer, None))?;
+		ctx.held_buffers.lock().unwrap_or_else(|e| e.into_inner()).insert(buf);
+		Ok(Buffer{ctx: ctx, buf: buf})
 	}
 
 
@@ -740,23 +2555,159 @@ impl<'d: 'c, 'c> Buffer<'d, 'c> {
 	pub fn as_raw(&self) -> sys::ALuint { self.buf }
 
 
+	/// Take ownership of a buffer generated outside of alto, such as one handed over by middleware.
+	/// It will be deleted when dropped. Fails with `AlAlreadyAdopted` if alto already holds `buf` in this context.
+	///
+	/// # Safety
+	/// `buf` must belong to a context on the same device as `ctx`, and must not be deleted elsewhere.
+	pub unsafe fn from_raw(ctx: &'c Context<'d>, buf: sys::ALuint) -> AltoResult<Buffer<'d, 'c>> {
+		let _lock = ctx.make_current(true)?;
+		if ctx.api.head().alIsBuffer()(buf) == sys::AL_FALSE {
+			return Err(AltoError::AlInvalidName);
+		}
+		if !ctx.held_buffers.lock().unwrap_or_else(|e| e.into_inner()).insert(buf) {
+			return Err(AltoError::AlAlreadyAdopted);
+		}
+
+		Ok(Buffer{ctx: ctx, buf: buf})
+	}
+
+
+	/// Use a buffer owned by code outside of alto without taking ownership of it.
+	/// It is released rather than deleted when the returned handle is dropped.
+	/// Fails with `AlAlreadyAdopted` if alto already holds `buf` in this context.
+	///
+	/// # Safety
+	/// `buf` must belong to a context on the same device as `ctx`, and must not be deleted while borrowed.
+	pub unsafe fn borrow_raw(ctx: &'c Context<'d>, buf: sys::ALuint) -> AltoResult<Borrowed<Buffer<'d, 'c>>> {
+		Buffer::from_raw(ctx, buf).map(Borrowed::new)
+	}
+
+
+	/// Release ownership of the buffer without deleting it.
+	pub fn into_raw(self) -> sys::ALuint {
+		self.ctx.held_buffers.lock().unwrap_or_else(|e| e.into_inner()).remove(&self.buf);
+		self.leak()
+	}
+
+
+	/// Forget the buffer without deleting it, while it stays held by something else in alto, such as the registry.
+	fn leak(self) -> sys::ALuint {
+		let buf = self.buf;
+		mem::forget(self);
+		buf
+	}
+
+
 	/// `alBufferData()`
 	pub fn set_data<F: SampleFrame, R: AsBufferData<F>>(&mut self, data: R, freq: i32) -> AltoResult<()> {
+		self.upload(data.as_buffer_data(), freq)
+	}
+
+
+	/// Replace the contents of a buffer that may be attached to static sources, such as when reloading an asset
+	/// during development. The sources using the buffer are stopped and detached while the new data is uploaded,
+	/// then reattached and handled according to `mode`. They are reattached even if the upload fails, in which case
+	/// the buffer keeps its old data.
+	/// A buffer queued on a streaming source is owned by that source until it is unqueued, so it can't be reloaded
+	/// in place; unqueue it, replace its data with `set_data()` and queue it again instead.
+	pub fn replace_data<F: SampleFrame, R: AsBufferData<F>>(&self, data: R, freq: i32, mode: ReloadMode) -> AltoResult<()> {
 		let data = data.as_buffer_data();
+		let _lock = self.ctx.make_current(true)?;
+
+		let srcs: Vec<_> = self.ctx.sources.lock().unwrap_or_else(|e| e.into_inner()).live.iter().filter(|&(_, live)| !live.streaming).map(|(_, live)| live.src).collect();
+		let mut users = Vec::new();
+		for src in srcs {
+			let (mut buf, mut state, mut offset) = (0, 0, 0);
+			unsafe {
+				self.ctx.api.head().alGetSourcei()(src, sys::AL_BUFFER, &mut buf);
+				self.ctx.api.head().alGetSourcei()(src, sys::AL_SOURCE_STATE, &mut state);
+				self.ctx.api.head().alGetSourcei()(src, sys::AL_SAMPLE_OFFSET, &mut offset);
+			}
+			self.ctx.get_error().map_err(|e| e.at("alGetSourcei", ObjectKind::Source, Some("AL_BUFFER".into())))?;
+			if buf as sys::ALuint == self.buf {
+				users.push((src, SourceState::from_raw(state), offset));
+			}
+		}
+
+		for &(src, _, _) in &users {
+			unsafe {
+				self.ctx.api.head().alSourceStop()(src);
+				self.ctx.api.head().alSourcei()(src, sys::AL_BUFFER, 0);
+			}
+		}
+		// From here on every user gets the buffer back, so the sources stay in step with their attachments.
+		let uploaded = self.ctx.get_error().map_err(|e| e.at("alSourcei", ObjectKind::Source, Some("AL_BUFFER".into())))
+			.and_then(|_| self.upload(data, freq));
+
+		for &(src, state, offset) in &users {
+			unsafe { self.ctx.api.head().alSourcei()(src, sys::AL_BUFFER, self.buf as sys::ALint); }
+			match (mode, state) {
+				(ReloadMode::Restart, SourceState::Playing) => unsafe { self.ctx.api.head().alSourcePlay()(src); },
+				(ReloadMode::Resume, SourceState::Playing) | (ReloadMode::Resume, SourceState::Paused) => unsafe {
+					if (offset as usize) < data.len() {
+						self.ctx.api.head().alSourcei()(src, sys::AL_SAMPLE_OFFSET, offset);
+					}
+					self.ctx.api.head().alSourcePlay()(src);
+					if state == SourceState::Paused {
+						self.ctx.api.head().alSourcePause()(src);
+					}
+				},
+				_ => (),
+			}
+		}
+		let reattached = self.ctx.get_error().map_err(|e| e.at("alSourcei", ObjectKind::Source, Some("AL_BUFFER".into())));
+		uploaded.and(reattached)
+	}
+
+
+	fn upload<F: SampleFrame>(&self, data: &[F], freq: i32) -> AltoResult<()> {
+		check_upload(data.len().checked_mul(mem::size_of::<F>()), freq)?;
 		let size = data.len() * mem::size_of::<F>();
-		if sys::ALsizei::max_value() as usize / mem::size_of::<F>() < data.len() { return Err(AltoError::AlInvalidValue) }
 
-		let _lock = self.ctx.make_current(true)?;
-		unsafe {
-			self.ctx.api.head().alBufferData()(
-				self.buf,
-				F::format().into_raw(Some(self.ctx))?,
-				data.as_ptr() as *const sys::ALvoid,
-				size as sys::ALsizei,
-				freq as sys::ALint,
-			);
+		alto_event!(trace, buffer = self.buf, bytes = size, freq = freq, format = ?F::format(), "buffer upload");
+		let format = F::format().into_raw(Some(self.ctx))?;
+		unsafe { self.upload_raw(format, data.as_ptr() as *const sys::ALvoid, size, freq, 1) }
+	}
+
+
+	/// `alBufferData()` with sample data in any layout, such as encoded IMA4 or MSADPCM blocks.
+	/// The format was resolved beforehand, so this needs no extension checks.
+	/// Fails with `AlWrongContext` if `format` was resolved by another context, and with `AlInvalidRange` if the data
+	/// isn't a whole number of sample frames, or of blocks at the unpack block alignment for ADPCM formats.
+	pub fn set_data_raw(&mut self, format: ResolvedFormat, data: &[u8], freq: i32) -> AltoResult<()> {
+		if !format.belongs_to(self.ctx) { return Err(AltoError::AlWrongContext) }
+		check_upload(Some(data.len()), freq)?;
+		let alignment = match format.format().frame_size() {
+			Some(_) => 0,
+			None if self.ctx.exts.AL_SOFT_block_alignment().is_ok() => self.soft_unpack_block_alignment()?,
+			None => 0,
+		};
+		let alignment = cmp::max(alignment, 0) as usize;
+		let (block_frames, block_size) = match (format.format().block_frames(alignment), format.format().block_size(alignment)) {
+			(Some(frames), Some(size)) => (frames, size),
+			_ => return Err(AltoError::AlInvalidRange("unpack block alignment must be valid for the format")),
+		};
+		if data.len() % block_size != 0 {
+			return Err(AltoError::AlInvalidRange(if block_frames == 1 {
+				"data length must be a whole number of sample frames"
+			} else {
+				"data length must be a whole number of ADPCM blocks"
+			}));
 		}
-		self.ctx.get_error()
+
+		alto_event!(trace, buffer = self.buf, bytes = data.len(), freq = freq, format = ?format.format(), "buffer upload");
+		unsafe { self.upload_raw(format.as_raw(), data.as_ptr() as *const sys::ALvoid, data.len(), freq, block_frames) }
+	}
+
+
+	unsafe fn upload_raw(&self, format: sys::ALint, data: *const sys::ALvoid, size: usize, freq: i32, block_frames: usize) -> AltoResult<()> {
+		let _lock = self.ctx.make_current(true)?;
+		self.ctx.api.head().alBufferData()(self.buf, format, data, size as sys::ALsizei, freq as sys::ALint);
+		self.ctx.get_error().map_err(|e| e.at("alBufferData", ObjectKind::Buffer, Some(("size", size).into())))?;
+		self.ctx.memory.lock().unwrap_or_else(|e| e.into_inner()).uploaded(self.buf, size, block_frames);
+		self.ctx.dev.alto().metrics().report(|m| m.bytes_uploaded(size));
+		Ok(())
 	}
 
 
@@ -765,7 +2716,7 @@ impl<'d: 'c, 'c> Buffer<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { self.ctx.api.head().alGetBufferi()(self.buf, sys::AL_FREQUENCY, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetBufferi", ObjectKind::Buffer, Some("AL_FREQUENCY".into()))).map(|_| value)
 	}
 
 
@@ -774,7 +2725,7 @@ impl<'d: 'c, 'c> Buffer<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { self.ctx.api.head().alGetBufferi()(self.buf, sys::AL_BITS, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetBufferi", ObjectKind::Buffer, Some("AL_BITS".into()))).map(|_| value)
 	}
 
 
@@ -783,7 +2734,7 @@ impl<'d: 'c, 'c> Buffer<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { self.ctx.api.head().alGetBufferi()(self.buf, sys::AL_CHANNELS, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetBufferi", ObjectKind::Buffer, Some("AL_CHANNELS".into()))).map(|_| value)
 	}
 
 
@@ -792,7 +2743,56 @@ impl<'d: 'c, 'c> Buffer<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { self.ctx.api.head().alGetBufferi()(self.buf, sys::AL_SIZE, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetBufferi", ObjectKind::Buffer, Some("AL_SIZE".into()))).map(|_| value)
+	}
+
+
+	/// Length of the buffer in sample frames.
+	/// Queried with `alGetBufferi(AL_SAMPLE_LENGTH_SOFT)` if `AL_SOFT_source_length` is available, which is exact
+	/// for compressed formats, and otherwise computed from the size, bit depth, and channel count.
+	pub fn sample_len(&self) -> AltoResult<usize> {
+		{
+			let _lock = self.ctx.make_current(true)?;
+			if let Ok(param) = self.ctx.exts.AL_SOFT_source_length().and_then(|asl| asl.AL_SAMPLE_LENGTH_SOFT) {
+				let mut value = 0;
+				unsafe { self.ctx.api.head().alGetBufferi()(self.buf, param, &mut value); }
+				if self.ctx.get_error().is_ok() { return Ok(cmp::max(value, 0) as usize) }
+			}
+		}
+
+		let (bits, chans, size) = (self.bits()?, self.channels()?, self.size()?);
+		if bits <= 0 || chans <= 0 || size <= 0 { return Ok(0) }
+		Ok((size as u64 * 8 / (bits as u64 * chans as u64)) as usize)
+	}
+
+
+	/// Playback length of the buffer at its own frequency, from `sample_len()`.
+	pub fn duration(&self) -> AltoResult<Duration> {
+		let freq = self.frequency()?;
+		if freq <= 0 { return Ok(Duration::from_secs(0)) }
+
+		let (len, freq) = (self.sample_len()? as u64, freq as u64);
+		Ok(Duration::new(len / freq, (len % freq * 1_000_000_000 / freq) as u32))
+	}
+
+
+	/// `alGetBufferi(AL_UNPACK_BLOCK_ALIGNMENT_SOFT)`
+	/// Requires `AL_SOFT_block_alignment`
+	pub fn soft_unpack_block_alignment(&self) -> AltoResult<sys::ALint> {
+		let _lock = self.ctx.make_current(true)?;
+		let mut value = 0;
+		unsafe { self.ctx.api.head().alGetBufferi()(self.buf, self.ctx.exts.AL_SOFT_block_alignment()?.AL_UNPACK_BLOCK_ALIGNMENT_SOFT?, &mut value); }
+		self.ctx.get_error().map_err(|e| e.at("alGetBufferi", ObjectKind::Buffer, Some("AL_UNPACK_BLOCK_ALIGNMENT_SOFT".into()))).map(|_| value)
+	}
+	/// `alBufferi(AL_UNPACK_BLOCK_ALIGNMENT_SOFT)`
+	/// Requires `AL_SOFT_block_alignment`
+	///
+	/// ADPCM data uploaded with `set_data_raw()` must then be a whole number of blocks of this many sample frames,
+	/// and loop points must be on block boundaries.
+	pub fn set_soft_unpack_block_alignment(&self, value: sys::ALint) -> AltoResult<()> {
+		let _lock = self.ctx.make_current(true)?;
+		unsafe { self.ctx.api.head().alBufferi()(self.buf, self.ctx.exts.AL_SOFT_block_alignment()?.AL_UNPACK_BLOCK_ALIGNMENT_SOFT?, value); }
+		self.ctx.get_error().map_err(|e| e.at("alBufferi", ObjectKind::Buffer, Some(("AL_UNPACK_BLOCK_ALIGNMENT_SOFT", value).into())))
 	}
 
 
@@ -802,68 +2802,199 @@ impl<'d: 'c, 'c> Buffer<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = [0, 0];
 		unsafe { self.ctx.api.head().alGetBufferiv()(self.buf, self.ctx.exts.AL_SOFT_loop_points()?.AL_LOOP_POINTS_SOFT?, &mut value as *mut [sys::ALint; 2] as *mut sys::ALint); }
-		self.ctx.get_error().map(|_| (value[0], value[1]))
+		self.ctx.get_error().map_err(|e| e.at("alGetBufferiv", ObjectKind::Buffer, Some("AL_LOOP_POINTS_SOFT".into()))).map(|_| (value[0], value[1]))
 	}
 	/// `alBufferiv(AL_LOOP_POINTS_SOFT)`
 	/// Requires `AL_SOFT_loop_points`
+	///
+	/// Fails with `AlInvalidRange` if the buffer holds ADPCM data uploaded with `set_data_raw()`
+	/// and a loop point isn't on a block boundary, where playback would start mid-block.
 	pub fn set_soft_loop_points(&self, value: (sys::ALint, sys::ALint)) -> AltoResult<()> {
+		let block_frames = self.ctx.memory.lock().unwrap_or_else(|e| e.into_inner()).block_frames(self.buf) as sys::ALint;
+		if value.0 % block_frames != 0 || value.1 % block_frames != 0 {
+			return Err(AltoError::AlInvalidRange("loop points must be on ADPCM block boundaries"));
+		}
+
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { self.ctx.api.head().alBufferiv()(self.buf, self.ctx.exts.AL_SOFT_loop_points()?.AL_LOOP_POINTS_SOFT?, &[value.0, value.1] as *const [sys::ALint; 2] as *const sys::ALint); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alBufferiv", ObjectKind::Buffer, Some(("AL_LOOP_POINTS_SOFT", value).into())))
 	}
 }
 
 
-impl<'d: 'c, 'c> Drop for Buffer<'d, 'c> {
-	fn drop(&mut self) {
-		if let Ok(_lock) = self.ctx.make_current(true) {
-			unsafe { self.ctx.api.head().alDeleteBuffers()(1, &mut self.buf as *mut sys::ALuint); }
-			if let Err(_) = self.ctx.get_error() {
-				let _ = writeln!(io::stderr(), "ALTO ERROR: `alDeleteBuffers` failed in Buffer drop");
+/// Reject uploads OpenAL would refuse or truncate, given their size in bytes (`None` if it overflowed).
+fn check_upload(size: Option<usize>, freq: i32) -> AltoResult<()> {
+	if freq <= 0 { return Err(AltoError::AlInvalidRange("frequency must be positive")) }
+	match size {
+		Some(size) if size <= sys::ALsizei::max_value() as usize => Ok(()),
+		_ => Err(AltoError::AlInvalidRange("data must fit in ALsizei bytes")),
+	}
+}
+
+
+impl<'d: 'c, 'c> Drop for Buffer<'d, 'c> {
+	fn drop(&mut self) {
+		self.ctx.dropped_buffers.lock().unwrap_or_else(|e| e.into_inner()).push(self.buf);
+	}
+}
+
+
+/// Queries the current format and size of the buffer, for logging.
+impl<'d: 'c, 'c> fmt::Debug for Buffer<'d, 'c> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("Buffer")
+			.field("raw", &self.buf)
+			.field("frequency", &Live(self.frequency()))
+			.field("bits", &Live(self.bits()))
+			.field("channels", &Live(self.channels()))
+			.field("size", &Live(self.size()))
+			.field("duration", &Live(self.duration()))
+			.finish()
+	}
+}
+
+
+impl<'d: 'c, 'c> SourceImpl<'d, 'c> {
+	fn context(&self) -> &Context<'d> { self.ctx }
+	pub fn as_raw(&self) -> sys::ALuint { self.src }
+
+
+	/// `alGetSourcef()`, or the value held back for the property by a deferred batch.
+	fn get_f(&self, param: sys::ALenum, name: &'static str) -> AltoResult<f32> {
+		if let Some(DeferredValue::Float(value)) = self.ctx.deferred.get(DeferredTarget::Source(self.src), param) { return Ok(value) }
+
+		let _lock = self.ctx.make_current(true)?;
+		let mut value = 0.0;
+		unsafe { self.ctx.api.head().alGetSourcef()(self.src, param, &mut value); }
+		self.ctx.get_error().map_err(|e| e.at("alGetSourcef", ObjectKind::Source, Some(name.into()))).map(|_| value)
+	}
+	/// `alSourcef()`, or held back until the end of the current deferred batch, if any.
+	fn set_f(&self, param: sys::ALenum, name: &'static str, value: f32) -> AltoResult<()> {
+		if self.ctx.deferred.defer(&self.ctx.defer_rc, DeferredTarget::Source(self.src), param, name, DeferredValue::Float(value)) { return Ok(()) }
+
+		let _lock = self.ctx.make_current(true)?;
+		unsafe { self.ctx.api.head().alSourcef()(self.src, param, value); }
+		self.ctx.get_error().map_err(|e| e.at("alSourcef", ObjectKind::Source, Some((name, value).into())))
+	}
+
+
+	/// `alGetSourcefv()`, or the value held back for the property by a deferred batch.
+	fn get_fv(&self, param: sys::ALenum, name: &'static str) -> AltoResult<[f32; 3]> {
+		if let Some(DeferredValue::Vector(value)) = self.ctx.deferred.get(DeferredTarget::Source(self.src), param) { return Ok(value) }
+
+		let _lock = self.ctx.make_current(true)?;
+		let mut value = [0.0, 0.0, 0.0];
+		unsafe { self.ctx.api.head().alGetSourcefv()(self.src, param, value.as_mut_ptr()); }
+		self.ctx.get_error().map_err(|e| e.at("alGetSourcefv", ObjectKind::Source, Some(name.into()))).map(|_| value)
+	}
+	/// `alSourcefv()`, or held back until the end of the current deferred batch, if any.
+	fn set_fv(&self, param: sys::ALenum, name: &'static str, value: [f32; 3]) -> AltoResult<()> {
+		if self.ctx.deferred.defer(&self.ctx.defer_rc, DeferredTarget::Source(self.src), param, name, DeferredValue::Vector(value)) { return Ok(()) }
+
+		let _lock = self.ctx.make_current(true)?;
+		unsafe { self.ctx.api.head().alSourcefv()(self.src, param, value.as_ptr()); }
+		self.ctx.get_error().map_err(|e| e.at("alSourcefv", ObjectKind::Source, Some((name, value).into())))
+	}
+	/// Whether a vector write can be skipped under `Context::set_position_epsilon()`.
+	/// If not, `value` is recorded as the last one written.
+	fn within_epsilon<F: FnOnce(&mut LiveSource) -> &mut Option<[f32; 3]>>(&self, last: F, value: [f32; 3]) -> bool {
+		let epsilon = match self.ctx.position_epsilon() {
+			Some(epsilon) => epsilon,
+			None => return false,
+		};
+		self.ctx.with_live_source(self.slot, |live| {
+			let last = last(live);
+			if let Some(prev) = *last {
+				let d = [value[0] - prev[0], value[1] - prev[1], value[2] - prev[2]];
+				if d[0] * d[0] + d[1] * d[1] + d[2] * d[2] < epsilon * epsilon { return true }
+			}
+			*last = Some(value);
+			false
+		}).unwrap_or(false)
+	}
+
+
+	unsafe fn from_raw(ctx: &'c Context<'d>, src: sys::ALuint, streaming: bool) -> AltoResult<SourceImpl<'d, 'c>> {
+		let _lock = ctx.make_current(true)?;
+		if ctx.api.head().alIsSource()(src) == sys::AL_FALSE {
+			return Err(AltoError::AlInvalidName);
+		}
+		if ctx.sources.lock().unwrap_or_else(|e| e.into_inner()).slots.contains_key(&src) {
+			return Err(AltoError::AlAlreadyAdopted);
+		}
+
+		ctx.dev.alto().metrics().voice_created();
+		let slot = ctx.track_source(src, streaming);
+		Ok(SourceImpl{ctx: ctx, src: src, slot: slot})
+	}
+
+
+	/// Generate `n` sources at once. If the call fails, any names the implementation did generate are deleted again.
+	fn gen(ctx: &'c Context<'d>, n: usize, streaming: bool) -> AltoResult<Vec<SourceImpl<'d, 'c>>> {
+		if n == 0 { return Ok(Vec::new()) }
+		if n > sys::ALsizei::max_value() as usize { return Err(AltoError::AlInvalidValue) }
+
+		let _lock = ctx.make_current(true)?;
+		let mut srcs = vec![0; n];
+		unsafe { ctx.api.head().alGenSources()(n as sys::ALsizei, srcs.as_mut_ptr()); }
+		if let Err(e) = ctx.get_error() {
+			let gen: Vec<_> = srcs.into_iter().filter(|&src| src != 0 && unsafe { ctx.api.head().alIsSource()(src) } == sys::AL_TRUE).collect();
+			if !gen.is_empty() {
+				unsafe { ctx.api.head().alDeleteSources()(gen.len() as sys::ALsizei, gen.as_ptr()); }
+				if let Err(_) = ctx.get_error() {
+					let _ = writeln!(io::stderr(), "ALTO ERROR: `alDeleteSources` failed while rolling back `alGenSources`");
+				}
 			}
-		} else {
-			let _ = writeln!(io::stderr(), "ALTO ERROR: `alcMakeContextCurrent` failed in Buffer drop");
+			return Err(e.at("alGenSources", ObjectKind::Source, Some(("n", n).into())));
 		}
+
+		Ok(srcs.into_iter().map(|src| {
+			ctx.dev.alto().metrics().voice_created();
+			let slot = ctx.track_source(src, streaming);
+			SourceImpl{ctx: ctx, src: src, slot: slot}
+		}).collect())
 	}
-}
 
 
-impl<'d: 'c, 'c> SourceImpl<'d, 'c> {
-	fn context(&self) -> &Context<'d> { self.ctx }
-	pub fn as_raw(&self) -> sys::ALuint { self.src }
+	/// Give up the source without deleting it. The handle is returned if it is still shared, which an effect slot
+	/// being dropped on another thread can briefly do.
+	fn into_raw(src: Arc<SourceImpl<'d, 'c>>) -> Result<sys::ALuint, Arc<SourceImpl<'d, 'c>>> {
+		let src = mem::ManuallyDrop::new(Arc::try_unwrap(src)?);
+		src.ctx.dev.alto().metrics().voice_deleted();
+		src.ctx.forget_source(src.slot);
+		Ok(src.src)
+	}
 
 
 	fn state(&self) -> AltoResult<SourceState> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { self.ctx.api.head().alGetSourcei()(self.src, sys::AL_SOURCE_STATE, &mut value); }
-		self.ctx.get_error().and_then(|_| match value {
-			sys::AL_INITIAL => Ok(SourceState::Initial),
-			sys::AL_PLAYING => Ok(SourceState::Playing),
-			sys::AL_PAUSED => Ok(SourceState::Paused),
-			sys::AL_STOPPED => Ok(SourceState::Stopped),
-			_ => Err(AltoError::AlInvalidEnum),
-		})
+		self.ctx.get_error().map_err(|e| e.at("alGetSourcei", ObjectKind::Source, Some("AL_SOURCE_STATE".into()))).map(|_| SourceState::from_raw(value))
 	}
 	fn play(&self) -> AltoResult<()> {
+		alto_event!(debug, source = self.src, "play");
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { self.ctx.api.head().alSourcePlay()(self.src); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alSourcePlay", ObjectKind::Source, None))
 	}
 	fn pause(&self) -> AltoResult<()> {
+		alto_event!(debug, source = self.src, "pause");
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { self.ctx.api.head().alSourcePause()(self.src); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alSourcePause", ObjectKind::Source, None))
 	}
 	fn stop(&self) -> AltoResult<()> {
+		alto_event!(debug, source = self.src, "stop");
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { self.ctx.api.head().alSourceStop()(self.src); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alSourceStop", ObjectKind::Source, None))
 	}
 	fn rewind(&self) -> AltoResult<()> {
+		alto_event!(debug, source = self.src, "rewind");
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { self.ctx.api.head().alSourceRewind()(self.src); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alSourceRewind", ObjectKind::Source, None))
 	}
 
 
@@ -871,184 +3002,200 @@ impl<'d: 'c, 'c> SourceImpl<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { self.ctx.api.head().alGetSourcei()(self.src, sys::AL_SOURCE_RELATIVE, &mut value); }
-		self.ctx.get_error().map(|_| value == sys::AL_TRUE as sys::ALint)
+		self.ctx.get_error().map_err(|e| e.at("alGetSourcei", ObjectKind::Source, Some("AL_SOURCE_RELATIVE".into()))).map(|_| value == sys::AL_TRUE as sys::ALint)
 	}
 	fn set_relative(&self, value: bool) -> AltoResult<()> {
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { self.ctx.api.head().alSourcei()(self.src, sys::AL_SOURCE_RELATIVE, if value { sys::AL_TRUE } else { sys::AL_FALSE } as sys::ALint); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alSourcei", ObjectKind::Source, Some(("AL_SOURCE_RELATIVE", value).into())))
 	}
 
 
 	fn gain(&self) -> AltoResult<f32> {
-		let _lock = self.ctx.make_current(true)?;
-		let mut value = 0.0;
-		unsafe { self.ctx.api.head().alGetSourcef()(self.src, sys::AL_GAIN, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.get_f(sys::AL_GAIN, "AL_GAIN")
 	}
 	fn set_gain(&self, value: f32) -> AltoResult<()> {
-		let _lock = self.ctx.make_current(true)?;
-		unsafe { self.ctx.api.head().alSourcef()(self.src, sys::AL_GAIN, value); }
-		self.ctx.get_error()
+		let value = self.ctx.check_range("AL_GAIN", "source gain must not be negative", value, NON_NEGATIVE)?;
+		self.set_f(sys::AL_GAIN, "AL_GAIN", value)
 	}
 
 
 	fn min_gain(&self) -> AltoResult<f32> {
-		let _lock = self.ctx.make_current(true)?;
-		let mut value = 0.0;
-		unsafe { self.ctx.api.head().alGetSourcef()(self.src, sys::AL_MIN_GAIN, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.get_f(sys::AL_MIN_GAIN, "AL_MIN_GAIN")
 	}
 	fn set_min_gain(&self, value: f32) -> AltoResult<()> {
-		let _lock = self.ctx.make_current(true)?;
-		unsafe { self.ctx.api.head().alSourcef()(self.src, sys::AL_MIN_GAIN, value); }
-		self.ctx.get_error()
+		let value = self.ctx.check_range("AL_MIN_GAIN", "min_gain must be between 0 and 1", value, UNIT)?;
+		self.set_f(sys::AL_MIN_GAIN, "AL_MIN_GAIN", value)
 	}
 
 
 	fn max_gain(&self) -> AltoResult<f32> {
-		let _lock = self.ctx.make_current(true)?;
-		let mut value = 0.0;
-		unsafe { self.ctx.api.head().alGetSourcef()(self.src, sys::AL_MAX_GAIN, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.get_f(sys::AL_MAX_GAIN, "AL_MAX_GAIN")
 	}
 	fn set_max_gain(&self, value: f32) -> AltoResult<()> {
-		let _lock = self.ctx.make_current(true)?;
-		unsafe { self.ctx.api.head().alSourcef()(self.src, sys::AL_MAX_GAIN, value); }
-		self.ctx.get_error()
+		let value = self.ctx.check_range("AL_MAX_GAIN", "max_gain must be between 0 and 1", value, UNIT)?;
+		self.set_f(sys::AL_MAX_GAIN, "AL_MAX_GAIN", value)
 	}
 
 
 	fn reference_distance(&self) -> AltoResult<f32> {
-		let _lock = self.ctx.make_current(true)?;
-		let mut value = 0.0;
-		unsafe { self.ctx.api.head().alGetSourcef()(self.src, sys::AL_REFERENCE_DISTANCE, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.get_f(sys::AL_REFERENCE_DISTANCE, "AL_REFERENCE_DISTANCE")
 	}
 	fn set_reference_distance(&self, value: f32) -> AltoResult<()> {
-		let _lock = self.ctx.make_current(true)?;
-		unsafe { self.ctx.api.head().alSourcef()(self.src, sys::AL_REFERENCE_DISTANCE, value); }
-		self.ctx.get_error()
+		let value = self.ctx.check_range("AL_REFERENCE_DISTANCE", "reference_distance must not be negative", value, NON_NEGATIVE)?;
+		self.set_f(sys::AL_REFERENCE_DISTANCE, "AL_REFERENCE_DISTANCE", value)
 	}
 
 
 	fn rolloff_factor(&self) -> AltoResult<f32> {
-		let _lock = self.ctx.make_current(true)?;
-		let mut value = 0.0;
-		unsafe { self.ctx.api.head().alGetSourcef()(self.src, sys::AL_ROLLOFF_FACTOR, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.get_f(sys::AL_ROLLOFF_FACTOR, "AL_ROLLOFF_FACTOR")
 	}
 	fn set_rolloff_factor(&self, value: f32) -> AltoResult<()> {
-		let _lock = self.ctx.make_current(true)?;
-		unsafe { self.ctx.api.head().alSourcef()(self.src, sys::AL_ROLLOFF_FACTOR, value); }
-		self.ctx.get_error()
+		let value = self.ctx.check_range("AL_ROLLOFF_FACTOR", "rolloff_factor must not be negative", value, NON_NEGATIVE)?;
+		self.set_f(sys::AL_ROLLOFF_FACTOR, "AL_ROLLOFF_FACTOR", value)
 	}
 
 
 	fn max_distance(&self) -> AltoResult<f32> {
-		let _lock = self.ctx.make_current(true)?;
-		let mut value = 0.0;
-		unsafe { self.ctx.api.head().alGetSourcef()(self.src, sys::AL_MAX_DISTANCE, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.get_f(sys::AL_MAX_DISTANCE, "AL_MAX_DISTANCE")
 	}
 	fn set_max_distance(&self, value: f32) -> AltoResult<()> {
-		let _lock = self.ctx.make_current(true)?;
-		unsafe { self.ctx.api.head().alSourcef()(self.src, sys::AL_MAX_DISTANCE, value); }
-		self.ctx.get_error()
+		let value = self.ctx.check_range("AL_MAX_DISTANCE", "max_distance must not be negative", value, NON_NEGATIVE)?;
+		self.set_f(sys::AL_MAX_DISTANCE, "AL_MAX_DISTANCE", value)
 	}
 
 
 	fn pitch(&self) -> AltoResult<f32> {
-		let _lock = self.ctx.make_current(true)?;
-		let mut value = 0.0;
-		unsafe { self.ctx.api.head().alGetSourcef()(self.src, sys::AL_PITCH, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.get_f(sys::AL_PITCH, "AL_PITCH")
 	}
 	fn set_pitch(&self, value: f32) -> AltoResult<()> {
-		let _lock = self.ctx.make_current(true)?;
-		unsafe { self.ctx.api.head().alSourcef()(self.src, sys::AL_PITCH, value); }
-		self.ctx.get_error()
+		let value = self.ctx.check_range("AL_PITCH", "pitch must be positive", value, POSITIVE)?;
+		self.set_f(sys::AL_PITCH, "AL_PITCH", value)
 	}
 
 
 	fn position<V: From<[f32; 3]>>(&self) -> AltoResult<V> {
-		let _lock = self.ctx.make_current(true)?;
-		let mut value = [0.0, 0.0, 0.0];
-		unsafe { self.ctx.api.head().alGetSourcefv()(self.src, sys::AL_POSITION, &mut value as *mut [f32; 3] as *mut sys::ALfloat); }
-		self.ctx.get_error().map(|_| value.into())
+		self.get_fv(sys::AL_POSITION, "AL_POSITION").map(|value| value.into())
 	}
 	fn set_position<V: Into<[f32; 3]>>(&self, value: V) -> AltoResult<()> {
-		let _lock = self.ctx.make_current(true)?;
 		let value = value.into();
-		unsafe { self.ctx.api.head().alSourcefv()(self.src, sys::AL_POSITION, &value as *const [f32; 3] as *const sys::ALfloat); }
-		self.ctx.get_error()
+		if !self.within_epsilon(|live| &mut live.last_position, value) {
+			self.set_fv(sys::AL_POSITION, "AL_POSITION", value).map_err(|e| {
+				self.ctx.with_live_source(self.slot, |live| live.last_position = None);
+				e
+			})?;
+		}
+
+		let vel = self.ctx.with_live_source(self.slot, |live| live.velocity.as_mut().and_then(|t| t.update(value, Instant::now()))).and_then(|v| v);
+		if let Some(vel) = vel {
+			self.set_velocity(vel)?;
+		}
+		Ok(())
 	}
 
 
 	fn velocity<V: From<[f32; 3]>>(&self) -> AltoResult<V> {
-		let _lock = self.ctx.make_current(true)?;
-		let mut value = [0.0, 0.0, 0.0];
-		unsafe { self.ctx.api.head().alGetSourcefv()(self.src, sys::AL_VELOCITY, &mut value as *mut [f32; 3] as *mut sys::ALfloat); }
-		self.ctx.get_error().map(|_| value.into())
+		self.get_fv(sys::AL_VELOCITY, "AL_VELOCITY").map(|value| value.into())
 	}
 	fn set_velocity<V: Into<[f32; 3]>>(&self, value: V) -> AltoResult<()> {
-		let _lock = self.ctx.make_current(true)?;
 		let value = value.into();
-		unsafe { self.ctx.api.head().alSourcefv()(self.src, sys::AL_VELOCITY, &value as *const [f32; 3] as *const sys::ALfloat); }
-		self.ctx.get_error()
+		if self.within_epsilon(|live| &mut live.last_velocity, value) { return Ok(()) }
+
+		self.set_fv(sys::AL_VELOCITY, "AL_VELOCITY", value).map_err(|e| {
+			self.ctx.with_live_source(self.slot, |live| live.last_velocity = None);
+			e
+		})
+	}
+
+
+	fn auto_velocity(&self) -> Option<f32> {
+		self.ctx.with_live_source(self.slot, |live| live.velocity.map(|t| t.smoothing())).and_then(|s| s)
+	}
+	fn set_auto_velocity(&self, smoothing: Option<f32>) -> AltoResult<()> {
+		let was = self.ctx.with_live_source(self.slot, |live| mem::replace(&mut live.velocity, smoothing.map(VelocityTracker::new))).and_then(|t| t);
+		if was.is_some() && smoothing.is_none() {
+			self.set_velocity([0.0, 0.0, 0.0])?;
+		}
+		Ok(())
 	}
 
 
 	fn direction<V: From<[f32; 3]>>(&self) -> AltoResult<V> {
-		let _lock = self.ctx.make_current(true)?;
-		let mut value = [0.0, 0.0, 0.0];
-		unsafe { self.ctx.api.head().alGetSourcefv()(self.src, sys::AL_DIRECTION, &mut value as *mut [f32; 3] as *mut sys::ALfloat); }
-		self.ctx.get_error().map(|_| value.into())
+		self.get_fv(sys::AL_DIRECTION, "AL_DIRECTION").map(|value| value.into())
 	}
 	fn set_direction<V: Into<[f32; 3]>>(&self, value: V) -> AltoResult<()> {
+		self.set_fv(sys::AL_DIRECTION, "AL_DIRECTION", value.into())
+	}
+
+
+	fn orientation<V: From<[f32; 3]>>(&self) -> AltoResult<(V, V)> {
+		self.ctx.extensions().AL_EXT_BFORMAT()?;
 		let _lock = self.ctx.make_current(true)?;
-		let value = value.into();
-		unsafe { self.ctx.api.head().alSourcefv()(self.src, sys::AL_DIRECTION, &value as *const [f32; 3] as *const sys::ALfloat); }
-		self.ctx.get_error()
+		let mut value = [[0.0, 0.0, 0.0], [0.0, 0.0, 0.0]];
+		unsafe { self.ctx.api.head().alGetSourcefv()(self.src, sys::AL_ORIENTATION, &mut value as *mut [[f32; 3]; 2] as *mut sys::ALfloat); }
+		self.ctx.get_error().map_err(|e| e.at("alGetSourcefv", ObjectKind::Source, Some("AL_ORIENTATION".into()))).map(|_| (value[0].into(), value[1].into()))
+	}
+	fn set_orientation<V: Into<[f32; 3]>>(&self, value: (V, V)) -> AltoResult<()> {
+		self.ctx.extensions().AL_EXT_BFORMAT()?;
+		let _lock = self.ctx.make_current(true)?;
+		let value = [value.0.into(), value.1.into()];
+		unsafe { self.ctx.api.head().alSourcefv()(self.src, sys::AL_ORIENTATION, &value as *const [[f32; 3]; 2] as *const sys::ALfloat); }
+		self.ctx.get_error().map_err(|e| e.at("alSourcefv", ObjectKind::Source, Some(("AL_ORIENTATION", value).into())))
 	}
 
 
 	fn cone_inner_angle(&self) -> AltoResult<f32> {
-		let _lock = self.ctx.make_current(true)?;
-		let mut value = 0.0;
-		unsafe { self.ctx.api.head().alGetSourcef()(self.src, sys::AL_CONE_INNER_ANGLE, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.get_f(sys::AL_CONE_INNER_ANGLE, "AL_CONE_INNER_ANGLE")
 	}
 	fn set_cone_inner_angle(&self, value: f32) -> AltoResult<()> {
-		let _lock = self.ctx.make_current(true)?;
-		unsafe { self.ctx.api.head().alSourcef()(self.src, sys::AL_CONE_INNER_ANGLE, value); }
-		self.ctx.get_error()
+		let value = self.ctx.check_range("AL_CONE_INNER_ANGLE", "cone inner angle must be between 0 and 360", value, ANGLE)?;
+		self.set_f(sys::AL_CONE_INNER_ANGLE, "AL_CONE_INNER_ANGLE", value)
 	}
 
 
 	fn cone_outer_angle(&self) -> AltoResult<f32> {
-		let _lock = self.ctx.make_current(true)?;
-		let mut value = 0.0;
-		unsafe { self.ctx.api.head().alGetSourcef()(self.src, sys::AL_CONE_OUTER_ANGLE, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.get_f(sys::AL_CONE_OUTER_ANGLE, "AL_CONE_OUTER_ANGLE")
 	}
 	fn set_cone_outer_angle(&self, value: f32) -> AltoResult<()> {
-		let _lock = self.ctx.make_current(true)?;
-		unsafe { self.ctx.api.head().alSourcef()(self.src, sys::AL_CONE_OUTER_ANGLE, value); }
-		self.ctx.get_error()
+		let value = self.ctx.check_range("AL_CONE_OUTER_ANGLE", "cone outer angle must be between 0 and 360", value, ANGLE)?;
+		self.set_f(sys::AL_CONE_OUTER_ANGLE, "AL_CONE_OUTER_ANGLE", value)
 	}
 
 
 	fn cone_outer_gain(&self) -> AltoResult<f32> {
-		let _lock = self.ctx.make_current(true)?;
-		let mut value = 0.0;
-		unsafe { self.ctx.api.head().alGetSourcef()(self.src, sys::AL_CONE_OUTER_GAIN, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.get_f(sys::AL_CONE_OUTER_GAIN, "AL_CONE_OUTER_GAIN")
 	}
 	fn set_cone_outer_gain(&self, value: f32) -> AltoResult<()> {
+		let value = self.ctx.check_range("AL_CONE_OUTER_GAIN", "cone outer gain must be between 0 and 1", value, UNIT)?;
+		self.set_f(sys::AL_CONE_OUTER_GAIN, "AL_CONE_OUTER_GAIN", value)
+	}
+	fn set_cone(&self, cone: SourceCone) -> AltoResult<()> {
+		let angle = |a: f32| a >= 0.0 && a <= 360.0;
+		let gain = |g: f32| g >= 0.0 && g <= 1.0;
+		if !angle(cone.inner_angle) { return Err(AltoError::AlInvalidRange("inner_angle must be between 0 and 360")) }
+		if !angle(cone.outer_angle) { return Err(AltoError::AlInvalidRange("outer_angle must be between 0 and 360")) }
+		if cone.inner_angle > cone.outer_angle { return Err(AltoError::AlInvalidRange("inner_angle must not exceed outer_angle")) }
+		if !gain(cone.outer_gain) { return Err(AltoError::AlInvalidRange("outer_gain must be between 0 and 1")) }
+		if let Some(gain_hf) = cone.outer_gain_hf {
+			if !gain(gain_hf) { return Err(AltoError::AlInvalidRange("outer_gain_hf must be between 0 and 1")) }
+			#[cfg(feature = "efx")]
+			self.ctx.dev.extensions().ALC_EXT_EFX()?;
+			#[cfg(not(feature = "efx"))]
+			return Err(AltoError::AlExtensionNotPresent);
+		}
+
 		let _lock = self.ctx.make_current(true)?;
-		unsafe { self.ctx.api.head().alSourcef()(self.src, sys::AL_CONE_OUTER_GAIN, value); }
-		self.ctx.get_error()
+		let suspend = self.ctx.suspended().ok();
+		self.set_cone_inner_angle(cone.inner_angle)?;
+		self.set_cone_outer_angle(cone.outer_angle)?;
+		self.set_cone_outer_gain(cone.outer_gain)?;
+		#[cfg(feature = "efx")]
+		{
+			if let Some(gain_hf) = cone.outer_gain_hf {
+				self.set_cone_outer_gainhf(gain_hf)?;
+			}
+		}
+		suspend.map_or(Ok(()), SuspendLock::commit)
 	}
 
 
@@ -1056,12 +3203,12 @@ impl<'d: 'c, 'c> SourceImpl<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { self.ctx.api.head().alGetSourcef()(self.src, sys::AL_SEC_OFFSET, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetSourcef", ObjectKind::Source, Some("AL_SEC_OFFSET".into()))).map(|_| value)
 	}
 	fn set_sec_offset(&self, value: f32) -> AltoResult<()> {
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { self.ctx.api.head().alSourcef()(self.src, sys::AL_SEC_OFFSET, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alSourcef", ObjectKind::Source, Some(("AL_SEC_OFFSET", value).into())))
 	}
 
 
@@ -1069,12 +3216,12 @@ impl<'d: 'c, 'c> SourceImpl<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { self.ctx.api.head().alGetSourcei()(self.src, sys::AL_SAMPLE_OFFSET, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetSourcei", ObjectKind::Source, Some("AL_SAMPLE_OFFSET".into()))).map(|_| value)
 	}
 	fn set_sample_offset(&self, value: sys::ALint) -> AltoResult<()> {
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { self.ctx.api.head().alSourcei()(self.src, sys::AL_SAMPLE_OFFSET, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alSourcei", ObjectKind::Source, Some(("AL_SAMPLE_OFFSET", value).into())))
 	}
 
 
@@ -1082,12 +3229,12 @@ impl<'d: 'c, 'c> SourceImpl<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { self.ctx.api.head().alGetSourcei()(self.src, sys::AL_BYTE_OFFSET, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetSourcei", ObjectKind::Source, Some("AL_BYTE_OFFSET".into()))).map(|_| value)
 	}
 	fn set_byte_offset(&self, value: sys::ALint) -> AltoResult<()> {
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { self.ctx.api.head().alSourcei()(self.src, sys::AL_BYTE_OFFSET, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alSourcei", ObjectKind::Source, Some(("AL_BYTE_OFFSET", value).into())))
 	}
 
 
@@ -1096,7 +3243,7 @@ impl<'d: 'c, 'c> SourceImpl<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = [0.0, 0.0];
 		unsafe { assl.alGetSourcedvSOFT?(self.src, assl.AL_SEC_OFFSET_LATENCY_SOFT?, &mut value as *mut [f64; 2] as *mut f64); }
-		self.ctx.get_error().map(|_| (value[0], value[1]))
+		self.ctx.get_error().map_err(|e| e.at("alGetSourcedvSOFT", ObjectKind::Source, Some("AL_SEC_OFFSET_LATENCY_SOFT".into()))).map(|_| (value[0], value[1]))
 	}
 
 
@@ -1105,7 +3252,7 @@ impl<'d: 'c, 'c> SourceImpl<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = [0, 0];
 		unsafe { assl.alGetSourcei64vSOFT?(self.src, assl.AL_SAMPLE_OFFSET_LATENCY_SOFT?, &mut value as *mut [i64; 2] as *mut i64); }
-		self.ctx.get_error().map(|_| ((value[0] >> 32) as i32, value[0] as i32, value[1]))
+		self.ctx.get_error().map_err(|e| e.at("alGetSourcei64vSOFT", ObjectKind::Source, Some("AL_SAMPLE_OFFSET_LATENCY_SOFT".into()))).map(|_| ((value[0] >> 32) as i32, value[0] as i32, value[1]))
 	}
 
 
@@ -1113,12 +3260,12 @@ impl<'d: 'c, 'c> SourceImpl<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { self.ctx.api.head().alGetSourcei()(self.src, self.ctx.exts.AL_SOFT_direct_channels()?.AL_DIRECT_CHANNELS_SOFT?, &mut value); }
-		self.ctx.get_error().map(|_| value == sys::AL_TRUE as sys::ALint)
+		self.ctx.get_error().map_err(|e| e.at("alGetSourcei", ObjectKind::Source, Some("AL_DIRECT_CHANNELS_SOFT".into()))).map(|_| value == sys::AL_TRUE as sys::ALint)
 	}
 	fn set_soft_direct_channels(&self, value: bool) -> AltoResult<()> {
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { self.ctx.api.head().alSourcei()(self.src, self.ctx.exts.AL_SOFT_direct_channels()?.AL_DIRECT_CHANNELS_SOFT?, if value { sys::AL_TRUE } else { sys::AL_FALSE } as sys::ALint); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alSourcei", ObjectKind::Source, Some(("AL_DIRECT_CHANNELS_SOFT", value).into())))
 	}
 
 
@@ -1126,7 +3273,7 @@ impl<'d: 'c, 'c> SourceImpl<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { self.ctx.api.head().alGetSourcef()(self.src, self.ctx.exts.AL_SOFT_source_length()?.AL_SEC_LENGTH_SOFT?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetSourcef", ObjectKind::Source, Some("AL_SEC_LENGTH_SOFT".into()))).map(|_| value)
 	}
 
 
@@ -1134,7 +3281,7 @@ impl<'d: 'c, 'c> SourceImpl<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { self.ctx.api.head().alGetSourcei()(self.src, self.ctx.exts.AL_SOFT_source_length()?.AL_SAMPLE_LENGTH_SOFT?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetSourcei", ObjectKind::Source, Some("AL_SAMPLE_LENGTH_SOFT".into()))).map(|_| value)
 	}
 
 
@@ -1142,7 +3289,7 @@ impl<'d: 'c, 'c> SourceImpl<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { self.ctx.api.head().alGetSourcei()(self.src, self.ctx.exts.AL_SOFT_source_length()?.AL_BYTE_LENGTH_SOFT?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetSourcei", ObjectKind::Source, Some("AL_BYTE_LENGTH_SOFT".into()))).map(|_| value)
 	}
 
 
@@ -1151,7 +3298,7 @@ impl<'d: 'c, 'c> SourceImpl<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { self.ctx.api.head().alGetSourcei()(self.src, sys::AL_DISTANCE_MODEL, &mut value); }
-		self.ctx.get_error().and_then(|_| match value {
+		self.ctx.get_error().map_err(|e| e.at("alGetSourcei", ObjectKind::Source, Some("AL_DISTANCE_MODEL".into()))).and_then(|_| match value {
 			sys::AL_NONE => Ok(DistanceModel::None),
 			sys::AL_INVERSE_DISTANCE => Ok(DistanceModel::Inverse),
 			sys::AL_INVERSE_DISTANCE_CLAMPED => Ok(DistanceModel::InverseClamped),
@@ -1164,64 +3311,71 @@ impl<'d: 'c, 'c> SourceImpl<'d, 'c> {
 	}
 	fn set_distance_model(&self, value: DistanceModel) -> AltoResult<()> {
 		self.ctx.exts.AL_EXT_source_distance_model()?;
+		let model = match value {
+			DistanceModel::None => sys::AL_NONE,
+			DistanceModel::Inverse => sys::AL_INVERSE_DISTANCE,
+			DistanceModel::InverseClamped => sys::AL_INVERSE_DISTANCE_CLAMPED,
+			DistanceModel::Linear => sys::AL_LINEAR_DISTANCE,
+			DistanceModel::LinearClamped => sys::AL_LINEAR_DISTANCE_CLAMPED,
+			DistanceModel::Exponent => sys::AL_EXPONENT_DISTANCE,
+			DistanceModel::ExponentClamped => sys::AL_EXPONENT_DISTANCE_CLAMPED,
+		};
 		let _lock = self.ctx.make_current(true)?;
-		unsafe {
-			self.ctx.api.head().alSourcei()(self.src, sys::AL_DISTANCE_MODEL, match value {
-				DistanceModel::None => sys::AL_NONE,
-				DistanceModel::Inverse => sys::AL_INVERSE_DISTANCE,
-				DistanceModel::InverseClamped => sys::AL_INVERSE_DISTANCE_CLAMPED,
-				DistanceModel::Linear => sys::AL_LINEAR_DISTANCE,
-				DistanceModel::LinearClamped => sys::AL_LINEAR_DISTANCE_CLAMPED,
-				DistanceModel::Exponent => sys::AL_EXPONENT_DISTANCE,
-				DistanceModel::ExponentClamped => sys::AL_EXPONENT_DISTANCE_CLAMPED,
-			});
-		}
-		self.ctx.get_error()
+		unsafe { self.ctx.api.head().alSourcei()(self.src, sys::AL_DISTANCE_MODEL, model); }
+		self.ctx.get_error().map_err(|e| e.at("alSourcei", ObjectKind::Source, Some(("AL_DISTANCE_MODEL", model).into())))
 	}
 
 
+	#[cfg(feature = "efx")]
 	fn set_direct_filter<F: FilterTrait<'d, 'c>>(&self, value: &F) -> AltoResult<()> {
 		let efx = self.ctx.dev.extensions().ALC_EXT_EFX()?;
 		if value.context() != self.ctx {
-			return Err(AltoError::AlInvalidValue);
+			return Err(AltoError::AlWrongContext);
 		}
 
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { self.ctx.api.head().alSourcei()(self.src, efx.AL_DIRECT_FILTER?, value.as_raw() as sys::ALint); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alSourcei", ObjectKind::Source, Some("AL_DIRECT_FILTER".into())))
 	}
+	#[cfg(feature = "efx")]
 	fn clear_direct_filter(&self) -> AltoResult<()> {
 		let efx = self.ctx.dev.extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { self.ctx.api.head().alSourcei()(self.src, efx.AL_DIRECT_FILTER?, 0); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alSourcei", ObjectKind::Source, Some("AL_DIRECT_FILTER".into())))
 	}
 
 
+	#[cfg(feature = "efx")]
 	fn set_auxiliary_send(arc_self: &Arc<SourceImpl<'d, 'c>>, send: sys::ALint, slot: &mut AuxEffectSlot<'d, 'c>) -> AltoResult<()> {
 		SourceImpl::set_auxiliary_send_impl(arc_self, send, slot, 0)
 	}
+	#[cfg(feature = "efx")]
 	fn set_auxiliary_send_filter<F: FilterTrait<'d, 'c>>(arc_self: &Arc<SourceImpl<'d, 'c>>, send: sys::ALint, slot: &mut AuxEffectSlot<'d, 'c>, filter: &F) -> AltoResult<()> {
 		if filter.context() != arc_self.ctx {
-			return Err(AltoError::AlInvalidValue);
+			return Err(AltoError::AlWrongContext);
 		}
 
 		SourceImpl::set_auxiliary_send_impl(arc_self, send, slot, filter.as_raw())
 	}
+	#[cfg(feature = "efx")]
 	fn set_auxiliary_send_impl(arc_self: &Arc<SourceImpl<'d, 'c>>, send: sys::ALint, slot: &mut AuxEffectSlot<'d, 'c>, filter: sys::ALuint) -> AltoResult<()> {
 		let efx = arc_self.ctx.dev.extensions().ALC_EXT_EFX()?;
-		if send >= arc_self.ctx.device().max_auxiliary_sends()? || slot.context() != arc_self.ctx {
+		if slot.context() != arc_self.ctx {
+			return Err(AltoError::AlWrongContext);
+		}
+		if send >= arc_self.ctx.device().max_auxiliary_sends()? {
 			return Err(AltoError::AlInvalidValue);
 		}
 
 		let _lock = arc_self.ctx.make_current(true)?;
-		let mut sends = arc_self.sends.lock().unwrap();
 		unsafe { arc_self.ctx.api.head().alSourceiv()(arc_self.src, efx.AL_AUXILIARY_SEND_FILTER?, &mut [slot.as_raw() as sys::ALint, send, filter as sys::ALint] as *mut [sys::ALint; 3] as *mut sys::ALint); }
-		arc_self.ctx.get_error()?;
-		sends[send as usize] = 0;
+		arc_self.ctx.get_error().map_err(|e| e.at("alSourceiv", ObjectKind::Source, Some("AL_AUXILIARY_SEND_FILTER".into())))?;
+		arc_self.ctx.with_live_source(arc_self.slot, |live| live.sends[send as usize] = slot.as_raw());
 		slot.add_input(Arc::downgrade(arc_self));
 		Ok(())
 	}
+	#[cfg(feature = "efx")]
 	fn clear_auxiliary_send(&self, send: sys::ALint) -> AltoResult<()> {
 		let efx = self.ctx.dev.extensions().ALC_EXT_EFX()?;
 		if send >= self.ctx.device().max_auxiliary_sends()? {
@@ -1229,82 +3383,234 @@ impl<'d: 'c, 'c> SourceImpl<'d, 'c> {
 		}
 
 		let _lock = self.ctx.make_current(true)?;
-		let mut sends = self.sends.lock().unwrap();
 		unsafe { self.ctx.api.head().alSourceiv()(self.src, efx.AL_AUXILIARY_SEND_FILTER?, &mut [0, send, 0] as *mut [sys::ALint; 3] as *mut sys::ALint); }
-		self.ctx.get_error()?;
-		sends[send as usize] = 0;
+		self.ctx.get_error().map_err(|e| e.at("alSourceiv", ObjectKind::Source, Some("AL_AUXILIARY_SEND_FILTER".into())))?;
+		self.ctx.with_live_source(self.slot, |live| live.sends[send as usize] = 0);
 		Ok(())
 	}
+	#[cfg(feature = "efx")]
 	pub fn clear_auxiliary_effect_slot(&self, slot: sys::ALuint) -> AltoResult<()> {
 		let efx = self.ctx.dev.extensions().ALC_EXT_EFX()?;
-		for (i, s) in self.sends.lock().unwrap().iter_mut().enumerate() {
-			if *s == slot {
-				unsafe { self.ctx.api.head().alSourceiv()(self.src, efx.AL_AUXILIARY_SEND_FILTER.unwrap(), &mut [0, i as sys::ALint, 0] as *mut [sys::ALint; 3] as *mut sys::ALint); }
-				*s = 0;
-			}
+		let cleared: Vec<usize> = self.ctx.with_live_source(self.slot, |live| {
+			live.sends.iter_mut().enumerate().filter(|&(_, ref s)| **s == slot).map(|(i, s)| { *s = 0; i }).collect()
+		}).unwrap_or_else(Vec::new);
+		for i in cleared {
+			unsafe { self.ctx.api.head().alSourceiv()(self.src, efx.AL_AUXILIARY_SEND_FILTER.unwrap(), &mut [0, i as sys::ALint, 0] as *mut [sys::ALint; 3] as *mut sys::ALint); }
 		}
 
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alSourceiv", ObjectKind::Source, None))
 	}
 
 
+	#[cfg(feature = "efx")]
 	fn air_absorption_factor(&self) -> AltoResult<f32> {
 		let efx = self.ctx.dev.extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { self.ctx.api.head().alGetSourcef()(self.src, efx.AL_AIR_ABSORPTION_FACTOR?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetSourcef", ObjectKind::Source, Some("AL_AIR_ABSORPTION_FACTOR".into()))).map(|_| value)
 	}
+	#[cfg(feature = "efx")]
 	fn set_air_absorption_factor(&self, value: f32) -> AltoResult<()> {
+		let value = self.ctx.check_range("AL_AIR_ABSORPTION_FACTOR", "air absorption factor must be between 0 and 10", value, EFX_FACTOR)?;
 		let efx = self.ctx.dev.extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { self.ctx.api.head().alSourcef()(self.src, efx.AL_AIR_ABSORPTION_FACTOR?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alSourcef", ObjectKind::Source, Some(("AL_AIR_ABSORPTION_FACTOR", value).into())))
 	}
 
 
+	#[cfg(feature = "efx")]
 	fn room_rolloff_factor(&self) -> AltoResult<f32> {
 		let efx = self.ctx.dev.extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { self.ctx.api.head().alGetSourcef()(self.src, efx.AL_ROOM_ROLLOFF_FACTOR?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetSourcef", ObjectKind::Source, Some("AL_ROOM_ROLLOFF_FACTOR".into()))).map(|_| value)
 	}
+	#[cfg(feature = "efx")]
 	fn set_room_rolloff_factor(&self, value: f32) -> AltoResult<()> {
+		let value = self.ctx.check_range("AL_ROOM_ROLLOFF_FACTOR", "room rolloff factor must be between 0 and 10", value, EFX_FACTOR)?;
 		let efx = self.ctx.dev.extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { self.ctx.api.head().alSourcef()(self.src, efx.AL_ROOM_ROLLOFF_FACTOR?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alSourcef", ObjectKind::Source, Some(("AL_ROOM_ROLLOFF_FACTOR", value).into())))
 	}
 
 
+	#[cfg(feature = "efx")]
 	fn cone_outer_gainhf(&self) -> AltoResult<f32> {
 		let efx = self.ctx.dev.extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { self.ctx.api.head().alGetSourcef()(self.src, efx.AL_CONE_OUTER_GAINHF?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetSourcef", ObjectKind::Source, Some("AL_CONE_OUTER_GAINHF".into()))).map(|_| value)
 	}
+	#[cfg(feature = "efx")]
 	fn set_cone_outer_gainhf(&self, value: f32) -> AltoResult<()> {
+		let value = self.ctx.check_range("AL_CONE_OUTER_GAINHF", "cone outer gainhf must be between 0 and 1", value, UNIT)?;
 		let efx = self.ctx.dev.extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { self.ctx.api.head().alSourcef()(self.src, efx.AL_CONE_OUTER_GAINHF?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alSourcef", ObjectKind::Source, Some(("AL_CONE_OUTER_GAINHF", value).into())))
 	}
 
 
+	#[cfg(feature = "efx")]
 	fn direct_filter_gainhf_auto(&self) -> AltoResult<bool> {
 		let efx = self.ctx.dev.extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { self.ctx.api.head().alGetSourcei()(self.src, efx.AL_CONE_OUTER_GAINHF?, &mut value); }
-		self.ctx.get_error().map(|_| value == sys::AL_TRUE as sys::ALint)
+		self.ctx.get_error().map_err(|e| e.at("alGetSourcei", ObjectKind::Source, Some("AL_CONE_OUTER_GAINHF".into()))).map(|_| value == sys::AL_TRUE as sys::ALint)
 	}
+	#[cfg(feature = "efx")]
 	fn set_direct_filter_gainhf_auto(&self, value: bool) -> AltoResult<()> {
 		let efx = self.ctx.dev.extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { self.ctx.api.head().alSourcei()(self.src, efx.AL_CONE_OUTER_GAINHF?, if value { sys::AL_TRUE } else { sys::AL_FALSE } as sys::ALint); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alSourcei", ObjectKind::Source, Some(("AL_CONE_OUTER_GAINHF", value).into())))
+	}
+
+
+	fn state_snapshot(&self) -> AltoResult<SourceProps> {
+		let _lock = self.ctx.make_current(true)?;
+		let sdm = self.ctx.exts.AL_EXT_source_distance_model().is_ok();
+		#[cfg(feature = "efx")]
+		let efx = self.ctx.dev.extensions().ALC_EXT_EFX().is_ok();
+		Ok(SourceProps{
+			relative: self.relative()?,
+			gain: self.gain()?,
+			min_gain: self.min_gain()?,
+			max_gain: self.max_gain()?,
+			pitch: self.pitch()?,
+			position: self.position()?,
+			velocity: self.velocity()?,
+			direction: self.direction()?,
+			reference_distance: self.reference_distance()?,
+			rolloff_factor: self.rolloff_factor()?,
+			max_distance: self.max_distance()?,
+			cone_inner_angle: self.cone_inner_angle()?,
+			cone_outer_angle: self.cone_outer_angle()?,
+			cone_outer_gain: self.cone_outer_gain()?,
+			distance_model: if sdm { Some(self.distance_model()?) } else { None },
+			#[cfg(feature = "efx")]
+			air_absorption_factor: if efx { Some(self.air_absorption_factor()?) } else { None },
+			#[cfg(feature = "efx")]
+			room_rolloff_factor: if efx { Some(self.room_rolloff_factor()?) } else { None },
+			#[cfg(feature = "efx")]
+			cone_outer_gainhf: if efx { Some(self.cone_outer_gainhf()?) } else { None },
+		})
+	}
+	fn apply(&self, props: &SourceProps) -> AltoResult<()> {
+		let _lock = self.ctx.make_current(true)?;
+		let suspend = self.ctx.suspended().ok();
+
+		self.set_relative(props.relative)?;
+		self.set_gain(props.gain)?;
+		self.set_min_gain(props.min_gain)?;
+		self.set_max_gain(props.max_gain)?;
+		self.set_pitch(props.pitch)?;
+		self.set_position(props.position)?;
+		self.set_velocity(props.velocity)?;
+		self.set_direction(props.direction)?;
+		self.set_reference_distance(props.reference_distance)?;
+		self.set_rolloff_factor(props.rolloff_factor)?;
+		self.set_max_distance(props.max_distance)?;
+		self.set_cone_inner_angle(props.cone_inner_angle)?;
+		self.set_cone_outer_angle(props.cone_outer_angle)?;
+		self.set_cone_outer_gain(props.cone_outer_gain)?;
+		if let Some(model) = props.distance_model {
+			self.set_distance_model(model)?;
+		}
+		#[cfg(feature = "efx")]
+		{
+			if let Some(factor) = props.air_absorption_factor {
+				self.set_air_absorption_factor(factor)?;
+			}
+			if let Some(factor) = props.room_rolloff_factor {
+				self.set_room_rolloff_factor(factor)?;
+			}
+			if let Some(gain) = props.cone_outer_gainhf {
+				self.set_cone_outer_gainhf(gain)?;
+			}
+		}
+		suspend.map_or(Ok(()), SuspendLock::commit)
+	}
+	fn apply_changed(&self, old: &SourceProps, new: &SourceProps) -> AltoResult<()> {
+		if old == new { return Ok(()) }
+		let _lock = self.ctx.make_current(true)?;
+		let suspend = self.ctx.suspended().ok();
+
+		if new.relative != old.relative { self.set_relative(new.relative)?; }
+		if new.gain != old.gain { self.set_gain(new.gain)?; }
+		if new.min_gain != old.min_gain { self.set_min_gain(new.min_gain)?; }
+		if new.max_gain != old.max_gain { self.set_max_gain(new.max_gain)?; }
+		if new.pitch != old.pitch { self.set_pitch(new.pitch)?; }
+		if new.position != old.position { self.set_position(new.position)?; }
+		if new.velocity != old.velocity { self.set_velocity(new.velocity)?; }
+		if new.direction != old.direction { self.set_direction(new.direction)?; }
+		if new.reference_distance != old.reference_distance { self.set_reference_distance(new.reference_distance)?; }
+		if new.rolloff_factor != old.rolloff_factor { self.set_rolloff_factor(new.rolloff_factor)?; }
+		if new.max_distance != old.max_distance { self.set_max_distance(new.max_distance)?; }
+		if new.cone_inner_angle != old.cone_inner_angle { self.set_cone_inner_angle(new.cone_inner_angle)?; }
+		if new.cone_outer_angle != old.cone_outer_angle { self.set_cone_outer_angle(new.cone_outer_angle)?; }
+		if new.cone_outer_gain != old.cone_outer_gain { self.set_cone_outer_gain(new.cone_outer_gain)?; }
+		if let (Some(model), true) = (new.distance_model, new.distance_model != old.distance_model) {
+			self.set_distance_model(model)?;
+		}
+		#[cfg(feature = "efx")]
+		{
+			if let (Some(factor), true) = (new.air_absorption_factor, new.air_absorption_factor != old.air_absorption_factor) {
+				self.set_air_absorption_factor(factor)?;
+			}
+			if let (Some(factor), true) = (new.room_rolloff_factor, new.room_rolloff_factor != old.room_rolloff_factor) {
+				self.set_room_rolloff_factor(factor)?;
+			}
+			if let (Some(gain), true) = (new.cone_outer_gainhf, new.cone_outer_gainhf != old.cone_outer_gainhf) {
+				self.set_cone_outer_gainhf(gain)?;
+			}
+		}
+		suspend.map_or(Ok(()), SuspendLock::commit)
+	}
+	fn get_many(&self, props: &[SourceProp]) -> AltoResult<PropValues> {
+		let _lock = self.ctx.make_current(true)?;
+		let head = self.ctx.api.head();
+		let mut values = Vec::with_capacity(props.len());
+		for &prop in props {
+			// Integer properties are read with `alGetSourceiv()`, and the rest with `alGetSourcefv()`, which takes scalars too.
+			let value = match prop {
+				SourceProp::State | SourceProp::Relative | SourceProp::Looping
+					| SourceProp::SampleOffset | SourceProp::ByteOffset | SourceProp::BuffersQueued | SourceProp::BuffersProcessed
+				=> {
+					let mut value = 0;
+					unsafe { head.alGetSourceiv()(self.src, prop.param(), &mut value); }
+					self.ctx.get_error().map_err(|e| e.at("alGetSourceiv", ObjectKind::Source, Some(prop.name().into())))?;
+					match prop {
+						SourceProp::State => PropValue::State(SourceState::from_raw(value)),
+						SourceProp::Relative | SourceProp::Looping => PropValue::Bool(value == sys::AL_TRUE as sys::ALint),
+						_ => PropValue::Int(value),
+					}
+				},
+				_ => {
+					let mut value = [0.0, 0.0, 0.0];
+					unsafe { head.alGetSourcefv()(self.src, prop.param(), value.as_mut_ptr()); }
+					self.ctx.get_error().map_err(|e| e.at("alGetSourcefv", ObjectKind::Source, Some(prop.name().into())))?;
+					match prop {
+						SourceProp::Position | SourceProp::Velocity | SourceProp::Direction => PropValue::Vector(value),
+						_ => PropValue::Float(value[0]),
+					}
+				},
+			};
+			values.push(value);
+		}
+		Ok(PropValues(props.iter().cloned().zip(values).map(|(prop, value)| {
+			match self.ctx.deferred.get(DeferredTarget::Source(self.src), prop.param()) {
+				Some(DeferredValue::Float(pending)) => (prop, PropValue::Float(pending)),
+				Some(DeferredValue::Vector(pending)) => (prop, PropValue::Vector(pending)),
+				None => (prop, value),
+			}
+		}).collect()))
 	}
 }
 
@@ -1327,7 +3633,10 @@ impl<'d: 'c, 'c> Hash for SourceImpl<'d, 'c> {
 
 impl<'d: 'c, 'c> Drop for SourceImpl<'d, 'c> {
 	fn drop(&mut self) {
-		if let Ok(_lock) = self.ctx.make_current(true) {
+		self.ctx.dev.alto().metrics().voice_deleted();
+		self.ctx.forget_source(self.slot);
+		self.ctx.deferred.discard(DeferredTarget::Source(self.src));
+		if let Ok(_lock) = self.ctx.make_current_unchecked(true) {
 			unsafe { self.ctx.api.head().alDeleteSources()(1, &mut self.src as *mut sys::ALuint); }
 			if let Err(_) = self.ctx.get_error() {
 				let _ = writeln!(io::stderr(), "ALTO ERROR: `alDeleteSources` failed in Source drop");
@@ -1345,8 +3654,47 @@ impl<'d: 'c, 'c> StaticSource<'d, 'c> {
 		let _lock = ctx.make_current(true)?;
 		let mut src = 0;
 		unsafe { ctx.api.head().alGenSources()(1, &mut src as *mut sys::ALuint); }
-		let sends = iter::repeat(0).take(ctx.dev.max_auxiliary_sends().unwrap_or(0) as usize).collect();
-		ctx.get_error().map(|_| StaticSource{src: Arc::new(SourceImpl{ctx: ctx, src: src, sends: Mutex::new(sends)}), buf: None})
+		ctx.get_error().map_err(|e| e.at("alGenSources", ObjectKind::Source, None))?;
+		ctx.dev.alto().metrics().voice_created();
+		let slot = ctx.track_source(src, false);
+		Ok(StaticSource{src: Arc::new(SourceImpl{ctx: ctx, src: src, slot: slot}), buf: None})
+	}
+
+
+	/// Take ownership of a source generated outside of alto. It will be deleted when dropped.
+	/// Fails with `AlAlreadyAdopted` if alto already holds `src`.
+	///
+	/// # Safety
+	/// `src` must belong to `ctx` and must not be deleted elsewhere. Any buffer already attached to
+	/// the source is not tracked, and must outlive it or be replaced with `set_buffer()`.
+	pub unsafe fn from_raw(ctx: &'c Context<'d>, src: sys::ALuint) -> AltoResult<StaticSource<'d, 'c>> {
+		SourceImpl::from_raw(ctx, src, false).map(|src| StaticSource{src: Arc::new(src), buf: None})
+	}
+
+
+	/// Use a source owned by code outside of alto without taking ownership of it.
+	/// It is released rather than deleted when the returned handle is dropped, and a buffer attached
+	/// through it is dropped then, to be deleted once the source no longer uses it.
+	/// Fails with `AlAlreadyAdopted` if alto already holds `src`.
+	///
+	/// # Safety
+	/// `src` must belong to `ctx` and must not be deleted while borrowed. Any buffer already attached to
+	/// the source is not tracked, and must outlive it or be replaced with `set_buffer()`.
+	pub unsafe fn borrow_raw(ctx: &'c Context<'d>, src: sys::ALuint) -> AltoResult<Borrowed<StaticSource<'d, 'c>>> {
+		StaticSource::from_raw(ctx, src).map(Borrowed::new)
+	}
+
+
+	/// Release ownership of the source without deleting it.
+	/// The attached buffer, if any, is returned so that it can be kept alive for as long as the source uses it.
+	/// Fails with `AlInvalidOperation` while an effect slot dropped on another thread still refers to the source,
+	/// handing the source back so that it can be tried again.
+	pub fn into_raw(self) -> Result<(sys::ALuint, Option<Arc<Buffer<'d, 'c>>>), (AltoError, StaticSource<'d, 'c>)> {
+		let StaticSource{src, buf} = self;
+		match SourceImpl::into_raw(src) {
+			Ok(src) => Ok((src, buf)),
+			Err(src) => Err((AltoError::AlInvalidOperation, StaticSource{src: src, buf: buf})),
+		}
 	}
 
 
@@ -1354,17 +3702,19 @@ impl<'d: 'c, 'c> StaticSource<'d, 'c> {
 
 
 	/// `alSourcei(AL_BUFFER)`
+	/// Buffers are shared by every context of a device; one created on another device fails with `AlWrongDevice`.
 	pub fn set_buffer(&mut self, buf: Arc<Buffer<'d, 'c>>) -> AltoResult<()> {
 		if buf.ctx.device().as_raw() != self.src.ctx.device().as_raw() {
-			return Err(AltoError::AlInvalidValue);
+			return Err(AltoError::AlWrongDevice);
 		}
 
 		{
 			let _lock = self.src.ctx.make_current(true)?;
 			unsafe { self.src.ctx.api.head().alSourcei()(self.src.src, sys::AL_BUFFER, buf.buf as sys::ALint); }
-			self.src.ctx.get_error()?;
+			self.src.ctx.get_error().map_err(|e| e.at("alSourcei", ObjectKind::Source, Some("AL_BUFFER".into())))?;
 		}
 
+		self.src.ctx.attach_buffers(self.src.slot, &[buf.buf], true);
 		self.buf = Some(buf);
 		Ok(())
 	}
@@ -1373,26 +3723,51 @@ impl<'d: 'c, 'c> StaticSource<'d, 'c> {
 		{
 			let _lock = self.src.ctx.make_current(true)?;
 			unsafe { self.src.ctx.api.head().alSourcei()(self.src.src, sys::AL_BUFFER, 0); }
-			self.src.ctx.get_error()?;
+			self.src.ctx.get_error().map_err(|e| e.at("alSourcei", ObjectKind::Source, Some("AL_BUFFER".into())))?;
 		}
 
+		self.src.ctx.attach_buffers(self.src.slot, &[], true);
 		self.buf = None;
 		Ok(())
 	}
 
 
+	/// Play only the sample frames `[start, end)` of the attached buffer, such as one sound packed into an audio atlas.
+	/// Playback begins at `start` right away, but is only stopped at `end` by `Context::maintain()`,
+	/// so it overshoots by up to the interval at which that is called. A looping source is sought back to
+	/// `start` instead of being stopped. The range is forgotten once the source stops.
+	pub fn play_range(&mut self, start: sys::ALint, end: sys::ALint) -> AltoResult<()> {
+		if self.buf.is_none() { return Err(AltoError::AlInvalidOperation) }
+		if start < 0 { return Err(AltoError::AlInvalidRange("range start must not be negative")) }
+		if start >= end { return Err(AltoError::AlInvalidRange("range start must be before its end")) }
+
+		{
+			let _lock = self.src.ctx.make_current(true)?;
+			unsafe {
+				self.src.ctx.api.head().alSourceRewind()(self.src.src);
+				self.src.ctx.api.head().alSourcei()(self.src.src, sys::AL_SAMPLE_OFFSET, start);
+				self.src.ctx.api.head().alSourcePlay()(self.src.src);
+			}
+			self.src.ctx.get_error().map_err(|e| e.at("alSourcePlay", ObjectKind::Source, Some(("AL_SAMPLE_OFFSET", start).into())))?;
+		}
+
+		self.src.ctx.set_source_range(self.src.slot, Some((start, end)));
+		Ok(())
+	}
+
+
 	/// `alGetSourcei(AL_LOOPING)`
 	pub fn looping(&self) -> AltoResult<bool> {
 		let _lock = self.src.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { self.src.ctx.api.head().alGetSourcei()(self.src.src, sys::AL_LOOPING, &mut value); }
-		self.src.ctx.get_error().map(|_| value == sys::AL_TRUE as sys::ALint)
+		self.src.ctx.get_error().map_err(|e| e.at("alGetSourcei", ObjectKind::Source, Some("AL_LOOPING".into()))).map(|_| value == sys::AL_TRUE as sys::ALint)
 	}
 	/// `alSourcei(AL_LOOPING)`
 	pub fn set_looping(&mut self, value: bool) -> AltoResult<()> {
 		let _lock = self.src.ctx.make_current(true)?;
 		unsafe { self.src.ctx.api.head().alSourcei()(self.src.src, sys::AL_LOOPING, if value { sys::AL_TRUE } else { sys::AL_FALSE } as sys::ALint); }
-		self.src.ctx.get_error()
+		self.src.ctx.get_error().map_err(|e| e.at("alSourcei", ObjectKind::Source, Some(("AL_LOOPING", value).into())))
 	}
 
 
@@ -1438,10 +3813,15 @@ unsafe impl<'d: 'c, 'c> SourceTrait<'d, 'c> for StaticSource<'d, 'c> {
 
 	fn velocity<V: From<[f32; 3]>>(&self) -> AltoResult<V> { self.src.velocity() }
 	fn set_velocity<V: Into<[f32; 3]>>(&mut self, value: V) -> AltoResult<()> { self.src.set_velocity(value) }
+	fn auto_velocity(&self) -> Option<f32> { self.src.auto_velocity() }
+	fn set_auto_velocity(&mut self, smoothing: Option<f32>) -> AltoResult<()> { self.src.set_auto_velocity(smoothing) }
 
 	fn direction<V: From<[f32; 3]>>(&self) -> AltoResult<V> { self.src.direction() }
 	fn set_direction<V: Into<[f32; 3]>>(&mut self, value: V) -> AltoResult<()> { self.src.set_direction(value) }
 
+	fn orientation<V: From<[f32; 3]>>(&self) -> AltoResult<(V, V)> { self.src.orientation() }
+	fn set_orientation<V: Into<[f32; 3]>>(&mut self, value: (V, V)) -> AltoResult<()> { self.src.set_orientation(value) }
+
 	fn cone_inner_angle(&self) -> AltoResult<f32> { self.src.cone_inner_angle() }
 	fn set_cone_inner_angle(&mut self, value: f32) -> AltoResult<()> { self.src.set_cone_inner_angle(value) }
 
@@ -1450,6 +3830,7 @@ unsafe impl<'d: 'c, 'c> SourceTrait<'d, 'c> for StaticSource<'d, 'c> {
 
 	fn cone_outer_gain(&self) -> AltoResult<f32> { self.src.cone_outer_gain() }
 	fn set_cone_outer_gain(&mut self, value: f32) -> AltoResult<()> { self.src.set_cone_outer_gain(value) }
+	fn set_cone(&mut self, cone: SourceCone) -> AltoResult<()> { self.src.set_cone(cone) }
 
 	fn sec_offset(&self) -> AltoResult<f32> { self.src.sec_offset() }
 	fn set_sec_offset(&mut self, value: f32) -> AltoResult<()> { self.src.set_sec_offset(value) }
@@ -1476,23 +3857,41 @@ unsafe impl<'d: 'c, 'c> SourceTrait<'d, 'c> for StaticSource<'d, 'c> {
 	fn distance_model(&self) -> AltoResult<DistanceModel> { self.src.distance_model() }
 	fn set_distance_model(&mut self, value: DistanceModel) -> AltoResult<()> { self.src.set_distance_model(value) }
 
+	fn state_snapshot(&self) -> AltoResult<SourceProps> { self.src.state_snapshot() }
+	fn apply(&mut self, props: &SourceProps) -> AltoResult<()> { self.src.apply(props) }
+	fn apply_changed(&mut self, old: &SourceProps, new: &SourceProps) -> AltoResult<()> { self.src.apply_changed(old, new) }
+	fn get_many(&self, props: &[SourceProp]) -> AltoResult<PropValues> { self.src.get_many(props) }
+
+	#[cfg(feature = "efx")]
 	fn set_direct_filter<F: FilterTrait<'d, 'c>>(&mut self, value: &F) -> AltoResult<()> { self.src.set_direct_filter(value) }
+	#[cfg(feature = "efx")]
 	fn clear_direct_filter(&mut self) -> AltoResult<()> { self.src.clear_direct_filter() }
 
+	#[cfg(feature = "efx")]
 	fn set_auxiliary_send(&mut self, send: sys::ALint, slot: &mut AuxEffectSlot<'d, 'c>) -> AltoResult<()> { SourceImpl::set_auxiliary_send(&self.src, send, slot) }
+	#[cfg(feature = "efx")]
 	fn set_auxiliary_send_filter<F: FilterTrait<'d, 'c>>(&mut self, send: sys::ALint, slot: &mut AuxEffectSlot<'d, 'c>, filter: &F) -> AltoResult<()> { SourceImpl::set_auxiliary_send_filter(&self.src, send, slot, filter) }
+	#[cfg(feature = "efx")]
 	fn clear_auxiliary_send(&mut self, send: sys::ALint) -> AltoResult<()> { self.src.clear_auxiliary_send(send) }
 
+	#[cfg(feature = "efx")]
 	fn air_absorption_factor(&self) -> AltoResult<f32> { self.src.air_absorption_factor() }
+	#[cfg(feature = "efx")]
 	fn set_air_absorption_factor(&mut self, value: f32) -> AltoResult<()> { self.src.set_air_absorption_factor(value) }
 
+	#[cfg(feature = "efx")]
 	fn room_rolloff_factor(&self) -> AltoResult<f32> { self.src.room_rolloff_factor() }
+	#[cfg(feature = "efx")]
 	fn set_room_rolloff_factor(&mut self, value: f32) -> AltoResult<()> { self.src.set_room_rolloff_factor(value) }
 
+	#[cfg(feature = "efx")]
 	fn cone_outer_gainhf(&self) -> AltoResult<f32> { self.src.cone_outer_gainhf() }
+	#[cfg(feature = "efx")]
 	fn set_cone_outer_gainhf(&mut self, value: f32) -> AltoResult<()> { self.src.set_cone_outer_gainhf(value) }
 
+	#[cfg(feature = "efx")]
 	fn direct_filter_gainhf_auto(&self) -> AltoResult<bool> { self.src.direct_filter_gainhf_auto() }
+	#[cfg(feature = "efx")]
 	fn set_direct_filter_gainhf_auto(&mut self, value: bool) -> AltoResult<()> { self.src.set_direct_filter_gainhf_auto(value) }
 }
 
@@ -1505,14 +3904,63 @@ impl<'d: 'c, 'c> PartialEq for StaticSource<'d, 'c> {
 impl<'d: 'c, 'c> Eq for StaticSource<'d, 'c> { }
 
 
+/// Queries the current playback state and properties of the source, for logging.
+impl<'d: 'c, 'c> fmt::Debug for StaticSource<'d, 'c> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut d = f.debug_struct("StaticSource");
+		debug_source(self, &mut d);
+		d.field("buffer", &self.buf.as_ref().map(|buf| buf.as_raw()))
+			.field("looping", &Live(self.looping()))
+			.finish()
+	}
+}
+
+
 impl<'d: 'c, 'c> StreamingSource<'d, 'c> {
 	#[doc(hidden)]
 	pub fn new(ctx: &'c Context<'d>) -> AltoResult<StreamingSource<'d, 'c>> {
 		let _lock = ctx.make_current(true)?;
 		let mut src = 0;
 		unsafe { ctx.api.head().alGenSources()(1, &mut src as *mut sys::ALuint); }
-		let sends = iter::repeat(0).take(ctx.dev.max_auxiliary_sends().unwrap_or(0) as usize).collect();
-		ctx.get_error().map(|_| StreamingSource{src: Arc::new(SourceImpl{ctx: ctx, src: src, sends: Mutex::new(sends)}), bufs: VecDeque::new()})
+		ctx.get_error().map_err(|e| e.at("alGenSources", ObjectKind::Source, None))?;
+		ctx.dev.alto().metrics().voice_created();
+		let slot = ctx.track_source(src, true);
+		Ok(StreamingSource{src: Arc::new(SourceImpl{ctx: ctx, src: src, slot: slot}), bufs: VecDeque::new()})
+	}
+
+
+	/// Take ownership of a source generated outside of alto. It will be deleted when dropped.
+	/// Fails with `AlAlreadyAdopted` if alto already holds `src`.
+	///
+	/// # Safety
+	/// `src` must belong to `ctx`, must not be deleted elsewhere, and must have no buffers queued.
+	pub unsafe fn from_raw(ctx: &'c Context<'d>, src: sys::ALuint) -> AltoResult<StreamingSource<'d, 'c>> {
+		SourceImpl::from_raw(ctx, src, true).map(|src| StreamingSource{src: Arc::new(src), bufs: VecDeque::new()})
+	}
+
+
+	/// Use a source owned by code outside of alto without taking ownership of it.
+	/// It is released rather than deleted when the returned handle is dropped, and the buffers still queued
+	/// through it are dropped then, to be deleted once the source no longer uses them.
+	/// Fails with `AlAlreadyAdopted` if alto already holds `src`.
+	///
+	/// # Safety
+	/// `src` must belong to `ctx`, must not be deleted while borrowed, and must have no buffers queued.
+	pub unsafe fn borrow_raw(ctx: &'c Context<'d>, src: sys::ALuint) -> AltoResult<Borrowed<StreamingSource<'d, 'c>>> {
+		StreamingSource::from_raw(ctx, src).map(Borrowed::new)
+	}
+
+
+	/// Release ownership of the source without deleting it.
+	/// The queued buffers are returned in queue order, and must be kept alive for as long as the source uses them.
+	/// Fails with `AlInvalidOperation` while an effect slot dropped on another thread still refers to the source,
+	/// handing the source back so that it can be tried again.
+	pub fn into_raw(self) -> Result<(sys::ALuint, Vec<Buffer<'d, 'c>>), (AltoError, StreamingSource<'d, 'c>)> {
+		let StreamingSource{src, bufs} = self;
+		match SourceImpl::into_raw(src) {
+			Ok(src) => Ok((src, bufs.into_iter().collect())),
+			Err(src) => Err((AltoError::AlInvalidOperation, StreamingSource{src: src, bufs: bufs})),
+		}
 	}
 
 
@@ -1527,15 +3975,16 @@ impl<'d: 'c, 'c> StreamingSource<'d, 'c> {
 		let _lock = self.src.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { self.src.ctx.api.head().alGetSourcei()(self.src.src, sys::AL_BUFFERS_PROCESSED, &mut value); }
-		self.src.ctx.get_error().map(|_| value)
+		self.src.ctx.get_error().map_err(|e| e.at("alGetSourcei", ObjectKind::Source, Some("AL_BUFFERS_PROCESSED".into()))).map(|_| value)
 	}
 
 
 	/// `alSourceQueueBuffers()`
+	/// Buffers are shared by every context of a device; one created on another device fails with `AlWrongDevice`.
 	pub fn queue_buffer(&mut self, buf: Buffer<'d, 'c>) -> Result<(), (AltoError, Buffer<'d, 'c>)> {
 		{
 			if buf.ctx.device().as_raw() != self.src.ctx.device().as_raw() {
-				return Err((AltoError::AlInvalidValue, buf));
+				return Err((AltoError::AlWrongDevice, buf));
 			}
 			let _lock = match self.src.ctx.make_current(true) {
 				Ok(lock) => lock,
@@ -1544,12 +3993,13 @@ impl<'d: 'c, 'c> StreamingSource<'d, 'c> {
 
 			unsafe { self.src.ctx.api.head().alSourceQueueBuffers()(self.src.src, 1, &buf.buf); }
 
-			match self.src.ctx.get_error() {
+			match self.src.ctx.get_error().map_err(|e| e.at("alSourceQueueBuffers", ObjectKind::Source, None)) {
 				Ok(_) => (),
 				Err(e) => return Err((e, buf)),
 			};
 		}
 
+		self.src.ctx.attach_buffers(self.src.slot, &[buf.buf], false);
 		self.bufs.push_back(buf);
 		Ok(())
 	}
@@ -1561,10 +4011,75 @@ impl<'d: 'c, 'c> StreamingSource<'d, 'c> {
 			let _lock = self.src.ctx.make_current(true)?;
 			let mut buf = 0;
 			unsafe { self.src.ctx.api.head().alSourceUnqueueBuffers()(self.src.src, 1, &mut buf); }
-			self.src.ctx.get_error()?;
+			self.src.ctx.get_error().map_err(|e| e.at("alSourceUnqueueBuffers", ObjectKind::Source, None))?;
+		}
+
+		let buf = self.bufs.pop_front().unwrap();
+		self.src.ctx.detach_buffers(self.src.slot, &[buf.buf]);
+		Ok(buf)
+	}
+
+
+	/// `alSourceQueueBuffers()`
+	/// Queue several buffers in order, taking the context lock once for all of them.
+	/// If this fails, none of the buffers are queued, and they are returned.
+	pub fn queue_buffers(&mut self, bufs: Vec<Buffer<'d, 'c>>) -> Result<(), (AltoError, Vec<Buffer<'d, 'c>>)> {
+		if bufs.is_empty() { return Ok(()) }
+		if bufs.iter().any(|buf| buf.ctx.device().as_raw() != self.src.ctx.device().as_raw()) {
+			return Err((AltoError::AlWrongDevice, bufs));
+		}
+
+		{
+			let _lock = match self.src.ctx.make_current(true) {
+				Ok(lock) => lock,
+				Err(e) => return Err((e, bufs)),
+			};
+
+			let ids: Vec<_> = bufs.iter().map(|buf| buf.buf).collect();
+			unsafe { self.src.ctx.api.head().alSourceQueueBuffers()(self.src.src, ids.len() as sys::ALsizei, ids.as_ptr()); }
+
+			if let Err(e) = self.src.ctx.get_error() {
+				return Err((e.at("alSourceQueueBuffers", ObjectKind::Source, Some(("n", ids.len()).into())), bufs));
+			}
+			self.src.ctx.attach_buffers(self.src.slot, &ids, false);
 		}
 
-		Ok(self.bufs.pop_front().unwrap())
+		self.bufs.extend(bufs);
+		Ok(())
+	}
+
+
+	/// `alSourceUnqueueBuffers()`
+	/// Unqueue the `n` oldest buffers, taking the context lock once for all of them.
+	pub fn unqueue_buffers(&mut self, n: usize) -> AltoResult<Vec<Buffer<'d, 'c>>> {
+		if n == 0 { return Ok(Vec::new()) }
+
+		let _lock = self.src.ctx.make_current(true)?;
+		self.unqueue_current(n)
+	}
+
+
+	/// Unqueue every buffer that has finished playing, taking the context lock once.
+	pub fn unqueue_processed(&mut self) -> AltoResult<Vec<Buffer<'d, 'c>>> {
+		let _lock = self.src.ctx.make_current(true)?;
+		let mut processed = 0;
+		unsafe { self.src.ctx.api.head().alGetSourcei()(self.src.src, sys::AL_BUFFERS_PROCESSED, &mut processed); }
+		self.src.ctx.get_error().map_err(|e| e.at("alGetSourcei", ObjectKind::Source, Some("AL_BUFFERS_PROCESSED".into())))?;
+
+		if processed == 0 { return Ok(Vec::new()) }
+		self.unqueue_current(processed as usize)
+	}
+
+
+	/// The context must be current.
+	fn unqueue_current(&mut self, n: usize) -> AltoResult<Vec<Buffer<'d, 'c>>> {
+		if n > self.bufs.len() { return Err(AltoError::AlInvalidValue) }
+
+		let mut ids = vec![0; n];
+		unsafe { self.src.ctx.api.head().alSourceUnqueueBuffers()(self.src.src, n as sys::ALsizei, ids.as_mut_ptr()); }
+		self.src.ctx.get_error().map_err(|e| e.at("alSourceUnqueueBuffers", ObjectKind::Source, Some(("n", n).into())))?;
+		self.src.ctx.detach_buffers(self.src.slot, &ids);
+		Ok(self.bufs.drain(.. n).collect())
 	}
 }
 
@@ -1608,10 +4123,15 @@ unsafe impl<'d: 'c, 'c> SourceTrait<'d, 'c> for StreamingSource<'d, 'c> {
 
 	fn velocity<V: From<[f32; 3]>>(&self) -> AltoResult<V> { self.src.velocity() }
 	fn set_velocity<V: Into<[f32; 3]>>(&mut self, value: V) -> AltoResult<()> { self.src.set_velocity(value) }
+	fn auto_velocity(&self) -> Option<f32> { self.src.auto_velocity() }
+	fn set_auto_velocity(&mut self, smoothing: Option<f32>) -> AltoResult<()> { self.src.set_auto_velocity(smoothing) }
 
 	fn direction<V: From<[f32; 3]>>(&self) -> AltoResult<V> { self.src.direction() }
 	fn set_direction<V: Into<[f32; 3]>>(&mut self, value: V) -> AltoResult<()> { self.src.set_direction(value) }
 
+	fn orientation<V: From<[f32; 3]>>(&self) -> AltoResult<(V, V)> { self.src.orientation() }
+	fn set_orientation<V: Into<[f32; 3]>>(&mut self, value: (V, V)) -> AltoResult<()> { self.src.set_orientation(value) }
+
 	fn cone_inner_angle(&self) -> AltoResult<f32> { self.src.cone_inner_angle() }
 	fn set_cone_inner_angle(&mut self, value: f32) -> AltoResult<()> { self.src.set_cone_inner_angle(value) }
 
@@ -1620,6 +4140,7 @@ unsafe impl<'d: 'c, 'c> SourceTrait<'d, 'c> for StreamingSource<'d, 'c> {
 
 	fn cone_outer_gain(&self) -> AltoResult<f32> { self.src.cone_outer_gain() }
 	fn set_cone_outer_gain(&mut self, value: f32) -> AltoResult<()> { self.src.set_cone_outer_gain(value) }
+	fn set_cone(&mut self, cone: SourceCone) -> AltoResult<()> { self.src.set_cone(cone) }
 
 	fn sec_offset(&self) -> AltoResult<f32> { self.src.sec_offset() }
 	fn set_sec_offset(&mut self, value: f32) -> AltoResult<()> { self.src.set_sec_offset(value) }
@@ -1646,23 +4167,41 @@ unsafe impl<'d: 'c, 'c> SourceTrait<'d, 'c> for StreamingSource<'d, 'c> {
 	fn distance_model(&self) -> AltoResult<DistanceModel> { self.src.distance_model() }
 	fn set_distance_model(&mut self, value: DistanceModel) -> AltoResult<()> { self.src.set_distance_model(value) }
 
+	fn state_snapshot(&self) -> AltoResult<SourceProps> { self.src.state_snapshot() }
+	fn apply(&mut self, props: &SourceProps) -> AltoResult<()> { self.src.apply(props) }
+	fn apply_changed(&mut self, old: &SourceProps, new: &SourceProps) -> AltoResult<()> { self.src.apply_changed(old, new) }
+	fn get_many(&self, props: &[SourceProp]) -> AltoResult<PropValues> { self.src.get_many(props) }
+
+	#[cfg(feature = "efx")]
 	fn set_direct_filter<F: FilterTrait<'d, 'c>>(&mut self, value: &F) -> AltoResult<()> { self.src.set_direct_filter(value) }
+	#[cfg(feature = "efx")]
 	fn clear_direct_filter(&mut self) -> AltoResult<()> { self.src.clear_direct_filter() }
 
+	#[cfg(feature = "efx")]
 	fn set_auxiliary_send(&mut self, send: sys::ALint, slot: &mut AuxEffectSlot<'d, 'c>) -> AltoResult<()> { SourceImpl::set_auxiliary_send(&self.src, send, slot) }
+	#[cfg(feature = "efx")]
 	fn set_auxiliary_send_filter<F: FilterTrait<'d, 'c>>(&mut self, send: sys::ALint, slot: &mut AuxEffectSlot<'d, 'c>, filter: &F) -> AltoResult<()> { SourceImpl::set_auxiliary_send_filter(&self.src, send, slot, filter) }
+	#[cfg(feature = "efx")]
 	fn clear_auxiliary_send(&mut self, send: sys::ALint) -> AltoResult<()> { self.src.clear_auxiliary_send(send) }
 
+	#[cfg(feature = "efx")]
 	fn air_absorption_factor(&self) -> AltoResult<f32> { self.src.air_absorption_factor() }
+	#[cfg(feature = "efx")]
 	fn set_air_absorption_factor(&mut self, value: f32) -> AltoResult<()> { self.src.set_air_absorption_factor(value) }
 
+	#[cfg(feature = "efx")]
 	fn room_rolloff_factor(&self) -> AltoResult<f32> { self.src.room_rolloff_factor() }
+	#[cfg(feature = "efx")]
 	fn set_room_rolloff_factor(&mut self, value: f32) -> AltoResult<()> { self.src.set_room_rolloff_factor(value) }
 
+	#[cfg(feature = "efx")]
 	fn cone_outer_gainhf(&self) -> AltoResult<f32> { self.src.cone_outer_gainhf() }
+	#[cfg(feature = "efx")]
 	fn set_cone_outer_gainhf(&mut self, value: f32) -> AltoResult<()> { self.src.set_cone_outer_gainhf(value) }
 
+	#[cfg(feature = "efx")]
 	fn direct_filter_gainhf_auto(&self) -> AltoResult<bool> { self.src.direct_filter_gainhf_auto() }
+	#[cfg(feature = "efx")]
 	fn set_direct_filter_gainhf_auto(&mut self, value: bool) -> AltoResult<()> { self.src.set_direct_filter_gainhf_auto(value) }
 }
 
@@ -1673,3 +4212,168 @@ impl<'d: 'c, 'c> PartialEq for StreamingSource<'d, 'c> {
 	}
 }
 impl<'d: 'c, 'c> Eq for StreamingSource<'d, 'c> { }
+
+
+/// Queries the current playback state, properties, and queue depth of the source, for logging.
+impl<'d: 'c, 'c> fmt::Debug for StreamingSource<'d, 'c> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut d = f.debug_struct("StreamingSource");
+		debug_source(self, &mut d);
+		d.field("buffers_queued", &Live(self.buffers_queued()))
+			.field("buffers_processed", &Live(self.buffers_processed()))
+			.finish()
+	}
+}
+
+
+impl<'d: 'c, 'c: 's, 's> SourceBuilder<'d, 'c, 's> {
+	fn new(ctx: &'c Context<'d>) -> SourceBuilder<'d, 'c, 's> {
+		SourceBuilder{
+			ctx: ctx,
+			buf: None,
+			looping: None,
+			relative: None,
+			gain: None,
+			pitch: None,
+			position: None,
+			velocity: None,
+			direction: None,
+			cone: None,
+			#[cfg(feature = "efx")]
+			sends: Vec::new(),
+			#[cfg(not(feature = "efx"))]
+			sends: ::std::marker::PhantomData,
+		}
+	}
+
+
+	/// `alSourcei(AL_BUFFER)`
+	/// Static sources only.
+	pub fn buffer(mut self, buf: Arc<Buffer<'d, 'c>>) -> SourceBuilder<'d, 'c, 's> {
+		self.buf = Some(buf);
+		self
+	}
+
+
+	/// `alSourcei(AL_LOOPING)`
+	/// Static sources only.
+	pub fn looping(mut self, looping: bool) -> SourceBuilder<'d, 'c, 's> {
+		self.looping = Some(looping);
+		self
+	}
+
+
+	/// `alSourcei(AL_SOURCE_RELATIVE)`
+	pub fn relative(mut self, relative: bool) -> SourceBuilder<'d, 'c, 's> {
+		self.relative = Some(relative);
+		self
+	}
+
+
+	/// `alSourcef(AL_GAIN)`
+	pub fn gain(mut self, gain: f32) -> SourceBuilder<'d, 'c, 's> {
+		self.gain = Some(gain);
+		self
+	}
+
+
+	/// `alSourcef(AL_PITCH)`
+	pub fn pitch(mut self, pitch: f32) -> SourceBuilder<'d, 'c, 's> {
+		self.pitch = Some(pitch);
+		self
+	}
+
+
+	/// `alSourcefv(AL_POSITION)`
+	pub fn position<V: Into<[f32; 3]>>(mut self, position: V) -> SourceBuilder<'d, 'c, 's> {
+		self.position = Some(position.into());
+		self
+	}
+
+
+	/// `alSourcefv(AL_VELOCITY)`
+	pub fn velocity<V: Into<[f32; 3]>>(mut self, velocity: V) -> SourceBuilder<'d, 'c, 's> {
+		self.velocity = Some(velocity.into());
+		self
+	}
+
+
+	/// `alSourcefv(AL_DIRECTION)`
+	pub fn direction<V: Into<[f32; 3]>>(mut self, direction: V) -> SourceBuilder<'d, 'c, 's> {
+		self.direction = Some(direction.into());
+		self
+	}
+
+
+	/// `AL_CONE_INNER_ANGLE`, `AL_CONE_OUTER_ANGLE`, and `AL_CONE_OUTER_GAIN`
+	pub fn cone(mut self, inner_angle: f32, outer_angle: f32, outer_gain: f32) -> SourceBuilder<'d, 'c, 's> {
+		self.cone = Some((inner_angle, outer_angle, outer_gain));
+		self
+	}
+
+
+	#[cfg(feature = "efx")]
+	/// `alSourceiv(AL_AUXILIARY_SEND_FILTER)`
+	/// Requires `ALC_EXT_EFX`
+	pub fn send(mut self, send: sys::ALint, slot: &'s mut AuxEffectSlot<'d, 'c>) -> SourceBuilder<'d, 'c, 's> {
+		self.sends.push((send, slot));
+		self
+	}
+
+
+	/// Create a static source with the configured properties.
+	pub fn build_static(self) -> AltoResult<StaticSource<'d, 'c>> {
+		let mut src = self.ctx.new_static_source()?;
+		if let Some(ref buf) = self.buf {
+			src.set_buffer(buf.clone())?;
+		}
+		if let Some(looping) = self.looping {
+			src.set_looping(looping)?;
+		}
+		self.apply(&mut src)?;
+		Ok(src)
+	}
+
+
+	/// Create a streaming source with the configured properties.
+	/// Fails with `AlInvalidOperation` if a buffer or looping was set, since those only apply to static sources.
+	pub fn build_streaming(self) -> AltoResult<StreamingSource<'d, 'c>> {
+		if self.buf.is_some() || self.looping.is_some() { return Err(AltoError::AlInvalidOperation) }
+
+		let mut src = self.ctx.new_streaming_source()?;
+		self.apply(&mut src)?;
+		Ok(src)
+	}
+
+
+	fn apply<S: SourceTrait<'d, 'c>>(self, src: &mut S) -> AltoResult<()> {
+		let _lock = self.ctx.make_current(true)?;
+
+		if let Some(relative) = self.relative {
+			src.set_relative(relative)?;
+		}
+		if let Some(gain) = self.gain {
+			src.set_gain(gain)?;
+		}
+		if let Some(pitch) = self.pitch {
+			src.set_pitch(pitch)?;
+		}
+		if let Some(position) = self.position {
+			src.set_position(position)?;
+		}
+		if let Some(velocity) = self.velocity {
+			src.set_velocity(velocity)?;
+		}
+		if let Some(direction) = self.direction {
+			src.set_direction(direction)?;
+		}
+		if let Some((inner, outer, outer_gain)) = self.cone {
+			src.set_cone(SourceCone{inner_angle: inner, outer_angle: outer, outer_gain: outer_gain, outer_gain_hf: None})?;
+		}
+		#[cfg(feature = "efx")]
+		for (send, slot) in self.sends {
+			src.set_auxiliary_send(send, slot)?;
+		}
+		Ok(())
+	}
+}
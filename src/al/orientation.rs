@@ -0,0 +1,91 @@
+use ::{AltoError, AltoResult};
+
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+	a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+	[a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+
+fn normalize(v: [f32; 3]) -> AltoResult<[f32; 3]> {
+	let len = dot(v, v).sqrt();
+	if !(len > 1e-6) || !len.is_finite() { return Err(AltoError::AlInvalidValue) }
+	Ok([v[0] / len, v[1] / len, v[2] / len])
+}
+
+
+/// Rotate `v` by the unit quaternion `(x, y, z, w)`.
+fn rotate(q: [f32; 4], v: [f32; 3]) -> [f32; 3] {
+	let u = [q[0], q[1], q[2]];
+	let t = cross(u, v);
+	let t = [2.0 * t[0], 2.0 * t[1], 2.0 * t[2]];
+	let ut = cross(u, t);
+	[v[0] + q[3] * t[0] + ut[0], v[1] + q[3] * t[1] + ut[1], v[2] + q[3] * t[2] + ut[2]]
+}
+
+
+/// Convert a rotation quaternion in `(x, y, z, w)` order into an OpenAL `(forward, up)` orientation.
+/// The identity rotation faces down -Z with +Y up, which is the default orientation in OpenAL's right-handed
+/// coordinate system. The quaternion doesn't need to be normalized, but fails with `AlInvalidValue` if it is zero.
+pub fn orientation_from_quat<Q: Into<[f32; 4]>>(q: Q) -> AltoResult<([f32; 3], [f32; 3])> {
+	let q = q.into();
+	let len = (q[0] * q[0] + q[1] * q[1] + q[2] * q[2] + q[3] * q[3]).sqrt();
+	if !(len > 1e-6) || !len.is_finite() { return Err(AltoError::AlInvalidValue) }
+	let q = [q[0] / len, q[1] / len, q[2] / len, q[3] / len];
+
+	Ok((rotate(q, [0.0, 0.0, -1.0]), rotate(q, [0.0, 1.0, 0.0])))
+}
+
+
+/// Compute the `(forward, up)` orientation of an object at `eye` facing `target`.
+/// `up` only needs to point roughly upward; it is made perpendicular to the forward vector.
+/// Fails with `AlInvalidValue` if `target` is at `eye` or `up` is parallel to the line of sight.
+pub fn orientation_look_at<V: Into<[f32; 3]>>(eye: V, target: V, up: V) -> AltoResult<([f32; 3], [f32; 3])> {
+	let (eye, target, up) = (eye.into(), target.into(), up.into());
+	let fwd = normalize([target[0] - eye[0], target[1] - eye[1], target[2] - eye[2]])?;
+	let d = dot(up, fwd);
+	let up = normalize([up[0] - d * fwd[0], up[1] - d * fwd[1], up[2] - d * fwd[2]])?;
+
+	Ok((fwd, up))
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+
+	fn close(a: ([f32; 3], [f32; 3]), b: ([f32; 3], [f32; 3])) -> bool {
+		a.0.iter().chain(a.1.iter()).zip(b.0.iter().chain(b.1.iter())).all(|(x, y)| (x - y).abs() < 1e-5)
+	}
+
+
+	#[test]
+	fn from_quat() {
+		assert!(close(orientation_from_quat([0.0, 0.0, 0.0, 1.0]).unwrap(), ([0.0, 0.0, -1.0], [0.0, 1.0, 0.0])));
+		// A quarter turn to the left about +Y.
+		let s = 0.5f32.sqrt();
+		assert!(close(orientation_from_quat([0.0, s, 0.0, s]).unwrap(), ([-1.0, 0.0, 0.0], [0.0, 1.0, 0.0])));
+		assert!(close(orientation_from_quat([0.0, 3.0 * s, 0.0, 3.0 * s]).unwrap(), ([-1.0, 0.0, 0.0], [0.0, 1.0, 0.0])));
+		// A quarter turn about +X, facing straight up with up pointing back along +Z.
+		assert!(close(orientation_from_quat([s, 0.0, 0.0, s]).unwrap(), ([0.0, 1.0, 0.0], [0.0, 0.0, 1.0])));
+
+		assert!(orientation_from_quat([0.0; 4]).unwrap_err().is_invalid_value());
+		assert!(orientation_from_quat([::std::f32::NAN, 0.0, 0.0, 1.0]).is_err());
+	}
+
+
+	#[test]
+	fn look_at() {
+		assert!(close(orientation_look_at([1.0, 2.0, 3.0], [1.0, 2.0, -2.0], [0.0, 1.0, 0.0]).unwrap(), ([0.0, 0.0, -1.0], [0.0, 1.0, 0.0])));
+		// `up` is made perpendicular to the line of sight.
+		assert!(close(orientation_look_at([0.0; 3], [4.0, 0.0, 0.0], [1.0, 1.0, 0.0]).unwrap(), ([1.0, 0.0, 0.0], [0.0, 1.0, 0.0])));
+
+		assert!(orientation_look_at([1.0; 3], [1.0; 3], [0.0, 1.0, 0.0]).unwrap_err().is_invalid_value());
+		assert!(orientation_look_at([0.0; 3], [0.0, 2.0, 0.0], [0.0, 1.0, 0.0]).unwrap_err().is_invalid_value());
+	}
+}
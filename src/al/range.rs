@@ -0,0 +1,156 @@
+use std::f32;
+use std::fmt;
+use std::sync::Arc;
+
+use ::{AltoError, AltoResult};
+use super::*;
+
+
+/// Called with each value clamped under `RangePolicy::Clamp`.
+pub type RangeHook = Arc<dyn Fn(&RangeViolation) + Send + Sync>;
+
+
+/// How a context handles property values outside the limits set by the OpenAL specification.
+#[derive(Clone)]
+pub enum RangePolicy {
+	/// Pass values through unchanged, leaving their handling to the implementation.
+	PassThrough,
+	/// Clamp values to the limits, calling the hook, if any, with each value that was out of range.
+	/// NaN can't be clamped, and fails with `AlInvalidValue`.
+	Clamp(Option<RangeHook>),
+	/// Fail with `AlInvalidRange` describing the limit that was exceeded.
+	Reject,
+}
+
+
+/// A property value outside the limits set by the specification.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct RangeViolation {
+	/// Name of the property, such as `AL_GAIN`.
+	pub param: &'static str,
+	pub value: f32,
+	/// The value actually set.
+	pub clamped: f32,
+	pub min: f32,
+	pub max: f32,
+}
+
+
+impl Default for RangePolicy {
+	fn default() -> RangePolicy { RangePolicy::PassThrough }
+}
+
+
+impl fmt::Debug for RangePolicy {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			RangePolicy::PassThrough => f.write_str("PassThrough"),
+			RangePolicy::Clamp(ref hook) => write!(f, "Clamp({})", if hook.is_some() { "Some(..)" } else { "None" }),
+			RangePolicy::Reject => f.write_str("Reject"),
+		}
+	}
+}
+
+
+/// Source and listener gain, distance attenuation, and doppler limits.
+pub const NON_NEGATIVE: (f32, f32) = (0.0, f32::INFINITY);
+/// Gain clamps and cone gains.
+pub const UNIT: (f32, f32) = (0.0, 1.0);
+/// Cone angles, in degrees.
+pub const ANGLE: (f32, f32) = (0.0, 360.0);
+/// Pitch and the speed of sound, which must be greater than zero.
+pub const POSITIVE: (f32, f32) = (f32::MIN_POSITIVE, f32::INFINITY);
+#[cfg(feature = "efx")]
+/// Air absorption and room rolloff factors.
+pub const EFX_FACTOR: (f32, f32) = (0.0, 10.0);
+/// `AL_METERS_PER_UNIT`
+pub const METERS_PER_UNIT: (f32, f32) = (f32::MIN_POSITIVE, f32::MAX);
+
+
+impl RangePolicy {
+	/// Apply the policy to a value for `param`, returning the value to set.
+	fn check(&self, param: &'static str, reason: &'static str, value: f32, (min, max): (f32, f32)) -> AltoResult<f32> {
+		if value >= min && value <= max { return Ok(value) }
+
+		match *self {
+			RangePolicy::PassThrough => Ok(value),
+			RangePolicy::Reject => Err(AltoError::AlInvalidRange(reason)),
+			RangePolicy::Clamp(_) if value.is_nan() => Err(AltoError::AlInvalidValue),
+			RangePolicy::Clamp(ref hook) => {
+				let clamped = value.max(min).min(max);
+				alto_event!(debug, param = param, value = value, clamped = clamped, "clamped out-of-range value");
+				if let Some(ref hook) = *hook {
+					hook(&RangeViolation{param: param, value: value, clamped: clamped, min: min, max: max});
+				}
+				Ok(clamped)
+			},
+		}
+	}
+}
+
+
+impl<'d> Context<'d> {
+	/// How out-of-range property values are handled. Defaults to `RangePolicy::PassThrough`.
+	pub fn range_policy(&self) -> RangePolicy {
+		self.range_policy.lock().unwrap_or_else(|e| e.into_inner()).clone()
+	}
+	/// Change how out-of-range property values are handled for sources, the listener, and global properties.
+	pub fn set_range_policy(&self, policy: RangePolicy) {
+		*self.range_policy.lock().unwrap_or_else(|e| e.into_inner()) = policy;
+	}
+
+
+	/// Apply the range policy to a value for `param`, returning the value to set.
+	#[doc(hidden)]
+	pub fn check_range(&self, param: &'static str, reason: &'static str, value: f32, (min, max): (f32, f32)) -> AltoResult<f32> {
+		// Checked before the policy is locked, since nearly every value is in range.
+		if value >= min && value <= max { return Ok(value) }
+		self.range_policy().check(param, reason, value, (min, max))
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Mutex;
+
+	use super::*;
+
+
+	#[test]
+	fn in_range_passes() {
+		for policy in &[RangePolicy::PassThrough, RangePolicy::Clamp(None), RangePolicy::Reject] {
+			assert_eq!(policy.check("AL_GAIN", "gain", 0.0, UNIT).unwrap(), 0.0);
+			assert_eq!(policy.check("AL_GAIN", "gain", 1.0, UNIT).unwrap(), 1.0);
+			assert_eq!(policy.check("AL_PITCH", "pitch", f32::INFINITY, POSITIVE).unwrap(), f32::INFINITY);
+		}
+	}
+
+
+	#[test]
+	fn out_of_range() {
+		assert_eq!(RangePolicy::PassThrough.check("AL_GAIN", "gain", -1.0, NON_NEGATIVE).unwrap(), -1.0);
+		assert!(RangePolicy::PassThrough.check("AL_GAIN", "gain", f32::NAN, NON_NEGATIVE).unwrap().is_nan());
+
+		match RangePolicy::Reject.check("AL_GAIN", "gain must not be negative", -1.0, NON_NEGATIVE) {
+			Err(AltoError::AlInvalidRange(reason)) => assert_eq!(reason, "gain must not be negative"),
+			r => panic!("unexpected {:?}", r),
+		}
+		assert!(RangePolicy::Reject.check("AL_GAIN", "gain", f32::NAN, NON_NEGATIVE).is_err());
+
+		assert_eq!(RangePolicy::Clamp(None).check("AL_CONE_INNER_ANGLE", "angle", 400.0, ANGLE).unwrap(), 360.0);
+		assert_eq!(RangePolicy::Clamp(None).check("AL_PITCH", "pitch", 0.0, POSITIVE).unwrap(), f32::MIN_POSITIVE);
+		assert!(RangePolicy::Clamp(None).check("AL_GAIN", "gain", f32::NAN, UNIT).unwrap_err().is_invalid_value());
+	}
+
+
+	#[test]
+	fn clamp_hook() {
+		let seen = Arc::new(Mutex::new(Vec::new()));
+		let hook_seen = seen.clone();
+		let policy = RangePolicy::Clamp(Some(Arc::new(move |v: &RangeViolation| hook_seen.lock().unwrap().push(*v))));
+		policy.check("AL_GAIN", "gain", 0.5, UNIT).unwrap();
+		policy.check("AL_GAIN", "gain", 1.5, UNIT).unwrap();
+		assert_eq!(*seen.lock().unwrap(), vec![RangeViolation{param: "AL_GAIN", value: 1.5, clamped: 1.0, min: 0.0, max: 1.0}]);
+	}
+}
@@ -0,0 +1,434 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::io::{self, Write};
+use std::mem;
+
+use ::{AltoError, AltoResult, ObjectKind};
+use sys;
+use super::*;
+
+
+/// A copyable handle to a buffer owned by the registry of a [`Context`](struct.Context.html).
+/// Handles carry no lifetime, so they can be stored anywhere, such as in the components of an ECS.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct BufferId {
+	ctx: usize,
+	buf: sys::ALuint,
+}
+
+
+/// A copyable handle to a source owned by the registry of a [`Context`](struct.Context.html).
+/// Handles carry no lifetime, so they can be stored anywhere, such as in the components of an ECS.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct SourceId {
+	ctx: usize,
+	src: sys::ALuint,
+}
+
+
+/// Objects owned by a context on behalf of `BufferId` and `SourceId` handles.
+/// Everything still registered is deleted when the context is dropped.
+#[doc(hidden)]
+#[derive(Default)]
+pub struct Registry {
+	buffers: HashSet<sys::ALuint>,
+	sources: HashMap<sys::ALuint, RegisteredSource>,
+}
+
+
+#[derive(Clone)]
+struct RegisteredSource {
+	streaming: bool,
+	buffer: Option<sys::ALuint>,
+	queue: Vec<sys::ALuint>,
+	/// Deleted by `Context::maintain()` once stopped.
+	oneshot: bool,
+}
+
+
+impl BufferId {
+	/// Raw handle as provided by OpenAL.
+	#[inline]
+	pub fn as_raw(&self) -> sys::ALuint { self.buf }
+}
+
+
+impl SourceId {
+	/// Raw handle as provided by OpenAL.
+	#[inline]
+	pub fn as_raw(&self) -> sys::ALuint { self.src }
+}
+
+
+/// Give up a temporary source wrapper without deleting the source. Its bookkeeping stays in the context.
+fn release_source(src: Arc<SourceImpl>) {
+	match Arc::try_unwrap(src) {
+		Ok(src) => mem::forget(src),
+		Err(src) => mem::forget(src),
+	}
+}
+
+
+impl<'d> Context<'d> {
+	fn buffer_key(&self, buf: sys::ALuint) -> BufferId { BufferId{ctx: self.ctx as usize, buf: buf} }
+	fn source_key(&self, src: sys::ALuint) -> SourceId { SourceId{ctx: self.ctx as usize, src: src} }
+
+
+	fn check_buffer(&self, id: BufferId) -> AltoResult<()> {
+		if id.ctx != self.ctx as usize { return Err(AltoError::AlWrongContext) }
+		if !self.registry.lock().unwrap_or_else(|e| e.into_inner()).buffers.contains(&id.buf) {
+			return Err(AltoError::AlInvalidName);
+		}
+		Ok(())
+	}
+
+
+	fn registered_source(&self, id: SourceId, streaming: bool) -> AltoResult<RegisteredSource> {
+		if id.ctx != self.ctx as usize { return Err(AltoError::AlWrongContext) }
+
+		match self.registry.lock().unwrap_or_else(|e| e.into_inner()).sources.get(&id.src) {
+			Some(entry) if entry.streaming == streaming => Ok(entry.clone()),
+			Some(_) => Err(AltoError::AlInvalidOperation),
+			None => Err(AltoError::AlInvalidName),
+		}
+	}
+
+
+	/// Move a buffer into the registry of this context, returning a copyable handle to it.
+	pub fn register_buffer<'c>(&'c self, buf: Buffer<'d, 'c>) -> Result<BufferId, (AltoError, Buffer<'d, 'c>)> {
+		if buf.ctx != self {
+			return Err((AltoError::AlWrongContext, buf));
+		}
+
+		let buf = buf.leak();
+		self.registry.lock().unwrap_or_else(|e| e.into_inner()).buffers.insert(buf);
+		Ok(self.buffer_key(buf))
+	}
+
+
+	/// Access a registered buffer for the duration of a closure.
+	pub fn with_buffer<R, F>(&self, id: BufferId, f: F) -> AltoResult<R> where
+		F: for<'c> FnOnce(&mut Buffer<'d, 'c>) -> R,
+	{
+		self.check_buffer(id)?;
+		let mut buf = mem::ManuallyDrop::new(Buffer{ctx: self, buf: id.buf});
+		Ok(f(&mut buf))
+	}
+
+
+	/// Delete a registered buffer. Fails with `AlInvalidOperation` if it is still attached to a source.
+	pub fn delete_buffer(&self, id: BufferId) -> AltoResult<()> {
+		self.check_buffer(id)?;
+		{
+			let mut reg = self.registry.lock().unwrap_or_else(|e| e.into_inner());
+			if reg.sources.values().any(|s| s.buffer == Some(id.buf)) || self.sources.lock().unwrap_or_else(|e| e.into_inner()).users.contains_key(&id.buf) {
+				return Err(AltoError::AlInvalidOperation);
+			}
+			if !reg.buffers.remove(&id.buf) {
+				return Err(AltoError::AlInvalidName);
+			}
+		}
+
+		// The registry is only locked after the context, never while waiting for it, so the entry is
+		// taken out first and put back if the buffer can't be deleted.
+		let deleted = self.make_current(true).and_then(|_lock| {
+			unsafe { self.api.head().alDeleteBuffers()(1, &id.buf); }
+			self.get_error().map_err(|e| e.at("alDeleteBuffers", ObjectKind::Buffer, None))
+		});
+		if let Err(e) = deleted {
+			self.registry.lock().unwrap_or_else(|e| e.into_inner()).buffers.insert(id.buf);
+			return Err(e);
+		}
+		self.buffers_deleted(&[id.buf]);
+		Ok(())
+	}
+
+
+	/// Recreate the registered buffers of this context in `ctx`, such as on a device reopened with
+	/// `Device::reopen()` after this one was disconnected. OpenAL can't read sample data back, so `reload`
+	/// is called with the old handle of each buffer and a new buffer to upload the same data into.
+	/// Each old handle is returned with the handle of its replacement, now registered with `ctx`, or with
+	/// the error that kept it from being restored. Registered sources are not recreated.
+	pub fn restore_buffers<'e, F>(&self, ctx: &Context<'e>, mut reload: F) -> Vec<(BufferId, AltoResult<BufferId>)> where
+		F: for<'c> FnMut(BufferId, &mut Buffer<'e, 'c>) -> AltoResult<()>,
+	{
+		let bufs: Vec<_> = self.registry.lock().unwrap_or_else(|e| e.into_inner()).buffers.iter().cloned().collect();
+		bufs.into_iter().map(|buf| {
+			let id = self.buffer_key(buf);
+			let restored = ctx.new_buffer().and_then(|mut new| {
+				reload(id, &mut new)?;
+				ctx.register_buffer(new).map_err(|(e, _)| e)
+			});
+			(id, restored)
+		}).collect()
+	}
+
+
+	/// Move a static source into the registry of this context, returning a copyable handle to it.
+	/// An attached buffer is moved into the registry as well, and must not be shared with any other source.
+	/// Fails with `AlInvalidOperation` while an effect slot dropped on another thread still refers to the source.
+	pub fn register_static_source<'c>(&'c self, src: StaticSource<'d, 'c>) -> Result<SourceId, (AltoError, StaticSource<'d, 'c>)> {
+		if src.context() != self {
+			return Err((AltoError::AlWrongContext, src));
+		}
+		if src.buf.as_ref().map(|b| Arc::strong_count(b) > 1).unwrap_or(false) {
+			return Err((AltoError::AlInvalidOperation, src));
+		}
+
+		let StaticSource{src, buf} = src;
+		let src = match Arc::try_unwrap(src) {
+			Ok(src) => mem::ManuallyDrop::new(src),
+			Err(src) => return Err((AltoError::AlInvalidOperation, StaticSource{src: src, buf: buf})),
+		};
+		let name = src.src;
+		if let Some(live) = self.untrack_source(src.slot) {
+			self.sources.lock().unwrap_or_else(|e| e.into_inner()).insert(LiveSource{registered: true, range: None, ..live});
+		}
+
+		let buf = buf.map(|b| Arc::try_unwrap(b).ok().unwrap().leak());
+		let mut reg = self.registry.lock().unwrap_or_else(|e| e.into_inner());
+		if let Some(buf) = buf {
+			reg.buffers.insert(buf);
+		}
+		reg.sources.insert(name, RegisteredSource{streaming: false, buffer: buf, queue: Vec::new(), oneshot: false});
+		Ok(self.source_key(name))
+	}
+
+
+	/// Move a streaming source into the registry of this context, returning a copyable handle to it.
+	/// Its queued buffers are owned by the registry until they are unqueued.
+	/// Fails with `AlInvalidOperation` while an effect slot dropped on another thread still refers to the source.
+	pub fn register_streaming_source<'c>(&'c self, src: StreamingSource<'d, 'c>) -> Result<SourceId, (AltoError, StreamingSource<'d, 'c>)> {
+		if src.context() != self {
+			return Err((AltoError::AlWrongContext, src));
+		}
+
+		let StreamingSource{src, bufs} = src;
+		let src = match Arc::try_unwrap(src) {
+			Ok(src) => mem::ManuallyDrop::new(src),
+			Err(src) => return Err((AltoError::AlInvalidOperation, StreamingSource{src: src, bufs: bufs})),
+		};
+		let name = src.src;
+		if let Some(live) = self.untrack_source(src.slot) {
+			self.sources.lock().unwrap_or_else(|e| e.into_inner()).insert(LiveSource{registered: true, range: None, ..live});
+		}
+
+		let queue = bufs.into_iter().map(|b| b.leak()).collect();
+		self.registry.lock().unwrap_or_else(|e| e.into_inner()).sources.insert(name, RegisteredSource{streaming: true, buffer: None, queue: queue, oneshot: false});
+		Ok(self.source_key(name))
+	}
+
+
+	/// Access a registered static source for the duration of a closure.
+	/// The source does not report its registered buffer through `buffer()`; use
+	/// [`source_buffer()`](#method.source_buffer) instead. A buffer attached with `set_buffer()` inside
+	/// the closure is moved into the registry, and must not be shared outside of it. If it is, the
+	/// previous buffer is restored and `AlInvalidOperation` is returned.
+	pub fn with_static_source<R, F>(&self, id: SourceId, f: F) -> AltoResult<R> where
+		F: for<'c> FnOnce(&mut StaticSource<'d, 'c>) -> R,
+	{
+		let mut entry = self.registered_source(id, false)?;
+		let slot = self.source_slot(id.src).ok_or(AltoError::AlInvalidName)?;
+		let mut src = mem::ManuallyDrop::new(StaticSource{
+			src: Arc::new(SourceImpl{ctx: self, src: id.src, slot: slot}),
+			buf: None,
+		});
+		let ret = f(&mut src);
+
+		let StaticSource{src, buf} = mem::ManuallyDrop::into_inner(src);
+		release_source(src);
+
+		let mut result = Ok(ret);
+		match buf.map(Arc::try_unwrap) {
+			Some(Ok(buf)) => {
+				let buf = buf.leak();
+				self.registry.lock().unwrap_or_else(|e| e.into_inner()).buffers.insert(buf);
+				entry.buffer = Some(buf);
+			},
+			Some(Err(_)) => {
+				let _lock = self.make_current(true)?;
+				unsafe { self.api.head().alSourcei()(id.src, sys::AL_BUFFER, entry.buffer.unwrap_or(0) as sys::ALint); }
+				self.get_error().map_err(|e| e.at("alSourcei", ObjectKind::Source, Some("AL_BUFFER".into())))?;
+				self.attach_buffers(slot, &entry.buffer.into_iter().collect::<Vec<_>>(), true);
+				result = Err(AltoError::AlInvalidOperation);
+			},
+			None => {
+				// The buffer may have been cleared inside the closure.
+				let _lock = self.make_current(true)?;
+				let mut value = 0;
+				unsafe { self.api.head().alGetSourcei()(id.src, sys::AL_BUFFER, &mut value); }
+				self.get_error().map_err(|e| e.at("alGetSourcei", ObjectKind::Source, Some("AL_BUFFER".into())))?;
+				if value == 0 {
+					entry.buffer = None;
+				}
+			},
+		}
+
+		self.registry.lock().unwrap_or_else(|e| e.into_inner()).sources.insert(id.src, entry);
+		result
+	}
+
+
+	/// Access a registered streaming source for the duration of a closure.
+	/// Buffers unqueued inside the closure are handed over to it, and buffers queued inside it are
+	/// owned by the registry afterwards.
+	pub fn with_streaming_source<R, F>(&self, id: SourceId, f: F) -> AltoResult<R> where
+		F: for<'c> FnOnce(&mut StreamingSource<'d, 'c>) -> R,
+	{
+		let mut entry = self.registered_source(id, true)?;
+		let slot = self.source_slot(id.src).ok_or(AltoError::AlInvalidName)?;
+		let mut src = mem::ManuallyDrop::new(StreamingSource{
+			src: Arc::new(SourceImpl{ctx: self, src: id.src, slot: slot}),
+			bufs: entry.queue.iter().map(|&buf| Buffer{ctx: self, buf: buf}).collect(),
+		});
+		let ret = f(&mut src);
+
+		let StreamingSource{src, bufs} = mem::ManuallyDrop::into_inner(src);
+		release_source(src);
+		entry.queue = bufs.into_iter().map(|b| b.leak()).collect();
+
+		self.registry.lock().unwrap_or_else(|e| e.into_inner()).sources.insert(id.src, entry);
+		Ok(ret)
+	}
+
+
+	/// `alSourcei(AL_BUFFER)`
+	/// Attach a registered buffer to a registered static source, or detach it with `None`.
+	pub fn set_source_buffer(&self, src: SourceId, buf: Option<BufferId>) -> AltoResult<()> {
+		let mut entry = self.registered_source(src, false)?;
+		if let Some(buf) = buf {
+			self.check_buffer(buf)?;
+		}
+		let slot = self.source_slot(src.src).ok_or(AltoError::AlInvalidName)?;
+
+		{
+			let _lock = self.make_current(true)?;
+			unsafe { self.api.head().alSourcei()(src.src, sys::AL_BUFFER, buf.map(|b| b.buf).unwrap_or(0) as sys::ALint); }
+			self.get_error().map_err(|e| e.at("alSourcei", ObjectKind::Source, Some("AL_BUFFER".into())))?;
+		}
+		entry.buffer = buf.map(|b| b.buf);
+		self.attach_buffers(slot, &entry.buffer.into_iter().collect::<Vec<_>>(), true);
+		self.registry.lock().unwrap_or_else(|e| e.into_inner()).sources.insert(src.src, entry);
+		Ok(())
+	}
+
+
+	/// The registered buffer attached to a registered static source, if any.
+	pub fn source_buffer(&self, src: SourceId) -> AltoResult<Option<BufferId>> {
+		self.registered_source(src, false).map(|entry| entry.buffer.map(|b| self.buffer_key(b)))
+	}
+
+
+	/// Play a registered buffer once on a new registered source, which `maintain()` deletes after it stops.
+	/// The returned handle can be used to adjust the source while it plays, until it is deleted.
+	pub fn play_oneshot(&self, buf: BufferId) -> AltoResult<SourceId> {
+		self.check_buffer(buf)?;
+		let id = self.register_static_source(self.new_static_source()?).map_err(|(e, _)| e)?;
+		self.set_source_buffer(id, Some(buf)).and_then(|_| self.with_static_source(id, |src| src.play())?).map_err(|e| {
+			let _ = self.delete_source(id);
+			e
+		})?;
+
+		self.detach_source(id)?;
+		Ok(id)
+	}
+
+
+	/// Hand a registered source over to the context, which deletes it with `maintain()` once it has stopped.
+	/// A source that is still in its initial state is kept until it has been played and stopped.
+	pub fn detach_source(&self, id: SourceId) -> AltoResult<()> {
+		if id.ctx != self.ctx as usize { return Err(AltoError::AlWrongContext) }
+		match self.registry.lock().unwrap_or_else(|e| e.into_inner()).sources.get_mut(&id.src) {
+			Some(entry) => {
+				entry.oneshot = true;
+				Ok(())
+			},
+			None => Err(AltoError::AlInvalidName),
+		}
+	}
+
+
+	/// Delete detached and one-shot sources that have stopped, returning how many were deleted.
+	/// Sources played with `StaticSource::play_range()` are also stopped once they pass the end of their range,
+	/// and buffers dropped since the last call are deleted.
+	/// Call this periodically, such as once per frame, so that finished sources don't accumulate.
+	pub fn maintain(&self) -> AltoResult<usize> {
+		if self.dev.is_disconnected() { return Err(AltoError::AlcDeviceDisconnected) }
+		self.delete_dropped_buffers()?;
+		self.enforce_ranges()?;
+
+		let oneshots: Vec<_> = self.registry.lock().unwrap_or_else(|e| e.into_inner()).sources.iter()
+			.filter(|&(_, entry)| entry.oneshot)
+			.map(|(&src, _)| src)
+			.collect();
+		if oneshots.is_empty() { return Ok(0) }
+
+		let mut stopped = Vec::new();
+		{
+			let _lock = self.make_current(true)?;
+			for src in oneshots {
+				let mut state = 0;
+				unsafe { self.api.head().alGetSourcei()(src, sys::AL_SOURCE_STATE, &mut state); }
+				self.get_error().map_err(|e| e.at("alGetSourcei", ObjectKind::Source, Some("AL_SOURCE_STATE".into())))?;
+				if state == sys::AL_STOPPED { stopped.push(src) }
+			}
+		}
+
+		for &src in &stopped {
+			self.delete_source(self.source_key(src))?;
+		}
+		Ok(stopped.len())
+	}
+
+
+	/// Delete a registered source along with any buffers still queued on it.
+	/// A static source's buffer stays registered.
+	pub fn delete_source(&self, id: SourceId) -> AltoResult<()> {
+		if id.ctx != self.ctx as usize { return Err(AltoError::AlWrongContext) }
+		let entry = self.registry.lock().unwrap_or_else(|e| e.into_inner()).sources.remove(&id.src).ok_or(AltoError::AlInvalidName)?;
+
+		self.dev.alto().metrics().voice_deleted();
+		self.forget_named_source(id.src);
+		self.deferred.discard(DeferredTarget::Source(id.src));
+		let _lock = self.make_current(true)?;
+		unsafe { self.api.head().alDeleteSources()(1, &id.src); }
+		let deleted = self.get_error().map_err(|e| e.at("alDeleteSources", ObjectKind::Source, None));
+		if !entry.queue.is_empty() {
+			unsafe { self.api.head().alDeleteBuffers()(entry.queue.len() as sys::ALsizei, entry.queue.as_ptr()); }
+			self.get_error().map_err(|e| e.at("alDeleteBuffers", ObjectKind::Buffer, Some(("n", entry.queue.len()).into())))?;
+		}
+		deleted?;
+		self.buffers_deleted(&entry.queue);
+		Ok(())
+	}
+
+
+	/// Stop and delete everything left in the registry. Called when the context is dropped.
+	#[doc(hidden)]
+	pub fn clear_registry(&self) {
+		let reg = mem::replace(&mut *self.registry.lock().unwrap_or_else(|e| e.into_inner()), Registry::default());
+		if reg.sources.is_empty() && reg.buffers.is_empty() {
+			return;
+		}
+
+		if let Ok(_lock) = self.make_current_unchecked(true) {
+			let srcs: Vec<_> = reg.sources.keys().cloned().collect();
+			let bufs: Vec<_> = reg.sources.values().flat_map(|s| s.queue.iter().cloned()).chain(reg.buffers.iter().cloned()).collect();
+			for &src in &srcs {
+				self.dev.alto().metrics().voice_deleted();
+				self.forget_named_source(src);
+				self.deferred.discard(DeferredTarget::Source(src));
+			}
+			unsafe {
+				self.api.head().alSourceStopv()(srcs.len() as sys::ALsizei, srcs.as_ptr());
+				self.api.head().alDeleteSources()(srcs.len() as sys::ALsizei, srcs.as_ptr());
+				self.api.head().alDeleteBuffers()(bufs.len() as sys::ALsizei, bufs.as_ptr());
+			}
+			if let Err(_) = self.get_error() {
+				let _ = writeln!(io::stderr(), "ALTO ERROR: `alDeleteSources` or `alDeleteBuffers` failed in Context drop");
+			}
+		} else {
+			let _ = writeln!(io::stderr(), "ALTO ERROR: `alcMakeContextCurrent` failed in Context drop");
+		}
+	}
+}
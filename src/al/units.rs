@@ -0,0 +1,69 @@
+/// Convert a level in decibels to a linear gain. `-inf` dB is silence.
+#[inline]
+pub fn db_to_gain(db: f32) -> f32 {
+	if db == ::std::f32::NEG_INFINITY { 0.0 } else { 10.0f32.powf(db / 20.0) }
+}
+
+
+/// Convert a linear gain to a level in decibels. Silence, or any gain below it, is `-inf` dB.
+#[inline]
+pub fn gain_to_db(gain: f32) -> f32 {
+	if gain <= 0.0 { ::std::f32::NEG_INFINITY } else { 20.0 * gain.log10() }
+}
+
+
+/// The highest pitch multiplier OpenAL-Soft resamples at. Larger pitches are clamped to it.
+pub const MAX_PITCH: f32 = 10.0;
+
+
+/// Convert an interval in semitones to a pitch multiplier, clamped to `(0, MAX_PITCH]`.
+#[inline]
+pub fn semitones_to_pitch(semitones: f32) -> f32 {
+	2.0f32.powf(semitones / 12.0).max(::std::f32::MIN_POSITIVE).min(MAX_PITCH)
+}
+
+
+/// Convert a pitch multiplier to an interval in semitones.
+#[inline]
+pub fn pitch_to_semitones(pitch: f32) -> f32 {
+	12.0 * pitch.log2()
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+
+	fn close(a: f32, b: f32) -> bool { (a - b).abs() < 1e-5 }
+
+
+	#[test]
+	fn decibels() {
+		assert_eq!(db_to_gain(0.0), 1.0);
+		assert!(close(db_to_gain(-20.0), 0.1));
+		assert!(close(db_to_gain(6.0), 1.995_262));
+		assert_eq!(db_to_gain(::std::f32::NEG_INFINITY), 0.0);
+
+		assert_eq!(gain_to_db(1.0), 0.0);
+		assert!(close(gain_to_db(0.1), -20.0));
+		assert_eq!(gain_to_db(0.0), ::std::f32::NEG_INFINITY);
+		assert_eq!(gain_to_db(-1.0), ::std::f32::NEG_INFINITY);
+		for &db in &[-60.0, -3.0, 0.0, 12.0] {
+			assert!(close(gain_to_db(db_to_gain(db)), db));
+		}
+	}
+
+
+	#[test]
+	fn semitones() {
+		assert_eq!(semitones_to_pitch(0.0), 1.0);
+		assert!(close(semitones_to_pitch(12.0), 2.0));
+		assert!(close(semitones_to_pitch(-12.0), 0.5));
+		assert_eq!(semitones_to_pitch(100.0), MAX_PITCH);
+		assert!(semitones_to_pitch(-10000.0) > 0.0);
+
+		assert!(close(pitch_to_semitones(2.0), 12.0));
+		assert!(close(pitch_to_semitones(semitones_to_pitch(-7.0)), -7.0));
+	}
+}
@@ -0,0 +1,84 @@
+use std::time::Instant;
+
+
+/// Derives a velocity from successive positions, for automatic Doppler.
+#[derive(Copy, Clone, Debug)]
+pub struct VelocityTracker {
+	smoothing: f32,
+	last: Option<([f32; 3], Instant)>,
+	velocity: [f32; 3],
+}
+
+
+impl VelocityTracker {
+	pub fn new(smoothing: f32) -> VelocityTracker {
+		VelocityTracker{smoothing: smoothing.max(0.0), last: None, velocity: [0.0, 0.0, 0.0]}
+	}
+
+
+	#[inline]
+	pub fn smoothing(&self) -> f32 { self.smoothing }
+
+
+	/// Record a new position, returning the velocity to apply, if it can be determined yet.
+	/// Positions set less than a millisecond apart are treated as one update, so that the time step isn't too noisy.
+	pub fn update(&mut self, pos: [f32; 3], now: Instant) -> Option<[f32; 3]> {
+		let (last, then) = match self.last {
+			Some(last) => last,
+			None => {
+				self.last = Some((pos, now));
+				return None;
+			},
+		};
+
+		let dt = now.duration_since(then);
+		let dt = dt.as_secs() as f32 + dt.subsec_nanos() as f32 * 1e-9;
+		if dt < 0.001 { return None }
+		self.last = Some((pos, now));
+
+		// Exponential smoothing with a time constant of `smoothing` seconds, so the result doesn't depend on the update rate.
+		let alpha = if self.smoothing > 0.0 { 1.0 - (-dt / self.smoothing).exp() } else { 1.0 };
+		for i in 0 .. 3 {
+			let v = (pos[i] - last[i]) / dt;
+			self.velocity[i] += alpha * (v - self.velocity[i]);
+		}
+		Some(self.velocity)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use super::*;
+
+
+	#[test]
+	fn unsmoothed() {
+		let start = Instant::now();
+		let mut tracker = VelocityTracker::new(-1.0);
+		assert_eq!(tracker.smoothing(), 0.0);
+		assert_eq!(tracker.update([0.0, 0.0, 0.0], start), None);
+		// Too soon after the last position to measure.
+		assert_eq!(tracker.update([5.0, 0.0, 0.0], start + Duration::from_micros(500)), None);
+		assert_eq!(tracker.update([1.0, -2.0, 0.5], start + Duration::from_millis(500)), Some([2.0, -4.0, 1.0]));
+		assert_eq!(tracker.update([1.0, -2.0, 0.5], start + Duration::from_millis(1000)), Some([0.0, 0.0, 0.0]));
+	}
+
+
+	#[test]
+	fn smoothed() {
+		let start = Instant::now();
+		let mut tracker = VelocityTracker::new(0.1);
+		tracker.update([0.0, 0.0, 0.0], start);
+		let first = tracker.update([0.1, 0.0, 0.0], start + Duration::from_millis(100)).unwrap();
+		assert!(first[0] > 0.0 && first[0] < 1.0);
+
+		let mut v = first;
+		for i in 2 .. 50 {
+			v = tracker.update([0.1 * i as f32, 0.0, 0.0], start + Duration::from_millis(100 * i)).unwrap();
+		}
+		assert!((v[0] - 1.0).abs() < 1e-3);
+	}
+}
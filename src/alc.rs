@@ -1,20 +1,33 @@
 use std::ops::Deref;
 use std::ptr;
+#[cfg(feature = "capture")]
+use std::slice;
 use std::ffi::{CString, CStr};
-use std::sync::Arc;
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::path::Path;
+use std::sync::{Arc, RwLock};
+#[cfg(feature = "capture")]
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::path::{Path, PathBuf};
+use std::ffi::OsString;
+#[cfg(any(feature = "loopback", feature = "capture"))]
 use std::marker::PhantomData;
 use std::io::{self, Write};
+#[cfg(any(feature = "loopback", feature = "capture"))]
 use std::mem;
 use std::env;
 use std::fs;
+use std::fmt;
+use std::panic;
+use std::thread;
 use tempdir::TempDir;
 
-use ::{AltoError, AltoResult};
+use ::{AltoError, AltoResult, ObjectKind, CallParam, ParamValue};
+use owned;
 use sys;
 use al::*;
 use ext;
+use metrics::{Metrics, MetricsSink};
+use config::AltoConfig;
 
 
 /// Configuration hints for OpenAL-Soft.
@@ -71,7 +84,14 @@ pub enum SoftResampler {
 }
 
 
+/// The output frequencies accepted for a context, matching what OpenAL Soft can mix at.
+const MIN_FREQUENCY: sys::ALCint = 8000;
+const MAX_FREQUENCY: sys::ALCint = 192000;
+
+
 /// Attributes that may be supplied during context creation.
+/// They are checked against the device before the context is created, and the first one that
+/// is out of range or unsupported is reported as `AlcInvalidAttribute`.
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Default, Debug)]
 pub struct ContextAttrs {
 	/// `ALC_FREQUENCY`
@@ -94,6 +114,7 @@ pub struct ContextAttrs {
 }
 
 
+#[cfg(feature = "loopback")]
 /// Attributes that may be supplied during context creation from a loopback device.
 /// Requires `ALC_SOFT_loopback`
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Default, Debug)]
@@ -114,6 +135,7 @@ pub struct LoopbackAttrs {
 }
 
 
+#[cfg(feature = "loopback")]
 /// Channel format for a loopback context.
 /// Requires `ALC_SOFT_loopback`
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
@@ -133,6 +155,7 @@ pub enum LoopbackFormatChannels {
 }
 
 
+#[cfg(feature = "loopback")]
 /// Sample format for a loopback context.
 /// Requires `ALC_SOFT_loopback`
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
@@ -167,6 +190,47 @@ pub enum SoftHrtfStatus {
 }
 
 
+/// Builder for an extended device specifier, resolved against the names the implementation enumerates.
+/// With `ALC_ENUMERATE_ALL_EXT`, OpenAL-Soft exposes each backend endpoint under its own name
+/// (e.g. an ALSA PCM or a PulseAudio/WASAPI endpoint). A builder with no criteria selects the default device.
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct DeviceSpecifier {
+	name: Option<String>,
+	alsa: Option<(String, u32)>,
+	endpoint: Option<String>,
+	capture: bool,
+}
+
+
+/// What an output device supports, as found by `Alto::probe_outputs()`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DeviceCaps {
+	pub specifier: CString,
+	pub alc_extensions: Vec<ext::Alc>,
+	/// Extensions of a context on the device, including those that add sample formats.
+	pub al_extensions: Vec<ext::Al>,
+	/// Empty without `ALC_SOFT_HRTF`.
+	pub hrtfs: Vec<CString>,
+	/// `None` without `ALC_EXT_EFX`.
+	pub max_auxiliary_sends: Option<sys::ALCint>,
+	/// Advertised ALC and AL extensions that are missing symbols, which the runtime should have exported.
+	pub incomplete_extensions: Vec<ext::MissingSymbols>,
+}
+
+
+/// Builder for loading an OpenAL implementation from a list of candidate libraries.
+/// Candidates are tried in order: the environment override, if configured and set,
+/// then each explicit candidate, then the platform default library names.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct AltoLoader {
+	candidates: Vec<PathBuf>,
+	env_var: Option<OsString>,
+	exe_dir: bool,
+	no_defaults: bool,
+	hints: Option<SoftHints>,
+}
+
+
 rental! {
 	mod rent {
 		#[rental]
@@ -184,12 +248,29 @@ pub use self::rent::AlApi;
 
 /// This struct is the entry point of the API. Instantiating it will load an OpenAL implementation.
 /// From here, available devices can be queried and opened.
+/// When targeting emscripten, the browser's OpenAL implementation is linked statically instead,
+/// and only the extensions it reports will be available.
 pub struct Alto {
 	api: AlApi,
+	lib_path: Option<PathBuf>,
+	metrics: Metrics,
+	config: RwLock<AltoConfig>,
+	#[cfg(feature = "capture")]
+	capture_watermarks: Mutex<Vec<CaptureWatermark>>,
 	_hints_dir: Option<TempDir>,
 }
 
 
+#[cfg(feature = "capture")]
+/// A capture device watched by `Context::poll_events()`.
+struct CaptureWatermark {
+	dev: usize,
+	spec: CString,
+	frames: usize,
+	armed: bool,
+}
+
+
 /// Common capabilities expoed by both real and loopback devices.
 pub unsafe trait DeviceTrait {
 	/// Alto instance from which this device was opened.
@@ -200,17 +281,29 @@ pub unsafe trait DeviceTrait {
 	fn as_raw(&self) -> *mut sys::ALCdevice;
 	/// `alcIsExtensionPresent()`
 	fn is_extension_present(&self, ext::Alc) -> bool;
+	/// Enums and functions of an advertised extension that didn't resolve, or `None` if it isn't advertised or nothing is missing.
+	/// Calls needing a missing symbol fail with `AlcMissingSymbols` rather than `AlcExtensionNotPresent`.
+	fn missing_symbols(&self, ext::Alc) -> Option<ext::MissingSymbols>;
 	#[doc(hidden)]
 	fn extensions(&self) -> &ext::AlcCache;
 	/// `alcGetIntegerv(ALC_CONNECTED)`
 	/// Requires `ALC_EXT_disconnect`
 	fn connected(&self) -> AltoResult<bool>;
+	/// Whether the device was found to be disconnected, by `connected()` or `Context::poll_events()`.
+	/// From then on, every call on its contexts and their objects fails with `AlcDeviceDisconnected`.
+	fn is_disconnected(&self) -> bool;
+	#[doc(hidden)]
+	fn set_disconnected(&self);
 	/// `alcGetStringiSOFT(ALC_HRTF_SPECIFIER_SOFT)`
 	/// Requires `ALC_SOFT_HRTF`
 	fn enumerate_soft_hrtfs(&self) -> AltoResult<Vec<CString>>;
 	/// `alcGetIntegerv(ALC_HRTF_STATUS_SOFT)`
 	/// Requires `ALC_SOFT_HRTF`
 	fn soft_hrtf_status(&self) -> AltoResult<SoftHrtfStatus>;
+	/// `alcGetString(ALC_HRTF_SPECIFIER_SOFT)`
+	/// Name of the HRTF currently in use, or an empty string if none is.
+	/// Requires `ALC_SOFT_HRTF`
+	fn soft_hrtf_specifier(&self) -> AltoResult<CString>;
 	/// `alcGetIntegerv(ALC_MAX_AUXILIARY_SENDS)`
 	/// Requires `ALC_EXT_EFX`
 	fn max_auxiliary_sends(&self) -> AltoResult<sys::ALCint>;
@@ -218,12 +311,16 @@ pub unsafe trait DeviceTrait {
 
 
 /// A regular output device. This is typically a device as reported by the operating system.
+/// Its contexts borrow it, so it can only be dropped once all of them have been.
 pub struct Device<'a> {
 	alto: &'a Alto,
 	spec: CString,
 	dev: *mut sys::ALCdevice,
+	/// Whether the device is closed on drop; cleared by `into_raw()`.
+	owned: bool,
 	exts: ext::AlcCache<'a>,
 	pause_rc: Arc<AtomicUsize>,
+	disconnected: AtomicBool,
 }
 
 
@@ -232,6 +329,16 @@ pub struct Device<'a> {
 pub struct SoftPauseLock<'a: 'd, 'd>(&'d Device<'a>);
 
 
+/// Builder for a context on a `Device`, as an alternative to filling out `ContextAttrs`.
+/// Each attribute is checked against the device when the context is built, and the first
+/// one that is out of range or unsupported is reported as `AlcInvalidAttribute`.
+pub struct ContextBuilder<'a: 'd, 'd> {
+	dev: &'d Device<'a>,
+	attrs: ContextAttrs,
+}
+
+
+#[cfg(feature = "loopback")]
 /// A sample frame that is supported as a loopback device output format.
 pub unsafe trait LoopbackFrame: SampleFrame {
 	fn channels(&ext::ALC_SOFT_loopback) -> AltoResult<sys::ALint>;
@@ -239,17 +346,21 @@ pub unsafe trait LoopbackFrame: SampleFrame {
 }
 
 
+#[cfg(feature = "loopback")]
 /// A loopback device that outputs audio to a memory buffer.
 /// Requires `ALC_SOFT_loopback`
 pub struct LoopbackDevice<'a, F: LoopbackFrame> {
 	alto: &'a Alto,
 	spec: CString,
 	dev: *mut sys::ALCdevice,
+	/// Whether the device is closed on drop; cleared by `into_raw()`.
+	owned: bool,
 	exts: ext::AlcCache<'a>,
 	marker: PhantomData<F>,
 }
 
 
+#[cfg(feature = "capture")]
 /// A capture device from which audio data can be sampled.
 /// This is tyically an audio input as reported by the operating system.
 pub struct CaptureDevice<'a, F: StandardFrame> {
@@ -264,11 +375,7 @@ impl Alto {
 	/// Load the default OpenAL implementation for the platform.
 	/// This will prefer OpenAL-Soft if it is present, otherwise it will search for a generic implementation.
 	pub fn load_default() -> AltoResult<Alto> {
-		let api = Box::new(sys::AlApi::load_default()?);
-		Ok(Alto{
-			api: AlApi::new(api, |a| unsafe { ext::AlcNullCache::new(a, ptr::null_mut()) }),
-			_hints_dir: None,
-		}).and_then(|a| a.check_version())
+		Alto::from_api(sys::AlApi::load_default()?, None, None)
 	}
 
 
@@ -278,21 +385,14 @@ impl Alto {
 	/// NOTE: This involves setting the value of the `ALSOFT_CONF` environment variable. If it is already set,
 	/// then the provided hints will be discarded.
 	pub fn load_default_with_soft_hints(hints: SoftHints) -> AltoResult<Alto> {
-		let api = Box::new(sys::AlApi::load_default()?);
-		Ok(Alto{
-			api: AlApi::new(api, |a| unsafe { ext::AlcNullCache::new(a, ptr::null_mut()) }),
-			_hints_dir: Alto::set_hints(hints),
-		}).and_then(|a| a.check_version())
+		Alto::from_api(sys::AlApi::load_default()?, Some(hints), None)
 	}
 
 
 	/// Loads a specific OpenAL implementation from a specififed path.
 	pub fn load<P: AsRef<Path>>(path: P) -> AltoResult<Alto> {
-		let api = Box::new(sys::AlApi::load(path)?);
-		Ok(Alto{
-			api: AlApi::new(api, |a| unsafe { ext::AlcNullCache::new(a, ptr::null_mut()) }),
-			_hints_dir: None,
-		}).and_then(|a| a.check_version())
+		let path = path.as_ref();
+		Alto::from_api(sys::AlApi::load(path)?, None, Some(path.to_owned()))
 	}
 
 
@@ -302,14 +402,70 @@ impl Alto {
 	/// NOTE: This involves setting the value of the `ALSOFT_CONF` environment variable. If it is already set,
 	/// then the provided hints will be discarded.
 	pub fn load_with_soft_hints<P: AsRef<Path>>(path: P, hints: SoftHints) -> AltoResult<Alto> {
-		let api = Box::new(sys::AlApi::load(path)?);
+		let path = path.as_ref();
+		Alto::from_api(sys::AlApi::load(path)?, Some(hints), Some(path.to_owned()))
+	}
+
+
+	/// Use a table of entry points supplied by the caller instead of loading a library,
+	/// such as a fake implementation for tests.
+	pub fn load_fns(fns: sys::AlFns) -> AltoResult<Alto> {
+		Alto::from_api(sys::AlApi::from_fns(fns), None, None)
+	}
+
+
+	/// Use the in-memory fake implementation from the [`mock`](mock/index.html) module.
+	#[cfg(feature = "mock")]
+	pub fn load_mock() -> AltoResult<Alto> {
+		Alto::load_fns(::mock::fns())
+	}
+
+
+	fn from_api(api: sys::AlApi, hints: Option<SoftHints>, lib_path: Option<PathBuf>) -> AltoResult<Alto> {
 		Ok(Alto{
-			api: AlApi::new(api, |a| unsafe { ext::AlcNullCache::new(a, ptr::null_mut()) }),
-			_hints_dir: Alto::set_hints(hints),
+			api: AlApi::new(Box::new(api), |a| unsafe { ext::AlcNullCache::new(a, ptr::null_mut()) }),
+			lib_path: lib_path,
+			metrics: Metrics::default(),
+			config: RwLock::new(AltoConfig::from_env()),
+			#[cfg(feature = "capture")]
+			capture_watermarks: Mutex::new(Vec::new()),
+			_hints_dir: hints.and_then(Alto::set_hints),
 		}).and_then(|a| a.check_version())
 	}
 
 
+	/// Install a sink to receive audio health measurements from everything created through this instance,
+	/// or remove the current one with `None`.
+	pub fn set_metrics_sink(&self, sink: Option<Arc<dyn MetricsSink>>) {
+		self.metrics.set_sink(sink);
+	}
+
+
+	#[doc(hidden)]
+	pub fn metrics(&self) -> &Metrics { &self.metrics }
+
+
+	/// Overrides applied to devices and contexts created from now on.
+	/// These are read from `ALTO_*` environment variables when the implementation is loaded.
+	pub fn config(&self) -> AltoConfig {
+		self.config.read().unwrap().clone()
+	}
+
+
+	/// Replace the overrides, such as to ignore the environment with `AltoConfig::default()`.
+	pub fn set_config(&self, config: AltoConfig) {
+		*self.config.write().unwrap() = config;
+	}
+
+
+	/// Path of the library that was loaded, if known.
+	/// This is `None` when the implementation was found by `load_default()` or is linked statically.
+	pub fn library_path(&self) -> Option<&Path> {
+		self.lib_path.as_ref().map(|p| p.as_path())
+	}
+
+
+	#[cfg(not(target_os = "emscripten"))]
 	fn set_hints(hints: SoftHints) -> Option<TempDir> {
 		if hints.period_size.is_none()
 			&& hints.periods.is_none()
@@ -365,6 +521,13 @@ impl Alto {
 	}
 
 
+	/// The browser implementation used by emscripten has no configuration file, so hints are ignored.
+	#[cfg(target_os = "emscripten")]
+	fn set_hints(_hints: SoftHints) -> Option<TempDir> {
+		None
+	}
+
+
 	fn check_version(self) -> AltoResult<Alto> {
 		let mut major = 0;
 		unsafe { self.api.head().alcGetIntegerv()(ptr::null_mut(), sys::ALC_MAJOR_VERSION, 1, &mut major); }
@@ -391,7 +554,7 @@ impl Alto {
 			} else {
 				unsafe { CStr::from_ptr(self.api.head().alcGetString()(ptr::null_mut(), sys::ALC_DEFAULT_DEVICE_SPECIFIER)) }
 			};
-			self.get_error(ptr::null_mut()).map(|_| spec.to_owned())
+			self.get_error(ptr::null_mut()).map_err(|e| e.at("alcGetString", ObjectKind::Device, Some("ALC_DEFAULT_DEVICE_SPECIFIER".into()))).map(|_| spec.to_owned())
 		})
 	}
 
@@ -399,7 +562,7 @@ impl Alto {
 	/// `alcGetString(ALC_CAPTURE_DEFAULT_DEVICE_SPECIFIER)`
 	pub fn default_capture(&self) -> AltoResult<CString> {
 		let spec = unsafe { CStr::from_ptr(self.api.head().alcGetString()(ptr::null_mut(), sys::ALC_CAPTURE_DEFAULT_DEVICE_SPECIFIER)) };
-		self.get_error(ptr::null_mut()).map(|_| spec.to_owned())
+		self.get_error(ptr::null_mut()).map_err(|e| e.at("alcGetString", ObjectKind::Device, Some("ALC_CAPTURE_DEFAULT_DEVICE_SPECIFIER".into()))).map(|_| spec.to_owned())
 	}
 
 
@@ -411,7 +574,7 @@ impl Alto {
 			} else {
 				unsafe { self.api.head().alcGetString()(ptr::null_mut(), sys::ALC_DEVICE_SPECIFIER) }
 			};
-			self.get_error(ptr::null_mut()).and_then(|_| Alto::parse_enum_spec(spec as *const u8))
+			self.get_error(ptr::null_mut()).map_err(|e| e.at("alcGetString", ObjectKind::Device, Some("ALC_DEVICE_SPECIFIER".into()))).and_then(|_| Alto::parse_enum_spec(spec as *const u8))
 		})
 	}
 
@@ -419,7 +582,7 @@ impl Alto {
 	/// `alcGetString(ALC_CAPTURE_DEVICE_SPECIFIER)`
 	pub fn enumerate_captures(&self) -> AltoResult<Vec<CString>> {
 		let spec = unsafe { self.api.head().alcGetString()(ptr::null_mut(), sys::ALC_CAPTURE_DEVICE_SPECIFIER) };
-		self.get_error(ptr::null_mut()).and_then(|_| Alto::parse_enum_spec(spec as *const u8))
+		self.get_error(ptr::null_mut()).map_err(|e| e.at("alcGetString", ObjectKind::Device, Some("ALC_CAPTURE_DEVICE_SPECIFIER".into()))).and_then(|_| Alto::parse_enum_spec(spec as *const u8))
 	}
 
 
@@ -442,30 +605,111 @@ impl Alto {
 
 
 	/// `alcOpenDevice()`
+	/// The device named by `AltoConfig::device` is opened instead, if set.
 	pub fn open<'s, S: Into<Option<&'s CStr>>>(&self, spec: S) -> AltoResult<Device> {
-		let spec = if let Some(spec) = spec.into() {
+		let spec = if let Some(spec) = self.config().device {
+			spec
+		} else if let Some(spec) = spec.into() {
 			spec.to_owned()
 		} else {
 			self.default_output()?
 		};
+		self.open_exact(spec)
+	}
 
+
+	fn open_exact(&self, spec: CString) -> AltoResult<Device> {
+		let _span = alto_span!("open", spec = %spec.to_string_lossy());
 		let dev = unsafe { self.api.head().alcOpenDevice()(spec.as_ptr()) };
-		self.get_error(ptr::null_mut())?;
+		self.get_error(ptr::null_mut()).map_err(|e| device_error(AltoError::AlcOpenDevice, e, &spec))?;
 
 		if dev == ptr::null_mut() {
-			Err(AltoError::AlcInvalidDevice)
+			Err(device_error(AltoError::AlcOpenDevice, AltoError::AlcInvalidDevice, &spec))
 		} else {
+			alto_event!(debug, "opened device");
 			Ok(Device{
 				alto: self,
 				spec: spec,
 				dev: dev,
+				owned: true,
 				exts: unsafe { ext::AlcCache::new(self.api.head(), dev) },
-				pause_rc: Arc::new(AtomicUsize::new(0))
+				pause_rc: Arc::new(AtomicUsize::new(0)),
+				disconnected: AtomicBool::new(false),
 			})
 		}
 	}
 
 
+	/// Open the first device in a preference list that opens successfully, where `None` is the default device.
+	/// Fails with `AlcNoDevice` listing why each device failed, in order.
+	/// If `AltoConfig::device` is set, only that device is tried.
+	pub fn open_first_available(&self, specs: &[Option<&CStr>]) -> AltoResult<Device> {
+		let specs = if self.config().device.is_some() { &[None][..] } else { specs };
+		let mut errors = Vec::with_capacity(specs.len());
+		for &spec in specs {
+			match self.open(spec) {
+				Ok(dev) => return Ok(dev),
+				Err(e) => {
+					alto_event!(warn, error = %e, "failed to open device, trying the next one");
+					errors.push(e);
+				},
+			}
+		}
+		Err(AltoError::AlcNoDevice(errors))
+	}
+
+
+	/// Open every output device and read what it supports, probing up to `max_threads` devices at once.
+	/// Each result is in enumeration order, and is the error if the device failed to open or create a context.
+	/// `AltoConfig::device` is ignored, so that every device is probed.
+	pub fn probe_outputs(&self, max_threads: usize) -> AltoResult<Vec<AltoResult<DeviceCaps>>> {
+		let specs = self.enumerate_outputs()?;
+		let next = AtomicUsize::new(0);
+		let mut probed: Vec<_> = thread::scope(|s| {
+			let workers: Vec<_> = (0 .. max_threads.max(1).min(specs.len())).map(|_| s.spawn(|| {
+				let mut probed = Vec::new();
+				loop {
+					let i = next.fetch_add(1, Ordering::Relaxed);
+					match specs.get(i) {
+						Some(spec) => probed.push((i, self.probe_output(spec))),
+						None => return probed,
+					}
+				}
+			})).collect();
+			workers.into_iter().flat_map(|w| w.join().unwrap_or_else(|e| panic::resume_unwind(e))).collect()
+		});
+		probed.sort_by_key(|&(i, _)| i);
+		Ok(probed.into_iter().map(|(_, caps)| caps).collect())
+	}
+
+
+	fn probe_output(&self, spec: &CStr) -> AltoResult<DeviceCaps> {
+		let dev = self.open_exact(spec.to_owned())?;
+		let ctx = dev.new_context(None)?;
+		Ok(DeviceCaps{
+			specifier: spec.to_owned(),
+			alc_extensions: ext::Alc::ALL.iter().cloned().filter(|&e| dev.is_extension_present(e)).collect(),
+			al_extensions: ext::Al::ALL.iter().cloned().filter(|&e| ctx.is_extension_present(e)).collect(),
+			hrtfs: if dev.is_extension_present(ext::Alc::SoftHrtf) { dev.enumerate_soft_hrtfs()? } else { Vec::new() },
+			max_auxiliary_sends: if dev.is_extension_present(ext::Alc::Efx) { Some(dev.max_auxiliary_sends()?) } else { None },
+			incomplete_extensions: ext::Alc::ALL.iter().filter_map(|&e| dev.missing_symbols(e))
+				.chain(ext::Al::ALL.iter().filter_map(|&e| ctx.missing_symbols(e)))
+				.collect(),
+		})
+	}
+
+
+	/// `alcOpenDevice()` and `alcCreateContext()` in one call, for applications that only need one context.
+	/// The returned context keeps the device and this `Alto` alive. See `owned::Context::open()`.
+	pub fn open_context<'s, S, F>(self, spec: S, configure: F) -> AltoResult<owned::Context> where
+		S: Into<Option<&'s CStr>>,
+		F: for<'d> FnOnce(ContextBuilder<'static, 'd>) -> ContextBuilder<'static, 'd>,
+	{
+		owned::Context::open(Arc::new(self), spec, configure)
+	}
+
+
+	#[cfg(feature = "loopback")]
 	/// `alcLoopbackOpenDeviceSOFT()`
 	/// Requires `ALC_SOFT_loopback`
 	pub fn open_loopback<'s, S: Into<Option<&'s CStr>>, F: LoopbackFrame>(&self, spec: S) -> AltoResult<LoopbackDevice<F>> {
@@ -478,16 +722,19 @@ impl Alto {
 				self.default_output()?
 			};
 
+			let _span = alto_span!("open_loopback", spec = %spec.to_string_lossy());
 			let dev = unsafe { sl.alcLoopbackOpenDeviceSOFT?(spec.as_ptr()) };
-			self.get_error(ptr::null_mut())?;
+			self.get_error(ptr::null_mut()).map_err(|e| device_error(AltoError::AlcOpenDevice, e, &spec))?;
 
 			if dev == ptr::null_mut() {
-				Err(AltoError::AlcInvalidDevice)
+				Err(device_error(AltoError::AlcOpenDevice, AltoError::AlcInvalidDevice, &spec))
 			} else {
+				alto_event!(debug, "opened loopback device");
 				Ok(LoopbackDevice{
 					alto: self,
 					spec: spec,
 					dev: dev,
+					owned: true,
 					exts: unsafe { ext::AlcCache::new(self.api.head(), dev) },
 					marker: PhantomData
 				})
@@ -496,30 +743,347 @@ impl Alto {
 	}
 
 
+	#[cfg(feature = "capture")]
 	/// `alcCaptureOpenDevice()`
+	/// The device named by `AltoConfig::capture_device` is opened instead, if set.
 	pub fn open_capture<'s, S: Into<Option<&'s CStr>>, F: StandardFrame>(&self, spec: S, freq: sys::ALCuint, len: sys::ALCsizei) -> AltoResult<CaptureDevice<F>> {
-		let spec = if let Some(spec) = spec.into() {
+		let spec = if let Some(spec) = self.config().capture_device {
+			spec
+		} else if let Some(spec) = spec.into() {
 			spec.to_owned()
 		} else {
 			self.default_output()?
 		};
 
+		let _span = alto_span!("open_capture", spec = %spec.to_string_lossy(), freq = freq, len = len);
 		let dev = unsafe { self.api.head().alcCaptureOpenDevice()(spec.as_ptr(), freq, F::format().into_raw(None)?, len) };
-		self.get_error(ptr::null_mut())?;
+		self.get_error(ptr::null_mut()).map_err(|e| device_error(AltoError::AlcOpenCaptureDevice, e, &spec))?;
 
 		if dev == ptr::null_mut() {
-			Err(AltoError::AlcInvalidDevice)
+			Err(device_error(AltoError::AlcOpenCaptureDevice, AltoError::AlcInvalidDevice, &spec))
 		} else {
+			alto_event!(debug, "opened capture device");
 			Ok(CaptureDevice{alto: self, spec: spec, dev: dev, marker: PhantomData})
 		}
 	}
 
 
+	/// Open the output device selected by a `DeviceSpecifier`.
+	pub fn open_specifier(&self, spec: &DeviceSpecifier) -> AltoResult<Device> {
+		let spec = spec.clone().capture(false).resolve(self)?;
+		self.open(&*spec)
+	}
+
+
+	#[cfg(feature = "capture")]
+	/// Open the capture device selected by a `DeviceSpecifier`.
+	pub fn open_capture_specifier<F: StandardFrame>(&self, spec: &DeviceSpecifier, freq: sys::ALCuint, len: sys::ALCsizei) -> AltoResult<CaptureDevice<F>> {
+		let spec = spec.clone().capture(true).resolve(self)?;
+		self.open_capture(&*spec, freq, len)
+	}
+
+
+	#[cfg(feature = "capture")]
+	/// Specifiers and available frames of capture devices that have reached their watermarks since the last call.
+	#[doc(hidden)]
+	pub fn capture_watermarks_reached(&self) -> Vec<(CString, usize)> {
+		let mut reached = Vec::new();
+		for wm in self.capture_watermarks.lock().unwrap_or_else(|e| e.into_inner()).iter_mut() {
+			let dev = wm.dev as *mut sys::ALCdevice;
+			let mut samples = 0;
+			unsafe { self.api.head().alcGetIntegerv()(dev, sys::ALC_CAPTURE_SAMPLES, 1, &mut samples); }
+			if self.get_error(dev).is_err() { continue }
+
+			if samples as usize >= wm.frames {
+				if wm.armed {
+					wm.armed = false;
+					reached.push((wm.spec.clone(), samples as usize));
+				}
+			} else {
+				wm.armed = true;
+			}
+		}
+		reached
+	}
+
+
+	#[cfg(feature = "capture")]
+	fn set_capture_watermark(&self, dev: *mut sys::ALCdevice, spec: &CStr, frames: Option<usize>) {
+		let mut wms = self.capture_watermarks.lock().unwrap_or_else(|e| e.into_inner());
+		wms.retain(|wm| wm.dev != dev as usize);
+		if let Some(frames) = frames {
+			wms.push(CaptureWatermark{dev: dev as usize, spec: spec.to_owned(), frames: frames, armed: true});
+		}
+	}
+
+
 	#[doc(hidden)]
 	pub fn get_error(&self, dev: *mut sys::ALCdevice) -> AltoResult<()> {
 		match unsafe { self.api.head().alcGetError()(dev)} {
 			sys::ALC_NO_ERROR => Ok(()),
-			e => Err(AltoError::from_alc(e)),
+			e => {
+				let e = AltoError::from_alc(e);
+				alto_event!(warn, error = %e, "ALC error");
+				Err(e)
+			},
+		}
+	}
+
+
+	fn device_specifier(&self, dev: *mut sys::ALCdevice, param: sys::ALCenum) -> AltoResult<CString> {
+		if dev == ptr::null_mut() { return Err(AltoError::AlcInvalidDevice) }
+
+		let spec = unsafe { self.api.head().alcGetString()(dev, param) };
+		self.get_error(dev).map_err(|e| e.at("alcGetString", ObjectKind::Device, Some(CallParam{name: "param", value: Some(ParamValue::Enum(param))})))?;
+		if spec == ptr::null() {
+			Ok(CString::default())
+		} else {
+			Ok(unsafe { CStr::from_ptr(spec) }.to_owned())
+		}
+	}
+}
+
+
+impl AltoLoader {
+	pub fn new() -> AltoLoader {
+		AltoLoader::default()
+	}
+
+
+	/// Add a library name or path to try, after any previously added candidates.
+	pub fn candidate<P: Into<PathBuf>>(mut self, path: P) -> AltoLoader {
+		self.candidates.push(path.into());
+		self
+	}
+
+
+	/// Add several library names or paths to try, in order.
+	pub fn candidates<P: Into<PathBuf>, I: IntoIterator<Item = P>>(mut self, paths: I) -> AltoLoader {
+		self.candidates.extend(paths.into_iter().map(|p| p.into()));
+		self
+	}
+
+
+	/// Name of an environment variable that, if set, holds a library path to try before any other candidate.
+	pub fn env_override<S: Into<OsString>>(mut self, var: S) -> AltoLoader {
+		self.env_var = Some(var.into());
+		self
+	}
+
+
+	/// Also look for each relative candidate in the directory of the running executable, before
+	/// falling back to the normal library search path. Useful when shipping an implementation alongside the application.
+	pub fn search_exe_dir(mut self, search: bool) -> AltoLoader {
+		self.exe_dir = search;
+		self
+	}
+
+
+	/// Whether to try the platform default library names after the explicit candidates. Enabled by default.
+	pub fn system_defaults(mut self, defaults: bool) -> AltoLoader {
+		self.no_defaults = !defaults;
+		self
+	}
+
+
+	/// Hints to pass along if the loaded implementation is OpenAL-Soft. See `Alto::load_default_with_soft_hints()`.
+	pub fn soft_hints(mut self, hints: SoftHints) -> AltoLoader {
+		self.hints = Some(hints);
+		self
+	}
+
+
+	/// Every path that `load()` will try, in order.
+	pub fn search_paths(&self) -> Vec<PathBuf> {
+		let mut names = Vec::new();
+		if let Some(path) = self.env_var.as_ref().and_then(|v| env::var_os(v)) {
+			if !path.is_empty() {
+				names.push(PathBuf::from(path));
+			}
+		}
+		names.extend(self.candidates.iter().cloned());
+		if !self.no_defaults {
+			names.extend(sys::DEFAULT_LIBRARY_NAMES.iter().map(PathBuf::from));
+		}
+
+		let exe_dir = if self.exe_dir {
+			env::current_exe().ok().and_then(|p| p.parent().map(|p| p.to_owned()))
+		} else {
+			None
+		};
+
+		let mut paths = Vec::with_capacity(names.len() * 2);
+		for name in names {
+			if let Some(ref exe_dir) = exe_dir {
+				if name.is_relative() {
+					paths.push(exe_dir.join(&name));
+				}
+			}
+			paths.push(name);
+		}
+		paths
+	}
+
+
+	/// Load the first candidate that succeeds. `Alto::library_path()` reports which one it was.
+	#[cfg(not(any(target_os = "emscripten", feature = "bundled")))]
+	pub fn load(&self) -> AltoResult<Alto> {
+		let mut err = None;
+		for path in self.search_paths() {
+			match sys::AlApi::load(&path) {
+				Ok(api) => return Alto::from_api(api, self.hints, Some(path)),
+				Err(e) => err = Some(e),
+			}
+		}
+
+		Err(AltoError::Io(err.unwrap_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No OpenAL library candidates"))))
+	}
+
+
+	/// With a statically linked implementation there is nothing to search for, so this is equivalent to `Alto::load_default()`.
+	#[cfg(any(target_os = "emscripten", feature = "bundled"))]
+	pub fn load(&self) -> AltoResult<Alto> {
+		Alto::from_api(sys::AlApi::load_default()?, self.hints, None)
+	}
+}
+
+
+impl DeviceSpecifier {
+	pub fn new() -> DeviceSpecifier {
+		DeviceSpecifier::default()
+	}
+
+
+	/// Require the specifier to be exactly `name`, as returned by `Alto::enumerate_outputs()` or `Alto::enumerate_captures()`.
+	pub fn name<S: Into<String>>(mut self, name: S) -> DeviceSpecifier {
+		self.name = Some(name.into());
+		self
+	}
+
+
+	/// Select an ALSA PCM by card identifier and device number, as in `hw:CARD=PCH,DEV=0`.
+	pub fn alsa<S: Into<String>>(mut self, card: S, device: u32) -> DeviceSpecifier {
+		self.alsa = Some((card.into(), device));
+		self
+	}
+
+
+	/// Select an endpoint by its friendly name, such as a PulseAudio/PipeWire sink description or a WASAPI endpoint name.
+	/// Compared case-insensitively, ignoring the `OpenAL Soft on ` prefix and any ` #N` suffix used to disambiguate duplicates.
+	pub fn endpoint<S: Into<String>>(mut self, name: S) -> DeviceSpecifier {
+		self.endpoint = Some(name.into());
+		self
+	}
+
+
+	/// Resolve against capture devices rather than outputs.
+	pub fn capture(mut self, capture: bool) -> DeviceSpecifier {
+		self.capture = capture;
+		self
+	}
+
+
+	/// Whether an enumerated specifier satisfies every criterion of this builder.
+	pub fn matches(&self, spec: &CStr) -> bool {
+		let spec = spec.to_string_lossy();
+
+		if let Some(ref name) = self.name {
+			if *name != spec { return false; }
+		}
+		if let Some((ref card, device)) = self.alsa {
+			if !spec.ends_with(&format!("(CARD={},DEV={})", card, device)) { return false; }
+		}
+		if let Some(ref endpoint) = self.endpoint {
+			if endpoint_name(endpoint).to_lowercase() != endpoint_name(&spec).to_lowercase() { return false; }
+		}
+
+		true
+	}
+
+
+	/// Find the specifier to open.
+	/// Fails with `AlcInvalidValue` if a criterion contains a NUL byte or is empty,
+	/// and with `AlcInvalidDevice` if no enumerated device matches.
+	pub fn resolve(&self, alto: &Alto) -> AltoResult<CString> {
+		let name = self.name.as_ref().map(|n| n.as_str());
+		let card = self.alsa.as_ref().map(|a| a.0.as_str());
+		let endpoint = self.endpoint.as_ref().map(|e| e.as_str());
+		for s in name.iter().chain(card.iter()).chain(endpoint.iter()) {
+			if s.is_empty() || s.contains('\0') { return Err(AltoError::AlcInvalidValue); }
+		}
+
+		if name.is_none() && card.is_none() && endpoint.is_none() {
+			return if self.capture { alto.default_capture() } else { alto.default_output() };
+		}
+
+		let specs = if self.capture { alto.enumerate_captures()? } else { alto.enumerate_outputs()? };
+		specs.into_iter().find(|s| self.matches(s)).ok_or(AltoError::AlcInvalidDevice)
+	}
+}
+
+
+/// Check attributes against `dev` before they are passed to `alcCreateContext()`, which would only report
+/// `ALC_INVALID_VALUE`, reporting the first one that is out of range or unsupported as `AlcInvalidAttribute`.
+/// Source counts above the implementation's limit can't be known in advance, and are reduced by it instead.
+fn check_context_attrs<D: DeviceTrait + ?Sized>(dev: &D, attrs: &ContextAttrs) -> AltoResult<()> {
+	if attrs.frequency.map_or(false, |f| f < MIN_FREQUENCY || f > MAX_FREQUENCY) { return Err(AltoError::AlcInvalidAttribute("ALC_FREQUENCY")) }
+	if attrs.refresh.map_or(false, |r| r <= 0) { return Err(AltoError::AlcInvalidAttribute("ALC_REFRESH")) }
+	if attrs.mono_sources.map_or(false, |m| m < 0) { return Err(AltoError::AlcInvalidAttribute("ALC_MONO_SOURCES")) }
+	if attrs.stereo_sources.map_or(false, |s| s < 0) { return Err(AltoError::AlcInvalidAttribute("ALC_STEREO_SOURCES")) }
+	if attrs.mono_sources.unwrap_or(0).checked_add(attrs.stereo_sources.unwrap_or(0)).is_none() {
+		return Err(AltoError::AlcInvalidAttribute("ALC_STEREO_SOURCES"));
+	}
+
+	if attrs.soft_hrtf.is_some() && dev.extensions().ALC_SOFT_HRTF().is_err() {
+		return Err(AltoError::AlcInvalidAttribute("ALC_HRTF_SOFT"));
+	}
+	if let Some(id) = attrs.soft_hrtf_id {
+		// An HRTF can't be selected while HRTF is explicitly disabled.
+		if attrs.soft_hrtf == Some(false) || id < 0 { return Err(AltoError::AlcInvalidAttribute("ALC_HRTF_ID_SOFT")) }
+		let count = dev.enumerate_soft_hrtfs().map_err(|_| AltoError::AlcInvalidAttribute("ALC_HRTF_ID_SOFT"))?.len();
+		if id as usize >= count { return Err(AltoError::AlcInvalidAttribute("ALC_HRTF_ID_SOFT")) }
+	}
+
+	if let Some(max_sends) = attrs.max_auxiliary_sends {
+		if max_sends < 0 || dev.extensions().ALC_EXT_EFX().is_err() { return Err(AltoError::AlcInvalidAttribute("ALC_MAX_AUXILIARY_SENDS")) }
+	}
+
+	Ok(())
+}
+
+
+/// A null pointer for no attributes, since an empty list lacks the terminating 0.
+fn attrs_ptr(attrs_vec: &[sys::ALCint]) -> *const sys::ALCint {
+	if attrs_vec.is_empty() { ptr::null() } else { attrs_vec.as_ptr() }
+}
+
+
+/// Wrap an error from opening a device or creating a context with the specifier of the device.
+fn device_error(wrap: fn(Box<AltoError>, String) -> AltoError, e: AltoError, spec: &CStr) -> AltoError {
+	wrap(Box::new(e), spec.to_string_lossy().into_owned())
+}
+
+
+fn endpoint_name(spec: &str) -> &str {
+	let spec = spec.trim();
+	let spec = if spec.starts_with("OpenAL Soft on ") { &spec["OpenAL Soft on ".len() ..] } else { spec };
+	match spec.rfind(" #") {
+		Some(i) if i + 2 < spec.len() && spec[i + 2 ..].chars().all(|c| c.is_digit(10)) => &spec[.. i],
+		_ => spec,
+	}
+}
+
+
+impl fmt::Display for SoftHrtfStatus {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			SoftHrtfStatus::Disabled => f.pad("disabled"),
+			SoftHrtfStatus::Enabled => f.pad("enabled"),
+			SoftHrtfStatus::Denied => f.pad("denied"),
+			SoftHrtfStatus::Required => f.pad("required"),
+			SoftHrtfStatus::HeadphonesDetected => f.pad("headphones detected"),
+			SoftHrtfStatus::UnsupportedFormat => f.pad("unsupported format"),
+			SoftHrtfStatus::Unknown(s) => f.pad(&format!("unknown ({})", s)),
 		}
 	}
 }
@@ -534,9 +1098,37 @@ impl Eq for DeviceTrait { }
 
 
 impl<'a> Device<'a> {
+	/// Take ownership of a device opened outside of alto, such as by C code sharing the same OpenAL implementation.
+	/// The device will be closed when dropped.
+	///
+	/// # Safety
+	/// `dev` must be a valid output device opened from the same implementation as `alto`, and must not be closed elsewhere.
+	pub unsafe fn from_raw(alto: &'a Alto, dev: *mut sys::ALCdevice) -> AltoResult<Device<'a>> {
+		let spec = alto.device_specifier(dev, sys::ALC_DEVICE_SPECIFIER)?;
+		Ok(Device{
+			alto: alto,
+			spec: spec,
+			dev: dev,
+			owned: true,
+			exts: ext::AlcCache::new(alto.api.head(), dev),
+			pause_rc: Arc::new(AtomicUsize::new(0)),
+			disconnected: AtomicBool::new(false),
+		})
+	}
+
+
+	/// Release ownership of the device without closing it.
+	pub fn into_raw(mut self) -> *mut sys::ALCdevice {
+		self.owned = false;
+		self.dev
+	}
+
+
 	fn make_attrs_vec(&self, attrs: Option<ContextAttrs>) -> AltoResult<Vec<sys::ALCint>> {
+		let attrs = self.alto.config().apply_context_attrs(self, attrs);
 		let mut attrs_vec = Vec::with_capacity(15);
 		if let Some(attrs) = attrs {
+			check_context_attrs(self, &attrs)?;
 			if let Some(freq) = attrs.frequency {
 				attrs_vec.extend(&[sys::ALC_FREQUENCY, freq]);
 			}
@@ -573,10 +1165,34 @@ impl<'a> Device<'a> {
 
 	/// `alcCreateContext()`
 	pub fn new_context<A: Into<Option<ContextAttrs>>>(&self, attrs: A) -> AltoResult<Context> {
-		let attrs_vec = self.make_attrs_vec(attrs.into());
+		let _span = alto_span!("new_context", device = %self.spec.to_string_lossy());
+		if self.is_disconnected() {
+			return Err(device_error(AltoError::AlcCreateContext, AltoError::AlcDeviceDisconnected, &self.spec));
+		}
+		let attrs_vec = self.make_attrs_vec(attrs.into()).map_err(|e| device_error(AltoError::AlcCreateContext, e, &self.spec))?;
+
+		let ctx = unsafe { self.alto.api.head().alcCreateContext()(self.dev, attrs_ptr(&attrs_vec)) };
+		self.alto.get_error(self.dev).map_err(|e| device_error(AltoError::AlcCreateContext, e, &self.spec))?;
+
+		if ctx == ptr::null_mut() {
+			Err(device_error(AltoError::AlcCreateContext, AltoError::AlcInvalidContext, &self.spec))
+		} else {
+			Ok(unsafe { Context::new(self, &self.alto.api, ctx) })
+		}
+	}
+
+
+	/// Open a replacement for this device once it has been disconnected, such as when a headset is unplugged.
+	/// The same device is tried first, then the default output. Contexts have to be created again on the
+	/// new device, and registered buffers can be carried over with `Context::restore_buffers()`.
+	pub fn reopen(&self) -> AltoResult<Device<'a>> {
+		self.alto.open_exact(self.spec.clone()).or_else(|_| self.alto.open(None))
+	}
 
-		let ctx = unsafe { self.alto.api.head().alcCreateContext()(self.dev, attrs_vec.map(|a| a.as_slice().as_ptr()).unwrap_or(ptr::null())) };
-		self.alto.get_error(self.dev).map(|_| unsafe { Context::new(self, &self.alto.api, ctx) })
+
+	/// Start building a context with attributes that are validated against this device.
+	pub fn context_builder<'d>(&'d self) -> ContextBuilder<'a, 'd> {
+		ContextBuilder{dev: self, attrs: ContextAttrs::default()}
 	}
 
 
@@ -591,8 +1207,8 @@ impl<'a> Device<'a> {
 	/// Requires `ALC_SOFT_HRTF`
 	pub fn soft_reset<A: Into<Option<ContextAttrs>>>(&self, attrs: A) -> AltoResult<()> {
 		let ards = self.exts.ALC_SOFT_HRTF()?.alcResetDeviceSOFT?;
-		let attrs_vec = self.make_attrs_vec(attrs.into());
-		unsafe { ards(self.dev, attrs_vec.map(|a| a.as_slice().as_ptr()).unwrap_or(ptr::null())) };
+		let attrs_vec = self.make_attrs_vec(attrs.into())?;
+		unsafe { ards(self.dev, attrs_ptr(&attrs_vec)) };
 		self.alto.get_error(self.dev)
 	}
 }
@@ -618,13 +1234,36 @@ unsafe impl<'a> DeviceTrait for Device<'a> {
 	}
 
 
+	fn missing_symbols(&self, ext: ext::Alc) -> Option<ext::MissingSymbols> {
+		let missing = match ext {
+			ext::Alc::Dedicated => self.exts.ALC_EXT_DEDICATED().ok().map(|e| e.missing_symbols()),
+			ext::Alc::Disconnect => self.exts.ALC_EXT_DISCONNECT().ok().map(|e| e.missing_symbols()),
+			ext::Alc::Efx => self.exts.ALC_EXT_EFX().ok().map(|e| e.missing_symbols()),
+			ext::Alc::SoftHrtf => self.exts.ALC_SOFT_HRTF().ok().map(|e| e.missing_symbols()),
+			ext::Alc::SoftPauseDevice => self.exts.ALC_SOFT_pause_device().ok().map(|e| e.missing_symbols()),
+		};
+		missing.filter(|m| !m.is_empty())
+	}
+
+
 	fn extensions(&self) -> &ext::AlcCache { &self.exts }
 
 
 	fn connected(&self) -> AltoResult<bool> {
 		let mut value = 0;
 		unsafe { self.alto.api.head().alcGetIntegerv()(self.dev, self.exts.ALC_EXT_DISCONNECT()?.ALC_CONNECTED?, 1, &mut value); }
-		self.alto.get_error(self.dev).map(|_| value == sys::ALC_TRUE as sys::ALCint)
+		self.alto.get_error(self.dev).map_err(|e| e.at("alcGetIntegerv", ObjectKind::Device, Some("ALC_CONNECTED".into())))?;
+		if value != sys::ALC_TRUE as sys::ALCint {
+			self.set_disconnected();
+		}
+		Ok(value == sys::ALC_TRUE as sys::ALCint)
+	}
+	#[inline]
+	fn is_disconnected(&self) -> bool { self.disconnected.load(Ordering::Acquire) }
+	fn set_disconnected(&self) {
+		if !self.disconnected.swap(true, Ordering::AcqRel) {
+			alto_event!(warn, device = %self.spec.to_string_lossy(), "device disconnected");
+		}
 	}
 
 
@@ -633,13 +1272,13 @@ unsafe impl<'a> DeviceTrait for Device<'a> {
 
 		let mut value = 0;
 		unsafe { self.alto.api.head().alcGetIntegerv()(self.dev, ash.ALC_NUM_HRTF_SPECIFIERS_SOFT?, 1, &mut value); }
-		self.alto.get_error(self.dev)?;
+		self.alto.get_error(self.dev).map_err(|e| e.at("alcGetIntegerv", ObjectKind::Device, Some("ALC_NUM_HRTF_SPECIFIERS_SOFT".into())))?;
 
 		let mut spec_vec = Vec::new();
 		for i in 0 .. value {
 			unsafe {
-				let spec = ash.alcGetStringiSOFT?(self.dev, ash.ALC_HRTF_SPECIFIER_SOFT?, i) as *mut _;
-				spec_vec.push(self.alto.get_error(self.dev).map(|_| CString::from_raw(spec))?);
+				let spec = ash.alcGetStringiSOFT?(self.dev, ash.ALC_HRTF_SPECIFIER_SOFT?, i);
+				spec_vec.push(self.alto.get_error(self.dev).map(|_| CStr::from_ptr(spec).to_owned())?);
 			}
 		}
 		Ok(spec_vec)
@@ -651,7 +1290,7 @@ unsafe impl<'a> DeviceTrait for Device<'a> {
 
 		let mut value = 0;
 		unsafe { self.alto.api.head().alcGetIntegerv()(self.dev, ash.ALC_HRTF_STATUS_SOFT?, 1, &mut value); }
-		self.alto.get_error(self.dev).and_then(|_| match value {
+		self.alto.get_error(self.dev).map_err(|e| e.at("alcGetIntegerv", ObjectKind::Device, Some("ALC_HRTF_STATUS_SOFT".into()))).and_then(|_| match value {
 			s if s == ash.ALC_HRTF_DISABLED_SOFT? => Ok(SoftHrtfStatus::Disabled),
 			s if s == ash.ALC_HRTF_ENABLED_SOFT? => Ok(SoftHrtfStatus::Enabled),
 			s if s == ash.ALC_HRTF_DENIED_SOFT? => Ok(SoftHrtfStatus::Denied),
@@ -663,10 +1302,16 @@ unsafe impl<'a> DeviceTrait for Device<'a> {
 	}
 
 
+	fn soft_hrtf_specifier(&self) -> AltoResult<CString> {
+		let ash = self.exts.ALC_SOFT_HRTF()?;
+		self.alto.device_specifier(self.dev, ash.ALC_HRTF_SPECIFIER_SOFT?)
+	}
+
+
 	fn max_auxiliary_sends(&self) -> AltoResult<sys::ALCint> {
 		let mut value = 0;
 		unsafe { self.alto.api.head().alcGetIntegerv()(self.dev, self.exts.ALC_EXT_EFX()?.ALC_MAX_AUXILIARY_SENDS?, 1, &mut value); }
-		self.alto.get_error(self.dev).map(|_| value)
+		self.alto.get_error(self.dev).map_err(|e| e.at("alcGetIntegerv", ObjectKind::Device, Some("ALC_MAX_AUXILIARY_SENDS".into()))).map(|_| value)
 	}
 }
 
@@ -681,6 +1326,7 @@ impl<'a> Eq for Device<'a> { }
 
 impl<'a> Drop for Device<'a> {
 	fn drop(&mut self) {
+		if !self.owned { return; }
 		unsafe { self.alto.api.head().alcCloseDevice()(self.dev); }
 		if let Err(_) = self.alto.get_error(self.dev) {
 			let _ = writeln!(io::stderr(), "ALTO ERROR: `alcCloseDevice` failed in Device drop");
@@ -693,6 +1339,78 @@ unsafe impl<'a> Send for Device<'a> { }
 unsafe impl<'a> Sync for Device<'a> { }
 
 
+impl<'a: 'd, 'd> ContextBuilder<'a, 'd> {
+	/// `ALC_FREQUENCY`: output frequency in Hz. Must be from 8000 to 192000.
+	pub fn frequency(mut self, freq: sys::ALCint) -> ContextBuilder<'a, 'd> {
+		self.attrs.frequency = Some(freq);
+		self
+	}
+
+
+	/// `ALC_REFRESH`: mixing updates per second. Must be positive.
+	pub fn refresh(mut self, refresh: sys::ALCint) -> ContextBuilder<'a, 'd> {
+		self.attrs.refresh = Some(refresh);
+		self
+	}
+
+
+	/// `ALC_MONO_SOURCES`: number of mono sources to reserve.
+	pub fn mono_sources(mut self, mono: sys::ALCint) -> ContextBuilder<'a, 'd> {
+		self.attrs.mono_sources = Some(mono);
+		self
+	}
+
+
+	/// `ALC_STEREO_SOURCES`: number of stereo sources to reserve.
+	pub fn stereo_sources(mut self, stereo: sys::ALCint) -> ContextBuilder<'a, 'd> {
+		self.attrs.stereo_sources = Some(stereo);
+		self
+	}
+
+
+	/// `ALC_HRTF_SOFT`
+	/// Requires `ALC_SOFT_HRTF`
+	pub fn hrtf(mut self, hrtf: bool) -> ContextBuilder<'a, 'd> {
+		self.attrs.soft_hrtf = Some(hrtf);
+		self
+	}
+
+
+	/// `ALC_HRTF_ID_SOFT`: index into `DeviceTrait::enumerate_soft_hrtfs()`.
+	/// Requires `ALC_SOFT_HRTF`
+	pub fn hrtf_id(mut self, id: sys::ALCint) -> ContextBuilder<'a, 'd> {
+		self.attrs.soft_hrtf_id = Some(id);
+		self
+	}
+
+
+	/// `ALC_MAX_AUXILIARY_SENDS`
+	/// Requires `ALC_EXT_EFX`
+	pub fn max_auxiliary_sends(mut self, max_sends: sys::ALCint) -> ContextBuilder<'a, 'd> {
+		self.attrs.max_auxiliary_sends = Some(max_sends);
+		self
+	}
+
+
+	/// The attributes set so far, without validation.
+	#[inline]
+	pub fn attrs(&self) -> ContextAttrs { self.attrs }
+
+
+	/// Check every attribute against the device, returning the attributes that would be used.
+	pub fn validate(&self) -> AltoResult<ContextAttrs> {
+		check_context_attrs(self.dev, &self.attrs).map(|_| self.attrs)
+	}
+
+
+	/// Validate the attributes and create the context.
+	pub fn build(self) -> AltoResult<Context<'d>> {
+		let attrs = self.validate()?;
+		self.dev.new_context(attrs)
+	}
+}
+
+
 impl<'a: 'd, 'd> SoftPauseLock<'a, 'd> {
 	fn new(dev: &'d Device<'a>) -> AltoResult<SoftPauseLock<'a, 'd>> {
 		let adps = dev.exts.ALC_SOFT_pause_device()?.alcDevicePauseSOFT?;
@@ -731,8 +1449,45 @@ impl<'a: 'd, 'd> Drop for SoftPauseLock<'a, 'd> {
 }
 
 
+#[cfg(feature = "loopback")]
 impl<'a, F: LoopbackFrame> LoopbackDevice<'a, F> {
+	/// Take ownership of a loopback device opened outside of alto.
+	/// The device will be closed when dropped.
+	///
+	/// # Safety
+	/// `dev` must be a valid loopback device opened from the same implementation as `alto`, and must not be closed elsewhere.
+	/// Any context created on it must render in the format described by `F`.
+	pub unsafe fn from_raw(alto: &'a Alto, dev: *mut sys::ALCdevice) -> AltoResult<LoopbackDevice<'a, F>> {
+		let spec = alto.device_specifier(dev, sys::ALC_DEVICE_SPECIFIER)?;
+		Ok(LoopbackDevice{
+			alto: alto,
+			spec: spec,
+			dev: dev,
+			owned: true,
+			exts: ext::AlcCache::new(alto.api.head(), dev),
+			marker: PhantomData
+		})
+	}
+
+
+	/// Release ownership of the device without closing it.
+	pub fn into_raw(mut self) -> *mut sys::ALCdevice {
+		self.owned = false;
+		self.dev
+	}
+
+
 	fn make_attrs_vec<A: Into<Option<LoopbackAttrs>>>(&self, freq: sys::ALCint, attrs: A) -> AltoResult<Vec<sys::ALCint>> {
+		let attrs = self.alto.config().apply_loopback_attrs(self, attrs.into());
+		check_context_attrs(self, &ContextAttrs{
+			frequency: Some(freq),
+			refresh: None,
+			mono_sources: attrs.and_then(|a| a.mono_sources),
+			stereo_sources: attrs.and_then(|a| a.stereo_sources),
+			soft_hrtf: attrs.and_then(|a| a.soft_hrtf),
+			soft_hrtf_id: attrs.and_then(|a| a.soft_hrtf_id),
+			max_auxiliary_sends: attrs.and_then(|a| a.max_auxiliary_sends),
+		})?;
 		self.alto.api.rent(move|exts| {
 			let asl = exts.ALC_SOFT_loopback()?;
 
@@ -740,7 +1495,7 @@ impl<'a, F: LoopbackFrame> LoopbackDevice<'a, F> {
 			attrs_vec.extend(&[sys::ALC_FREQUENCY, freq]);
 			attrs_vec.extend(&[asl.ALC_FORMAT_CHANNELS_SOFT?, F::channels(&asl)?]);
 			attrs_vec.extend(&[asl.ALC_FORMAT_TYPE_SOFT?, F::sample_ty(&asl)?]);
-			if let Some(attrs) = attrs.into() {
+			if let Some(attrs) = attrs {
 				if let Some(mono) = attrs.mono_sources {
 					attrs_vec.extend(&[sys::ALC_MONO_SOURCES, mono]);
 				}
@@ -771,22 +1526,64 @@ impl<'a, F: LoopbackFrame> LoopbackDevice<'a, F> {
 
 	/// `alcCreateContext()`
 	pub fn new_context<A: Into<Option<LoopbackAttrs>>>(&self, freq: sys::ALCint, attrs: A) -> AltoResult<Context> {
-		let attrs_vec = self.make_attrs_vec(freq, attrs.into())?;
-		let ctx = unsafe { self.alto.api.head().alcCreateContext()(self.dev, attrs_vec.as_slice().as_ptr()) };
-		self.alto.get_error(self.dev).map(|_| unsafe { Context::new(self, &self.alto.api, ctx) })
+		let _span = alto_span!("new_context", device = %self.spec.to_string_lossy(), freq = freq);
+		let attrs_vec = self.make_attrs_vec(freq, attrs.into()).map_err(|e| device_error(AltoError::AlcCreateContext, e, &self.spec))?;
+		let ctx = unsafe { self.alto.api.head().alcCreateContext()(self.dev, attrs_ptr(&attrs_vec)) };
+		self.alto.get_error(self.dev).map_err(|e| device_error(AltoError::AlcCreateContext, e, &self.spec))?;
+
+		if ctx == ptr::null_mut() {
+			Err(device_error(AltoError::AlcCreateContext, AltoError::AlcInvalidContext, &self.spec))
+		} else {
+			Ok(unsafe { Context::new(self, &self.alto.api, ctx) })
+		}
 	}
 
 
 	/// `alcRenderSamplesSOFT()`
+	/// Samples are rendered straight into `data`, without an intermediate buffer.
 	pub fn soft_render_samples<R: AsBufferDataMut<F>>(&mut self, mut data: R) -> AltoResult<()> {
-		let mut data = data.as_buffer_data_mut();
-		if sys::ALCsizei::max_value() as usize / mem::size_of::<F>() < data.len() { return Err(AltoError::AlcInvalidValue) }
+		let data = data.as_buffer_data_mut();
+		unsafe { self.render_raw(data.as_mut_ptr() as *mut sys::ALvoid, data.len()) }
+	}
+
+
+	/// `alcRenderSamplesSOFT()` into interleaved samples, such as the output buffer of an audio API.
+	/// The length of `data` must be a whole number of sample frames, that is a multiple of `channels()`.
+	pub fn render_interleaved(&mut self, data: &mut [F::Sample]) -> AltoResult<()> {
+		let len = mem::size_of_val(data);
+		if len % mem::size_of::<F>() != 0 { return Err(AltoError::AlInvalidRange("data length must be a whole number of sample frames")) }
+
+		unsafe { self.render_raw(data.as_mut_ptr() as *mut sys::ALvoid, len / mem::size_of::<F>()) }
+	}
+
+
+	/// `alcRenderSamplesSOFT()` into raw bytes, such as the input of an encoder or the payload of a network packet.
+	/// The length of `data` must be a multiple of `frame_size()`, but it need not be aligned for `F`.
+	/// Samples are in native byte order.
+	pub fn render_bytes(&mut self, data: &mut [u8]) -> AltoResult<()> {
+		if data.len() % mem::size_of::<F>() != 0 { return Err(AltoError::AlInvalidRange("data length must be a whole number of sample frames")) }
+
+		unsafe { self.render_raw(data.as_mut_ptr() as *mut sys::ALvoid, data.len() / mem::size_of::<F>()) }
+	}
+
+
+	/// Channels in each sample frame rendered by this device.
+	#[inline]
+	pub fn channels(&self) -> usize { F::len() }
+	/// Bytes in each sample frame rendered by this device.
+	#[inline]
+	pub fn frame_size(&self) -> usize { mem::size_of::<F>() }
+
+
+	/// `data` must be valid for writes of `frames` sample frames of `F`.
+	unsafe fn render_raw(&mut self, data: *mut sys::ALvoid, frames: usize) -> AltoResult<()> {
+		if sys::ALCsizei::max_value() as usize / mem::size_of::<F>() < frames { return Err(AltoError::AlcInvalidValue) }
 
 		self.alto.api.rent(move|exts| {
 			let asl = exts.ALC_SOFT_loopback()?;
 
-			unsafe { asl.alcRenderSamplesSOFT?(self.dev, data.as_mut_ptr() as *mut _, data.len() as sys::ALCsizei); }
-			self.alto.get_error(self.dev)
+			asl.alcRenderSamplesSOFT?(self.dev, data, frames as sys::ALCsizei);
+			self.alto.get_error(self.dev).map_err(|e| e.at("alcRenderSamplesSOFT", ObjectKind::Device, None))
 		})
 	}
 
@@ -796,13 +1593,14 @@ impl<'a, F: LoopbackFrame> LoopbackDevice<'a, F> {
 	pub fn soft_reset<A: Into<Option<LoopbackAttrs>>>(&self, freq: sys::ALCint, attrs: A) -> AltoResult<()> {
 		let ards = self.exts.ALC_SOFT_HRTF()?.alcResetDeviceSOFT?;
 
-		let attrs_vec = self.make_attrs_vec(freq, attrs.into());
-		unsafe { ards(self.dev, attrs_vec.map(|a| a.as_slice().as_ptr()).unwrap_or(ptr::null())) };
+		let attrs_vec = self.make_attrs_vec(freq, attrs.into())?;
+		unsafe { ards(self.dev, attrs_ptr(&attrs_vec)) };
 		self.alto.get_error(self.dev)
 	}
 }
 
 
+#[cfg(feature = "loopback")]
 unsafe impl<'a, F: LoopbackFrame> DeviceTrait for LoopbackDevice<'a, F> {
 	#[inline]
 	fn alto(&self) -> &Alto { &self.alto }
@@ -812,6 +1610,11 @@ unsafe impl<'a, F: LoopbackFrame> DeviceTrait for LoopbackDevice<'a, F> {
 	fn as_raw(&self) -> *mut sys::ALCdevice { self.dev }
 	#[inline]
 	fn connected(&self) -> AltoResult<bool> { Ok(true) }
+	/// A loopback device can't be disconnected.
+	#[inline]
+	fn is_disconnected(&self) -> bool { false }
+	#[inline]
+	fn set_disconnected(&self) { }
 
 
 	fn is_extension_present(&self, ext: ext::Alc) -> bool {
@@ -825,6 +1628,18 @@ unsafe impl<'a, F: LoopbackFrame> DeviceTrait for LoopbackDevice<'a, F> {
 	}
 
 
+	fn missing_symbols(&self, ext: ext::Alc) -> Option<ext::MissingSymbols> {
+		let missing = match ext {
+			ext::Alc::Dedicated => self.exts.ALC_EXT_DEDICATED().ok().map(|e| e.missing_symbols()),
+			ext::Alc::Disconnect => self.exts.ALC_EXT_DISCONNECT().ok().map(|e| e.missing_symbols()),
+			ext::Alc::Efx => self.exts.ALC_EXT_EFX().ok().map(|e| e.missing_symbols()),
+			ext::Alc::SoftHrtf => self.exts.ALC_SOFT_HRTF().ok().map(|e| e.missing_symbols()),
+			ext::Alc::SoftPauseDevice => self.exts.ALC_SOFT_pause_device().ok().map(|e| e.missing_symbols()),
+		};
+		missing.filter(|m| !m.is_empty())
+	}
+
+
 	fn extensions(&self) -> &ext::AlcCache { &self.exts }
 
 
@@ -833,13 +1648,13 @@ unsafe impl<'a, F: LoopbackFrame> DeviceTrait for LoopbackDevice<'a, F> {
 
 		let mut value = 0;
 		unsafe { self.alto.api.head().alcGetIntegerv()(self.dev, ash.ALC_NUM_HRTF_SPECIFIERS_SOFT?, 1, &mut value); }
-		self.alto.get_error(self.dev)?;
+		self.alto.get_error(self.dev).map_err(|e| e.at("alcGetIntegerv", ObjectKind::Device, Some("ALC_NUM_HRTF_SPECIFIERS_SOFT".into())))?;
 
 		let mut spec_vec = Vec::new();
 		for i in 0 .. value {
 			unsafe {
-				let spec = ash.alcGetStringiSOFT?(self.dev, ash.ALC_HRTF_SPECIFIER_SOFT?, i) as *mut _;
-				spec_vec.push(self.alto.get_error(self.dev).map(|_| CString::from_raw(spec))?);
+				let spec = ash.alcGetStringiSOFT?(self.dev, ash.ALC_HRTF_SPECIFIER_SOFT?, i);
+				spec_vec.push(self.alto.get_error(self.dev).map(|_| CStr::from_ptr(spec).to_owned())?);
 			}
 		}
 		Ok(spec_vec)
@@ -851,7 +1666,7 @@ unsafe impl<'a, F: LoopbackFrame> DeviceTrait for LoopbackDevice<'a, F> {
 
 		let mut value = 0;
 		unsafe { self.alto.api.head().alcGetIntegerv()(self.dev, ash.ALC_HRTF_STATUS_SOFT?, 1, &mut value); }
-		self.alto.get_error(self.dev).and_then(|_| match value {
+		self.alto.get_error(self.dev).map_err(|e| e.at("alcGetIntegerv", ObjectKind::Device, Some("ALC_HRTF_STATUS_SOFT".into()))).and_then(|_| match value {
 			s if s == ash.ALC_HRTF_DISABLED_SOFT? => Ok(SoftHrtfStatus::Disabled),
 			s if s == ash.ALC_HRTF_ENABLED_SOFT? => Ok(SoftHrtfStatus::Enabled),
 			s if s == ash.ALC_HRTF_DENIED_SOFT? => Ok(SoftHrtfStatus::Denied),
@@ -863,24 +1678,34 @@ unsafe impl<'a, F: LoopbackFrame> DeviceTrait for LoopbackDevice<'a, F> {
 	}
 
 
+	fn soft_hrtf_specifier(&self) -> AltoResult<CString> {
+		let ash = self.exts.ALC_SOFT_HRTF()?;
+		self.alto.device_specifier(self.dev, ash.ALC_HRTF_SPECIFIER_SOFT?)
+	}
+
+
 	fn max_auxiliary_sends(&self) -> AltoResult<sys::ALCint> {
 		let mut value = 0;
 		unsafe { self.alto.api.head().alcGetIntegerv()(self.dev, self.exts.ALC_EXT_EFX()?.ALC_MAX_AUXILIARY_SENDS?, 1, &mut value); }
-		self.alto.get_error(self.dev).map(|_| value)
+		self.alto.get_error(self.dev).map_err(|e| e.at("alcGetIntegerv", ObjectKind::Device, Some("ALC_MAX_AUXILIARY_SENDS".into()))).map(|_| value)
 	}
 }
 
 
+#[cfg(feature = "loopback")]
 impl<'a, F: LoopbackFrame> PartialEq for LoopbackDevice<'a, F> {
 	fn eq(&self, other: &LoopbackDevice<'a, F>) -> bool {
 		self.dev == other.dev
 	}
 }
+#[cfg(feature = "loopback")]
 impl<'a, F: LoopbackFrame> Eq for LoopbackDevice<'a, F> { }
 
 
+#[cfg(feature = "loopback")]
 impl<'a, F: LoopbackFrame> Drop for LoopbackDevice<'a, F> {
 	fn drop(&mut self) {
+		if !self.owned { return; }
 		unsafe { self.alto.api.head().alcCloseDevice()(self.dev); }
 		if let Err(_) = self.alto.get_error(self.dev) {
 			let _ = writeln!(io::stderr(), "ALTO ERROR: `alcCloseDevice` failed in LoopbackDevice drop");
@@ -889,10 +1714,13 @@ impl<'a, F: LoopbackFrame> Drop for LoopbackDevice<'a, F> {
 }
 
 
+#[cfg(feature = "loopback")]
 unsafe impl<'a, F: LoopbackFrame> Send for LoopbackDevice<'a, F> { }
+#[cfg(feature = "loopback")]
 unsafe impl<'a, F: LoopbackFrame> Sync for LoopbackDevice<'a, F> { }
 
 
+#[cfg(feature = "capture")]
 impl<'a, F: StandardFrame> CaptureDevice<'a, F> {
 	/// Alto struct from which this device was opened.
 	#[inline]
@@ -905,17 +1733,34 @@ impl<'a, F: StandardFrame> CaptureDevice<'a, F> {
 	pub fn as_raw(&self) -> *mut sys::ALCdevice { self.dev }
 
 
+	/// Take ownership of a capture device opened outside of alto.
+	///
+	/// # Safety
+	/// `dev` must be a valid capture device opened from the same implementation as `alto`,
+	/// in the format described by `F`.
+	pub unsafe fn from_raw(alto: &'a Alto, dev: *mut sys::ALCdevice) -> AltoResult<CaptureDevice<'a, F>> {
+		let spec = alto.device_specifier(dev, sys::ALC_CAPTURE_DEVICE_SPECIFIER)?;
+		Ok(CaptureDevice{alto: alto, spec: spec, dev: dev, marker: PhantomData})
+	}
+
+
+	/// Release ownership of the device.
+	pub fn into_raw(self) -> *mut sys::ALCdevice {
+		self.dev
+	}
+
+
 	/// `alcCaptureStart()`
 	pub fn start(&mut self) -> AltoResult<()> {
 		unsafe { self.alto.api.head().alcCaptureStart()(self.dev); }
-		self.alto.get_error(self.dev)
+		self.alto.get_error(self.dev).map_err(|e| e.at("alcCaptureStart", ObjectKind::Device, None))
 	}
 
 
 	/// `alcCaptureStop()`
 	pub fn stop(&mut self) -> AltoResult<()> {
 		unsafe { self.alto.api.head().alcCaptureStop()(self.dev); }
-		self.alto.get_error(self.dev)
+		self.alto.get_error(self.dev).map_err(|e| e.at("alcCaptureStop", ObjectKind::Device, None))
 	}
 
 
@@ -923,7 +1768,7 @@ impl<'a, F: StandardFrame> CaptureDevice<'a, F> {
 	pub fn samples_len(&self) -> AltoResult<sys::ALCint> {
 		let mut samples = 0;
 		unsafe { self.alto.api.head().alcGetIntegerv()(self.dev, sys::ALC_CAPTURE_SAMPLES, 1, &mut samples); }
-		self.alto.get_error(self.dev).map(|_| samples)
+		self.alto.get_error(self.dev).map_err(|e| e.at("alcGetIntegerv", ObjectKind::Device, Some("ALC_CAPTURE_SAMPLES".into()))).map(|_| samples)
 	}
 
 
@@ -933,17 +1778,54 @@ impl<'a, F: StandardFrame> CaptureDevice<'a, F> {
 		if data.len() > self.samples_len()? as usize { return Err(AltoError::AlcInvalidValue) }
 
 		unsafe { self.alto.api.head().alcCaptureSamples()(self.dev, data.as_mut_ptr() as *mut _, data.len() as sys::ALCsizei); }
-		self.alto.get_error(self.dev)
+		self.alto.get_error(self.dev).map_err(|e| e.at("alcCaptureSamples", ObjectKind::Device, None))
+	}
+	/// `alcCaptureSamples()` into uninitialized memory, skipping the zeroing that `capture_samples()` would need.
+	/// Returns the same memory, now filled.
+	pub fn capture_samples_uninit<'b>(&mut self, data: &'b mut [mem::MaybeUninit<F>]) -> AltoResult<&'b mut [F]> {
+		if data.len() > self.samples_len()? as usize { return Err(AltoError::AlcInvalidValue) }
+
+		unsafe { self.alto.api.head().alcCaptureSamples()(self.dev, data.as_mut_ptr() as *mut _, data.len() as sys::ALCsizei); }
+		self.alto.get_error(self.dev).map_err(|e| e.at("alcCaptureSamples", ObjectKind::Device, None))?;
+		Ok(unsafe { slice::from_raw_parts_mut(data.as_mut_ptr() as *mut F, data.len()) })
+	}
+
+
+	/// Read every sample frame available into a new `Vec`, without zeroing it first.
+	pub fn capture_available(&mut self) -> AltoResult<Vec<F>> {
+		let len = self.samples_len()? as usize;
+		let mut frames = Vec::with_capacity(len);
+		self.capture_samples_uninit(&mut frames.spare_capacity_mut()[..len])?;
+		unsafe { frames.set_len(len); }
+		Ok(frames)
+	}
+
+
+	/// Report an `AudioEvent::CaptureWatermark` from `Context::poll_events()` on any context of the same `Alto`
+	/// once at least `frames` sample frames are available to read, or stop reporting with `None`.
+	pub fn set_watermark(&self, frames: Option<usize>) {
+		self.alto.set_capture_watermark(self.dev, &self.spec, frames);
+	}
+}
+
+
+#[cfg(feature = "capture")]
+impl<'a, F: StandardFrame> Drop for CaptureDevice<'a, F> {
+	fn drop(&mut self) {
+		self.alto.set_capture_watermark(self.dev, &self.spec, None);
 	}
 }
 
 
+#[cfg(feature = "capture")]
 impl<'a, F: StandardFrame> PartialEq for CaptureDevice<'a, F> {
 	fn eq(&self, other: &CaptureDevice<'a, F>) -> bool {
 		self.dev == other.dev
 	}
 }
+#[cfg(feature = "capture")]
 impl<'a, F: StandardFrame> Eq for CaptureDevice<'a, F> { }
 
 
+#[cfg(feature = "capture")]
 unsafe impl<'a, F: StandardFrame> Send for CaptureDevice<'a, F> { }
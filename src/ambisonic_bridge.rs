@@ -0,0 +1,136 @@
+//! Adapter for using alto as the output backend of higher-level spatial audio crates such as `ambisonic`.
+//! Such crates mix their sources into a first-order B-format sound field, which alto can then decode
+//! to the listener's speaker layout or, with HRTF enabled, to binaural headphone output.
+//! A [`BFormatStream`](struct.BFormatStream.html) accepts sound field frames as they are produced,
+//! and [`render_binaural()`](fn.render_binaural.html) pulls mixed output from a loopback device for
+//! engines that drive their own audio callback.
+
+
+use std::ffi::CStr;
+
+use ::{AltoError, AltoResult};
+use sys;
+use al::*;
+use alc::*;
+
+
+/// Plays a B-format sound field pushed by a spatial audio engine through a streaming source.
+/// Requires `AL_EXT_BFORMAT` and `AL_EXT_float32`
+pub struct BFormatStream<'d: 'c, 'c> {
+	ctx: &'c Context<'d>,
+	src: StreamingSource<'d, 'c>,
+	spare: Vec<Buffer<'d, 'c>>,
+	block: Vec<BFormat3D<f32>>,
+	freq: sys::ALint,
+	period: usize,
+	periods: usize,
+}
+
+
+impl<'d: 'c, 'c> BFormatStream<'d, 'c> {
+	/// Create a stream at `freq` frames per second that keeps up to `periods` buffers of `period` frames queued.
+	/// The source is listener-relative, so the sound field only turns with the listener if it is rotated with `set_rotation()`.
+	pub fn new(ctx: &'c Context<'d>, freq: sys::ALint, period: usize, periods: usize) -> AltoResult<BFormatStream<'d, 'c>> {
+		ctx.extensions().AL_EXT_BFORMAT()?;
+		if period == 0 || periods == 0 { return Err(AltoError::AlInvalidValue) }
+
+		let mut src = ctx.new_streaming_source()?;
+		src.set_relative(true)?;
+
+		Ok(BFormatStream{
+			ctx: ctx,
+			src: src,
+			spare: Vec::with_capacity(periods),
+			block: Vec::with_capacity(period),
+			freq: freq,
+			period: period,
+			periods: periods,
+		})
+	}
+
+
+	/// The underlying streaming source.
+	#[inline]
+	pub fn source(&self) -> &StreamingSource<'d, 'c> { &self.src }
+	#[inline]
+	pub fn source_mut(&mut self) -> &mut StreamingSource<'d, 'c> { &mut self.src }
+
+
+	/// Reclaim buffers the source has finished with, and return how many more frames `push()` will accept.
+	pub fn free_frames(&mut self) -> AltoResult<usize> {
+		self.spare.extend(self.src.unqueue_processed()?);
+
+		let queued = self.src.buffers_queued()? as usize;
+		Ok(self.periods.saturating_sub(queued) * self.period - self.block.len())
+	}
+
+
+	/// Queue as many frames as there is room for, returning how many were taken from `frames`.
+	/// Playback is started, or restarted after an underrun, once a full period has been queued.
+	pub fn push<I: IntoIterator<Item = BFormat3D<f32>>>(&mut self, frames: I) -> AltoResult<usize> {
+		let mut pushed = 0;
+		let mut batch = Vec::new();
+		for frame in frames.into_iter().take(self.free_frames()?) {
+			self.block.push(frame);
+			pushed += 1;
+
+			if self.block.len() == self.period {
+				let mut buf = match self.spare.pop() {
+					Some(buf) => buf,
+					None => self.ctx.new_buffer()?,
+				};
+				buf.set_data(&self.block[..], self.freq)?;
+				batch.push(buf);
+				self.block.clear();
+			}
+		}
+		self.src.queue_buffers(batch).map_err(|(e, _)| e)?;
+
+		if self.src.buffers_queued()? > 0 && self.src.state()? != SourceState::Playing {
+			self.src.play()?;
+		}
+		Ok(pushed)
+	}
+
+
+	/// Rotate the sound field, given `forward` and `up` vectors relative to the listener.
+	pub fn set_rotation<V: Into<[f32; 3]>>(&mut self, forward: V, up: V) -> AltoResult<()> {
+		self.src.set_orientation((forward, up))
+	}
+}
+
+
+/// Find the index of an HRTF by name, for use as `soft_hrtf_id` in `ContextAttrs` or `LoopbackAttrs`.
+/// With no name, the implementation's default HRTF is used and `None` is returned.
+/// Requires `ALC_SOFT_HRTF`
+pub fn find_hrtf<D: DeviceTrait + ?Sized>(dev: &D, name: Option<&CStr>) -> AltoResult<Option<sys::ALCint>> {
+	let hrtfs = dev.enumerate_soft_hrtfs()?;
+	match name {
+		Some(name) => hrtfs.iter()
+			.position(|h| h.as_c_str() == name)
+			.map(|i| Some(i as sys::ALCint))
+			.ok_or(AltoError::AlcInvalidValue),
+		None => Ok(None),
+	}
+}
+
+
+/// Whether the device is currently rendering through an HRTF.
+/// Requires `ALC_SOFT_HRTF`
+pub fn hrtf_active<D: DeviceTrait + ?Sized>(dev: &D) -> AltoResult<bool> {
+	match dev.soft_hrtf_status()? {
+		SoftHrtfStatus::Enabled | SoftHrtfStatus::Required | SoftHrtfStatus::HeadphonesDetected => Ok(true),
+		_ => Ok(false),
+	}
+}
+
+
+#[cfg(feature = "loopback")]
+/// Render the next `frames` frames of a loopback device's mix as interleaved stereo.
+/// Open the device's context with `soft_hrtf: Some(true)` in its `LoopbackAttrs` for binaural output.
+/// Requires `ALC_SOFT_loopback`
+pub fn render_binaural(dev: &mut LoopbackDevice<Stereo<f32>>, frames: usize) -> AltoResult<Vec<Stereo<f32>>> {
+	let mut out = vec![Stereo{left: 0.0, right: 0.0}; frames];
+	dev.soft_render_samples(&mut out[..])?;
+	Ok(out)
+}
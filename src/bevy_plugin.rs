@@ -0,0 +1,302 @@
+//! Bevy integration, as an alternative to the engine's built-in mixer.
+//! Adding [`AltoPlugin`](struct.AltoPlugin.html) opens an OpenAL device and registers an asset loader
+//! for audio files. Entities with an [`AltoSource`](struct.AltoSource.html) play a sound from their
+//! position in the world, heard from the entity marked with [`AltoListener`](struct.AltoListener.html).
+//! The [`AltoAudio`](struct.AltoAudio.html) resource exposes the underlying context for EFX and
+//! other direct use of the API.
+//!
+//! The OpenAL device and context live for the rest of the program once the plugin is built.
+//! If the device can't be opened, sounds still load but the playback systems don't run.
+
+
+use std::io::{self, Cursor, Write};
+use std::ffi::CString;
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Poll;
+use bevy::prelude::*;
+use bevy::asset::{Asset, AssetLoader, LoadContext, UntypedAssetId, VisitAssetDependencies};
+use bevy::asset::io::Reader;
+use bevy::ecs::component::{Component, StorageType};
+use bevy::reflect::TypePath;
+use bevy::tasks::futures_lite::{future, AsyncRead};
+use bevy::transform::TransformSystem;
+use bevy::utils::ConditionalSendFuture;
+
+use ::AltoResult;
+use al::*;
+use alc::*;
+use decode::SymphoniaDecoder;
+
+
+/// Opens an OpenAL device when added to an app, and keeps alto sources in sync with the world.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct AltoPlugin {
+	/// Output device to open, or the default device if `None`.
+	pub device: Option<CString>,
+	/// Attributes for the context, such as enabling HRTF or raising `max_auxiliary_sends` for EFX.
+	pub attrs: ContextAttrs,
+}
+
+
+/// The OpenAL context used for playback.
+pub struct AltoAudio {
+	ctx: &'static Context<'static>,
+}
+
+
+/// Encoded audio, decoded into an OpenAL buffer the first time it is played.
+/// Any format supported by `SymphoniaDecoder` can be loaded.
+pub struct AltoSound {
+	bytes: Arc<[u8]>,
+}
+
+
+/// Asset loader for `AltoSound`.
+#[derive(Copy, Clone, Default, Debug)]
+pub struct AltoSoundLoader;
+
+
+/// Plays a sound from the entity's `GlobalTransform`.
+#[derive(Clone, Debug)]
+pub struct AltoSource {
+	pub sound: Handle<AltoSound>,
+	pub looping: bool,
+	pub gain: f32,
+	pub pitch: f32,
+}
+
+
+/// The OpenAL source playing an `AltoSource`, inserted once its sound has loaded.
+pub struct AltoVoice(StaticSource<'static, 'static>);
+
+
+/// Marks the entity whose `GlobalTransform` positions the listener.
+#[derive(Copy, Clone, Default, Debug)]
+pub struct AltoListener;
+
+
+/// Decoded buffers, shared between every source playing the same sound.
+#[derive(Default)]
+struct AltoBuffers(HashMap<AssetId<AltoSound>, Arc<Buffer<'static, 'static>>>);
+
+
+impl Plugin for AltoPlugin {
+	fn build(&self, app: &mut App) {
+		// Sounds stay loadable without a device, so that handles in scenes and components still resolve.
+		app.init_resource::<AltoBuffers>()
+			.init_asset::<AltoSound>()
+			.register_asset_loader(AltoSoundLoader)
+			.add_systems(PostUpdate, (
+				invalidate_buffers,
+				start_sources,
+				update_sources,
+				sync_sources,
+				sync_listener,
+			).chain().after(TransformSystem::TransformPropagate).run_if(resource_exists::<AltoAudio>));
+
+		match AltoAudio::open(self.device.clone(), self.attrs) {
+			Ok(audio) => { app.insert_resource(audio); },
+			Err(e) => {
+				let _ = writeln!(io::stderr(), "ALTO ERROR: Failed to open audio device for AltoPlugin: {}", e);
+			},
+		}
+	}
+}
+
+
+impl AltoAudio {
+	/// Load the default implementation and open a context on `device`.
+	/// The implementation, device, and context are leaked so that sources can be stored in components.
+	pub fn open(device: Option<CString>, attrs: ContextAttrs) -> AltoResult<AltoAudio> {
+		let alto: &'static Alto = Box::leak(Box::new(Alto::load_default()?));
+		let dev: &'static Device<'static> = Box::leak(Box::new(alto.open(device.as_ref().map(|d| d.as_c_str()))?));
+		let ctx: &'static Context<'static> = Box::leak(Box::new(dev.new_context(attrs)?));
+		Ok(AltoAudio{ctx: ctx})
+	}
+
+
+	#[inline]
+	pub fn context(&self) -> &'static Context<'static> { self.ctx }
+}
+
+
+impl Resource for AltoAudio { }
+
+
+impl AltoSound {
+	/// Wrap encoded audio that has already been loaded into memory.
+	pub fn from_bytes<B: Into<Arc<[u8]>>>(bytes: B) -> AltoSound {
+		AltoSound{bytes: bytes.into()}
+	}
+
+
+	/// Decode the entire sound into a new buffer.
+	pub fn decode<'d: 'c, 'c>(&self, ctx: &'c Context<'d>) -> AltoResult<Buffer<'d, 'c>> {
+		let mut dec = SymphoniaDecoder::new(Cursor::new(self.bytes.clone()), None)?;
+		Buffer::from_decoder(ctx, &mut dec)
+	}
+}
+
+
+impl TypePath for AltoSound {
+	fn type_path() -> &'static str { "alto::bevy_plugin::AltoSound" }
+	fn short_type_path() -> &'static str { "AltoSound" }
+}
+impl VisitAssetDependencies for AltoSound {
+	fn visit_dependencies(&self, _visit: &mut impl FnMut(UntypedAssetId)) { }
+}
+impl Asset for AltoSound { }
+
+
+impl AssetLoader for AltoSoundLoader {
+	type Asset = AltoSound;
+	type Settings = ();
+	type Error = io::Error;
+
+
+	fn load<'a>(&'a self, reader: &'a mut Reader<'_>, _settings: &'a (), _ctx: &'a mut LoadContext<'_>) -> impl ConditionalSendFuture<Output = Result<AltoSound, io::Error>> {
+		let mut bytes = Vec::new();
+		future::poll_fn(move |cx| {
+			let mut chunk = [0; 8192];
+			loop {
+				match Pin::new(&mut *reader).poll_read(cx, &mut chunk) {
+					Poll::Ready(Ok(0)) => return Poll::Ready(Ok(AltoSound::from_bytes(::std::mem::replace(&mut bytes, Vec::new())))),
+					Poll::Ready(Ok(len)) => bytes.extend_from_slice(&chunk[.. len]),
+					Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+					Poll::Pending => return Poll::Pending,
+				}
+			}
+		})
+	}
+
+
+	fn extensions(&self) -> &[&str] {
+		&["wav", "ogg", "mp3", "flac", "m4a", "aac"]
+	}
+}
+
+
+impl AltoSource {
+	pub fn new(sound: Handle<AltoSound>) -> AltoSource {
+		AltoSource{sound: sound, looping: false, gain: 1.0, pitch: 1.0}
+	}
+}
+
+
+impl Component for AltoSource {
+	const STORAGE_TYPE: StorageType = StorageType::Table;
+}
+
+
+impl AltoVoice {
+	/// The source, for use with EFX sends and filters or other properties not managed by the plugin.
+	#[inline]
+	pub fn source(&self) -> &StaticSource<'static, 'static> { &self.0 }
+	#[inline]
+	pub fn source_mut(&mut self) -> &mut StaticSource<'static, 'static> { &mut self.0 }
+}
+
+
+impl Component for AltoVoice {
+	const STORAGE_TYPE: StorageType = StorageType::Table;
+}
+
+
+impl Component for AltoListener {
+	const STORAGE_TYPE: StorageType = StorageType::SparseSet;
+}
+
+
+impl Resource for AltoBuffers { }
+
+
+fn report(res: AltoResult<()>, what: &str) {
+	if let Err(e) = res {
+		let _ = writeln!(io::stderr(), "ALTO ERROR: {} failed in AltoPlugin: {}", what, e);
+	}
+}
+
+
+/// Drop cached buffers for sounds that were reloaded or unloaded. Sources already playing keep their buffer alive.
+fn invalidate_buffers(mut events: EventReader<AssetEvent<AltoSound>>, mut bufs: ResMut<AltoBuffers>) {
+	for event in events.read() {
+		match *event {
+			AssetEvent::Modified{id} | AssetEvent::Removed{id} => { bufs.0.remove(&id); },
+			_ => (),
+		}
+	}
+}
+
+
+fn start_sources(
+	mut commands: Commands,
+	audio: Res<AltoAudio>,
+	sounds: Res<Assets<AltoSound>>,
+	mut bufs: ResMut<AltoBuffers>,
+	query: Query<(Entity, &AltoSource, Option<&GlobalTransform>), Without<AltoVoice>>,
+) {
+	for (entity, source, transform) in query.iter() {
+		let id = source.sound.id();
+		let buf = match bufs.0.get(&id) {
+			Some(buf) => buf.clone(),
+			None => {
+				let sound = match sounds.get(id) {
+					Some(sound) => sound,
+					None => continue,
+				};
+				let buf = match sound.decode(audio.ctx) {
+					Ok(buf) => Arc::new(buf),
+					Err(e) => {
+						let _ = writeln!(io::stderr(), "ALTO ERROR: Failed to decode sound in AltoPlugin: {}", e);
+						commands.entity(entity).remove::<AltoSource>();
+						continue;
+					},
+				};
+				bufs.0.insert(id, buf.clone());
+				buf
+			},
+		};
+
+		let res = audio.ctx.new_static_source().and_then(|mut src| {
+			src.set_buffer(buf)?;
+			src.set_looping(source.looping)?;
+			src.set_gain(source.gain)?;
+			src.set_pitch(source.pitch)?;
+			if let Some(transform) = transform {
+				src.set_position(transform.translation().to_array())?;
+			}
+			src.play()?;
+			Ok(src)
+		});
+		match res {
+			Ok(src) => { commands.entity(entity).insert(AltoVoice(src)); },
+			Err(e) => report(Err(e), "Starting a source"),
+		}
+	}
+}
+
+
+fn update_sources(mut query: Query<(&AltoSource, &mut AltoVoice), Changed<AltoSource>>) {
+	for (source, mut voice) in query.iter_mut() {
+		report(voice.0.set_looping(source.looping), "`alSourcei(AL_LOOPING)`");
+		report(voice.0.set_gain(source.gain), "`alSourcef(AL_GAIN)`");
+		report(voice.0.set_pitch(source.pitch), "`alSourcef(AL_PITCH)`");
+	}
+}
+
+
+fn sync_sources(mut query: Query<(&GlobalTransform, &mut AltoVoice), Changed<GlobalTransform>>) {
+	for (transform, mut voice) in query.iter_mut() {
+		report(voice.0.set_position(transform.translation().to_array()), "`alSourcefv(AL_POSITION)`");
+	}
+}
+
+
+fn sync_listener(audio: Res<AltoAudio>, query: Query<&GlobalTransform, (With<AltoListener>, Changed<GlobalTransform>)>) {
+	if let Some(transform) = query.iter().next() {
+		report(audio.ctx.set_position(transform.translation().to_array()), "`alListenerfv(AL_POSITION)`");
+		report(audio.ctx.set_orientation((transform.forward().to_array(), transform.up().to_array())), "`alListenerfv(AL_ORIENTATION)`");
+	}
+}
@@ -0,0 +1,202 @@
+use std::env;
+use std::ffi::CString;
+
+use sys;
+use alc::*;
+
+
+/// HRTF selection from `ALTO_HRTF`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum ConfigHrtf {
+	Disabled,
+	Enabled,
+	/// Enable the HRTF with this name, as reported by `DeviceTrait::enumerate_soft_hrtfs()`.
+	Named(CString),
+}
+
+
+/// Overrides applied when devices and contexts are created, taking precedence over what the application requested.
+/// `Alto` reads these from the environment when it is loaded, so behavior can be changed on a user's
+/// machine without rebuilding. Settings the implementation doesn't support are ignored.
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct AltoConfig {
+	/// `ALTO_DEVICE`: specifier of the output device to open.
+	pub device: Option<CString>,
+	/// `ALTO_CAPTURE_DEVICE`: specifier of the capture device to open.
+	pub capture_device: Option<CString>,
+	/// `ALTO_HRTF`: `0`/`off`/`false`, `1`/`on`/`true`, or the name of an HRTF.
+	/// Requires `ALC_SOFT_HRTF`
+	pub hrtf: Option<ConfigHrtf>,
+	/// `ALTO_FREQUENCY`: output frequency of new contexts on real devices.
+	pub frequency: Option<sys::ALCint>,
+	/// `ALTO_DISABLE_EFX`: if `1`/`on`/`true`, contexts are created with no auxiliary sends so no effects are heard.
+	pub disable_efx: bool,
+}
+
+
+fn env_string(var: &str) -> Option<String> {
+	env::var(var).ok().map(|v| v.trim().to_owned()).and_then(|v| if v.is_empty() { None } else { Some(v) })
+}
+
+
+fn env_cstring(var: &str) -> Option<CString> {
+	env_string(var).and_then(|v| match CString::new(v) {
+		Ok(v) => Some(v),
+		Err(_) => {
+			alto_event!(warn, var = var, "ignoring environment variable containing a NUL byte");
+			None
+		},
+	})
+}
+
+
+fn parse_flag(value: &str) -> Option<bool> {
+	match &*value.to_lowercase() {
+		"1" | "on" | "true" | "yes" => Some(true),
+		"0" | "off" | "false" | "no" => Some(false),
+		_ => None,
+	}
+}
+
+
+impl AltoConfig {
+	/// Read every `ALTO_*` variable. Unset, empty, or malformed variables leave the setting alone.
+	pub fn from_env() -> AltoConfig {
+		let hrtf = env_string("ALTO_HRTF").and_then(|v| match parse_flag(&v) {
+			Some(true) => Some(ConfigHrtf::Enabled),
+			Some(false) => Some(ConfigHrtf::Disabled),
+			None => CString::new(v).ok().map(ConfigHrtf::Named),
+		});
+
+		let frequency = env_string("ALTO_FREQUENCY").and_then(|v| match v.parse() {
+			Ok(freq) if freq > 0 => Some(freq),
+			_ => {
+				alto_event!(warn, value = %v, "ignoring invalid ALTO_FREQUENCY");
+				None
+			},
+		});
+
+		AltoConfig{
+			device: env_cstring("ALTO_DEVICE"),
+			capture_device: env_cstring("ALTO_CAPTURE_DEVICE"),
+			hrtf: hrtf,
+			frequency: frequency,
+			disable_efx: env_string("ALTO_DISABLE_EFX").and_then(|v| parse_flag(&v)).unwrap_or(false),
+		}
+	}
+
+
+	/// Whether no settings are overridden.
+	pub fn is_empty(&self) -> bool {
+		*self == AltoConfig::default()
+	}
+
+
+	/// HRTF settings as `(soft_hrtf, soft_hrtf_id)`, or `None` if HRTF is not overridden.
+	/// A name the device doesn't know falls back to the default HRTF.
+	fn hrtf_attrs<D: DeviceTrait + ?Sized>(&self, dev: &D) -> Option<(bool, Option<sys::ALCint>)> {
+		match self.hrtf {
+			Some(ConfigHrtf::Disabled) => Some((false, None)),
+			Some(ConfigHrtf::Enabled) => Some((true, None)),
+			Some(ConfigHrtf::Named(ref name)) => {
+				let id = dev.enumerate_soft_hrtfs().ok().and_then(|hrtfs| hrtfs.iter().position(|h| h == name));
+				if id.is_none() {
+					alto_event!(warn, hrtf = %name.to_string_lossy(), "HRTF requested by ALTO_HRTF not found");
+				}
+				Some((true, id.map(|i| i as sys::ALCint)))
+			},
+			None => None,
+		}
+	}
+
+
+	/// Apply the overrides to attributes for a context on `dev`.
+	pub fn apply_context_attrs<D: DeviceTrait + ?Sized>(&self, dev: &D, attrs: Option<ContextAttrs>) -> Option<ContextAttrs> {
+		if self.frequency.is_none() && self.hrtf.is_none() && !self.disable_efx { return attrs }
+
+		let mut attrs = attrs.unwrap_or_default();
+		if let Some(freq) = self.frequency {
+			attrs.frequency = Some(freq);
+		}
+		if let Some((hrtf, id)) = self.hrtf_attrs(dev) {
+			attrs.soft_hrtf = Some(hrtf);
+			attrs.soft_hrtf_id = id;
+		}
+		if self.disable_efx {
+			attrs.max_auxiliary_sends = Some(0);
+		}
+		Some(attrs)
+	}
+
+
+	#[cfg(feature = "loopback")]
+	/// Apply the overrides to attributes for a context on a loopback device.
+	/// The frequency of a loopback context is always chosen by the application.
+	pub fn apply_loopback_attrs<D: DeviceTrait + ?Sized>(&self, dev: &D, attrs: Option<LoopbackAttrs>) -> Option<LoopbackAttrs> {
+		if self.hrtf.is_none() && !self.disable_efx { return attrs }
+
+		let mut attrs = attrs.unwrap_or_default();
+		if let Some((hrtf, id)) = self.hrtf_attrs(dev) {
+			attrs.soft_hrtf = Some(hrtf);
+			attrs.soft_hrtf_id = id;
+		}
+		if self.disable_efx {
+			attrs.max_auxiliary_sends = Some(0);
+		}
+		Some(attrs)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+
+	#[test]
+	fn flags() {
+		for v in &["1", "on", "TRUE", "Yes"] {
+			assert_eq!(parse_flag(v), Some(true), "{}", v);
+		}
+		for v in &["0", "Off", "false", "NO"] {
+			assert_eq!(parse_flag(v), Some(false), "{}", v);
+		}
+		for v in &["", "2", "enabled", " on"] {
+			assert_eq!(parse_flag(v), None, "{}", v);
+		}
+	}
+
+
+	/// Every variable is set and checked in this one test, since the environment is shared by the whole process.
+	#[test]
+	fn from_env() {
+		const VARS: &[&str] = &["ALTO_DEVICE", "ALTO_CAPTURE_DEVICE", "ALTO_HRTF", "ALTO_FREQUENCY", "ALTO_DISABLE_EFX"];
+		let set = |values: &[&str]| for (var, value) in VARS.iter().zip(values) { env::set_var(var, value) };
+
+		set(&["", "  ", "", "", ""]);
+		assert!(AltoConfig::from_env().is_empty());
+
+		set(&[" Speakers ", "Mic", "off", "48000", "on"]);
+		assert_eq!(AltoConfig::from_env(), AltoConfig{
+			device: Some(CString::new("Speakers").unwrap()),
+			capture_device: Some(CString::new("Mic").unwrap()),
+			hrtf: Some(ConfigHrtf::Disabled),
+			frequency: Some(48000),
+			disable_efx: true,
+		});
+
+		set(&["", "", "Default HRTF", "-1", "maybe"]);
+		let config = AltoConfig::from_env();
+		assert_eq!(config.hrtf, Some(ConfigHrtf::Named(CString::new("Default HRTF").unwrap())));
+		assert_eq!((config.frequency, config.disable_efx), (None, false));
+
+		set(&["", "", "1", "fast", "0"]);
+		let config = AltoConfig::from_env();
+		assert_eq!((config.hrtf, config.frequency), (Some(ConfigHrtf::Enabled), None));
+
+		for var in VARS {
+			env::remove_var(var);
+		}
+		assert!(AltoConfig::from_env().is_empty());
+	}
+}
@@ -0,0 +1,625 @@
+//! Conversion between sample types, between planar and interleaved layouts, and between channel layouts.
+//! With the `simd` feature these use SSE2 on x86_64, which produces the same results as the scalar
+//! loops used elsewhere. Slices of mismatched lengths fail with `AlInvalidValue`.
+//!
+//! Float samples are in the range [-1.0, 1.0]. Out-of-range floats are clamped, and NaN becomes -1.0.
+
+
+use std::any::TypeId;
+
+use ::{AltoError, AltoResult};
+
+
+/// Convert float samples to signed 16-bit.
+pub fn f32_to_i16(src: &[f32], dst: &mut [i16]) -> AltoResult<()> {
+	if src.len() != dst.len() { return Err(AltoError::AlInvalidValue) }
+
+	let done = simd::f32_to_i16(src, dst);
+	for (d, &s) in dst[done..].iter_mut().zip(&src[done..]) {
+		*d = (s.max(-1.0).min(1.0) * 32767.0) as i16;
+	}
+	Ok(())
+}
+
+
+/// Convert signed 16-bit samples to float.
+pub fn i16_to_f32(src: &[i16], dst: &mut [f32]) -> AltoResult<()> {
+	if src.len() != dst.len() { return Err(AltoError::AlInvalidValue) }
+
+	let done = simd::i16_to_f32(src, dst);
+	for (d, &s) in dst[done..].iter_mut().zip(&src[done..]) {
+		*d = s as f32 / 32768.0;
+	}
+	Ok(())
+}
+
+
+/// Convert float samples to unsigned 8-bit, where silence is 128.
+pub fn f32_to_u8(src: &[f32], dst: &mut [u8]) -> AltoResult<()> {
+	if src.len() != dst.len() { return Err(AltoError::AlInvalidValue) }
+
+	let done = simd::f32_to_u8(src, dst);
+	for (d, &s) in dst[done..].iter_mut().zip(&src[done..]) {
+		*d = (s.max(-1.0).min(1.0) * 127.0 + 128.0) as u8;
+	}
+	Ok(())
+}
+
+
+/// Convert unsigned 8-bit samples to float.
+pub fn u8_to_f32(src: &[u8], dst: &mut [f32]) -> AltoResult<()> {
+	if src.len() != dst.len() { return Err(AltoError::AlInvalidValue) }
+
+	let done = simd::u8_to_f32(src, dst);
+	for (d, &s) in dst[done..].iter_mut().zip(&src[done..]) {
+		*d = (s as f32 - 128.0) / 128.0;
+	}
+	Ok(())
+}
+
+
+/// Convert signed 16-bit samples to unsigned 8-bit, keeping the high byte.
+pub fn i16_to_u8(src: &[i16], dst: &mut [u8]) -> AltoResult<()> {
+	if src.len() != dst.len() { return Err(AltoError::AlInvalidValue) }
+
+	let done = simd::i16_to_u8(src, dst);
+	for (d, &s) in dst[done..].iter_mut().zip(&src[done..]) {
+		*d = ((s >> 8) + 128) as u8;
+	}
+	Ok(())
+}
+
+
+/// Convert unsigned 8-bit samples to signed 16-bit.
+pub fn u8_to_i16(src: &[u8], dst: &mut [i16]) -> AltoResult<()> {
+	if src.len() != dst.len() { return Err(AltoError::AlInvalidValue) }
+
+	let done = simd::u8_to_i16(src, dst);
+	for (d, &s) in dst[done..].iter_mut().zip(&src[done..]) {
+		*d = (s as i16 - 128) << 8;
+	}
+	Ok(())
+}
+
+
+/// Interleave one slice per channel into `dst`, which must hold every sample of every channel.
+/// Stereo `f32`, `i32`, `u32`, `i16`, and `u16` samples take the vectorized path.
+pub fn interleave<T: Copy + 'static>(channels: &[&[T]], dst: &mut [T]) -> AltoResult<()> {
+	let frames = channels.first().map_or(0, |c| c.len());
+	if channels.iter().any(|c| c.len() != frames) || dst.len() != frames * channels.len() {
+		return Err(AltoError::AlInvalidValue);
+	}
+
+	let done = if channels.len() == 2 {
+		unsafe { simd::interleave_stereo(channels[0].as_ptr() as *const u8, channels[1].as_ptr() as *const u8, dst.as_mut_ptr() as *mut u8, frames, lane_width::<T>()) }
+	} else {
+		0
+	};
+	let n = channels.len();
+	for f in done..frames {
+		for (c, chan) in channels.iter().enumerate() {
+			dst[f * n + c] = chan[f];
+		}
+	}
+	Ok(())
+}
+
+
+/// Split interleaved samples into one slice per channel, each of which must hold every frame of `src`.
+/// Stereo `f32`, `i32`, `u32`, `i16`, and `u16` samples take the vectorized path.
+pub fn deinterleave<T: Copy + 'static>(src: &[T], channels: &mut [&mut [T]]) -> AltoResult<()> {
+	let n = channels.len();
+	if n == 0 || src.len() % n != 0 || channels.iter().any(|c| c.len() != src.len() / n) {
+		return Err(AltoError::AlInvalidValue);
+	}
+	let frames = src.len() / n;
+
+	let done = if n == 2 {
+		let (left, right) = channels.split_at_mut(1);
+		unsafe { simd::deinterleave_stereo(src.as_ptr() as *const u8, left[0].as_mut_ptr() as *mut u8, right[0].as_mut_ptr() as *mut u8, frames, lane_width::<T>()) }
+	} else {
+		0
+	};
+	for f in done..frames {
+		for (c, chan) in channels.iter_mut().enumerate() {
+			chan[f] = src[f * n + c];
+		}
+	}
+	Ok(())
+}
+
+
+/// The speaker layout of interleaved frames, with channels in the order of the matching frame type.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum ChannelLayout {
+	/// `Mono`
+	Mono,
+	/// `Stereo`
+	Stereo,
+	/// `McQuad`
+	Quad,
+	/// `Mc51Chn`
+	Mc51,
+	/// `Mc61Chn`
+	Mc61,
+	/// `Mc71Chn`
+	Mc71,
+}
+
+
+/// The role of each channel, used to derive remix gains between layouts.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Speaker { FrontLeft, FrontRight, FrontCenter, LowFreq, BackLeft, BackRight, BackCenter, SideLeft, SideRight }
+
+
+/// -3 dB, the gain at which a channel is split between two speakers or folded into a front one.
+const FOLD: f32 = ::std::f32::consts::FRAC_1_SQRT_2;
+
+
+impl ChannelLayout {
+	/// The layout with `channels` channels, if there is one.
+	pub fn from_channels(channels: usize) -> Option<ChannelLayout> {
+		match channels {
+			1 => Some(ChannelLayout::Mono),
+			2 => Some(ChannelLayout::Stereo),
+			4 => Some(ChannelLayout::Quad),
+			6 => Some(ChannelLayout::Mc51),
+			7 => Some(ChannelLayout::Mc61),
+			8 => Some(ChannelLayout::Mc71),
+			_ => None,
+		}
+	}
+
+
+	/// Number of channels in each frame.
+	#[inline]
+	pub fn channels(self) -> usize { self.speakers().len() }
+
+
+	fn speakers(self) -> &'static [Speaker] {
+		use self::Speaker::*;
+		match self {
+			ChannelLayout::Mono => &[FrontCenter],
+			ChannelLayout::Stereo => &[FrontLeft, FrontRight],
+			ChannelLayout::Quad => &[FrontLeft, FrontRight, BackLeft, BackRight],
+			ChannelLayout::Mc51 => &[FrontLeft, FrontRight, FrontCenter, LowFreq, BackLeft, BackRight],
+			ChannelLayout::Mc61 => &[FrontLeft, FrontRight, FrontCenter, LowFreq, BackLeft, BackRight, BackCenter],
+			ChannelLayout::Mc71 => &[FrontLeft, FrontRight, FrontCenter, LowFreq, BackLeft, BackRight, SideLeft, SideRight],
+		}
+	}
+
+
+	/// Add the gains that carry `speaker` into this layout onto `row`.
+	fn route(self, speaker: Speaker, gain: f32, row: &mut [f32; 8]) {
+		use self::Speaker::*;
+		if let Some(i) = self.speakers().iter().position(|&s| s == speaker) {
+			row[i] += gain;
+			return;
+		}
+		match speaker {
+			FrontLeft | FrontRight => self.route(FrontCenter, gain * 0.5, row),
+			FrontCenter => {
+				self.route(FrontLeft, gain * FOLD, row);
+				self.route(FrontRight, gain * FOLD, row);
+			},
+			LowFreq => (),
+			BackLeft if self.speakers().contains(&SideLeft) => self.route(SideLeft, gain, row),
+			BackRight if self.speakers().contains(&SideRight) => self.route(SideRight, gain, row),
+			SideLeft if self.speakers().contains(&BackLeft) => self.route(BackLeft, gain, row),
+			SideRight if self.speakers().contains(&BackRight) => self.route(BackRight, gain, row),
+			BackLeft | SideLeft => self.route(FrontLeft, gain * FOLD, row),
+			BackRight | SideRight => self.route(FrontRight, gain * FOLD, row),
+			BackCenter => {
+				self.route(BackLeft, gain * FOLD, row);
+				self.route(BackRight, gain * FOLD, row);
+			},
+		}
+	}
+}
+
+
+/// Remix interleaved float frames from one channel layout to another. `dst` must hold as many frames as `src`.
+///
+/// Channels missing from `to` are folded into the nearest ones at -3 dB, following ITU-R BS.775 for downmixes
+/// to stereo, and the LFE channel is dropped. Mono is spread over the front speakers at -3 dB each. Upmixes
+/// leave speakers without a source silent. Downmixes are not normalized, so loud passages can exceed [-1.0, 1.0].
+///
+/// Stereo to mono, mono to stereo, and 5.1 and 7.1 to stereo have dedicated kernels, vectorized with the `simd` feature.
+pub fn remix(src: &[f32], from: ChannelLayout, dst: &mut [f32], to: ChannelLayout) -> AltoResult<()> {
+	let (n, m) = (from.channels(), to.channels());
+	if src.len() % n != 0 || dst.len() != src.len() / n * m { return Err(AltoError::AlInvalidValue) }
+	let frames = src.len() / n;
+
+	match (from, to) {
+		_ if from == to => dst.copy_from_slice(src),
+		(ChannelLayout::Stereo, ChannelLayout::Mono) => {
+			let done = simd::stereo_to_mono(src, dst);
+			for f in done..frames {
+				dst[f] = (src[f * 2] + src[f * 2 + 1]) * 0.5;
+			}
+		},
+		(ChannelLayout::Mono, ChannelLayout::Stereo) => {
+			let done = simd::mono_to_stereo(src, dst);
+			for f in done..frames {
+				let s = src[f] * FOLD;
+				dst[f * 2] = s;
+				dst[f * 2 + 1] = s;
+			}
+		},
+		(ChannelLayout::Mc51, ChannelLayout::Stereo) => {
+			let done = simd::mc51_to_stereo(src, dst);
+			for f in done..frames {
+				let x = &src[f * 6 .. f * 6 + 6];
+				dst[f * 2] = x[0] + FOLD * (x[2] + x[4]);
+				dst[f * 2 + 1] = x[1] + FOLD * (x[2] + x[5]);
+			}
+		},
+		(ChannelLayout::Mc71, ChannelLayout::Stereo) => {
+			let done = simd::mc71_to_stereo(src, dst);
+			for f in done..frames {
+				let x = &src[f * 8 .. f * 8 + 8];
+				dst[f * 2] = x[0] + FOLD * (x[2] + (x[4] + x[6]));
+				dst[f * 2 + 1] = x[1] + FOLD * (x[2] + (x[5] + x[7]));
+			}
+		},
+		_ => {
+			let mut matrix = [[0.0; 8]; 8];
+			for (i, &speaker) in from.speakers().iter().enumerate() {
+				let mut row = [0.0; 8];
+				to.route(speaker, 1.0, &mut row);
+				for o in 0..m {
+					matrix[o][i] = row[o];
+				}
+			}
+			for (x, y) in src.chunks(n).zip(dst.chunks_mut(m)) {
+				for (o, y) in y.iter_mut().enumerate() {
+					*y = x.iter().zip(&matrix[o][..n]).map(|(&x, &g)| x * g).sum();
+				}
+			}
+		},
+	}
+	Ok(())
+}
+
+
+/// Size of `T` if it is a plain sample type that can be moved as raw lanes, or 0 otherwise.
+fn lane_width<T: 'static>() -> usize {
+	let ty = TypeId::of::<T>();
+	if ty == TypeId::of::<f32>() || ty == TypeId::of::<i32>() || ty == TypeId::of::<u32>() {
+		4
+	} else if ty == TypeId::of::<i16>() || ty == TypeId::of::<u16>() {
+		2
+	} else {
+		0
+	}
+}
+
+
+/// Each routine converts as many leading samples as it can, returning how many, and leaves the rest to the scalar loop.
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod simd {
+	use std::arch::x86_64::*;
+
+
+	pub fn f32_to_i16(src: &[f32], dst: &mut [i16]) -> usize {
+		let n = src.len() / 8 * 8;
+		unsafe {
+			let (lo, hi, scale) = (_mm_set1_ps(-1.0), _mm_set1_ps(1.0), _mm_set1_ps(32767.0));
+			// `_mm_max_ps` returns its second operand for NaN, matching `f32::max`.
+			let conv = |p: *const f32| _mm_cvttps_epi32(_mm_mul_ps(_mm_min_ps(_mm_max_ps(_mm_loadu_ps(p), lo), hi), scale));
+			for i in (0..n).step_by(8) {
+				let (a, b) = (conv(src.as_ptr().add(i)), conv(src.as_ptr().add(i + 4)));
+				_mm_storeu_si128(dst.as_mut_ptr().add(i) as *mut __m128i, _mm_packs_epi32(a, b));
+			}
+		}
+		n
+	}
+
+
+	pub fn i16_to_f32(src: &[i16], dst: &mut [f32]) -> usize {
+		let n = src.len() / 8 * 8;
+		unsafe {
+			let scale = _mm_set1_ps(1.0 / 32768.0);
+			for i in (0..n).step_by(8) {
+				let x = _mm_loadu_si128(src.as_ptr().add(i) as *const __m128i);
+				let (a, b) = (_mm_srai_epi32(_mm_unpacklo_epi16(x, x), 16), _mm_srai_epi32(_mm_unpackhi_epi16(x, x), 16));
+				_mm_storeu_ps(dst.as_mut_ptr().add(i), _mm_mul_ps(_mm_cvtepi32_ps(a), scale));
+				_mm_storeu_ps(dst.as_mut_ptr().add(i + 4), _mm_mul_ps(_mm_cvtepi32_ps(b), scale));
+			}
+		}
+		n
+	}
+
+
+	pub fn f32_to_u8(src: &[f32], dst: &mut [u8]) -> usize {
+		let n = src.len() / 16 * 16;
+		unsafe {
+			let (lo, hi, scale, bias) = (_mm_set1_ps(-1.0), _mm_set1_ps(1.0), _mm_set1_ps(127.0), _mm_set1_ps(128.0));
+			let conv = |p: *const f32| _mm_cvttps_epi32(_mm_add_ps(_mm_mul_ps(_mm_min_ps(_mm_max_ps(_mm_loadu_ps(p), lo), hi), scale), bias));
+			for i in (0..n).step_by(16) {
+				let p = src.as_ptr().add(i);
+				let a = _mm_packs_epi32(conv(p), conv(p.add(4)));
+				let b = _mm_packs_epi32(conv(p.add(8)), conv(p.add(12)));
+				_mm_storeu_si128(dst.as_mut_ptr().add(i) as *mut __m128i, _mm_packus_epi16(a, b));
+			}
+		}
+		n
+	}
+
+
+	pub fn u8_to_f32(src: &[u8], dst: &mut [f32]) -> usize {
+		let n = src.len() / 16 * 16;
+		unsafe {
+			let (zero, bias, scale) = (_mm_setzero_si128(), _mm_set1_ps(128.0), _mm_set1_ps(1.0 / 128.0));
+			for i in (0..n).step_by(16) {
+				let x = _mm_loadu_si128(src.as_ptr().add(i) as *const __m128i);
+				let (lo, hi) = (_mm_unpacklo_epi8(x, zero), _mm_unpackhi_epi8(x, zero));
+				let words = [_mm_unpacklo_epi16(lo, zero), _mm_unpackhi_epi16(lo, zero), _mm_unpacklo_epi16(hi, zero), _mm_unpackhi_epi16(hi, zero)];
+				for (j, w) in words.iter().enumerate() {
+					_mm_storeu_ps(dst.as_mut_ptr().add(i + j * 4), _mm_mul_ps(_mm_sub_ps(_mm_cvtepi32_ps(*w), bias), scale));
+				}
+			}
+		}
+		n
+	}
+
+
+	pub fn i16_to_u8(src: &[i16], dst: &mut [u8]) -> usize {
+		let n = src.len() / 16 * 16;
+		unsafe {
+			let flip = _mm_set1_epi8(-128);
+			for i in (0..n).step_by(16) {
+				let a = _mm_srai_epi16(_mm_loadu_si128(src.as_ptr().add(i) as *const __m128i), 8);
+				let b = _mm_srai_epi16(_mm_loadu_si128(src.as_ptr().add(i + 8) as *const __m128i), 8);
+				_mm_storeu_si128(dst.as_mut_ptr().add(i) as *mut __m128i, _mm_xor_si128(_mm_packs_epi16(a, b), flip));
+			}
+		}
+		n
+	}
+
+
+	pub fn u8_to_i16(src: &[u8], dst: &mut [i16]) -> usize {
+		let n = src.len() / 16 * 16;
+		unsafe {
+			let (zero, flip) = (_mm_setzero_si128(), _mm_set1_epi8(-128));
+			for i in (0..n).step_by(16) {
+				let x = _mm_xor_si128(_mm_loadu_si128(src.as_ptr().add(i) as *const __m128i), flip);
+				_mm_storeu_si128(dst.as_mut_ptr().add(i) as *mut __m128i, _mm_unpacklo_epi8(zero, x));
+				_mm_storeu_si128(dst.as_mut_ptr().add(i + 8) as *mut __m128i, _mm_unpackhi_epi8(zero, x));
+			}
+		}
+		n
+	}
+
+
+	pub unsafe fn interleave_stereo(left: *const u8, right: *const u8, dst: *mut u8, frames: usize, width: usize) -> usize {
+		let per = match width { 4 => 4, 2 => 8, _ => return 0 };
+		let n = frames / per * per;
+		for i in (0..n).step_by(per) {
+			let l = _mm_loadu_si128(left.add(i * width) as *const __m128i);
+			let r = _mm_loadu_si128(right.add(i * width) as *const __m128i);
+			let (a, b) = if width == 4 {
+				(_mm_unpacklo_epi32(l, r), _mm_unpackhi_epi32(l, r))
+			} else {
+				(_mm_unpacklo_epi16(l, r), _mm_unpackhi_epi16(l, r))
+			};
+			_mm_storeu_si128(dst.add(i * 2 * width) as *mut __m128i, a);
+			_mm_storeu_si128(dst.add(i * 2 * width + 16) as *mut __m128i, b);
+		}
+		n
+	}
+
+
+	pub fn stereo_to_mono(src: &[f32], dst: &mut [f32]) -> usize {
+		let n = dst.len() / 4 * 4;
+		unsafe {
+			let half = _mm_set1_ps(0.5);
+			for i in (0..n).step_by(4) {
+				let (a, b) = (_mm_loadu_ps(src.as_ptr().add(i * 2)), _mm_loadu_ps(src.as_ptr().add(i * 2 + 4)));
+				let (l, r) = (_mm_shuffle_ps(a, b, 0b10_00_10_00), _mm_shuffle_ps(a, b, 0b11_01_11_01));
+				_mm_storeu_ps(dst.as_mut_ptr().add(i), _mm_mul_ps(_mm_add_ps(l, r), half));
+			}
+		}
+		n
+	}
+
+
+	pub fn mono_to_stereo(src: &[f32], dst: &mut [f32]) -> usize {
+		let n = src.len() / 4 * 4;
+		unsafe {
+			let fold = _mm_set1_ps(super::FOLD);
+			for i in (0..n).step_by(4) {
+				let x = _mm_mul_ps(_mm_loadu_ps(src.as_ptr().add(i)), fold);
+				_mm_storeu_ps(dst.as_mut_ptr().add(i * 2), _mm_unpacklo_ps(x, x));
+				_mm_storeu_ps(dst.as_mut_ptr().add(i * 2 + 4), _mm_unpackhi_ps(x, x));
+			}
+		}
+		n
+	}
+
+
+	/// Two frames at a time, one in each half of the registers.
+	pub fn mc51_to_stereo(src: &[f32], dst: &mut [f32]) -> usize {
+		let n = dst.len() / 2 / 2 * 2;
+		unsafe {
+			let fold = _mm_set1_ps(super::FOLD);
+			let pair = |p: *const f32| _mm_castsi128_ps(_mm_loadl_epi64(p as *const __m128i));
+			for i in (0..n).step_by(2) {
+				let p = src.as_ptr().add(i * 6);
+				let (a, b) = (_mm_loadu_ps(p), _mm_loadu_ps(p.add(6)));
+				let front = _mm_movelh_ps(a, b);
+				let center = _mm_shuffle_ps(a, b, 0b10_10_10_10);
+				let back = _mm_movelh_ps(pair(p.add(4)), pair(p.add(10)));
+				_mm_storeu_ps(dst.as_mut_ptr().add(i * 2), _mm_add_ps(front, _mm_mul_ps(fold, _mm_add_ps(center, back))));
+			}
+		}
+		n
+	}
+
+
+	/// Two frames at a time, one in each half of the registers.
+	pub fn mc71_to_stereo(src: &[f32], dst: &mut [f32]) -> usize {
+		let n = dst.len() / 2 / 2 * 2;
+		unsafe {
+			let fold = _mm_set1_ps(super::FOLD);
+			for i in (0..n).step_by(2) {
+				let p = src.as_ptr().add(i * 8);
+				let (a, b) = (_mm_loadu_ps(p), _mm_loadu_ps(p.add(8)));
+				let (c, d) = (_mm_loadu_ps(p.add(4)), _mm_loadu_ps(p.add(12)));
+				let front = _mm_movelh_ps(a, b);
+				let center = _mm_shuffle_ps(a, b, 0b10_10_10_10);
+				let surround = _mm_movelh_ps(_mm_add_ps(c, _mm_movehl_ps(c, c)), _mm_add_ps(d, _mm_movehl_ps(d, d)));
+				_mm_storeu_ps(dst.as_mut_ptr().add(i * 2), _mm_add_ps(front, _mm_mul_ps(fold, _mm_add_ps(center, surround))));
+			}
+		}
+		n
+	}
+
+
+	pub unsafe fn deinterleave_stereo(src: *const u8, left: *mut u8, right: *mut u8, frames: usize, width: usize) -> usize {
+		let per = match width { 4 => 4, 2 => 8, _ => return 0 };
+		let n = frames / per * per;
+		for i in (0..n).step_by(per) {
+			let a = _mm_loadu_si128(src.add(i * 2 * width) as *const __m128i);
+			let b = _mm_loadu_si128(src.add(i * 2 * width + 16) as *const __m128i);
+			let (l, r) = if width == 4 {
+				let (a, b) = (_mm_shuffle_epi32(a, 0b11_01_10_00), _mm_shuffle_epi32(b, 0b11_01_10_00));
+				(_mm_unpacklo_epi64(a, b), _mm_unpackhi_epi64(a, b))
+			} else {
+				let even = |x| _mm_srai_epi32(_mm_slli_epi32(x, 16), 16);
+				(_mm_packs_epi32(even(a), even(b)), _mm_packs_epi32(_mm_srai_epi32(a, 16), _mm_srai_epi32(b, 16)))
+			};
+			_mm_storeu_si128(left.add(i * width) as *mut __m128i, l);
+			_mm_storeu_si128(right.add(i * width) as *mut __m128i, r);
+		}
+		n
+	}
+}
+
+
+#[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+mod simd {
+	pub fn f32_to_i16(_: &[f32], _: &mut [i16]) -> usize { 0 }
+	pub fn i16_to_f32(_: &[i16], _: &mut [f32]) -> usize { 0 }
+	pub fn f32_to_u8(_: &[f32], _: &mut [u8]) -> usize { 0 }
+	pub fn u8_to_f32(_: &[u8], _: &mut [f32]) -> usize { 0 }
+	pub fn i16_to_u8(_: &[i16], _: &mut [u8]) -> usize { 0 }
+	pub fn u8_to_i16(_: &[u8], _: &mut [i16]) -> usize { 0 }
+	pub unsafe fn interleave_stereo(_: *const u8, _: *const u8, _: *mut u8, _: usize, _: usize) -> usize { 0 }
+	pub unsafe fn deinterleave_stereo(_: *const u8, _: *mut u8, _: *mut u8, _: usize, _: usize) -> usize { 0 }
+	pub fn stereo_to_mono(_: &[f32], _: &mut [f32]) -> usize { 0 }
+	pub fn mono_to_stereo(_: &[f32], _: &mut [f32]) -> usize { 0 }
+	pub fn mc51_to_stereo(_: &[f32], _: &mut [f32]) -> usize { 0 }
+	pub fn mc71_to_stereo(_: &[f32], _: &mut [f32]) -> usize { 0 }
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+
+	/// Floats covering the clamped range, values just inside it, and ones the SIMD path must clamp the same way.
+	fn floats(n: usize) -> Vec<f32> {
+		let special = [0.0, -0.0, 1.0, -1.0, 1.5, -1.5, 0.999_99, -0.999_99, ::std::f32::NAN, ::std::f32::INFINITY, ::std::f32::NEG_INFINITY];
+		let mut x = 0x2545_f491u32;
+		(0..n).map(|i| {
+			x ^= x << 13; x ^= x >> 17; x ^= x << 5;
+			if i % 7 == 3 { special[i / 7 % special.len()] } else { (x as f32 / ::std::u32::MAX as f32) * 2.4 - 1.2 }
+		}).collect()
+	}
+
+
+	/// Convert `src` in one call, which vectorizes its leading samples, and one sample at a time, which never does.
+	fn same<S: Copy, D: Copy + Default + PartialEq + ::std::fmt::Debug>(src: &[S], f: fn(&[S], &mut [D]) -> AltoResult<()>) {
+		let mut whole = vec![D::default(); src.len()];
+		f(src, &mut whole).unwrap();
+		for (i, s) in src.iter().enumerate() {
+			let mut one = [D::default()];
+			f(&[*s], &mut one).unwrap();
+			assert_eq!(whole[i], one[0], "sample {} of {}", i, src.len());
+		}
+	}
+
+
+	#[test]
+	fn sample_conversions_match_scalar() {
+		for n in 0..40 {
+			let f = floats(n);
+			let i: Vec<i16> = f.iter().map(|&s| (s * 40000.0) as i16).collect();
+			let u: Vec<u8> = f.iter().map(|&s| (s * 200.0) as i8 as u8).collect();
+			same(&f, f32_to_i16);
+			same(&f, f32_to_u8);
+			same(&i, i16_to_f32);
+			same(&i, i16_to_u8);
+			same(&u, u8_to_f32);
+			same(&u, u8_to_i16);
+		}
+	}
+
+
+	#[test]
+	fn mismatched_lengths() {
+		assert!(f32_to_i16(&[0.0; 4], &mut [0; 3]).unwrap_err().is_invalid_value());
+		assert!(interleave(&[&[0; 2][..], &[0; 3][..]], &mut [0; 5]).unwrap_err().is_invalid_value());
+		assert!(deinterleave(&[0; 5], &mut [&mut [0; 2][..], &mut [0; 2][..]]).unwrap_err().is_invalid_value());
+	}
+
+
+	fn round_trip<T: Copy + Default + PartialEq + ::std::fmt::Debug + 'static>(left: &[T], right: &[T]) {
+		let mut inter = vec![T::default(); left.len() * 2];
+		interleave(&[left, right], &mut inter).unwrap();
+		for f in 0..left.len() {
+			assert_eq!((inter[f * 2], inter[f * 2 + 1]), (left[f], right[f]), "frame {} of {}", f, left.len());
+		}
+
+		let (mut l, mut r) = (vec![T::default(); left.len()], vec![T::default(); left.len()]);
+		deinterleave(&inter, &mut [&mut l[..], &mut r[..]]).unwrap();
+		assert_eq!((&l[..], &r[..]), (left, right));
+	}
+
+
+	#[test]
+	fn stereo_interleaving() {
+		for n in 0..40 {
+			let f = floats(n * 2);
+			let (l, r) = f.split_at(n);
+			let bits = |x: &[f32]| x.iter().map(|s| s.to_bits()).collect::<Vec<_>>();
+			round_trip(&bits(l), &bits(r));
+			round_trip(&bits(l).iter().map(|&s| s as i16).collect::<Vec<_>>(), &bits(r).iter().map(|&s| s as i16).collect::<Vec<_>>());
+			round_trip(&bits(l).iter().map(|&s| s as u16).collect::<Vec<_>>(), &bits(r).iter().map(|&s| s as u16).collect::<Vec<_>>());
+			round_trip(&bits(l).iter().map(|&s| s as u8).collect::<Vec<_>>(), &bits(r).iter().map(|&s| s as u8).collect::<Vec<_>>());
+		}
+	}
+
+
+	#[test]
+	fn remix_kernels_match_scalar() {
+		use self::ChannelLayout::*;
+		for &(from, to) in &[(Stereo, Mono), (Mono, Stereo), (Mc51, Stereo), (Mc71, Stereo)] {
+			let (n, m) = (from.channels(), to.channels());
+			for frames in 0..20 {
+				// Finite samples only: the kernels add in the same order as the scalar loops, but NaN payloads may differ.
+				let src: Vec<f32> = floats(frames * n).into_iter().map(|s| if s.is_finite() { s } else { 0.5 }).collect();
+				let mut whole = vec![0.0; frames * m];
+				remix(&src, from, &mut whole, to).unwrap();
+				for (x, y) in src.chunks(n).zip(whole.chunks(m)) {
+					let mut one = vec![0.0; m];
+					remix(x, from, &mut one, to).unwrap();
+					assert_eq!(y, &one[..], "{:?} to {:?}, {} frames", from, to, frames);
+				}
+			}
+		}
+	}
+
+
+	#[test]
+	fn remix_folds_center() {
+		let mut dst = [0.0; 2];
+		remix(&[0.0, 0.0, 1.0, 0.0, 0.0, 0.0], ChannelLayout::Mc51, &mut dst, ChannelLayout::Stereo).unwrap();
+		assert_eq!(dst, [FOLD, FOLD]);
+		let mut dst = [0.0; 6];
+		remix(&[1.0, 1.0], ChannelLayout::Stereo, &mut dst, ChannelLayout::Mc51).unwrap();
+		assert_eq!(dst, [1.0, 1.0, 0.0, 0.0, 0.0, 0.0]);
+	}
+}
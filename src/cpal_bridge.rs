@@ -0,0 +1,373 @@
+//! Capture bridge backed by `cpal`.
+//! On some platforms OpenAL capture is unreliable even though playback works fine. This module
+//! records through a `cpal` input stream instead and hands the samples to alto in the same shape
+//! as a regular `CaptureDevice`, so they can be uploaded into buffers and queued on streaming sources.
+
+
+use std::ffi::CString;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::io;
+use std::cmp;
+use cpal;
+use cpal::traits::{DeviceTrait as CpalDeviceTrait, HostTrait, StreamTrait};
+
+use ::{AltoError, AltoResult};
+use ::catch_panic;
+use sys;
+use alc::*;
+use al::*;
+use metrics::{Metrics, MetricsSink};
+
+
+/// A standard frame that can be assembled from a group of interleaved `f32` samples.
+pub trait FromCpalFrame: StandardFrame + Send + 'static {
+	/// Build a frame from one group of interleaved samples in the range [-1.0, 1.0].
+	fn from_f32(samples: &[f32]) -> Self;
+}
+
+
+/// A capture device that records through a `cpal` input stream.
+/// Captured frames are kept in a ring of fixed length. If the ring is full,
+/// the oldest frames are discarded.
+pub struct CpalCapture<F: FromCpalFrame> {
+	name: String,
+	freq: u32,
+	len: usize,
+	ring: Arc<Mutex<VecDeque<F>>>,
+	metrics: Arc<Metrics>,
+	/// A panic caught in the input callback, such as from the metrics sink, or an error reported by the stream,
+	/// to be returned by the next read.
+	panicked: Arc<Mutex<Option<AltoError>>>,
+	stream: cpal::Stream,
+}
+
+
+impl FromCpalFrame for Mono<u8> {
+	fn from_f32(samples: &[f32]) -> Mono<u8> {
+		Mono{center: f32_to_u8(mix_down(samples))}
+	}
+}
+impl FromCpalFrame for Mono<i16> {
+	fn from_f32(samples: &[f32]) -> Mono<i16> {
+		Mono{center: f32_to_i16(mix_down(samples))}
+	}
+}
+impl FromCpalFrame for Stereo<u8> {
+	fn from_f32(samples: &[f32]) -> Stereo<u8> {
+		let (left, right) = split_stereo(samples);
+		Stereo{left: f32_to_u8(left), right: f32_to_u8(right)}
+	}
+}
+impl FromCpalFrame for Stereo<i16> {
+	fn from_f32(samples: &[f32]) -> Stereo<i16> {
+		let (left, right) = split_stereo(samples);
+		Stereo{left: f32_to_i16(left), right: f32_to_i16(right)}
+	}
+}
+
+
+fn mix_down(samples: &[f32]) -> f32 {
+	if samples.is_empty() {
+		0.0
+	} else {
+		samples.iter().fold(0.0, |a, s| a + s) / samples.len() as f32
+	}
+}
+
+
+fn split_stereo(samples: &[f32]) -> (f32, f32) {
+	match samples.len() {
+		0 => (0.0, 0.0),
+		1 => (samples[0], samples[0]),
+		_ => (samples[0], samples[1]),
+	}
+}
+
+
+fn f32_to_u8(s: f32) -> u8 {
+	(s.max(-1.0).min(1.0) * 127.0 + 128.0) as u8
+}
+
+
+fn f32_to_i16(s: f32) -> i16 {
+	(s.max(-1.0).min(1.0) * 32767.0) as i16
+}
+
+
+fn cpal_error<E: ::std::fmt::Display>(e: E) -> AltoError {
+	AltoError::Io(io::Error::new(io::ErrorKind::Other, e.to_string()))
+}
+
+
+/// Names of all input devices reported by the default `cpal` host.
+pub fn cpal_input_names() -> AltoResult<Vec<String>> {
+	let host = cpal::default_host();
+	let devs = host.input_devices().map_err(cpal_error)?;
+	Ok(devs.filter_map(|d| d.name().ok()).collect())
+}
+
+
+/// Find the OpenAL capture specifier that most likely refers to the same hardware as a `cpal` device name.
+/// Names are compared case-insensitively with backend prefixes and punctuation removed.
+pub fn match_capture_device(alto: &Alto, cpal_name: &str) -> AltoResult<Option<CString>> {
+	let needle = normalize_name(cpal_name);
+	if needle.is_empty() {
+		return Ok(None);
+	}
+
+	let mut best = None;
+	let mut best_score = 0;
+	for spec in alto.enumerate_captures()? {
+		let hay = normalize_name(&spec.to_string_lossy());
+		let score = if hay == needle {
+			usize::max_value()
+		} else if hay.contains(&needle) || needle.contains(&hay) {
+			cmp::min(hay.len(), needle.len())
+		} else {
+			0
+		};
+
+		if score > best_score {
+			best_score = score;
+			best = Some(spec);
+		}
+	}
+
+	Ok(best)
+}
+
+
+/// Find the `cpal` input device that most likely refers to the same hardware as an OpenAL capture specifier.
+pub fn match_cpal_device(spec: &CString) -> AltoResult<Option<cpal::Device>> {
+	let needle = normalize_name(&spec.to_string_lossy());
+	let host = cpal::default_host();
+
+	let mut best = None;
+	let mut best_score = 0;
+	for dev in host.input_devices().map_err(cpal_error)? {
+		let hay = match dev.name() {
+			Ok(name) => normalize_name(&name),
+			Err(_) => continue,
+		};
+		let score = if hay == needle {
+			usize::max_value()
+		} else if !hay.is_empty() && (hay.contains(&needle) || needle.contains(&hay)) {
+			cmp::min(hay.len(), needle.len())
+		} else {
+			0
+		};
+
+		if score > best_score {
+			best_score = score;
+			best = Some(dev);
+		}
+	}
+
+	Ok(best)
+}
+
+
+fn normalize_name(name: &str) -> String {
+	let name = name.trim();
+	let name = if name.starts_with("OpenAL Soft on ") { &name["OpenAL Soft on ".len() ..] } else { name };
+	name.chars().filter(|c| c.is_alphanumeric()).flat_map(|c| c.to_lowercase()).collect()
+}
+
+
+impl<F: FromCpalFrame> CpalCapture<F> {
+	/// Open a `cpal` input device by name, or the default input device if `None`.
+	/// `len` is the capacity of the capture ring in sample frames, as with `Alto::open_capture()`.
+	pub fn open(name: Option<&str>, freq: sys::ALCuint, len: sys::ALCsizei) -> AltoResult<CpalCapture<F>> {
+		let host = cpal::default_host();
+		let dev = if let Some(name) = name {
+			host.input_devices().map_err(cpal_error)?
+				.find(|d| d.name().map(|n| n == name).unwrap_or(false))
+				.ok_or(AltoError::AlcInvalidDevice)?
+		} else {
+			host.default_input_device().ok_or(AltoError::AlcInvalidDevice)?
+		};
+
+		CpalCapture::from_device(dev, freq, len)
+	}
+
+
+	/// Open the `cpal` input device that corresponds to an OpenAL capture specifier.
+	pub fn open_matching(spec: &CString, freq: sys::ALCuint, len: sys::ALCsizei) -> AltoResult<CpalCapture<F>> {
+		let dev = match_cpal_device(spec)?.ok_or(AltoError::AlcInvalidDevice)?;
+		CpalCapture::from_device(dev, freq, len)
+	}
+
+
+	/// Build a capture stream on an already selected `cpal` device.
+	pub fn from_device(dev: cpal::Device, freq: sys::ALCuint, len: sys::ALCsizei) -> AltoResult<CpalCapture<F>> {
+		if len <= 0 { return Err(AltoError::AlcInvalidValue) }
+
+		let name = dev.name().map_err(cpal_error)?;
+		let default = dev.default_input_config().map_err(cpal_error)?;
+		let config = cpal::StreamConfig{
+			channels: default.channels(),
+			sample_rate: cpal::SampleRate(freq),
+			buffer_size: cpal::BufferSize::Default,
+		};
+
+		let ring = Arc::new(Mutex::new(VecDeque::with_capacity(len as usize)));
+		let metrics = Arc::new(Metrics::default());
+		let panicked = Arc::new(Mutex::new(None));
+		let shared = (ring.clone(), metrics.clone(), panicked.clone());
+		let stream = match default.sample_format() {
+			cpal::SampleFormat::U8 => build_stream::<F, u8>(&dev, &config, shared, len as usize),
+			cpal::SampleFormat::I16 => build_stream::<F, i16>(&dev, &config, shared, len as usize),
+			cpal::SampleFormat::U16 => build_stream::<F, u16>(&dev, &config, shared, len as usize),
+			cpal::SampleFormat::I32 => build_stream::<F, i32>(&dev, &config, shared, len as usize),
+			cpal::SampleFormat::F32 => build_stream::<F, f32>(&dev, &config, shared, len as usize),
+			_ => Err(AltoError::AlcInvalidValue),
+		}?;
+
+		Ok(CpalCapture{
+			name: name,
+			freq: freq,
+			len: len as usize,
+			ring: ring,
+			metrics: metrics,
+			panicked: panicked,
+			stream: stream,
+		})
+	}
+
+
+	/// Name of the underlying `cpal` device.
+	#[inline]
+	pub fn name(&self) -> &str { &self.name }
+	/// Sample rate the stream was opened with.
+	#[inline]
+	pub fn frequency(&self) -> sys::ALCuint { self.freq }
+	/// Capacity of the capture ring in sample frames.
+	#[inline]
+	pub fn capacity(&self) -> usize { self.len }
+
+
+	/// Install a sink to be told when captured frames are discarded because the ring was full.
+	/// It is called from the input callback, and if it panics, the next read fails with `CallbackPanicked`.
+	pub fn set_metrics_sink(&self, sink: Option<Arc<dyn MetricsSink>>) {
+		self.metrics.set_sink(sink);
+	}
+
+
+	/// Start recording into the capture ring.
+	pub fn start(&mut self) -> AltoResult<()> {
+		self.stream.play().map_err(cpal_error)
+	}
+
+
+	/// Stop recording. Frames already captured remain available.
+	pub fn stop(&mut self) -> AltoResult<()> {
+		self.stream.pause().map_err(cpal_error)
+	}
+
+
+	/// Number of captured frames ready to be read.
+	pub fn samples_len(&self) -> AltoResult<sys::ALCint> {
+		self.check_panicked()?;
+		Ok(self.ring.lock().unwrap_or_else(|e| e.into_inner()).len() as sys::ALCint)
+	}
+
+
+	/// Read captured frames into `data`, with the same semantics as `CaptureDevice::capture_samples()`.
+	pub fn capture_samples<R: AsBufferDataMut<F>>(&mut self, mut data: R) -> AltoResult<()> {
+		self.check_panicked()?;
+		let data = data.as_buffer_data_mut();
+		let mut ring = self.ring.lock().unwrap_or_else(|e| e.into_inner());
+		if data.len() > ring.len() { return Err(AltoError::AlcInvalidValue) }
+
+		let len = data.len();
+		for (d, s) in data.iter_mut().zip(ring.drain(.. len)) {
+			*d = s;
+		}
+		Ok(())
+	}
+
+
+	/// Move up to `max_len` captured frames into a buffer, returning how many frames were uploaded.
+	/// Nothing is uploaded if no frames are available.
+	pub fn fill_buffer<'d: 'c, 'c>(&mut self, buf: &mut Buffer<'d, 'c>, max_len: usize) -> AltoResult<usize> where
+		[F]: AsBufferData<F>,
+	{
+		self.check_panicked()?;
+		let frames: Vec<F> = {
+			let mut ring = self.ring.lock().unwrap_or_else(|e| e.into_inner());
+			let len = cmp::min(max_len, ring.len());
+			ring.drain(.. len).collect()
+		};
+
+		if frames.is_empty() {
+			return Ok(0);
+		}
+		buf.set_data(frames.as_slice(), self.freq as sys::ALint)?;
+		Ok(frames.len())
+	}
+
+
+	/// Report a panic caught in the input callback or an error reported by the stream since the last read.
+	fn check_panicked(&self) -> AltoResult<()> {
+		match self.panicked.lock().unwrap_or_else(|e| e.into_inner()).take() {
+			Some(e) => Err(e),
+			None => Ok(()),
+		}
+	}
+}
+
+
+/// The capture ring, metrics, and first caught panic or stream error shared between a `CpalCapture` and its callbacks.
+type Shared<F> = (Arc<Mutex<VecDeque<F>>>, Arc<Metrics>, Arc<Mutex<Option<AltoError>>>);
+
+
+fn build_stream<F, T>(dev: &cpal::Device, config: &cpal::StreamConfig, shared: Shared<F>, len: usize) -> AltoResult<cpal::Stream> where
+	F: FromCpalFrame,
+	T: cpal::SizedSample,
+	f32: cpal::FromSample<T>,
+{
+	let (ring, metrics, panicked) = shared;
+	let failed = panicked.clone();
+	let err_metrics = metrics.clone();
+	let channels = config.channels as usize;
+	let mut scratch = Vec::with_capacity(channels);
+
+	dev.build_input_stream(
+		config,
+		// Backends may call this from native code, so a panic mustn't unwind out of it.
+		move |data: &[T], _: &cpal::InputCallbackInfo| if let Err(e) = catch_panic(|| {
+			let mut dropped = 0;
+			{
+				let mut ring = ring.lock().unwrap_or_else(|e| e.into_inner());
+				for group in data.chunks(channels) {
+					scratch.clear();
+					scratch.extend(group.iter().map(|s| <f32 as cpal::FromSample<T>>::from_sample_(*s)));
+					if ring.len() == len {
+						ring.pop_front();
+						dropped += 1;
+					}
+					ring.push_back(F::from_f32(&scratch));
+				}
+			}
+			if dropped > 0 {
+				metrics.report(|m| m.overrun(dropped));
+			}
+		}) {
+			*panicked.lock().unwrap_or_else(|e| e.into_inner()) = Some(e);
+		},
+		// Only the first error is kept for the next read, since later ones are usually caused by it.
+		move |e| {
+			let e = match e {
+				cpal::StreamError::DeviceNotAvailable => AltoError::AlcDeviceDisconnected,
+				e => cpal_error(e),
+			};
+			let _ = catch_panic(|| err_metrics.report(|m| m.capture_error(&e)));
+			let mut failed = failed.lock().unwrap_or_else(|e| e.into_inner());
+			if failed.is_none() {
+				*failed = Some(e);
+			}
+		},
+		None,
+	).map_err(cpal_error)
+}
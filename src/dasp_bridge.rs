@@ -0,0 +1,134 @@
+//! Interop with the `dasp` digital signal processing crates.
+//! Alto's frame structs can be converted to and from the fixed-size arrays that `dasp` uses as
+//! frames, and any `dasp` signal can be wrapped in a [`SignalDecoder`](struct.SignalDecoder.html)
+//! to be played back through a [`Stream`](../decode/struct.Stream.html).
+
+
+use dasp::{Frame, Sample, Signal};
+use dasp::sample::{FromSample, ToSample};
+
+use ::{AltoError, AltoResult};
+use sys;
+use al::*;
+use decode::{Decoder, DecodedSamples};
+
+
+/// An alto frame that has an equivalent `dasp` frame with the same channel layout.
+pub trait DaspFrame: Copy {
+	/// The corresponding `dasp` frame.
+	type Frame: Frame;
+
+
+	/// Build an alto frame from a `dasp` frame, with channels in OpenAL order.
+	fn from_dasp(frame: Self::Frame) -> Self;
+	/// Convert into a `dasp` frame, with channels in OpenAL order.
+	fn to_dasp(self) -> Self::Frame;
+}
+
+
+macro_rules! impl_dasp_frame {
+	($frame:ident, $len:expr, $($field:ident),+) => {
+		impl<S: Sample> DaspFrame for $frame<S> {
+			type Frame = [S; $len];
+
+
+			#[inline]
+			fn from_dasp(frame: [S; $len]) -> $frame<S> {
+				let mut iter = frame.iter();
+				$frame{$($field: *iter.next().unwrap()),+}
+			}
+
+
+			#[inline]
+			fn to_dasp(self) -> [S; $len] {
+				[$(self.$field),+]
+			}
+		}
+
+
+		impl<S: Sample> From<[S; $len]> for $frame<S> {
+			#[inline]
+			fn from(frame: [S; $len]) -> $frame<S> { $frame::from_dasp(frame) }
+		}
+	};
+}
+
+
+impl_dasp_frame!(Mono, 1, center);
+impl_dasp_frame!(Stereo, 2, left, right);
+impl_dasp_frame!(McRear, 1, rear);
+impl_dasp_frame!(McQuad, 4, front_left, front_right, back_left, back_right);
+impl_dasp_frame!(Mc51Chn, 6, front_left, front_right, front_center, low_freq, back_left, back_right);
+impl_dasp_frame!(Mc61Chn, 7, front_left, front_right, front_center, low_freq, back_left, back_right, back_center);
+impl_dasp_frame!(Mc71Chn, 8, front_left, front_right, front_center, low_freq, back_left, back_right, side_left, side_right);
+impl_dasp_frame!(BFormat2D, 3, w, x, y);
+impl_dasp_frame!(BFormat3D, 4, w, x, y, z);
+
+
+/// Convert a frame to another sample type with the same channel layout, using `dasp`'s sample conversions.
+/// For example, `Stereo<f32>` can be converted to `Stereo<i16>` before uploading to an implementation without `AL_EXT_float32`.
+pub fn convert_frame<A, B>(frame: A) -> B where
+	A: DaspFrame,
+	B: DaspFrame,
+	B::Frame: Frame<NumChannels = <A::Frame as Frame>::NumChannels>,
+	<B::Frame as Frame>::Sample: FromSample<<A::Frame as Frame>::Sample>,
+{
+	B::from_dasp(frame.to_dasp().map(Sample::to_sample))
+}
+
+
+/// Adapts a `dasp` signal into a [`Decoder`](../decode/trait.Decoder.html).
+/// Samples are converted to 16 bit integers, and the signal's channels are assumed to already
+/// be in OpenAL order. Only 1, 2, 4, 6, 7 or 8 channel signals can be uploaded.
+/// Most signals are infinite; the decoder only ends if the signal reports that it is exhausted.
+pub struct SignalDecoder<S: Signal> {
+	signal: S,
+	freq: sys::ALint,
+}
+
+
+impl<S: Signal> SignalDecoder<S> where
+	<S::Frame as Frame>::Sample: ToSample<i16>,
+{
+	/// Wrap a signal running at `freq` frames per second.
+	pub fn new(signal: S, freq: sys::ALint) -> AltoResult<SignalDecoder<S>> {
+		match S::Frame::CHANNELS {
+			1 | 2 | 4 | 6 | 7 | 8 => Ok(SignalDecoder{signal: signal, freq: freq}),
+			_ => Err(AltoError::AlInvalidValue),
+		}
+	}
+
+
+	/// The wrapped signal.
+	#[inline]
+	pub fn signal(&self) -> &S { &self.signal }
+	#[inline]
+	pub fn signal_mut(&mut self) -> &mut S { &mut self.signal }
+	#[inline]
+	pub fn into_inner(self) -> S { self.signal }
+}
+
+
+impl<S: Signal> Decoder for SignalDecoder<S> where
+	<S::Frame as Frame>::Sample: ToSample<i16>,
+{
+	fn channels(&self) -> usize { S::Frame::CHANNELS }
+	fn frequency(&self) -> sys::ALint { self.freq }
+
+
+	fn decode(&mut self, frames: usize) -> AltoResult<Option<DecodedSamples>> {
+		let mut data = Vec::with_capacity(frames * S::Frame::CHANNELS);
+		for _ in 0 .. frames {
+			if self.signal.is_exhausted() {
+				break;
+			}
+			data.extend(self.signal.next().channels().map(Sample::to_sample::<i16>));
+		}
+
+		if data.is_empty() {
+			Ok(None)
+		} else {
+			Ok(Some(DecodedSamples::I16(data)))
+		}
+	}
+}
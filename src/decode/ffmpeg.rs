@@ -0,0 +1,237 @@
+use std::io;
+use std::cmp;
+use std::path::Path;
+use ffmpeg;
+use ffmpeg::{ChannelLayout, Packet, Rational};
+use ffmpeg::codec;
+use ffmpeg::format::{self, Sample};
+use ffmpeg::format::sample::Type as SampleType;
+use ffmpeg::frame;
+use ffmpeg::media;
+use ffmpeg::software::resampling;
+
+use ::{AltoError, AltoResult};
+use sys;
+use al::*;
+use super::*;
+
+
+/// Streaming decoder for any container and codec supported by the system's FFmpeg libraries.
+/// Samples are decoded as 16 bit integers. Sources with an unusual channel count are remixed to the
+/// nearest layout alto can upload, and channels are reordered to match alto's multichannel frames.
+pub struct FfmpegDecoder {
+	input: format::context::Input,
+	codec: codec::decoder::Audio,
+	resampler: resampling::Context,
+	stream: usize,
+	time_base: Rational,
+	in_layout: ChannelLayout,
+	out_layout: ChannelLayout,
+	channels: usize,
+	freq: u32,
+	pending: Vec<i16>,
+	seek_to: Option<u64>,
+	eof: bool,
+}
+
+
+fn ffmpeg_error(e: ffmpeg::Error) -> AltoError {
+	AltoError::Io(io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+}
+
+
+/// The FFmpeg layout whose canonical channel order matches the alto frame with `channels` channels,
+/// or the closest larger one if there is no exact match.
+fn output_layout(channels: usize) -> (ChannelLayout, usize) {
+	match channels {
+		1 => (ChannelLayout::MONO, 1),
+		2 => (ChannelLayout::STEREO, 2),
+		3 | 4 => (ChannelLayout::QUAD, 4),
+		5 | 6 => (ChannelLayout::_5POINT1_BACK, 6),
+		7 => (ChannelLayout::_6POINT1, 7),
+		_ => (ChannelLayout::_7POINT1, 8),
+	}
+}
+
+
+impl FfmpegDecoder {
+	/// Open a media file and prepare to decode its best audio stream.
+	pub fn open<P: AsRef<Path>>(path: P) -> AltoResult<FfmpegDecoder> {
+		ffmpeg::init().map_err(ffmpeg_error)?;
+		FfmpegDecoder::from_input(format::input(&path).map_err(ffmpeg_error)?)
+	}
+
+
+	/// Decode the best audio stream of an already opened FFmpeg input.
+	pub fn from_input(input: format::context::Input) -> AltoResult<FfmpegDecoder> {
+		let (stream, time_base, params) = {
+			let stream = input.streams().best(media::Type::Audio).ok_or(AltoError::Io(io::Error::new(io::ErrorKind::InvalidData, "No audio stream found")))?;
+			(stream.index(), stream.time_base(), stream.parameters())
+		};
+		let codec = codec::context::Context::from_parameters(params).map_err(ffmpeg_error)?
+			.decoder()
+			.audio()
+			.map_err(ffmpeg_error)?;
+
+		if codec.channels() == 0 { return Err(AltoError::Io(io::Error::new(io::ErrorKind::InvalidData, "Unknown channel layout"))) }
+		if codec.rate() == 0 { return Err(AltoError::Io(io::Error::new(io::ErrorKind::InvalidData, "Unknown sample rate"))) }
+
+		// Some containers leave the layout unspecified, in which case FFmpeg's default for the channel count is assumed.
+		let in_layout = if codec.channel_layout().is_empty() {
+			ChannelLayout::default(codec.channels() as i32)
+		} else {
+			codec.channel_layout()
+		};
+		let (out_layout, channels) = output_layout(codec.channels() as usize);
+		let freq = codec.rate();
+		let resampler = resampling::Context::get(codec.format(), in_layout, freq, Sample::I16(SampleType::Packed), out_layout, freq).map_err(ffmpeg_error)?;
+
+		Ok(FfmpegDecoder{
+			input: input,
+			codec: codec,
+			resampler: resampler,
+			stream: stream,
+			time_base: time_base,
+			in_layout: in_layout,
+			out_layout: out_layout,
+			channels: channels,
+			freq: freq,
+			pending: Vec::new(),
+			seek_to: None,
+			eof: false,
+		})
+	}
+
+
+	/// Decode the next frame of the selected stream into the pending samples.
+	/// Returns `false` at the end of the stream.
+	fn decode_frame(&mut self) -> AltoResult<bool> {
+		if self.eof { return Ok(false) }
+
+		loop {
+			let mut decoded = frame::Audio::empty();
+			match self.codec.receive_frame(&mut decoded) {
+				Ok(()) => {
+					self.push_frame(decoded)?;
+					return Ok(true);
+				},
+				Err(ffmpeg::Error::Eof) => {
+					self.eof = true;
+					let mut out = frame::Audio::empty();
+					self.resampler.flush(&mut out).map_err(ffmpeg_error)?;
+					self.push_samples(&out, None);
+					return Ok(out.samples() > 0);
+				},
+				Err(ffmpeg::Error::Other{errno}) if errno == ffmpeg::error::EAGAIN => (),
+				Err(e) => return Err(ffmpeg_error(e)),
+			}
+
+			let mut packet = Packet::empty();
+			match packet.read(&mut self.input) {
+				Ok(()) => {
+					if packet.stream() != self.stream { continue; }
+					match self.codec.send_packet(&packet) {
+						// Corrupt packets are skipped rather than ending the stream.
+						Ok(()) | Err(ffmpeg::Error::InvalidData) => (),
+						Err(e) => return Err(ffmpeg_error(e)),
+					}
+				},
+				Err(ffmpeg::Error::Eof) => self.codec.send_eof().map_err(ffmpeg_error)?,
+				Err(e) => return Err(ffmpeg_error(e)),
+			}
+		}
+	}
+
+
+	fn push_frame(&mut self, mut decoded: frame::Audio) -> AltoResult<()> {
+		if decoded.channel_layout().is_empty() {
+			decoded.set_channel_layout(self.in_layout);
+		}
+
+		let start = decoded.timestamp().map(|ts| {
+			let frame = ts * self.time_base.numerator() as i64 * self.freq as i64 / self.time_base.denominator() as i64;
+			cmp::max(frame, 0) as u64
+		});
+
+		let mut out = frame::Audio::empty();
+		self.resampler.run(&decoded, &mut out).map_err(ffmpeg_error)?;
+		self.push_samples(&out, start);
+		Ok(())
+	}
+
+
+	fn push_samples(&mut self, out: &frame::Audio, start: Option<u64>) {
+		let frames = out.samples();
+		if frames == 0 { return }
+
+		// After a seek the demuxer may land on an earlier keyframe, so discard frames until the target is reached.
+		let skip = match (self.seek_to, start) {
+			(Some(target), Some(start)) => cmp::min(target.saturating_sub(start) as usize, frames),
+			_ => 0,
+		};
+		if skip < frames {
+			self.seek_to = None;
+		}
+
+		let bytes = &out.data(0)[skip * self.channels * 2 .. frames * self.channels * 2];
+		self.pending.extend(bytes.chunks(2).map(|b| i16::from_ne_bytes([b[0], b[1]])));
+	}
+}
+
+
+impl Decoder for FfmpegDecoder {
+	fn channels(&self) -> usize { self.channels }
+	fn frequency(&self) -> sys::ALint { self.freq as sys::ALint }
+
+
+	fn decode(&mut self, frames: usize) -> AltoResult<Option<DecodedSamples>> {
+		let len = frames * self.channels;
+		while self.pending.len() < len {
+			if !self.decode_frame()? {
+				break;
+			}
+		}
+
+		if self.pending.is_empty() {
+			return Ok(None);
+		}
+
+		let rest = if self.pending.len() > len { self.pending.split_off(len) } else { Vec::new() };
+		Ok(Some(DecodedSamples::I16(::std::mem::replace(&mut self.pending, rest))))
+	}
+	fn decode_append(&mut self, frames: usize, out: &mut DecodedSamples) -> AltoResult<bool> {
+		let len = frames * self.channels;
+		while self.pending.len() < len {
+			if !self.decode_frame()? {
+				break;
+			}
+		}
+
+		if self.pending.is_empty() {
+			return Ok(false);
+		}
+		append_pending(&mut self.pending, len, out).map(|_| true)
+	}
+
+
+	fn seek(&mut self, frame: u64) -> AltoResult<()> {
+		// Without a stream index, FFmpeg expects timestamps in microseconds.
+		let ts = (frame as i64).saturating_mul(1_000_000) / self.freq as i64;
+		self.input.seek(ts, .. ts).map_err(ffmpeg_error)?;
+
+		self.codec.flush();
+		self.resampler = resampling::Context::get(self.codec.format(), self.in_layout, self.freq, Sample::I16(SampleType::Packed), self.out_layout, self.freq).map_err(ffmpeg_error)?;
+		self.pending.clear();
+		self.seek_to = Some(frame);
+		self.eof = false;
+		Ok(())
+	}
+}
+
+
+impl<'d: 'c, 'c> Buffer<'d, 'c> {
+	/// Create a buffer from any media file FFmpeg can decode.
+	pub fn from_ffmpeg_file<P: AsRef<Path>>(ctx: &'c Context<'d>, path: P) -> AltoResult<Buffer<'d, 'c>> {
+		Buffer::from_decoder(ctx, &mut FfmpegDecoder::open(path)?)
+	}
+}
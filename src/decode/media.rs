@@ -0,0 +1,178 @@
+use std::io;
+use std::cmp;
+use std::fs::File;
+use std::path::Path;
+use symphonia;
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{self, DecoderOptions};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::{FormatOptions, FormatReader, SeekMode, SeekTo};
+use symphonia::core::io::{MediaSource, MediaSourceStream};
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+use symphonia::core::units::{Time, TimeBase};
+
+use ::{AltoError, AltoResult};
+use sys;
+use al::*;
+use super::*;
+
+
+/// Streaming decoder for any container and codec supported by `symphonia`,
+/// including MP3, AAC, Ogg Vorbis, FLAC, and WAV.
+/// Samples are decoded as 16 bit integers.
+pub struct SymphoniaDecoder {
+	format: Box<FormatReader>,
+	codec: Box<codecs::Decoder>,
+	track_id: u32,
+	time_base: Option<TimeBase>,
+	channels: usize,
+	freq: u32,
+	pending: Vec<i16>,
+	skip: usize,
+}
+
+
+fn symphonia_error(e: SymphoniaError) -> AltoError {
+	match e {
+		SymphoniaError::IoError(e) => AltoError::Io(e),
+		e => AltoError::Io(io::Error::new(io::ErrorKind::InvalidData, e.to_string())),
+	}
+}
+
+
+impl SymphoniaDecoder {
+	/// Probe a media source and prepare to decode its default track.
+	/// `extension` is an optional file extension used as a hint when probing the container.
+	pub fn new<M: MediaSource + 'static>(source: M, extension: Option<&str>) -> AltoResult<SymphoniaDecoder> {
+		let mut hint = Hint::new();
+		if let Some(ext) = extension {
+			hint.with_extension(ext);
+		}
+
+		let mss = MediaSourceStream::new(Box::new(source), Default::default());
+		let probed = symphonia::default::get_probe()
+			.format(&hint, mss, &FormatOptions::default(), &MetadataOptions::default())
+			.map_err(symphonia_error)?;
+		let format = probed.format;
+
+		let (track_id, params) = {
+			let track = format.default_track().ok_or(AltoError::Io(io::Error::new(io::ErrorKind::InvalidData, "No audio track found")))?;
+			(track.id, track.codec_params.clone())
+		};
+		let channels = params.channels.map(|c| c.count()).ok_or(AltoError::Io(io::Error::new(io::ErrorKind::InvalidData, "Unknown channel layout")))?;
+		let freq = params.sample_rate.ok_or(AltoError::Io(io::Error::new(io::ErrorKind::InvalidData, "Unknown sample rate")))?;
+		let codec = symphonia::default::get_codecs().make(&params, &DecoderOptions::default()).map_err(symphonia_error)?;
+
+		Ok(SymphoniaDecoder{
+			format: format,
+			codec: codec,
+			track_id: track_id,
+			time_base: params.time_base,
+			channels: channels,
+			freq: freq,
+			pending: Vec::new(),
+			skip: 0,
+		})
+	}
+
+
+	/// Open a media file from disk, using its extension as a probing hint.
+	pub fn open<P: AsRef<Path>>(path: P) -> AltoResult<SymphoniaDecoder> {
+		let path = path.as_ref();
+		let ext = path.extension().and_then(|e| e.to_str()).map(|e| e.to_owned());
+		SymphoniaDecoder::new(File::open(path)?, ext.as_ref().map(|e| e.as_str()))
+	}
+
+
+	/// Decode the next packet of the selected track into the pending samples.
+	/// Returns `false` at the end of the stream.
+	fn decode_packet(&mut self) -> AltoResult<bool> {
+		loop {
+			let packet = match self.format.next_packet() {
+				Ok(packet) => packet,
+				Err(SymphoniaError::IoError(ref e)) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(false),
+				Err(e) => return Err(symphonia_error(e)),
+			};
+			if packet.track_id() != self.track_id {
+				continue;
+			}
+
+			match self.codec.decode(&packet) {
+				Ok(decoded) => {
+					let mut samples = SampleBuffer::<i16>::new(decoded.capacity() as u64, *decoded.spec());
+					samples.copy_interleaved_ref(decoded);
+
+					let skip = cmp::min(self.skip * self.channels, samples.samples().len());
+					self.skip -= skip / self.channels;
+					self.pending.extend_from_slice(&samples.samples()[skip ..]);
+					return Ok(true);
+				},
+				// Corrupt packets are skipped rather than ending the stream.
+				Err(SymphoniaError::DecodeError(_)) => continue,
+				Err(e) => return Err(symphonia_error(e)),
+			}
+		}
+	}
+}
+
+
+impl Decoder for SymphoniaDecoder {
+	fn channels(&self) -> usize { self.channels }
+	fn frequency(&self) -> sys::ALint { self.freq as sys::ALint }
+
+
+	fn decode(&mut self, frames: usize) -> AltoResult<Option<DecodedSamples>> {
+		let len = frames * self.channels;
+		while self.pending.len() < len {
+			if !self.decode_packet()? {
+				break;
+			}
+		}
+
+		if self.pending.is_empty() {
+			return Ok(None);
+		}
+
+		let rest = if self.pending.len() > len { self.pending.split_off(len) } else { Vec::new() };
+		Ok(Some(DecodedSamples::I16(::std::mem::replace(&mut self.pending, rest))))
+	}
+	fn decode_append(&mut self, frames: usize, out: &mut DecodedSamples) -> AltoResult<bool> {
+		let len = frames * self.channels;
+		while self.pending.len() < len {
+			if !self.decode_packet()? {
+				break;
+			}
+		}
+
+		if self.pending.is_empty() {
+			return Ok(false);
+		}
+		append_pending(&mut self.pending, len, out).map(|_| true)
+	}
+
+
+	fn seek(&mut self, frame: u64) -> AltoResult<()> {
+		let freq = self.freq as u64;
+		let time = Time::new(frame / freq, (frame % freq) as f64 / freq as f64);
+		let seeked = self.format.seek(SeekMode::Accurate, SeekTo::Time{time: time, track_id: Some(self.track_id)}).map_err(symphonia_error)?;
+
+		self.codec.reset();
+		self.pending.clear();
+		// The demuxer may land before the requested position, so discard the difference once decoded.
+		let delta = seeked.required_ts.saturating_sub(seeked.actual_ts);
+		self.skip = match self.time_base {
+			Some(tb) => (delta * tb.numer as u64 * freq / tb.denom as u64) as usize,
+			None => delta as usize,
+		};
+		Ok(())
+	}
+}
+
+
+impl<'d: 'c, 'c> Buffer<'d, 'c> {
+	/// Create a buffer from any media file `symphonia` can decode.
+	pub fn from_media_file<P: AsRef<Path>>(ctx: &'c Context<'d>, path: P) -> AltoResult<Buffer<'d, 'c>> {
+		Buffer::from_decoder(ctx, &mut SymphoniaDecoder::open(path)?)
+	}
+}
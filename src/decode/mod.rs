@@ -0,0 +1,254 @@
+//! Helpers for decoding encoded audio into OpenAL buffers.
+//! Each supported container is exposed as a [`Decoder`](trait.Decoder.html) behind its own
+//! cargo feature. Decoders produce interleaved samples that can either be loaded into a
+//! single buffer in one go, or pulled in chunks to keep a streaming source fed.
+
+
+use ::{AltoError, AltoResult};
+use sys;
+use convert;
+use al::*;
+
+
+mod stream;
+pub use self::stream::*;
+
+
+mod pool;
+pub use self::pool::{DecodePool, PooledDecoder};
+
+
+#[cfg(feature = "hound")]
+mod wav;
+#[cfg(feature = "hound")]
+pub use self::wav::*;
+
+
+#[cfg(feature = "lewton")]
+mod vorbis;
+#[cfg(feature = "lewton")]
+pub use self::vorbis::*;
+
+
+#[cfg(feature = "minimp3")]
+mod mp3;
+#[cfg(feature = "minimp3")]
+pub use self::mp3::*;
+
+
+#[cfg(feature = "symphonia")]
+mod media;
+#[cfg(feature = "symphonia")]
+pub use self::media::*;
+
+
+#[cfg(feature = "audiopus")]
+mod opus;
+#[cfg(feature = "audiopus")]
+pub use self::opus::*;
+
+
+#[cfg(feature = "ffmpeg-next")]
+mod ffmpeg;
+#[cfg(feature = "ffmpeg-next")]
+pub use self::ffmpeg::*;
+
+
+/// A block of interleaved samples produced by a decoder.
+#[derive(Clone, PartialEq, Debug)]
+pub enum DecodedSamples {
+	U8(Vec<u8>),
+	I16(Vec<i16>),
+	F32(Vec<f32>),
+}
+
+
+/// A source of decoded audio.
+pub trait Decoder {
+	/// Number of interleaved channels in each sample frame.
+	fn channels(&self) -> usize;
+	/// Sample rate in frames per second.
+	fn frequency(&self) -> sys::ALint;
+	/// Decode up to `frames` sample frames, or `None` if the end of the stream has been reached.
+	fn decode(&mut self, frames: usize) -> AltoResult<Option<DecodedSamples>>;
+	/// Decode up to `frames` sample frames onto the end of `out`, or return `false` if the end of the stream has been reached.
+	/// Decoders that hold decoded samples back override this to copy them into `out` without allocating.
+	fn decode_append(&mut self, frames: usize, out: &mut DecodedSamples) -> AltoResult<bool> {
+		match self.decode(frames)? {
+			Some(block) => out.append(block).map(|_| true),
+			None => Ok(false),
+		}
+	}
+	/// Seek to an absolute sample frame, if the underlying format allows it.
+	fn seek(&mut self, _frame: u64) -> AltoResult<()> {
+		Err(AltoError::AlInvalidOperation)
+	}
+}
+
+
+impl DecodedSamples {
+	/// Number of individual samples, across all channels.
+	pub fn len(&self) -> usize {
+		match *self {
+			DecodedSamples::U8(ref s) => s.len(),
+			DecodedSamples::I16(ref s) => s.len(),
+			DecodedSamples::F32(ref s) => s.len(),
+		}
+	}
+
+
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+
+	/// Append another block of the same sample type. An empty block takes on the sample type of `other`.
+	pub fn append(&mut self, other: DecodedSamples) -> AltoResult<()> {
+		match (self, other) {
+			(&mut DecodedSamples::U8(ref mut a), DecodedSamples::U8(ref b)) => a.extend_from_slice(b),
+			(&mut DecodedSamples::I16(ref mut a), DecodedSamples::I16(ref b)) => a.extend_from_slice(b),
+			(&mut DecodedSamples::F32(ref mut a), DecodedSamples::F32(ref b)) => a.extend_from_slice(b),
+			(this, other) => {
+				if !this.is_empty() { return Err(AltoError::AlInvalidValue) }
+				*this = other;
+			},
+		}
+		Ok(())
+	}
+
+
+	/// Remove every sample, keeping the allocation for reuse.
+	pub fn clear(&mut self) {
+		match *self {
+			DecodedSamples::U8(ref mut s) => s.clear(),
+			DecodedSamples::I16(ref mut s) => s.clear(),
+			DecodedSamples::F32(ref mut s) => s.clear(),
+		}
+	}
+
+
+	/// The samples as signed 16-bit, converting them if needed.
+	pub fn to_i16(&self) -> Vec<i16> {
+		match *self {
+			DecodedSamples::U8(ref s) => convert_samples(s, convert::u8_to_i16),
+			DecodedSamples::I16(ref s) => s.clone(),
+			DecodedSamples::F32(ref s) => convert_samples(s, convert::f32_to_i16),
+		}
+	}
+
+
+	/// The samples as float, converting them if needed.
+	pub fn to_f32(&self) -> Vec<f32> {
+		match *self {
+			DecodedSamples::U8(ref s) => convert_samples(s, convert::u8_to_f32),
+			DecodedSamples::I16(ref s) => convert_samples(s, convert::i16_to_f32),
+			DecodedSamples::F32(ref s) => s.clone(),
+		}
+	}
+
+
+	/// Upload the samples into a buffer, choosing the format from the channel count and sample type.
+	/// More than two channels requires `AL_EXT_MCFORMATS`, and float samples require `AL_EXT_float32`.
+	pub fn upload<'d: 'c, 'c>(&self, buf: &mut Buffer<'d, 'c>, channels: usize, freq: sys::ALint) -> AltoResult<()> {
+		match *self {
+			DecodedSamples::U8(ref s) => upload_frames::<u8>(buf, s, channels, freq),
+			DecodedSamples::I16(ref s) => upload_frames::<i16>(buf, s, channels, freq),
+			DecodedSamples::F32(ref s) => upload_frames::<f32>(buf, s, channels, freq),
+		}
+	}
+}
+
+
+fn convert_samples<A, B: Copy + Default>(src: &[A], f: fn(&[A], &mut [B]) -> AltoResult<()>) -> Vec<B> {
+	let mut dst = vec![B::default(); src.len()];
+	f(src, &mut dst).expect("ALTO ERROR: conversion of equal lengths failed");
+	dst
+}
+
+
+fn upload_frames<'d: 'c, 'c, S>(buf: &mut Buffer<'d, 'c>, data: &[S], channels: usize, freq: sys::ALint) -> AltoResult<()> where
+	S: Copy,
+	Mono<S>: SampleFrame,
+	Stereo<S>: SampleFrame,
+	McQuad<S>: SampleFrame,
+	Mc51Chn<S>: SampleFrame,
+	Mc61Chn<S>: SampleFrame,
+	Mc71Chn<S>: SampleFrame,
+{
+	if channels == 0 || data.len() % channels != 0 { return Err(AltoError::AlInvalidValue) }
+
+	match channels {
+		1 => buf.set_data::<Mono<S>, _>(data, freq),
+		2 => buf.set_data::<Stereo<S>, _>(data, freq),
+		4 => buf.set_data::<McQuad<S>, _>(data, freq),
+		6 => buf.set_data::<Mc51Chn<S>, _>(data, freq),
+		7 => buf.set_data::<Mc61Chn<S>, _>(data, freq),
+		8 => buf.set_data::<Mc71Chn<S>, _>(data, freq),
+		_ => Err(AltoError::AlInvalidValue),
+	}
+}
+
+
+/// Decode the remainder of a stream into memory.
+pub fn decode_all<D: Decoder + ?Sized>(dec: &mut D) -> AltoResult<Option<DecodedSamples>> {
+	let mut all: Option<DecodedSamples> = None;
+	while let Some(block) = dec.decode(4096)? {
+		match all {
+			Some(ref mut all) => all.append(block)?,
+			None => all = Some(block),
+		}
+	}
+	Ok(all)
+}
+
+
+/// Decode up to `frames` sample frames into a buffer.
+/// Returns `false` without touching the buffer if the stream has ended.
+pub fn fill_buffer<'d: 'c, 'c, D: Decoder + ?Sized>(dec: &mut D, buf: &mut Buffer<'d, 'c>, frames: usize) -> AltoResult<bool> {
+	fill_buffer_with(dec, buf, frames, &mut DecodedSamples::I16(Vec::new()))
+}
+
+
+/// Like `fill_buffer()`, but decodes into `scratch`, whose allocation is reused from one call to the next.
+pub fn fill_buffer_with<'d: 'c, 'c, D: Decoder + ?Sized>(dec: &mut D, buf: &mut Buffer<'d, 'c>, frames: usize, scratch: &mut DecodedSamples) -> AltoResult<bool> {
+	scratch.clear();
+
+	// Decoders may return short blocks at packet boundaries, so top up until the request is met.
+	let want = frames * dec.channels();
+	while scratch.len() < want {
+		if !dec.decode_append((want - scratch.len()) / dec.channels(), scratch)? {
+			break;
+		}
+	}
+	if scratch.is_empty() { return Ok(false) }
+
+	scratch.upload(buf, dec.channels(), dec.frequency())?;
+	Ok(true)
+}
+
+
+/// Move up to `len` samples from the front of a decoder's pending output onto the end of `out`.
+fn append_pending(pending: &mut Vec<i16>, len: usize, out: &mut DecodedSamples) -> AltoResult<()> {
+	let len = len.min(pending.len());
+	if let DecodedSamples::I16(ref mut out) = *out {
+		out.extend_from_slice(&pending[..len]);
+	} else if out.is_empty() {
+		*out = DecodedSamples::I16(pending[..len].to_vec());
+	} else {
+		return Err(AltoError::AlInvalidValue);
+	}
+	pending.drain(..len);
+	Ok(())
+}
+
+
+impl<'d: 'c, 'c> Buffer<'d, 'c> {
+	/// Create a buffer holding the entire remaining output of a decoder.
+	pub fn from_decoder<D: Decoder + ?Sized>(ctx: &'c Context<'d>, dec: &mut D) -> AltoResult<Buffer<'d, 'c>> {
+		let mut buf = ctx.new_buffer()?;
+		if let Some(samples) = decode_all(dec)? {
+			samples.upload(&mut buf, dec.channels(), dec.frequency())?;
+		}
+		Ok(buf)
+	}
+}
@@ -0,0 +1,132 @@
+use std::io::{self, Read};
+use std::fs::File;
+use std::path::Path;
+use minimp3;
+
+use ::{AltoError, AltoResult};
+use sys;
+use al::*;
+use super::*;
+
+
+/// Streaming decoder for MP3 files, backed by `minimp3`.
+/// The channel count and sample rate are taken from the first frame.
+/// Samples are decoded as 16 bit integers.
+pub struct Mp3Decoder<R: Read> {
+	dec: minimp3::Decoder<R>,
+	channels: usize,
+	freq: sys::ALint,
+	pending: Vec<i16>,
+	ended: bool,
+}
+
+
+fn mp3_error(e: minimp3::Error) -> AltoError {
+	match e {
+		minimp3::Error::Io(e) => AltoError::Io(e),
+		e => AltoError::Io(io::Error::new(io::ErrorKind::InvalidData, e.to_string())),
+	}
+}
+
+
+impl<R: Read> Mp3Decoder<R> {
+	/// Decode the first frame of a stream to determine its format.
+	pub fn new(reader: R) -> AltoResult<Mp3Decoder<R>> {
+		let mut dec = minimp3::Decoder::new(reader);
+		let frame = dec.next_frame().map_err(|e| match e {
+			minimp3::Error::Eof => AltoError::Io(io::Error::new(io::ErrorKind::InvalidData, "No MP3 frames found")),
+			e => mp3_error(e),
+		})?;
+
+		Ok(Mp3Decoder{
+			dec: dec,
+			channels: frame.channels,
+			freq: frame.sample_rate as sys::ALint,
+			pending: frame.data,
+			ended: false,
+		})
+	}
+
+
+	/// Decode the next frame into the pending samples. Returns `false` at the end of the stream.
+	fn decode_frame(&mut self) -> AltoResult<bool> {
+		if self.ended {
+			return Ok(false);
+		}
+
+		match self.dec.next_frame() {
+			// A change of format mid-stream can't be represented in a single buffer, so stop there.
+			Ok(ref frame) if frame.channels != self.channels || frame.sample_rate as sys::ALint != self.freq => {
+				self.ended = true;
+				Ok(false)
+			},
+			Ok(frame) => {
+				self.pending.extend_from_slice(&frame.data);
+				Ok(true)
+			},
+			Err(minimp3::Error::Eof) => {
+				self.ended = true;
+				Ok(false)
+			},
+			Err(e) => Err(mp3_error(e)),
+		}
+	}
+}
+
+
+impl Mp3Decoder<io::BufReader<File>> {
+	/// Open an MP3 file from disk.
+	pub fn open<P: AsRef<Path>>(path: P) -> AltoResult<Mp3Decoder<io::BufReader<File>>> {
+		Mp3Decoder::new(io::BufReader::new(File::open(path)?))
+	}
+}
+
+
+impl<R: Read> Decoder for Mp3Decoder<R> {
+	fn channels(&self) -> usize { self.channels }
+	fn frequency(&self) -> sys::ALint { self.freq }
+
+
+	fn decode(&mut self, frames: usize) -> AltoResult<Option<DecodedSamples>> {
+		let len = frames * self.channels;
+		while self.pending.len() < len {
+			if !self.decode_frame()? {
+				break;
+			}
+		}
+
+		if self.pending.is_empty() {
+			return Ok(None);
+		}
+
+		let rest = if self.pending.len() > len { self.pending.split_off(len) } else { Vec::new() };
+		Ok(Some(DecodedSamples::I16(::std::mem::replace(&mut self.pending, rest))))
+	}
+	fn decode_append(&mut self, frames: usize, out: &mut DecodedSamples) -> AltoResult<bool> {
+		let len = frames * self.channels;
+		while self.pending.len() < len {
+			if !self.decode_frame()? {
+				break;
+			}
+		}
+
+		if self.pending.is_empty() {
+			return Ok(false);
+		}
+		append_pending(&mut self.pending, len, out).map(|_| true)
+	}
+}
+
+
+impl<'d: 'c, 'c> Buffer<'d, 'c> {
+	/// Create a buffer from a complete MP3 stream.
+	pub fn from_mp3<R: Read>(ctx: &'c Context<'d>, reader: R) -> AltoResult<Buffer<'d, 'c>> {
+		Buffer::from_decoder(ctx, &mut Mp3Decoder::new(reader)?)
+	}
+
+
+	/// Create a buffer from an MP3 file on disk.
+	pub fn from_mp3_file<P: AsRef<Path>>(ctx: &'c Context<'d>, path: P) -> AltoResult<Buffer<'d, 'c>> {
+		Buffer::from_decoder(ctx, &mut Mp3Decoder::open(path)?)
+	}
+}
@@ -0,0 +1,351 @@
+use std::io;
+use std::convert::TryFrom;
+use std::collections::BTreeMap;
+use audiopus;
+use audiopus::coder::{Decoder as OpusDecoder, Encoder as OpusEncoder};
+use audiopus::packet::Packet;
+
+use ::{AltoError, AltoResult};
+use sys;
+use al::*;
+
+
+/// Called with the samples produced for a lost packet, before they are queued.
+/// The slice initially holds Opus' own concealment output and may be modified in place.
+pub type ConcealHook = Box<dyn FnMut(u32, &mut [i16]) + Send>;
+
+
+fn opus_error(e: audiopus::Error) -> AltoError {
+	AltoError::Io(io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+}
+
+
+fn sample_rate(freq: sys::ALint) -> AltoResult<audiopus::SampleRate> {
+	audiopus::SampleRate::try_from(freq).map_err(|_| AltoError::AlInvalidValue)
+}
+
+
+fn channel_count(channels: usize) -> AltoResult<audiopus::Channels> {
+	match channels {
+		1 => Ok(audiopus::Channels::Mono),
+		2 => Ok(audiopus::Channels::Stereo),
+		_ => Err(AltoError::AlInvalidValue),
+	}
+}
+
+
+/// Check that `frame_len` samples per channel is one of the Opus frame durations (2.5 to 60 ms).
+fn check_frame_len(freq: sys::ALint, frame_len: usize) -> AltoResult<()> {
+	let freq = freq as usize;
+	if [freq / 400, freq / 200, freq / 100, freq / 50, freq / 25, freq * 3 / 50].contains(&frame_len) {
+		Ok(())
+	} else {
+		Err(AltoError::AlInvalidValue)
+	}
+}
+
+
+/// Reorders incoming voice packets by sequence number and holds a few back to absorb network jitter.
+pub struct JitterBuffer {
+	packets: BTreeMap<u32, Vec<u8>>,
+	next: Option<u32>,
+	depth: usize,
+	primed: bool,
+}
+
+
+/// What the jitter buffer has for the next sequence number.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum JitterPacket {
+	/// The packet arrived in time.
+	Present(Vec<u8>),
+	/// The packet is missing, but the following packet has arrived and may carry FEC data for it.
+	Lost(Option<Vec<u8>>),
+}
+
+
+impl JitterBuffer {
+	/// `depth` is the number of packets to accumulate before playback begins.
+	pub fn new(depth: usize) -> JitterBuffer {
+		JitterBuffer{packets: BTreeMap::new(), next: None, depth: depth, primed: false}
+	}
+
+
+	/// Add a packet. Packets older than the current playback position are discarded.
+	pub fn push(&mut self, seq: u32, packet: Vec<u8>) {
+		if let Some(next) = self.next {
+			if seq < next { return; }
+		}
+		self.packets.insert(seq, packet);
+	}
+
+
+	/// Number of packets currently held.
+	#[inline]
+	pub fn len(&self) -> usize { self.packets.len() }
+	#[inline]
+	pub fn is_empty(&self) -> bool { self.packets.is_empty() }
+
+
+	/// Take the packet for the next sequence number, or `None` if the buffer is still filling or has run dry.
+	/// Returns the sequence number alongside the packet.
+	pub fn pop(&mut self) -> Option<(u32, JitterPacket)> {
+		if !self.primed {
+			if self.packets.len() < self.depth { return None; }
+			self.primed = true;
+		}
+
+		let next = match self.next.or_else(|| self.packets.keys().next().cloned()) {
+			Some(next) => next,
+			None => return None,
+		};
+		if self.packets.is_empty() {
+			// Ran dry; wait until the buffer refills before continuing.
+			self.primed = false;
+			return None;
+		}
+
+		self.next = Some(next.wrapping_add(1));
+		match self.packets.remove(&next) {
+			Some(packet) => Some((next, JitterPacket::Present(packet))),
+			None => Some((next, JitterPacket::Lost(self.packets.get(&next.wrapping_add(1)).cloned()))),
+		}
+	}
+
+
+	/// Forget all held packets and the playback position.
+	pub fn reset(&mut self) {
+		self.packets.clear();
+		self.next = None;
+		self.primed = false;
+	}
+}
+
+
+/// Low-latency Opus decoder for voice, with packet-loss concealment.
+pub struct VoiceDecoder {
+	dec: OpusDecoder,
+	channels: usize,
+	freq: sys::ALint,
+	frame_len: usize,
+	conceal: Option<ConcealHook>,
+}
+
+
+impl VoiceDecoder {
+	/// `frame_len` is the number of samples per channel in each packet, such as 960 for 20 ms at 48 kHz.
+	pub fn new(freq: sys::ALint, channels: usize, frame_len: usize) -> AltoResult<VoiceDecoder> {
+		check_frame_len(freq, frame_len)?;
+		let dec = OpusDecoder::new(sample_rate(freq)?, channel_count(channels)?).map_err(opus_error)?;
+		Ok(VoiceDecoder{dec: dec, channels: channels, freq: freq, frame_len: frame_len, conceal: None})
+	}
+
+
+	#[inline]
+	pub fn channels(&self) -> usize { self.channels }
+	#[inline]
+	pub fn frequency(&self) -> sys::ALint { self.freq }
+	#[inline]
+	pub fn frame_len(&self) -> usize { self.frame_len }
+
+
+	/// Install a hook to post-process concealment output for lost packets.
+	pub fn set_conceal_hook(&mut self, hook: Option<ConcealHook>) {
+		self.conceal = hook;
+	}
+
+
+	/// Decode one packet into interleaved samples.
+	pub fn decode(&mut self, packet: &[u8]) -> AltoResult<Vec<i16>> {
+		let mut data = Vec::new();
+		self.decode_into(packet, &mut data)?;
+		Ok(data)
+	}
+	/// Like `decode()`, but replaces the contents of `data`, reusing its allocation.
+	pub fn decode_into(&mut self, packet: &[u8], data: &mut Vec<i16>) -> AltoResult<()> {
+		let packet = Packet::try_from(packet).map_err(opus_error)?;
+		self.decode_inner(Some(packet), false, data)
+	}
+
+
+	/// Produce a frame for a lost packet.
+	/// If the following packet is available, its forward error correction data is used to recover the loss.
+	pub fn conceal(&mut self, seq: u32, following: Option<&[u8]>) -> AltoResult<Vec<i16>> {
+		let mut data = Vec::new();
+		self.conceal_into(seq, following, &mut data)?;
+		Ok(data)
+	}
+	/// Like `conceal()`, but replaces the contents of `data`, reusing its allocation.
+	pub fn conceal_into(&mut self, seq: u32, following: Option<&[u8]>, data: &mut Vec<i16>) -> AltoResult<()> {
+		match following {
+			Some(following) => {
+				let packet = Packet::try_from(following).map_err(opus_error)?;
+				self.decode_inner(Some(packet), true, data)?;
+			},
+			None => self.decode_inner(None, false, data)?,
+		}
+
+		if let Some(ref mut hook) = self.conceal {
+			hook(seq, data);
+		}
+		Ok(())
+	}
+
+
+	fn decode_inner(&mut self, packet: Option<Packet>, fec: bool, data: &mut Vec<i16>) -> AltoResult<()> {
+		data.clear();
+		data.resize(self.frame_len * self.channels, 0);
+		let len = {
+			let out = audiopus::MutSignals::try_from(&mut *data).map_err(opus_error)?;
+			self.dec.decode(packet, out, fec).map_err(opus_error)?
+		};
+		data.truncate(len * self.channels);
+		Ok(())
+	}
+}
+
+
+/// Opus encoder tuned for voice, for the sending side of a call.
+pub struct VoiceEncoder {
+	enc: OpusEncoder,
+	channels: usize,
+	frame_len: usize,
+}
+
+
+impl VoiceEncoder {
+	/// `frame_len` is the number of samples per channel in each packet.
+	pub fn new(freq: sys::ALint, channels: usize, frame_len: usize) -> AltoResult<VoiceEncoder> {
+		check_frame_len(freq, frame_len)?;
+		let enc = OpusEncoder::new(sample_rate(freq)?, channel_count(channels)?, audiopus::Application::Voip).map_err(opus_error)?;
+		Ok(VoiceEncoder{enc: enc, channels: channels, frame_len: frame_len})
+	}
+
+
+	#[inline]
+	pub fn frame_len(&self) -> usize { self.frame_len }
+
+
+	/// Encode exactly one frame of interleaved samples into a packet.
+	pub fn encode(&mut self, data: &[i16]) -> AltoResult<Vec<u8>> {
+		if data.len() != self.frame_len * self.channels { return Err(AltoError::AlInvalidValue) }
+
+		let mut packet = vec![0; 1276];
+		let len = self.enc.encode(data, &mut packet).map_err(opus_error)?;
+		packet.truncate(len);
+		Ok(packet)
+	}
+}
+
+
+/// Plays a stream of Opus voice packets through a streaming source.
+/// Packets are pushed as they arrive from the network, and [`update`](#method.update)
+/// moves decoded frames from the jitter buffer into the source queue.
+pub struct VoiceStream<'d: 'c, 'c> {
+	ctx: &'c Context<'d>,
+	src: StreamingSource<'d, 'c>,
+	dec: VoiceDecoder,
+	jitter: JitterBuffer,
+	queue_len: usize,
+	started: bool,
+	/// Reused across updates so steady-state playback doesn't allocate.
+	scratch: Vec<i16>,
+	free: Vec<Buffer<'d, 'c>>,
+}
+
+
+impl<'d: 'c, 'c> VoiceStream<'d, 'c> {
+	/// `depth` is the number of packets the jitter buffer holds back, and `queue_len` is the
+	/// number of decoded frames kept queued on the source.
+	pub fn new(ctx: &'c Context<'d>, dec: VoiceDecoder, depth: usize, queue_len: usize) -> AltoResult<VoiceStream<'d, 'c>> {
+		if queue_len == 0 { return Err(AltoError::AlInvalidValue) }
+
+		Ok(VoiceStream{
+			ctx: ctx,
+			src: ctx.new_streaming_source()?,
+			dec: dec,
+			jitter: JitterBuffer::new(depth),
+			queue_len: queue_len,
+			started: false,
+			scratch: Vec::new(),
+			free: Vec::new(),
+		})
+	}
+
+
+	/// Hand over a packet received from the network.
+	pub fn push_packet(&mut self, seq: u32, packet: Vec<u8>) {
+		self.jitter.push(seq, packet);
+	}
+
+
+	/// Recycle played buffers, queue newly decoded frames, and restart the source after an underrun.
+	pub fn update(&mut self) -> AltoResult<()> {
+		self.free.extend(self.src.unqueue_processed()?);
+
+		// Packets decoded before an error are still queued.
+		let mut batch = Vec::new();
+		let decoded = self.decode_batch(&mut batch);
+		self.src.queue_buffers(batch).map_err(|(e, _)| e)?;
+		decoded?;
+
+		if self.src.state()? != SourceState::Playing && self.src.buffers_queued()? > 0 {
+			if self.started {
+				let src = self.src.as_raw();
+				self.ctx.device().alto().metrics().report(|m| m.underrun(src));
+				self.ctx.push_event(AudioEvent::Underrun(src));
+			}
+			self.src.play()?;
+			self.started = true;
+		}
+		Ok(())
+	}
+
+
+	/// Decode packets into buffers until the queue would be full or the jitter buffer is empty.
+	fn decode_batch(&mut self, batch: &mut Vec<Buffer<'d, 'c>>) -> AltoResult<()> {
+		while self.src.buffers_queued()? as usize + batch.len() < self.queue_len {
+			match self.jitter.pop() {
+				Some((_, JitterPacket::Present(packet))) => self.dec.decode_into(&packet, &mut self.scratch)?,
+				Some((seq, JitterPacket::Lost(following))) => self.dec.conceal_into(seq, following.as_ref().map(|p| p.as_slice()), &mut self.scratch)?,
+				None => break,
+			}
+			let data = &self.scratch;
+			if data.is_empty() {
+				continue;
+			}
+
+			let mut buf = match self.free.pop() {
+				Some(buf) => buf,
+				None => self.ctx.new_buffer()?,
+			};
+			match self.dec.channels() {
+				1 => buf.set_data::<Mono<i16>, _>(&data[..], self.dec.frequency())?,
+				_ => buf.set_data::<Stereo<i16>, _>(&data[..], self.dec.frequency())?,
+			}
+			batch.push(buf);
+		}
+		Ok(())
+	}
+
+
+	/// Stop playback and drop everything buffered, such as when a call is restarted.
+	pub fn reset(&mut self) -> AltoResult<()> {
+		self.src.stop()?;
+		let queued = self.src.buffers_queued()?;
+		self.free.extend(self.src.unqueue_buffers(queued as usize)?);
+		self.jitter.reset();
+		self.started = false;
+		Ok(())
+	}
+
+
+	#[inline]
+	pub fn source(&self) -> &StreamingSource<'d, 'c> { &self.src }
+	#[inline]
+	pub fn source_mut(&mut self) -> &mut StreamingSource<'d, 'c> { &mut self.src }
+	#[inline]
+	pub fn decoder_mut(&mut self) -> &mut VoiceDecoder { &mut self.dec }
+	#[inline]
+	pub fn jitter_buffer(&self) -> &JitterBuffer { &self.jitter }
+}
@@ -0,0 +1,357 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use ::{AltoError, AltoResult};
+use ::catch_panic;
+use sys;
+use super::*;
+
+
+/// Worker threads shared by many decoders to decode ahead of playback, so that each stream doesn't need
+/// a thread of its own. A decoder wrapped with [`wrap`](#method.wrap) can be used anywhere a decoder is
+/// expected, such as in a `Stream`, and its `decode()` hands out samples the workers already decoded.
+///
+/// Decoders take turns one block at a time, and the samples decoded ahead across all of them are capped
+/// by the pool's budget. A decoder with nothing decoded ahead is served regardless, so none is starved.
+/// A decoder that panics on a worker fails with `CallbackPanicked` instead, without affecting the others.
+/// The workers exit once the pool and every decoder wrapped by it have been dropped.
+#[derive(Clone)]
+pub struct DecodePool(Arc<PoolHandle>);
+
+
+/// A decoder whose output is decoded ahead by a `DecodePool`.
+/// `decode()` only blocks if the workers haven't kept up.
+pub struct PooledDecoder<D: Decoder + Send + 'static> {
+	ahead: Arc<Ahead<D>>,
+	pool: DecodePool,
+}
+
+
+/// Shuts the workers down when the last pool handle or wrapped decoder is dropped.
+struct PoolHandle(Arc<PoolShared>);
+
+
+struct PoolShared {
+	queue: Mutex<PoolQueue>,
+	wake: Condvar,
+	budget: usize,
+	/// Samples decoded ahead across every wrapped decoder.
+	buffered: AtomicUsize,
+}
+
+
+struct PoolQueue {
+	jobs: VecDeque<Arc<Job>>,
+	shutdown: bool,
+}
+
+
+trait Job: Send + Sync {
+	/// Decode one block if the decoder wants more, returning whether it should be queued again.
+	fn decode_ahead(&self, pool: &PoolShared) -> bool;
+}
+
+
+struct Ahead<D> {
+	dec: Mutex<D>,
+	state: Mutex<AheadState>,
+	ready: Condvar,
+	channels: usize,
+	frequency: sys::ALint,
+	block_frames: usize,
+	ahead_frames: usize,
+}
+
+
+#[derive(Default)]
+struct AheadState {
+	blocks: VecDeque<DecodedSamples>,
+	samples: usize,
+	ended: bool,
+	error: Option<AltoError>,
+	/// The message of a panic in the decoder, which is returned by every later call, since its state is unknown.
+	panicked: Option<String>,
+	/// Whether the job is queued or being run by a worker.
+	scheduled: bool,
+	closed: bool,
+	/// Bumped by every seek, so a block decoded from the old position is thrown away.
+	generation: u64,
+}
+
+
+impl DecodePool {
+	/// Start `threads` workers that decode ahead of playback, keeping at most `budget` samples
+	/// (across all channels and decoders) decoded but not yet consumed.
+	pub fn new(threads: usize, budget: usize) -> AltoResult<DecodePool> {
+		if threads == 0 { return Err(AltoError::AlInvalidValue) }
+
+		let shared = Arc::new(PoolShared{
+			queue: Mutex::new(PoolQueue{jobs: VecDeque::new(), shutdown: false}),
+			wake: Condvar::new(),
+			budget: budget,
+			buffered: AtomicUsize::new(0),
+		});
+		let pool = DecodePool(Arc::new(PoolHandle(shared.clone())));
+		for i in 0 .. threads {
+			let shared = shared.clone();
+			thread::Builder::new().name(format!("alto-decode-{}", i)).spawn(move || work(&shared))?;
+		}
+		Ok(pool)
+	}
+
+
+	/// Decode `dec` on the pool, keeping up to `ahead_frames` sample frames decoded ahead of what has been consumed.
+	/// Blocks are decoded a quarter of that at a time. Decoding starts right away.
+	pub fn wrap<D: Decoder + Send + 'static>(&self, dec: D, ahead_frames: usize) -> PooledDecoder<D> {
+		let ahead_frames = ahead_frames.max(1);
+		let ahead = Arc::new(Ahead{
+			channels: dec.channels(),
+			frequency: dec.frequency(),
+			dec: Mutex::new(dec),
+			state: Mutex::new(AheadState::default()),
+			ready: Condvar::new(),
+			block_frames: (ahead_frames / 4).max(1),
+			ahead_frames: ahead_frames,
+		});
+		schedule(&ahead, &mut ahead.lock_state(), &(self.0).0);
+		PooledDecoder{ahead: ahead, pool: self.clone()}
+	}
+
+
+	/// The maximum number of samples decoded ahead across every wrapped decoder.
+	#[inline]
+	pub fn budget(&self) -> usize { (self.0).0.budget }
+	/// Samples currently decoded ahead across every wrapped decoder.
+	pub fn buffered(&self) -> usize { (self.0).0.buffered.load(Ordering::SeqCst) }
+}
+
+
+impl Drop for PoolHandle {
+	fn drop(&mut self) {
+		self.0.queue.lock().unwrap_or_else(|e| e.into_inner()).shutdown = true;
+		self.0.wake.notify_all();
+	}
+}
+
+
+impl PoolShared {
+	fn push(&self, job: Arc<Job>) {
+		self.queue.lock().unwrap_or_else(|e| e.into_inner()).jobs.push_back(job);
+		self.wake.notify_one();
+	}
+
+
+	fn release(&self, samples: usize) {
+		self.buffered.fetch_sub(samples, Ordering::SeqCst);
+	}
+}
+
+
+fn work(shared: &PoolShared) {
+	loop {
+		let job = {
+			let mut queue = shared.queue.lock().unwrap_or_else(|e| e.into_inner());
+			loop {
+				if queue.shutdown { return }
+				if let Some(job) = queue.jobs.pop_front() { break job }
+				queue = shared.wake.wait(queue).unwrap_or_else(|e| e.into_inner());
+			}
+		};
+		if job.decode_ahead(shared) {
+			shared.push(job);
+		}
+	}
+}
+
+
+/// Queue the decoder's job if it wants more samples and isn't queued already.
+fn schedule<D: Decoder + Send + 'static>(ahead: &Arc<Ahead<D>>, state: &mut AheadState, pool: &PoolShared) {
+	if !state.scheduled && ahead.wants_more(state, pool) {
+		state.scheduled = true;
+		pool.push(ahead.clone());
+	}
+}
+
+
+impl<D> Ahead<D> {
+	fn lock_state(&self) -> MutexGuard<AheadState> {
+		self.state.lock().unwrap_or_else(|e| e.into_inner())
+	}
+
+
+	fn wants_more(&self, state: &AheadState, pool: &PoolShared) -> bool {
+		!state.closed && !state.ended && state.error.is_none() && state.panicked.is_none()
+			&& state.samples < self.ahead_frames * self.channels
+			&& (state.samples == 0 || pool.buffered.load(Ordering::SeqCst) < pool.budget)
+	}
+}
+
+
+impl<D: Decoder + Send> Job for Ahead<D> {
+	fn decode_ahead(&self, pool: &PoolShared) -> bool {
+		// The generation is read while holding the decoder, so it matches the position decoded from.
+		let mut dec = self.dec.lock().unwrap_or_else(|e| e.into_inner());
+		let generation = {
+			let mut state = self.lock_state();
+			if !self.wants_more(&state, pool) {
+				state.scheduled = false;
+				return false;
+			}
+			state.generation
+		};
+		// The decoder's state is unknown after a panic, so it is never decoded from again, even after a seek.
+		let result = catch_panic(|| dec.decode(self.block_frames));
+		drop(dec);
+
+		let mut state = self.lock_state();
+		match result {
+			Err(AltoError::CallbackPanicked(msg)) => {
+				state.panicked = Some(msg);
+				self.ready.notify_all();
+			},
+			result => if state.generation == generation && !state.closed {
+				match result.and_then(|r| r) {
+					Ok(Some(block)) => {
+						pool.buffered.fetch_add(block.len(), Ordering::SeqCst);
+						state.samples += block.len();
+						state.blocks.push_back(block);
+					},
+					Ok(None) => state.ended = true,
+					Err(e) => state.error = Some(e),
+				}
+				self.ready.notify_all();
+			},
+		}
+		state.scheduled = self.wants_more(&state, pool);
+		state.scheduled
+	}
+}
+
+
+impl<D: Decoder + Send + 'static> PooledDecoder<D> {
+	/// Samples of this decoder currently decoded ahead.
+	pub fn buffered(&self) -> usize { self.ahead.lock_state().samples }
+}
+
+
+impl<D: Decoder + Send + 'static> Decoder for PooledDecoder<D> {
+	#[inline]
+	fn channels(&self) -> usize { self.ahead.channels }
+	#[inline]
+	fn frequency(&self) -> sys::ALint { self.ahead.frequency }
+
+
+	fn decode(&mut self, frames: usize) -> AltoResult<Option<DecodedSamples>> {
+		let want = frames * self.ahead.channels;
+		let pool = &(self.pool.0).0;
+		let mut state = self.ahead.lock_state();
+		loop {
+			if let Some(ref msg) = state.panicked { return Err(AltoError::CallbackPanicked(msg.clone())) }
+			if let Some(e) = state.error.take() { return Err(e) }
+
+			if let Some(mut block) = state.blocks.pop_front() {
+				if block.len() > want {
+					let rest = split_front(&mut block, want);
+					state.blocks.push_front(rest);
+				}
+				state.samples -= block.len();
+				pool.release(block.len());
+				schedule(&self.ahead, &mut state, pool);
+				return Ok(Some(block));
+			}
+			if state.ended { return Ok(None) }
+
+			schedule(&self.ahead, &mut state, pool);
+			state = self.ahead.ready.wait(state).unwrap_or_else(|e| e.into_inner());
+		}
+	}
+
+
+	/// Seek the wrapped decoder, discarding what was decoded ahead from the old position.
+	fn seek(&mut self, frame: u64) -> AltoResult<()> {
+		let pool = &(self.pool.0).0;
+		let mut dec = self.ahead.dec.lock().unwrap_or_else(|e| e.into_inner());
+		if let Some(ref msg) = self.ahead.lock_state().panicked { return Err(AltoError::CallbackPanicked(msg.clone())) }
+		dec.seek(frame)?;
+
+		let mut state = self.ahead.lock_state();
+		pool.release(state.samples);
+		state.generation += 1;
+		state.blocks.clear();
+		state.samples = 0;
+		state.ended = false;
+		state.error = None;
+		schedule(&self.ahead, &mut state, pool);
+		Ok(())
+	}
+}
+
+
+impl<D: Decoder + Send + 'static> Drop for PooledDecoder<D> {
+	fn drop(&mut self) {
+		let mut state = self.ahead.lock_state();
+		(self.pool.0).0.release(state.samples);
+		state.closed = true;
+		state.blocks.clear();
+		state.samples = 0;
+	}
+}
+
+
+/// Cut `block` down to its first `len` samples, returning the rest.
+fn split_front(block: &mut DecodedSamples, len: usize) -> DecodedSamples {
+	match *block {
+		DecodedSamples::U8(ref mut s) => DecodedSamples::U8(s.split_off(len)),
+		DecodedSamples::I16(ref mut s) => DecodedSamples::I16(s.split_off(len)),
+		DecodedSamples::F32(ref mut s) => DecodedSamples::F32(s.split_off(len)),
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+	use std::sync::atomic::{AtomicUsize, Ordering};
+	use std::thread;
+	use std::time::Duration;
+
+	use sys;
+	use super::*;
+
+
+	/// Decodes one block, then panics on every later call.
+	struct Panicky(Arc<AtomicUsize>);
+
+
+	impl Decoder for Panicky {
+		fn channels(&self) -> usize { 1 }
+		fn frequency(&self) -> sys::ALint { 44100 }
+		fn decode(&mut self, frames: usize) -> AltoResult<Option<DecodedSamples>> {
+			if self.0.fetch_add(1, Ordering::SeqCst) > 0 { panic!("decoder broke") }
+			Ok(Some(DecodedSamples::I16(vec![0; frames])))
+		}
+	}
+
+
+	#[test]
+	fn panicked_decoder_is_not_rescheduled() {
+		let calls = Arc::new(AtomicUsize::new(0));
+		let pool = DecodePool::new(1, 1 << 16).unwrap();
+		let mut dec = pool.wrap(Panicky(calls.clone()), 4096);
+
+		// Whether the first block is returned depends on how soon the worker decodes the next one.
+		while let Ok(Some(_)) = dec.decode(16) { }
+		for _ in 0..3 {
+			match dec.decode(16) {
+				Err(ref e) if e.is_callback_panicked() => (),
+				r => panic!("{:?}", r.map(|b| b.map(|b| b.len()))),
+			}
+		}
+		assert!(dec.seek(0).unwrap_err().is_callback_panicked());
+
+		thread::sleep(Duration::from_millis(50));
+		assert_eq!(calls.load(Ordering::SeqCst), 2);
+	}
+}
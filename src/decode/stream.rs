@@ -0,0 +1,265 @@
+use std::mem;
+use std::sync::Mutex;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use ::{AltoError, AltoResult};
+use al::*;
+use super::*;
+
+
+/// Scratch blocks kept by streams that were dropped, for the next streams to reuse.
+const SCRATCH_POOL_LEN: usize = 8;
+
+
+lazy_static! {
+	static ref SCRATCH_POOL: Mutex<Vec<DecodedSamples>> = Mutex::new(Vec::new());
+}
+
+
+/// Keeps a streaming source fed from a decoder.
+/// A fixed number of buffers are cycled through the source queue. Call
+/// [`update`](#method.update) regularly to refill the buffers that have finished playing.
+pub struct Stream<'d: 'c, 'c, D: Decoder> {
+	src: StreamingSource<'d, 'c>,
+	dec: D,
+	frames: usize,
+	/// Sample frames in each buffer on the source queue, oldest first.
+	queued: VecDeque<usize>,
+	ended: bool,
+	scratch: Scratch,
+}
+
+
+/// Streaming parameters suited to common uses, as a starting point instead of guessing at buffer sizes.
+/// Smaller buffers lower latency, but leave less room for a late `Stream::update()` before the source
+/// runs dry. Use `Stream::latency()` to check what a setup actually achieves on the target device.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum StreamPreset {
+	/// Interactive voice: 3 buffers of 20ms, updated every 5ms.
+	Voip,
+	/// Music and ambience: 4 buffers of 100ms, updated every 25ms.
+	Music,
+	/// Long tracks that must survive loading hitches: 6 buffers of 250ms, updated every 100ms.
+	Cinematic,
+}
+
+
+/// How far behind the decoder a stream's output is heard, measured by `Stream::latency()`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct StreamLatency {
+	/// Decoded audio queued on the source that hasn't been played yet.
+	pub queued: Duration,
+	/// Time between the device mixing a sample and it being heard, as measured against the device clock.
+	/// Zero if `AL_SOFT_source_latency` is unavailable.
+	pub device: Duration,
+}
+
+
+/// The block a stream decodes into, so refills don't allocate once it has grown to the size of a buffer.
+/// Returned to a small shared pool on drop, so streams that come and go reuse them too.
+struct Scratch(DecodedSamples);
+
+
+impl Scratch {
+	fn take() -> Scratch {
+		let pooled = SCRATCH_POOL.lock().unwrap_or_else(|e| e.into_inner()).pop();
+		Scratch(pooled.unwrap_or(DecodedSamples::I16(Vec::new())))
+	}
+}
+
+
+impl Drop for Scratch {
+	fn drop(&mut self) {
+		let mut block = mem::replace(&mut self.0, DecodedSamples::I16(Vec::new()));
+		block.clear();
+		let mut pool = SCRATCH_POOL.lock().unwrap_or_else(|e| e.into_inner());
+		if pool.len() < SCRATCH_POOL_LEN {
+			pool.push(block);
+		}
+	}
+}
+
+
+impl<'d: 'c, 'c, D: Decoder> Stream<'d, 'c, D> {
+	/// Create a new streaming source and queue up to `buffers` buffers of `frames` sample frames each.
+	pub fn new(ctx: &'c Context<'d>, dec: D, buffers: usize, frames: usize) -> AltoResult<Stream<'d, 'c, D>> {
+		if buffers == 0 || frames == 0 { return Err(AltoError::AlInvalidValue) }
+
+		let src = ctx.new_streaming_source()?;
+		let mut stream = Stream{src: src, dec: dec, frames: frames, queued: VecDeque::new(), ended: false, scratch: Scratch::take()};
+		let bufs = (0 .. buffers).map(|_| ctx.new_buffer()).collect::<AltoResult<_>>()?;
+		stream.queue(bufs)?;
+		Ok(stream)
+	}
+
+
+	/// Create a new streaming source with the buffer count and size of a preset.
+	pub fn with_preset(ctx: &'c Context<'d>, dec: D, preset: StreamPreset) -> AltoResult<Stream<'d, 'c, D>> {
+		let frames = preset.buffer_frames(dec.frequency());
+		Stream::new(ctx, dec, preset.buffers(), frames)
+	}
+
+
+	/// Fill buffers in order until the decoder runs out, and queue the filled ones together.
+	/// Buffers filled before a decoding error are still queued.
+	fn queue(&mut self, bufs: Vec<Buffer<'d, 'c>>) -> AltoResult<()> {
+		let mut filled = Vec::with_capacity(bufs.len());
+		let mut frames = Vec::with_capacity(bufs.len());
+		let mut result = Ok(());
+		for mut buf in bufs {
+			match if self.ended { Ok(false) } else { fill_buffer_with(&mut self.dec, &mut buf, self.frames, &mut self.scratch.0) } {
+				Ok(true) => {
+					filled.push(buf);
+					frames.push(self.scratch.0.len() / self.dec.channels());
+				},
+				Ok(false) => {
+					self.ended = true;
+					break;
+				},
+				Err(e) => {
+					result = Err(e);
+					break;
+				},
+			}
+		}
+
+		self.src.queue_buffers(filled).map_err(|(e, _)| e)?;
+		self.queued.extend(frames);
+		result
+	}
+
+
+	/// Refill and requeue any buffers that have finished playing.
+	/// Returns `false` once the decoder is exhausted and every queued buffer has been played.
+	/// If the source stopped because it ran out of buffers before the decoder did, an underrun
+	/// is reported to the metrics sink and as an `AudioEvent::Underrun` of the context.
+	/// An `AudioEvent::StreamEnded` is reported when the last buffer has been played.
+	pub fn update(&mut self) -> AltoResult<bool> {
+		let src = self.src.as_raw();
+		let processed = self.src.buffers_processed()?;
+		if !self.ended && processed > 0 && processed == self.src.buffers_queued()? && self.src.state()? == SourceState::Stopped {
+			self.src.context().device().alto().metrics().report(|m| m.underrun(src));
+			self.src.context().push_event(AudioEvent::Underrun(src));
+		}
+
+		let bufs = self.src.unqueue_buffers(processed as usize)?;
+		let unqueued = bufs.len();
+		self.queued.drain(.. unqueued);
+		self.queue(bufs)?;
+
+		let playing = !self.ended || self.src.buffers_queued()? > 0;
+		if !playing && processed > 0 {
+			self.src.context().push_event(AudioEvent::StreamEnded(src));
+		}
+		Ok(playing)
+	}
+
+
+	/// Stop playback, seek the decoder to an absolute sample frame, and queue fresh buffers.
+	/// The source must be played again afterwards.
+	pub fn seek(&mut self, frame: u64) -> AltoResult<()> {
+		self.src.stop()?;
+		let queued = self.src.buffers_queued()?;
+		let bufs = self.src.unqueue_buffers(queued as usize)?;
+		self.queued.clear();
+
+		self.dec.seek(frame)?;
+		self.ended = false;
+		self.queue(bufs)
+	}
+
+
+	/// Measure how long it currently takes for decoded audio to be heard: the audio queued ahead of
+	/// the play position, plus the output latency of the device if `AL_SOFT_source_latency` is available.
+	pub fn latency(&self) -> AltoResult<StreamLatency> {
+		let device = match self.src.soft_sec_offset_latency() {
+			Ok((_, latency)) => secs_to_duration(latency),
+			Err(ref e) if e.is_extension_not_present() => Duration::from_secs(0),
+			Err(e) => return Err(e),
+		};
+		let queued: usize = self.queued.iter().sum();
+		let ahead = queued.saturating_sub(self.src.sample_offset()? as usize);
+		Ok(StreamLatency{queued: secs_to_duration(ahead as f64 / self.dec.frequency() as f64), device: device})
+	}
+
+
+	/// Whether the decoder has run out of data.
+	#[inline]
+	pub fn ended(&self) -> bool { self.ended }
+	/// Sample frames decoded into each buffer.
+	#[inline]
+	pub fn buffer_frames(&self) -> usize { self.frames }
+
+	/// The streaming source being fed.
+	#[inline]
+	pub fn source(&self) -> &StreamingSource<'d, 'c> { &self.src }
+	#[inline]
+	pub fn source_mut(&mut self) -> &mut StreamingSource<'d, 'c> { &mut self.src }
+
+	/// The decoder producing samples.
+	#[inline]
+	pub fn decoder(&self) -> &D { &self.dec }
+	#[inline]
+	pub fn decoder_mut(&mut self) -> &mut D { &mut self.dec }
+
+
+	/// Give back the source and decoder.
+	pub fn into_inner(self) -> (StreamingSource<'d, 'c>, D) {
+		(self.src, self.dec)
+	}
+}
+
+
+impl StreamPreset {
+	/// Number of buffers cycled through the source queue.
+	pub fn buffers(self) -> usize {
+		match self {
+			StreamPreset::Voip => 3,
+			StreamPreset::Music => 4,
+			StreamPreset::Cinematic => 6,
+		}
+	}
+
+
+	/// Length of audio decoded into each buffer.
+	pub fn buffer_duration(self) -> Duration {
+		match self {
+			StreamPreset::Voip => Duration::from_millis(20),
+			StreamPreset::Music => Duration::from_millis(100),
+			StreamPreset::Cinematic => Duration::from_millis(250),
+		}
+	}
+
+
+	/// Sample frames in each buffer at a sample rate of `freq`.
+	pub fn buffer_frames(self, freq: sys::ALint) -> usize {
+		let d = self.buffer_duration();
+		let frames = (d.as_secs() as f64 + d.subsec_nanos() as f64 * 1e-9) * freq as f64;
+		(frames.round() as usize).max(1)
+	}
+
+
+	/// How often to call `Stream::update()`. Waking several times per buffer means a refill is never
+	/// more than a fraction of a buffer late, while the rest of the queue covers scheduling jitter.
+	pub fn update_interval(self) -> Duration {
+		match self {
+			StreamPreset::Voip => Duration::from_millis(5),
+			StreamPreset::Music => Duration::from_millis(25),
+			StreamPreset::Cinematic => Duration::from_millis(100),
+		}
+	}
+}
+
+
+impl StreamLatency {
+	/// The end-to-end latency.
+	#[inline]
+	pub fn total(&self) -> Duration { self.queued + self.device }
+}
+
+
+fn secs_to_duration(secs: f64) -> Duration {
+	let secs = secs.max(0.0);
+	Duration::new(secs.trunc() as u64, (secs.fract() * 1e9) as u32)
+}
@@ -0,0 +1,129 @@
+use std::io::{self, Read, Seek};
+use std::fs::File;
+use std::path::Path;
+use lewton::VorbisError;
+use lewton::inside_ogg::OggStreamReader;
+
+use ::{AltoError, AltoResult};
+use sys;
+use al::*;
+use super::*;
+
+
+/// Streaming decoder for Ogg Vorbis files, backed by `lewton`.
+/// Samples are decoded as 16 bit integers.
+pub struct VorbisDecoder<R: Read + Seek> {
+	reader: OggStreamReader<R>,
+	pending: Vec<i16>,
+}
+
+
+fn vorbis_error(e: VorbisError) -> AltoError {
+	AltoError::Io(io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+}
+
+
+/// Vorbis orders surround channels differently than OpenAL, so shuffle them into OpenAL order.
+fn reorder_channels(data: &mut [i16], channels: usize) {
+	let order: &[usize] = match channels {
+		6 => &[0, 2, 1, 5, 3, 4],
+		7 => &[0, 2, 1, 6, 3, 4, 5],
+		8 => &[0, 2, 1, 7, 5, 6, 3, 4],
+		_ => return,
+	};
+
+	let mut frame = [0; 8];
+	for chunk in data.chunks_mut(channels) {
+		frame[.. channels].copy_from_slice(chunk);
+		for (s, &i) in chunk.iter_mut().zip(order) {
+			*s = frame[i];
+		}
+	}
+}
+
+
+impl<R: Read + Seek> VorbisDecoder<R> {
+	/// Read the Vorbis headers from a reader.
+	pub fn new(reader: R) -> AltoResult<VorbisDecoder<R>> {
+		let reader = OggStreamReader::new(reader).map_err(vorbis_error)?;
+		Ok(VorbisDecoder{reader: reader, pending: Vec::new()})
+	}
+
+
+	/// Comment tags as `(key, value)` pairs.
+	pub fn comments(&self) -> &[(String, String)] {
+		&self.reader.comment_hdr.comment_list
+	}
+}
+
+
+impl VorbisDecoder<io::BufReader<File>> {
+	/// Open an Ogg Vorbis file from disk.
+	pub fn open<P: AsRef<Path>>(path: P) -> AltoResult<VorbisDecoder<io::BufReader<File>>> {
+		VorbisDecoder::new(io::BufReader::new(File::open(path)?))
+	}
+}
+
+
+impl<R: Read + Seek> Decoder for VorbisDecoder<R> {
+	fn channels(&self) -> usize { self.reader.ident_hdr.audio_channels as usize }
+	fn frequency(&self) -> sys::ALint { self.reader.ident_hdr.audio_sample_rate as sys::ALint }
+
+
+	fn decode(&mut self, frames: usize) -> AltoResult<Option<DecodedSamples>> {
+		let len = frames * self.channels();
+		while self.pending.len() < len {
+			match self.reader.read_dec_packet_itl().map_err(vorbis_error)? {
+				Some(packet) => self.pending.extend_from_slice(&packet),
+				None => break,
+			}
+		}
+
+		if self.pending.is_empty() {
+			return Ok(None);
+		}
+
+		let rest = if self.pending.len() > len { self.pending.split_off(len) } else { Vec::new() };
+		let mut block = ::std::mem::replace(&mut self.pending, rest);
+		reorder_channels(&mut block, self.channels());
+		Ok(Some(DecodedSamples::I16(block)))
+	}
+	fn decode_append(&mut self, frames: usize, out: &mut DecodedSamples) -> AltoResult<bool> {
+		let channels = self.channels();
+		let len = frames * channels;
+		while self.pending.len() < len {
+			match self.reader.read_dec_packet_itl().map_err(vorbis_error)? {
+				Some(packet) => self.pending.extend_from_slice(&packet),
+				None => break,
+			}
+		}
+
+		if self.pending.is_empty() {
+			return Ok(false);
+		}
+		let len = len.min(self.pending.len());
+		reorder_channels(&mut self.pending[..len], channels);
+		append_pending(&mut self.pending, len, out).map(|_| true)
+	}
+
+
+	/// Seeks with page granularity, so playback may resume slightly before the requested frame.
+	fn seek(&mut self, frame: u64) -> AltoResult<()> {
+		self.pending.clear();
+		self.reader.seek_absgp_pg(frame).map_err(vorbis_error)
+	}
+}
+
+
+impl<'d: 'c, 'c> Buffer<'d, 'c> {
+	/// Create a buffer from a complete Ogg Vorbis stream.
+	pub fn from_vorbis<R: Read + Seek>(ctx: &'c Context<'d>, reader: R) -> AltoResult<Buffer<'d, 'c>> {
+		Buffer::from_decoder(ctx, &mut VorbisDecoder::new(reader)?)
+	}
+
+
+	/// Create a buffer from an Ogg Vorbis file on disk.
+	pub fn from_vorbis_file<P: AsRef<Path>>(ctx: &'c Context<'d>, path: P) -> AltoResult<Buffer<'d, 'c>> {
+		Buffer::from_decoder(ctx, &mut VorbisDecoder::open(path)?)
+	}
+}
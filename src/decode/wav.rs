@@ -0,0 +1,107 @@
+use std::io::{self, Read, Seek};
+use std::fs::File;
+use std::path::Path;
+use hound;
+
+use ::{AltoError, AltoResult};
+use sys;
+use al::*;
+use super::*;
+
+
+/// Streaming decoder for RIFF WAVE files, backed by `hound`.
+/// 8 and 16 bit integer files are decoded as-is, wider integer files are reduced to 16 bits,
+/// and 32 bit float files are decoded as float samples.
+pub struct WavDecoder<R: Read> {
+	reader: hound::WavReader<R>,
+	spec: hound::WavSpec,
+}
+
+
+fn wav_error(e: hound::Error) -> AltoError {
+	match e {
+		hound::Error::IoError(e) => AltoError::Io(e),
+		e => AltoError::Io(io::Error::new(io::ErrorKind::InvalidData, e.to_string())),
+	}
+}
+
+
+impl<R: Read> WavDecoder<R> {
+	/// Read the WAVE header from a reader.
+	pub fn new(reader: R) -> AltoResult<WavDecoder<R>> {
+		let reader = hound::WavReader::new(reader).map_err(wav_error)?;
+		let spec = reader.spec();
+
+		match (spec.sample_format, spec.bits_per_sample) {
+			(hound::SampleFormat::Int, 1 ..= 32) | (hound::SampleFormat::Float, 32) => (),
+			_ => return Err(AltoError::Io(io::Error::new(io::ErrorKind::InvalidData, "Unsupported WAVE sample format"))),
+		}
+
+		Ok(WavDecoder{reader: reader, spec: spec})
+	}
+
+
+	/// Format information from the WAVE header.
+	#[inline]
+	pub fn spec(&self) -> hound::WavSpec { self.spec }
+	/// Total length of the file in sample frames.
+	#[inline]
+	pub fn duration(&self) -> u32 { self.reader.duration() }
+
+
+	fn read<S: hound::Sample, T, C: Fn(S) -> T>(&mut self, len: usize, conv: C) -> AltoResult<Vec<T>> {
+		let mut data = Vec::with_capacity(len);
+		for s in self.reader.samples::<S>().take(len) {
+			data.push(conv(s.map_err(wav_error)?));
+		}
+		Ok(data)
+	}
+}
+
+
+impl WavDecoder<io::BufReader<File>> {
+	/// Open a WAVE file from disk.
+	pub fn open<P: AsRef<Path>>(path: P) -> AltoResult<WavDecoder<io::BufReader<File>>> {
+		WavDecoder::new(io::BufReader::new(File::open(path)?))
+	}
+}
+
+
+impl<R: Read + Seek> Decoder for WavDecoder<R> {
+	fn channels(&self) -> usize { self.spec.channels as usize }
+	fn frequency(&self) -> sys::ALint { self.spec.sample_rate as sys::ALint }
+
+
+	fn decode(&mut self, frames: usize) -> AltoResult<Option<DecodedSamples>> {
+		let len = frames * self.channels();
+		let bits = self.spec.bits_per_sample;
+		let samples = match self.spec.sample_format {
+			hound::SampleFormat::Float => DecodedSamples::F32(self.read::<f32, _, _>(len, |s| s)?),
+			hound::SampleFormat::Int if bits <= 8 => DecodedSamples::U8(self.read::<i8, _, _>(len, |s| (s as i16 + 128) as u8)?),
+			hound::SampleFormat::Int if bits <= 16 => DecodedSamples::I16(self.read::<i16, _, _>(len, |s| s)?),
+			hound::SampleFormat::Int => DecodedSamples::I16(self.read::<i32, _, _>(len, |s| (s >> (bits - 16)) as i16)?),
+		};
+
+		if samples.is_empty() { Ok(None) } else { Ok(Some(samples)) }
+	}
+
+
+	fn seek(&mut self, frame: u64) -> AltoResult<()> {
+		if frame > u32::max_value() as u64 { return Err(AltoError::AlInvalidValue) }
+		self.reader.seek(frame as u32).map_err(AltoError::Io)
+	}
+}
+
+
+impl<'d: 'c, 'c> Buffer<'d, 'c> {
+	/// Create a buffer from a complete WAVE stream.
+	pub fn from_wav<R: Read + Seek>(ctx: &'c Context<'d>, reader: R) -> AltoResult<Buffer<'d, 'c>> {
+		Buffer::from_decoder(ctx, &mut WavDecoder::new(reader)?)
+	}
+
+
+	/// Create a buffer from a WAVE file on disk.
+	pub fn from_wav_file<P: AsRef<Path>>(ctx: &'c Context<'d>, path: P) -> AltoResult<Buffer<'d, 'c>> {
+		Buffer::from_decoder(ctx, &mut WavDecoder::open(path)?)
+	}
+}
@@ -2,7 +2,7 @@ use std::sync::Weak;
 use std::io::{self, Write};
 use enum_primitive::FromPrimitive;
 
-use ::{AltoError, AltoResult};
+use ::{AltoError, AltoResult, ObjectKind};
 use sys;
 use al;
 
@@ -270,7 +270,7 @@ impl<'d: 'c, 'c> AuxEffectSlot<'d, 'c> {
 		let _lock = ctx.make_current(true)?;
 		let mut slot = 0;
 		unsafe { efx.alGenAuxiliaryEffectSlots?(1, &mut slot); }
-		ctx.get_error()?;
+		ctx.get_error().map_err(|e| e.at("alGenAuxiliaryEffectSlots", ObjectKind::EffectSlot, None))?;
 		Ok(AuxEffectSlot{ctx: ctx, slot: slot, inputs: Vec::new()})
 	}
 
@@ -294,19 +294,19 @@ impl<'d: 'c, 'c> AuxEffectSlot<'d, 'c> {
 	/// `alAuxiliaryEffectSloti(AL_EFFECTSLOT_EFFECT)`
 	pub fn set_effect<E: EffectTrait<'d, 'c>>(&mut self, value: &E) -> AltoResult<()> {
 		if value.context() != self.ctx {
-			return Err(AltoError::AlInvalidValue);
+			return Err(AltoError::AlWrongContext);
 		}
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alAuxiliaryEffectSloti?(self.slot, efx.AL_EFFECTSLOT_EFFECT?, value.as_raw() as sys::ALint); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alAuxiliaryEffectSloti", ObjectKind::EffectSlot, Some("AL_EFFECTSLOT_EFFECT".into())))
 	}
 	/// `alAuxiliaryEffectSloti(AL_EFFECTSLOT_EFFECT)`
 	pub fn clear_effect(&mut self) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alAuxiliaryEffectSloti?(self.slot, efx.AL_EFFECTSLOT_EFFECT?, 0); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alAuxiliaryEffectSloti", ObjectKind::EffectSlot, Some("AL_EFFECTSLOT_EFFECT".into())))
 	}
 
 
@@ -316,15 +316,19 @@ impl<'d: 'c, 'c> AuxEffectSlot<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetAuxiliaryEffectSlotf?(self.slot, efx.AL_EFFECTSLOT_GAIN?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetAuxiliaryEffectSlotf", ObjectKind::EffectSlot, Some("AL_EFFECTSLOT_GAIN".into()))).map(|_| value)
 	}
 	/// `alAuxiliaryEffectSloti(AL_EFFECTSLOT_GAIN)`
 	pub fn set_gain(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alAuxiliaryEffectSlotf?(self.slot, efx.AL_EFFECTSLOT_GAIN?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alAuxiliaryEffectSlotf", ObjectKind::EffectSlot, Some(("AL_EFFECTSLOT_GAIN", value).into())))
 	}
+	/// `alGetAuxiliaryEffectSlotf(AL_EFFECTSLOT_GAIN)` in decibels, where silence is `-inf`.
+	pub fn gain_db(&self) -> AltoResult<f32> { self.gain().map(al::gain_to_db) }
+	/// `alAuxiliaryEffectSlotf(AL_EFFECTSLOT_GAIN)` in decibels, where `-inf` mutes everything sent to the slot.
+	pub fn set_gain_db(&mut self, db: f32) -> AltoResult<()> { self.set_gain(al::db_to_gain(db)) }
 
 
 	/// `alGetAuxiliaryEffectSloti(AL_EFFECTSLOT_AUXILIARY_SEND_AUTO)`
@@ -333,14 +337,14 @@ impl<'d: 'c, 'c> AuxEffectSlot<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { efx.alGetAuxiliaryEffectSloti?(self.slot, efx.AL_EFFECTSLOT_AUXILIARY_SEND_AUTO?, &mut value); }
-		self.ctx.get_error().map(|_| value == sys::AL_TRUE as sys::ALint)
+		self.ctx.get_error().map_err(|e| e.at("alGetAuxiliaryEffectSloti", ObjectKind::EffectSlot, Some("AL_EFFECTSLOT_AUXILIARY_SEND_AUTO".into()))).map(|_| value == sys::AL_TRUE as sys::ALint)
 	}
 	/// `alAuxiliaryEffectSloti(AL_EFFECTSLOT_AUXILIARY_SEND_AUTO)`
 	pub fn set_auxiliary_send_auto(&mut self, value: bool) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alAuxiliaryEffectSloti?(self.slot, efx.AL_EFFECTSLOT_AUXILIARY_SEND_AUTO?, if value { sys::AL_TRUE } else { sys::AL_FALSE } as sys::ALint); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alAuxiliaryEffectSloti", ObjectKind::EffectSlot, Some(("AL_EFFECTSLOT_AUXILIARY_SEND_AUTO", value).into())))
 	}
 }
 
@@ -348,7 +352,7 @@ impl<'d: 'c, 'c> AuxEffectSlot<'d, 'c> {
 impl<'d: 'c, 'c> Drop for AuxEffectSlot<'d, 'c> {
 	fn drop(&mut self) {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX().unwrap();
-		if let Ok(_lock) = self.ctx.make_current(true) {
+		if let Ok(_lock) = self.ctx.make_current_unchecked(true) {
 			for src in self.inputs.drain(..) {
 				if let Some(src) = src.upgrade() {
 					if let Err(_) = src.clear_auxiliary_effect_slot(self.slot) {
@@ -374,10 +378,10 @@ unsafe impl<'d: 'c, 'c> EffectTrait<'d, 'c> for EaxReverbEffect<'d, 'c> {
 		let _lock = ctx.make_current(true)?;
 		let mut effect = 0;
 		unsafe { efx.alGenEffects?(1, &mut effect); }
-		ctx.get_error()?;
+		ctx.get_error().map_err(|e| e.at("alGenEffects", ObjectKind::Effect, None))?;
 		let effect = EaxReverbEffect{ctx: ctx, effect: effect};
 		unsafe { efx.alEffecti?(effect.as_raw(), efx.AL_EFFECT_TYPE?, efx.AL_EFFECT_EAXREVERB?); }
-		ctx.get_error().map(|_| effect)
+		ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some("AL_EFFECT_TYPE".into()))).map(|_| effect)
 	}
 
 
@@ -427,14 +431,14 @@ impl<'d: 'c, 'c> EaxReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EAXREVERB_DENSITY?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EAXREVERB_DENSITY".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EAXREVERB_DENSITY)`
 	pub fn set_density(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EAXREVERB_DENSITY?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EAXREVERB_DENSITY", value).into())))
 	}
 
 
@@ -444,14 +448,14 @@ impl<'d: 'c, 'c> EaxReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EAXREVERB_DIFFUSION?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EAXREVERB_DIFFUSION".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EAXREVERB_DIFFUSION)`
 	pub fn set_diffusion(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EAXREVERB_DIFFUSION?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EAXREVERB_DIFFUSION", value).into())))
 	}
 
 
@@ -461,14 +465,14 @@ impl<'d: 'c, 'c> EaxReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EAXREVERB_GAIN?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EAXREVERB_GAIN".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EAXREVERB_GAIN)`
 	pub fn set_gain(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EAXREVERB_GAIN?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EAXREVERB_GAIN", value).into())))
 	}
 
 
@@ -478,14 +482,14 @@ impl<'d: 'c, 'c> EaxReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EAXREVERB_GAINHF?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EAXREVERB_GAINHF".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EAXREVERB_GAINHF)`
 	pub fn set_gainhf(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EAXREVERB_GAINHF?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EAXREVERB_GAINHF", value).into())))
 	}
 
 
@@ -495,14 +499,14 @@ impl<'d: 'c, 'c> EaxReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EAXREVERB_GAINLF?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EAXREVERB_GAINLF".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EAXREVERB_GAINLF)`
 	pub fn set_gainlf(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EAXREVERB_GAINLF?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EAXREVERB_GAINLF", value).into())))
 	}
 
 
@@ -512,14 +516,14 @@ impl<'d: 'c, 'c> EaxReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EAXREVERB_DECAY_TIME?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EAXREVERB_DECAY_TIME".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EAXREVERB_DECAY_TIME)`
 	pub fn set_decay_time(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EAXREVERB_DECAY_TIME?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EAXREVERB_DECAY_TIME", value).into())))
 	}
 
 
@@ -529,14 +533,14 @@ impl<'d: 'c, 'c> EaxReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EAXREVERB_DECAY_HFRATIO?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EAXREVERB_DECAY_HFRATIO".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EAXREVERB_DECAY_HFRATIO)`
 	pub fn set_decay_hfratio(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EAXREVERB_DECAY_HFRATIO?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EAXREVERB_DECAY_HFRATIO", value).into())))
 	}
 
 
@@ -546,14 +550,14 @@ impl<'d: 'c, 'c> EaxReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EAXREVERB_DECAY_LFRATIO?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EAXREVERB_DECAY_LFRATIO".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EAXREVERB_DECAY_LFRATIO)`
 	pub fn set_decay_lfratio(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EAXREVERB_DECAY_LFRATIO?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EAXREVERB_DECAY_LFRATIO", value).into())))
 	}
 
 
@@ -563,14 +567,14 @@ impl<'d: 'c, 'c> EaxReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EAXREVERB_REFLECTIONS_GAIN?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EAXREVERB_REFLECTIONS_GAIN".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EAXREVERB_REFLECTIONS_GAIN)`
 	pub fn set_reflections_gain(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EAXREVERB_REFLECTIONS_GAIN?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EAXREVERB_REFLECTIONS_GAIN", value).into())))
 	}
 
 
@@ -580,14 +584,14 @@ impl<'d: 'c, 'c> EaxReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EAXREVERB_REFLECTIONS_DELAY?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EAXREVERB_REFLECTIONS_DELAY".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EAXREVERB_REFLECTIONS_DELAY)`
 	pub fn set_reflections_delay(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EAXREVERB_REFLECTIONS_DELAY?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EAXREVERB_REFLECTIONS_DELAY", value).into())))
 	}
 
 
@@ -597,14 +601,14 @@ impl<'d: 'c, 'c> EaxReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = [0.0, 0.0, 0.0];
 		unsafe { efx.alGetEffectfv?(self.effect, efx.AL_EAXREVERB_REFLECTIONS_PAN?, &mut value as *mut [f32; 3] as *mut f32); }
-		self.ctx.get_error().map(|_| value.into())
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectfv", ObjectKind::Effect, Some("AL_EAXREVERB_REFLECTIONS_PAN".into()))).map(|_| value.into())
 	}
 	/// `alEffectfv(AL_EAXREVERB_REFLECTIONS_PAN)`
 	pub fn set_reflections_pan<V: Into<[f32; 3]>>(&mut self, value: V) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectfv?(self.effect, efx.AL_EAXREVERB_REFLECTIONS_PAN?, &mut value.into() as *mut [f32; 3] as *mut f32); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectfv", ObjectKind::Effect, Some("AL_EAXREVERB_REFLECTIONS_PAN".into())))
 	}
 
 
@@ -614,14 +618,14 @@ impl<'d: 'c, 'c> EaxReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EAXREVERB_LATE_REVERB_GAIN?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EAXREVERB_LATE_REVERB_GAIN".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EAXREVERB_LATE_REVERB_GAIN)`
 	pub fn set_late_reverb_gain(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EAXREVERB_LATE_REVERB_GAIN?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EAXREVERB_LATE_REVERB_GAIN", value).into())))
 	}
 
 
@@ -631,14 +635,14 @@ impl<'d: 'c, 'c> EaxReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EAXREVERB_LATE_REVERB_DELAY?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EAXREVERB_LATE_REVERB_DELAY".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EAXREVERB_LATE_REVERB_DELAY)`
 	pub fn set_late_reverb_delay(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EAXREVERB_LATE_REVERB_DELAY?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EAXREVERB_LATE_REVERB_DELAY", value).into())))
 	}
 
 
@@ -648,14 +652,14 @@ impl<'d: 'c, 'c> EaxReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = [0.0, 0.0, 0.0];
 		unsafe { efx.alGetEffectfv?(self.effect, efx.AL_EAXREVERB_LATE_REVERB_PAN?, &mut value as *mut [f32; 3] as *mut f32); }
-		self.ctx.get_error().map(|_| value.into())
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectfv", ObjectKind::Effect, Some("AL_EAXREVERB_LATE_REVERB_PAN".into()))).map(|_| value.into())
 	}
 	/// `alEffectfv(AL_EAXREVERB_LATE_REVERB_PAN)`
 	pub fn set_late_reverb_pan<V: Into<[f32; 3]>>(&mut self, value: V) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectfv?(self.effect, efx.AL_EAXREVERB_LATE_REVERB_PAN?, &mut value.into() as *mut [f32; 3] as *mut f32); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectfv", ObjectKind::Effect, Some("AL_EAXREVERB_LATE_REVERB_PAN".into())))
 	}
 
 
@@ -665,14 +669,14 @@ impl<'d: 'c, 'c> EaxReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EAXREVERB_ECHO_TIME?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EAXREVERB_ECHO_TIME".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EAXREVERB_ECHO_TIME)`
 	pub fn set_echo_time(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EAXREVERB_ECHO_TIME?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EAXREVERB_ECHO_TIME", value).into())))
 	}
 
 
@@ -682,14 +686,14 @@ impl<'d: 'c, 'c> EaxReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EAXREVERB_ECHO_DEPTH?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EAXREVERB_ECHO_DEPTH".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EAXREVERB_ECHO_DEPTH)`
 	pub fn set_echo_depth(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EAXREVERB_ECHO_DEPTH?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EAXREVERB_ECHO_DEPTH", value).into())))
 	}
 
 
@@ -699,14 +703,14 @@ impl<'d: 'c, 'c> EaxReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EAXREVERB_MODULATION_TIME?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EAXREVERB_MODULATION_TIME".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EAXREVERB_MODULATION_TIME)`
 	pub fn set_modulation_time(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EAXREVERB_MODULATION_TIME?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EAXREVERB_MODULATION_TIME", value).into())))
 	}
 
 
@@ -716,14 +720,14 @@ impl<'d: 'c, 'c> EaxReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EAXREVERB_MODULATION_DEPTH?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EAXREVERB_MODULATION_DEPTH".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EAXREVERB_MODULATION_DEPTH)`
 	pub fn set_modulation_depth(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EAXREVERB_MODULATION_DEPTH?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EAXREVERB_MODULATION_DEPTH", value).into())))
 	}
 
 
@@ -733,14 +737,14 @@ impl<'d: 'c, 'c> EaxReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EAXREVERB_HFREFERENCE?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EAXREVERB_HFREFERENCE".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EAXREVERB_HFREFERENCE)`
 	pub fn set_hfreference(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EAXREVERB_HFREFERENCE?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EAXREVERB_HFREFERENCE", value).into())))
 	}
 
 
@@ -750,14 +754,14 @@ impl<'d: 'c, 'c> EaxReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EAXREVERB_LFREFERENCE?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EAXREVERB_LFREFERENCE".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EAXREVERB_LFREFERENCE)`
 	pub fn set_lfreference(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EAXREVERB_LFREFERENCE?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EAXREVERB_LFREFERENCE", value).into())))
 	}
 
 
@@ -767,14 +771,14 @@ impl<'d: 'c, 'c> EaxReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EAXREVERB_AIR_ABSORPTION_GAINHF?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EAXREVERB_AIR_ABSORPTION_GAINHF".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EAXREVERB_AIR_ABSORPTION_GAINHF)`
 	pub fn set_air_absorption_gainhf(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EAXREVERB_AIR_ABSORPTION_GAINHF?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EAXREVERB_AIR_ABSORPTION_GAINHF", value).into())))
 	}
 
 
@@ -784,14 +788,14 @@ impl<'d: 'c, 'c> EaxReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EAXREVERB_ROOM_ROLLOFF_FACTOR?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EAXREVERB_ROOM_ROLLOFF_FACTOR".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EAXREVERB_ROOM_ROLLOFF_FACTOR)`
 	pub fn set_room_rolloff_factor(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EAXREVERB_ROOM_ROLLOFF_FACTOR?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EAXREVERB_ROOM_ROLLOFF_FACTOR", value).into())))
 	}
 
 
@@ -801,14 +805,14 @@ impl<'d: 'c, 'c> EaxReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { efx.alGetEffecti?(self.effect, efx.AL_EAXREVERB_DECAY_HFLIMIT?, &mut value); }
-		self.ctx.get_error().map(|_| value == sys::AL_TRUE as sys::ALint)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffecti", ObjectKind::Effect, Some("AL_EAXREVERB_DECAY_HFLIMIT".into()))).map(|_| value == sys::AL_TRUE as sys::ALint)
 	}
 	/// `alEffecti(AL_EAXREVERB_DECAY_HFLIMIT)`
 	pub fn set_decay_hflimit(&mut self, value: bool) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffecti?(self.effect, efx.AL_EAXREVERB_DECAY_HFLIMIT?, if value { sys::AL_TRUE } else { sys::AL_FALSE } as sys::ALint); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some(("AL_EAXREVERB_DECAY_HFLIMIT", value).into())))
 	}
 }
 
@@ -816,7 +820,7 @@ impl<'d: 'c, 'c> EaxReverbEffect<'d, 'c> {
 impl<'d: 'c, 'c> Drop for EaxReverbEffect<'d, 'c> {
 	fn drop(&mut self) {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX().unwrap();
-		if let Ok(_lock) = self.ctx.make_current(true) {
+		if let Ok(_lock) = self.ctx.make_current_unchecked(true) {
 			unsafe { efx.alDeleteEffects.unwrap()(1, &mut self.effect as *mut sys::ALuint); }
 			if let Err(_) = self.ctx.get_error() {
 				let _ = writeln!(io::stderr(), "ALTO ERROR: `alDeleteEffects` failed in EaxReverbEffect drop");
@@ -834,10 +838,10 @@ unsafe impl<'d: 'c, 'c> EffectTrait<'d, 'c> for ReverbEffect<'d, 'c> {
 		let _lock = ctx.make_current(true)?;
 		let mut effect = 0;
 		unsafe { efx.alGenEffects?(1, &mut effect); }
-		ctx.get_error()?;
+		ctx.get_error().map_err(|e| e.at("alGenEffects", ObjectKind::Effect, None))?;
 		let effect = ReverbEffect{ctx: ctx, effect: effect};
 		unsafe { efx.alEffecti?(effect.as_raw(), efx.AL_EFFECT_TYPE?, efx.AL_EFFECT_REVERB?); }
-		ctx.get_error().map(|_| effect)
+		ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some("AL_EFFECT_TYPE".into()))).map(|_| effect)
 	}
 
 
@@ -877,14 +881,14 @@ impl<'d: 'c, 'c> ReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_REVERB_DENSITY?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_REVERB_DENSITY".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_REVERB_DENSITY)`
 	pub fn set_density(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_REVERB_DENSITY?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_REVERB_DENSITY", value).into())))
 	}
 
 
@@ -894,14 +898,14 @@ impl<'d: 'c, 'c> ReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_REVERB_DIFFUSION?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_REVERB_DIFFUSION".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_REVERB_DIFFUSION)`
 	pub fn set_diffusion(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_REVERB_DIFFUSION?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_REVERB_DIFFUSION", value).into())))
 	}
 
 
@@ -911,14 +915,14 @@ impl<'d: 'c, 'c> ReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_REVERB_GAIN?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_REVERB_GAIN".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_REVERB_GAIN)`
 	pub fn set_gain(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_REVERB_GAIN?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_REVERB_GAIN", value).into())))
 	}
 
 
@@ -928,14 +932,14 @@ impl<'d: 'c, 'c> ReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_REVERB_GAINHF?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_REVERB_GAINHF".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_REVERB_GAINHF)`
 	pub fn set_gainhf(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_REVERB_GAINHF?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_REVERB_GAINHF", value).into())))
 	}
 
 
@@ -945,14 +949,14 @@ impl<'d: 'c, 'c> ReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_REVERB_DECAY_TIME?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_REVERB_DECAY_TIME".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_REVERB_DECAY_TIME)`
 	pub fn set_decay_time(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_REVERB_DECAY_TIME?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_REVERB_DECAY_TIME", value).into())))
 	}
 
 
@@ -962,14 +966,14 @@ impl<'d: 'c, 'c> ReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_REVERB_DECAY_HFRATIO?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_REVERB_DECAY_HFRATIO".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_REVERB_DECAY_HFRATIO)`
 	pub fn set_decay_hfratio(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_REVERB_DECAY_HFRATIO?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_REVERB_DECAY_HFRATIO", value).into())))
 	}
 
 
@@ -979,14 +983,14 @@ impl<'d: 'c, 'c> ReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_REVERB_REFLECTIONS_GAIN?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_REVERB_REFLECTIONS_GAIN".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_REVERB_REFLECTIONS_GAIN)`
 	pub fn set_reflections_gain(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_REVERB_REFLECTIONS_GAIN?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_REVERB_REFLECTIONS_GAIN", value).into())))
 	}
 
 
@@ -996,14 +1000,14 @@ impl<'d: 'c, 'c> ReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_REVERB_REFLECTIONS_DELAY?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_REVERB_REFLECTIONS_DELAY".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_REVERB_REFLECTIONS_DELAY)`
 	pub fn set_reflections_delay(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_REVERB_REFLECTIONS_DELAY?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_REVERB_REFLECTIONS_DELAY", value).into())))
 	}
 
 
@@ -1013,14 +1017,14 @@ impl<'d: 'c, 'c> ReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_REVERB_LATE_REVERB_GAIN?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_REVERB_LATE_REVERB_GAIN".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_REVERB_LATE_REVERB_GAIN)`
 	pub fn set_late_reverb_gain(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_REVERB_LATE_REVERB_GAIN?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_REVERB_LATE_REVERB_GAIN", value).into())))
 	}
 
 
@@ -1030,14 +1034,14 @@ impl<'d: 'c, 'c> ReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_REVERB_LATE_REVERB_DELAY?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_REVERB_LATE_REVERB_DELAY".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_REVERB_LATE_REVERB_DELAY)`
 	pub fn set_late_reverb_delay(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_REVERB_LATE_REVERB_DELAY?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_REVERB_LATE_REVERB_DELAY", value).into())))
 	}
 
 
@@ -1047,14 +1051,14 @@ impl<'d: 'c, 'c> ReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_REVERB_AIR_ABSORPTION_GAINHF?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_REVERB_AIR_ABSORPTION_GAINHF".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_REVERB_AIR_ABSORPTION_GAINHF)`
 	pub fn set_air_absorption_gainhf(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_REVERB_AIR_ABSORPTION_GAINHF?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_REVERB_AIR_ABSORPTION_GAINHF", value).into())))
 	}
 
 
@@ -1064,14 +1068,14 @@ impl<'d: 'c, 'c> ReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_REVERB_ROOM_ROLLOFF_FACTOR?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_REVERB_ROOM_ROLLOFF_FACTOR".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_REVERB_ROOM_ROLLOFF_FACTOR)`
 	pub fn set_room_rolloff_factor(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_REVERB_ROOM_ROLLOFF_FACTOR?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_REVERB_ROOM_ROLLOFF_FACTOR", value).into())))
 	}
 
 
@@ -1081,14 +1085,14 @@ impl<'d: 'c, 'c> ReverbEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { efx.alGetEffecti?(self.effect, efx.AL_REVERB_DECAY_HFLIMIT?, &mut value); }
-		self.ctx.get_error().map(|_| value == sys::AL_TRUE as sys::ALint)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffecti", ObjectKind::Effect, Some("AL_REVERB_DECAY_HFLIMIT".into()))).map(|_| value == sys::AL_TRUE as sys::ALint)
 	}
 	/// `alEffecti(AL_REVERB_DECAY_HFLIMIT)`
 	pub fn set_decay_hflimit(&mut self, value: bool) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffecti?(self.effect, efx.AL_REVERB_DECAY_HFLIMIT?, if value { sys::AL_TRUE } else { sys::AL_FALSE } as sys::ALint); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some(("AL_REVERB_DECAY_HFLIMIT", value).into())))
 	}
 }
 
@@ -1096,7 +1100,7 @@ impl<'d: 'c, 'c> ReverbEffect<'d, 'c> {
 impl<'d: 'c, 'c> Drop for ReverbEffect<'d, 'c> {
 	fn drop(&mut self) {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX().unwrap();
-		if let Ok(_lock) = self.ctx.make_current(true) {
+		if let Ok(_lock) = self.ctx.make_current_unchecked(true) {
 			unsafe { efx.alDeleteEffects.unwrap()(1, &mut self.effect as *mut sys::ALuint); }
 			if let Err(_) = self.ctx.get_error() {
 				let _ = writeln!(io::stderr(), "ALTO ERROR: `alDeleteEffects` failed in ReverbEffect drop");
@@ -1114,10 +1118,10 @@ unsafe impl<'d: 'c, 'c> EffectTrait<'d, 'c> for ChorusEffect<'d, 'c> {
 		let _lock = ctx.make_current(true)?;
 		let mut effect = 0;
 		unsafe { efx.alGenEffects?(1, &mut effect); }
-		ctx.get_error()?;
+		ctx.get_error().map_err(|e| e.at("alGenEffects", ObjectKind::Effect, None))?;
 		let effect = ChorusEffect{ctx: ctx, effect: effect};
 		unsafe { efx.alEffecti?(effect.as_raw(), efx.AL_EFFECT_TYPE?, efx.AL_EFFECT_CHORUS?); }
-		ctx.get_error().map(|_| effect)
+		ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some("AL_EFFECT_TYPE".into()))).map(|_| effect)
 	}
 
 
@@ -1135,14 +1139,14 @@ impl<'d: 'c, 'c> ChorusEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { efx.alGetEffecti?(self.effect, efx.AL_CHORUS_WAVEFORM?, &mut value); }
-		self.ctx.get_error().and_then(|_| ChorusWaveform::from_i32(value as i32).ok_or(AltoError::AlInvalidValue))
+		self.ctx.get_error().map_err(|e| e.at("alGetEffecti", ObjectKind::Effect, Some("AL_CHORUS_WAVEFORM".into()))).and_then(|_| ChorusWaveform::from_i32(value as i32).ok_or(AltoError::AlInvalidValue))
 	}
 	/// `alEffecti(AL_CHORUS_WAVEFORM)`
 	pub fn set_waveform(&mut self, value: ChorusWaveform) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffecti?(self.effect, efx.AL_CHORUS_WAVEFORM?, value as sys::ALint) };
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some(("AL_CHORUS_WAVEFORM", value as sys::ALint).into())))
 	}
 
 
@@ -1152,14 +1156,14 @@ impl<'d: 'c, 'c> ChorusEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { efx.alGetEffecti?(self.effect, efx.AL_CHORUS_PHASE?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffecti", ObjectKind::Effect, Some("AL_CHORUS_PHASE".into()))).map(|_| value)
 	}
 	/// `alEffecti(AL_CHORUS_PHASE)`
 	pub fn set_phase(&mut self, value: sys::ALint) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffecti?(self.effect, efx.AL_CHORUS_PHASE?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some(("AL_CHORUS_PHASE", value).into())))
 	}
 
 
@@ -1169,14 +1173,14 @@ impl<'d: 'c, 'c> ChorusEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_CHORUS_RATE?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_CHORUS_RATE".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_CHORUS_RATE)`
 	pub fn set_rate(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_CHORUS_RATE?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_CHORUS_RATE", value).into())))
 	}
 
 
@@ -1186,14 +1190,14 @@ impl<'d: 'c, 'c> ChorusEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_CHORUS_DEPTH?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_CHORUS_DEPTH".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_CHORUS_DEPTH)`
 	pub fn set_depth(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_CHORUS_DEPTH?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_CHORUS_DEPTH", value).into())))
 	}
 
 
@@ -1203,14 +1207,14 @@ impl<'d: 'c, 'c> ChorusEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_CHORUS_FEEDBACK?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_CHORUS_FEEDBACK".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_CHORUS_FEEDBACK)`
 	pub fn set_feedback(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_CHORUS_FEEDBACK?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_CHORUS_FEEDBACK", value).into())))
 	}
 
 
@@ -1220,14 +1224,14 @@ impl<'d: 'c, 'c> ChorusEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_CHORUS_DELAY?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_CHORUS_DELAY".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_CHORUS_DELAY)`
 	pub fn set_delay(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_CHORUS_DELAY?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_CHORUS_DELAY", value).into())))
 	}
 }
 
@@ -1235,7 +1239,7 @@ impl<'d: 'c, 'c> ChorusEffect<'d, 'c> {
 impl<'d: 'c, 'c> Drop for ChorusEffect<'d, 'c> {
 	fn drop(&mut self) {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX().unwrap();
-		if let Ok(_lock) = self.ctx.make_current(true) {
+		if let Ok(_lock) = self.ctx.make_current_unchecked(true) {
 			unsafe { efx.alDeleteEffects.unwrap()(1, &mut self.effect as *mut sys::ALuint); }
 			if let Err(_) = self.ctx.get_error() {
 				let _ = writeln!(io::stderr(), "ALTO ERROR: `alDeleteEffects` failed in ChorusEffect drop");
@@ -1253,10 +1257,10 @@ unsafe impl<'d: 'c, 'c> EffectTrait<'d, 'c> for DistortionEffect<'d, 'c> {
 		let _lock = ctx.make_current(true)?;
 		let mut effect = 0;
 		unsafe { efx.alGenEffects?(1, &mut effect); }
-		ctx.get_error()?;
+		ctx.get_error().map_err(|e| e.at("alGenEffects", ObjectKind::Effect, None))?;
 		let effect = DistortionEffect{ctx: ctx, effect: effect};
 		unsafe { efx.alEffecti?(effect.as_raw(), efx.AL_EFFECT_TYPE?, efx.AL_EFFECT_DISTORTION?); }
-		ctx.get_error().map(|_| effect)
+		ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some("AL_EFFECT_TYPE".into()))).map(|_| effect)
 	}
 
 
@@ -1274,14 +1278,14 @@ impl<'d: 'c, 'c> DistortionEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_DISTORTION_EDGE?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_DISTORTION_EDGE".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_DISTORTION_EDGE)`
 	pub fn set_edge(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_DISTORTION_EDGE?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_DISTORTION_EDGE", value).into())))
 	}
 
 
@@ -1291,14 +1295,14 @@ impl<'d: 'c, 'c> DistortionEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_DISTORTION_LOWPASS_CUTOFF?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_DISTORTION_LOWPASS_CUTOFF".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_DISTORTION_LOWPASS_CUTOFF)`
 	pub fn set_lowpass_cutoff(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_DISTORTION_LOWPASS_CUTOFF?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_DISTORTION_LOWPASS_CUTOFF", value).into())))
 	}
 
 
@@ -1308,14 +1312,14 @@ impl<'d: 'c, 'c> DistortionEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_DISTORTION_EQCENTER?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_DISTORTION_EQCENTER".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_DISTORTION_EQCENTER)`
 	pub fn set_eqcenter(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_DISTORTION_EQCENTER?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_DISTORTION_EQCENTER", value).into())))
 	}
 
 
@@ -1325,14 +1329,14 @@ impl<'d: 'c, 'c> DistortionEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_DISTORTION_EQBANDWIDTH?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_DISTORTION_EQBANDWIDTH".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_DISTORTION_EQBANDWIDTH)`
 	pub fn set_eqbandwidth(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_DISTORTION_EQBANDWIDTH?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_DISTORTION_EQBANDWIDTH", value).into())))
 	}
 }
 
@@ -1340,7 +1344,7 @@ impl<'d: 'c, 'c> DistortionEffect<'d, 'c> {
 impl<'d: 'c, 'c> Drop for DistortionEffect<'d, 'c> {
 	fn drop(&mut self) {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX().unwrap();
-		if let Ok(_lock) = self.ctx.make_current(true) {
+		if let Ok(_lock) = self.ctx.make_current_unchecked(true) {
 			unsafe { efx.alDeleteEffects.unwrap()(1, &mut self.effect as *mut sys::ALuint); }
 			if let Err(_) = self.ctx.get_error() {
 				let _ = writeln!(io::stderr(), "ALTO ERROR: `alDeleteEffects` failed in DistortionEffect drop");
@@ -1358,10 +1362,10 @@ unsafe impl<'d: 'c, 'c> EffectTrait<'d, 'c> for EchoEffect<'d, 'c> {
 		let _lock = ctx.make_current(true)?;
 		let mut effect = 0;
 		unsafe { efx.alGenEffects?(1, &mut effect); }
-		ctx.get_error()?;
+		ctx.get_error().map_err(|e| e.at("alGenEffects", ObjectKind::Effect, None))?;
 		let effect = EchoEffect{ctx: ctx, effect: effect};
 		unsafe { efx.alEffecti?(effect.as_raw(), efx.AL_EFFECT_TYPE?, efx.AL_EFFECT_ECHO?); }
-		ctx.get_error().map(|_| effect)
+		ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some("AL_EFFECT_TYPE".into()))).map(|_| effect)
 	}
 
 
@@ -1379,14 +1383,14 @@ impl<'d: 'c, 'c> EchoEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_ECHO_DELAY?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_ECHO_DELAY".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_ECHO_DELAY)`
 	pub fn set_delay(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_ECHO_DELAY?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_ECHO_DELAY", value).into())))
 	}
 
 
@@ -1396,14 +1400,14 @@ impl<'d: 'c, 'c> EchoEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_ECHO_LRDELAY?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_ECHO_LRDELAY".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_ECHO_LRDELAY)`
 	pub fn set_lrdelay(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_ECHO_LRDELAY?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_ECHO_LRDELAY", value).into())))
 	}
 
 
@@ -1413,14 +1417,14 @@ impl<'d: 'c, 'c> EchoEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_ECHO_DAMPING?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_ECHO_DAMPING".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_ECHO_DAMPING)`
 	pub fn set_damping(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_ECHO_DAMPING?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_ECHO_DAMPING", value).into())))
 	}
 
 
@@ -1430,14 +1434,14 @@ impl<'d: 'c, 'c> EchoEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_ECHO_FEEDBACK?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_ECHO_FEEDBACK".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_ECHO_FEEDBACK)`
 	pub fn set_feedback(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_ECHO_FEEDBACK?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_ECHO_FEEDBACK", value).into())))
 	}
 
 
@@ -1447,14 +1451,14 @@ impl<'d: 'c, 'c> EchoEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_ECHO_SPREAD?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_ECHO_SPREAD".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_ECHO_SPREAD)`
 	pub fn set_spread(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_ECHO_SPREAD?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_ECHO_SPREAD", value).into())))
 	}
 }
 
@@ -1462,7 +1466,7 @@ impl<'d: 'c, 'c> EchoEffect<'d, 'c> {
 impl<'d: 'c, 'c> Drop for EchoEffect<'d, 'c> {
 	fn drop(&mut self) {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX().unwrap();
-		if let Ok(_lock) = self.ctx.make_current(true) {
+		if let Ok(_lock) = self.ctx.make_current_unchecked(true) {
 			unsafe { efx.alDeleteEffects.unwrap()(1, &mut self.effect as *mut sys::ALuint); }
 			if let Err(_) = self.ctx.get_error() {
 				let _ = writeln!(io::stderr(), "ALTO ERROR: `alDeleteEffects` failed in EchoEffect drop");
@@ -1480,10 +1484,10 @@ unsafe impl<'d: 'c, 'c> EffectTrait<'d, 'c> for FlangerEffect<'d, 'c> {
 		let _lock = ctx.make_current(true)?;
 		let mut effect = 0;
 		unsafe { efx.alGenEffects?(1, &mut effect); }
-		ctx.get_error()?;
+		ctx.get_error().map_err(|e| e.at("alGenEffects", ObjectKind::Effect, None))?;
 		let effect = FlangerEffect{ctx: ctx, effect: effect};
 		unsafe { efx.alEffecti?(effect.as_raw(), efx.AL_EFFECT_TYPE?, efx.AL_EFFECT_FLANGER?); }
-		ctx.get_error().map(|_| effect)
+		ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some("AL_EFFECT_TYPE".into()))).map(|_| effect)
 	}
 
 
@@ -1501,14 +1505,14 @@ impl<'d: 'c, 'c> FlangerEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { efx.alGetEffecti?(self.effect, efx.AL_FLANGER_WAVEFORM?, &mut value); }
-		self.ctx.get_error().and_then(|_| FlangerWaveform::from_i32(value as i32).ok_or(AltoError::AlInvalidValue))
+		self.ctx.get_error().map_err(|e| e.at("alGetEffecti", ObjectKind::Effect, Some("AL_FLANGER_WAVEFORM".into()))).and_then(|_| FlangerWaveform::from_i32(value as i32).ok_or(AltoError::AlInvalidValue))
 	}
 	/// `alEffecti(AL_FLANGER_WAVEFORM)`
 	pub fn set_waveform(&mut self, value: FlangerWaveform) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffecti?(self.effect, efx.AL_FLANGER_WAVEFORM?, value as sys::ALint) };
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some(("AL_FLANGER_WAVEFORM", value as sys::ALint).into())))
 	}
 
 
@@ -1518,14 +1522,14 @@ impl<'d: 'c, 'c> FlangerEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { efx.alGetEffecti?(self.effect, efx.AL_FLANGER_PHASE?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffecti", ObjectKind::Effect, Some("AL_FLANGER_PHASE".into()))).map(|_| value)
 	}
 	/// `alEffecti(AL_FLANGER_PHASE)`
 	pub fn set_phase(&mut self, value: sys::ALint) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffecti?(self.effect, efx.AL_FLANGER_PHASE?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some(("AL_FLANGER_PHASE", value).into())))
 	}
 
 
@@ -1535,14 +1539,14 @@ impl<'d: 'c, 'c> FlangerEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_FLANGER_RATE?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_FLANGER_RATE".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_FLANGER_RATE)`
 	pub fn set_rate(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_FLANGER_RATE?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_FLANGER_RATE", value).into())))
 	}
 
 
@@ -1552,14 +1556,14 @@ impl<'d: 'c, 'c> FlangerEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_FLANGER_DEPTH?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_FLANGER_DEPTH".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_FLANGER_DEPTH)`
 	pub fn set_depth(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_FLANGER_DEPTH?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_FLANGER_DEPTH", value).into())))
 	}
 
 
@@ -1569,14 +1573,14 @@ impl<'d: 'c, 'c> FlangerEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_FLANGER_FEEDBACK?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_FLANGER_FEEDBACK".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_FLANGER_FEEDBACK)`
 	pub fn set_feedback(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_FLANGER_FEEDBACK?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_FLANGER_FEEDBACK", value).into())))
 	}
 
 
@@ -1586,14 +1590,14 @@ impl<'d: 'c, 'c> FlangerEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_FLANGER_DELAY?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_FLANGER_DELAY".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_FLANGER_DELAY)`
 	pub fn set_delay(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_FLANGER_DELAY?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_FLANGER_DELAY", value).into())))
 	}
 }
 
@@ -1601,7 +1605,7 @@ impl<'d: 'c, 'c> FlangerEffect<'d, 'c> {
 impl<'d: 'c, 'c> Drop for FlangerEffect<'d, 'c> {
 	fn drop(&mut self) {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX().unwrap();
-		if let Ok(_lock) = self.ctx.make_current(true) {
+		if let Ok(_lock) = self.ctx.make_current_unchecked(true) {
 			unsafe { efx.alDeleteEffects.unwrap()(1, &mut self.effect as *mut sys::ALuint); }
 			if let Err(_) = self.ctx.get_error() {
 				let _ = writeln!(io::stderr(), "ALTO ERROR: `alDeleteEffects` failed in FlangerEffect drop");
@@ -1619,10 +1623,10 @@ unsafe impl<'d: 'c, 'c> EffectTrait<'d, 'c> for FrequencyShifterEffect<'d, 'c> {
 		let _lock = ctx.make_current(true)?;
 		let mut effect = 0;
 		unsafe { efx.alGenEffects?(1, &mut effect); }
-		ctx.get_error()?;
+		ctx.get_error().map_err(|e| e.at("alGenEffects", ObjectKind::Effect, None))?;
 		let effect = FrequencyShifterEffect{ctx: ctx, effect: effect};
 		unsafe { efx.alEffecti?(effect.as_raw(), efx.AL_EFFECT_TYPE?, efx.AL_EFFECT_FREQUENCY_SHIFTER?); }
-		ctx.get_error().map(|_| effect)
+		ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some("AL_EFFECT_TYPE".into()))).map(|_| effect)
 	}
 
 
@@ -1640,14 +1644,14 @@ impl<'d: 'c, 'c> FrequencyShifterEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_FREQUENCY_SHIFTER_FREQUENCY?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_FREQUENCY_SHIFTER_FREQUENCY".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_FREQUENCY_SHIFTER_FREQUENCY)`
 	pub fn set_frequency(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_FREQUENCY_SHIFTER_FREQUENCY?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_FREQUENCY_SHIFTER_FREQUENCY", value).into())))
 	}
 
 
@@ -1657,14 +1661,14 @@ impl<'d: 'c, 'c> FrequencyShifterEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { efx.alGetEffecti?(self.effect, efx.AL_FREQUENCY_SHIFTER_LEFT_DIRECTION?, &mut value); }
-		self.ctx.get_error().and_then(|_| FrequencyShifterDirection::from_i32(value as i32).ok_or(AltoError::AlInvalidValue))
+		self.ctx.get_error().map_err(|e| e.at("alGetEffecti", ObjectKind::Effect, Some("AL_FREQUENCY_SHIFTER_LEFT_DIRECTION".into()))).and_then(|_| FrequencyShifterDirection::from_i32(value as i32).ok_or(AltoError::AlInvalidValue))
 	}
 	/// `alEffecti(AL_FREQUENCY_SHIFTER_LEFT_DIRECTION)`
 	pub fn set_left_direction(&mut self, value: FrequencyShifterDirection) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffecti?(self.effect, efx.AL_FREQUENCY_SHIFTER_LEFT_DIRECTION?, value as sys::ALint); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some(("AL_FREQUENCY_SHIFTER_LEFT_DIRECTION", value as sys::ALint).into())))
 	}
 
 
@@ -1674,14 +1678,14 @@ impl<'d: 'c, 'c> FrequencyShifterEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { efx.alGetEffecti?(self.effect, efx.AL_FREQUENCY_SHIFTER_RIGHT_DIRECTION?, &mut value); }
-		self.ctx.get_error().and_then(|_| FrequencyShifterDirection::from_i32(value as i32).ok_or(AltoError::AlInvalidValue))
+		self.ctx.get_error().map_err(|e| e.at("alGetEffecti", ObjectKind::Effect, Some("AL_FREQUENCY_SHIFTER_RIGHT_DIRECTION".into()))).and_then(|_| FrequencyShifterDirection::from_i32(value as i32).ok_or(AltoError::AlInvalidValue))
 	}
 	/// `alEffecti(AL_FREQUENCY_SHIFTER_RIGHT_DIRECTION)`
 	pub fn set_right_direction(&mut self, value: FrequencyShifterDirection) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffecti?(self.effect, efx.AL_FREQUENCY_SHIFTER_RIGHT_DIRECTION?, value as sys::ALint); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some(("AL_FREQUENCY_SHIFTER_RIGHT_DIRECTION", value as sys::ALint).into())))
 	}
 }
 
@@ -1689,7 +1693,7 @@ impl<'d: 'c, 'c> FrequencyShifterEffect<'d, 'c> {
 impl<'d: 'c, 'c> Drop for FrequencyShifterEffect<'d, 'c> {
 	fn drop(&mut self) {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX().unwrap();
-		if let Ok(_lock) = self.ctx.make_current(true) {
+		if let Ok(_lock) = self.ctx.make_current_unchecked(true) {
 			unsafe { efx.alDeleteEffects.unwrap()(1, &mut self.effect as *mut sys::ALuint); }
 			if let Err(_) = self.ctx.get_error() {
 				let _ = writeln!(io::stderr(), "ALTO ERROR: `alDeleteEffects` failed in FrequencyShifterEffect drop");
@@ -1707,10 +1711,10 @@ unsafe impl<'d: 'c, 'c> EffectTrait<'d, 'c> for VocalMorpherEffect<'d, 'c> {
 		let _lock = ctx.make_current(true)?;
 		let mut effect = 0;
 		unsafe { efx.alGenEffects?(1, &mut effect); }
-		ctx.get_error()?;
+		ctx.get_error().map_err(|e| e.at("alGenEffects", ObjectKind::Effect, None))?;
 		let effect = VocalMorpherEffect{ctx: ctx, effect: effect};
 		unsafe { efx.alEffecti?(effect.as_raw(), efx.AL_EFFECT_TYPE?, efx.AL_EFFECT_VOCAL_MORPHER?); }
-		ctx.get_error().map(|_| effect)
+		ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some("AL_EFFECT_TYPE".into()))).map(|_| effect)
 	}
 
 
@@ -1728,14 +1732,14 @@ impl<'d: 'c, 'c> VocalMorpherEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { efx.alGetEffecti?(self.effect, efx.AL_VOCAL_MORPHER_PHONEMEA?, &mut value); }
-		self.ctx.get_error().and_then(|_| VocalMorpherPhoneme::from_i32(value as i32).ok_or(AltoError::AlInvalidValue))
+		self.ctx.get_error().map_err(|e| e.at("alGetEffecti", ObjectKind::Effect, Some("AL_VOCAL_MORPHER_PHONEMEA".into()))).and_then(|_| VocalMorpherPhoneme::from_i32(value as i32).ok_or(AltoError::AlInvalidValue))
 	}
 	/// `alEffecti(AL_VOCAL_MORPHER_PHONEMEA)`
 	pub fn set_phonemea(&mut self, value: VocalMorpherPhoneme) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffecti?(self.effect, efx.AL_VOCAL_MORPHER_PHONEMEA?, value as sys::ALint); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some(("AL_VOCAL_MORPHER_PHONEMEA", value as sys::ALint).into())))
 	}
 
 
@@ -1745,14 +1749,14 @@ impl<'d: 'c, 'c> VocalMorpherEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { efx.alGetEffecti?(self.effect, efx.AL_VOCAL_MORPHER_PHONEMEB?, &mut value); }
-		self.ctx.get_error().and_then(|_| VocalMorpherPhoneme::from_i32(value as i32).ok_or(AltoError::AlInvalidValue))
+		self.ctx.get_error().map_err(|e| e.at("alGetEffecti", ObjectKind::Effect, Some("AL_VOCAL_MORPHER_PHONEMEB".into()))).and_then(|_| VocalMorpherPhoneme::from_i32(value as i32).ok_or(AltoError::AlInvalidValue))
 	}
 	/// `alEffecti(AL_VOCAL_MORPHER_PHONEMEB)`
 	pub fn set_phonemeb(&mut self, value: VocalMorpherPhoneme) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffecti?(self.effect, efx.AL_VOCAL_MORPHER_PHONEMEB?, value as sys::ALint); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some(("AL_VOCAL_MORPHER_PHONEMEB", value as sys::ALint).into())))
 	}
 
 
@@ -1762,14 +1766,14 @@ impl<'d: 'c, 'c> VocalMorpherEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { efx.alGetEffecti?(self.effect, efx.AL_VOCAL_MORPHER_PHONEMEA_COARSE_TUNING?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffecti", ObjectKind::Effect, Some("AL_VOCAL_MORPHER_PHONEMEA_COARSE_TUNING".into()))).map(|_| value)
 	}
 	/// `alEffecti(AL_VOCAL_MORPHER_PHONEMEA_COARSE_TUNING)`
 	pub fn set_phonemea_coarse_tuning(&mut self, value: sys::ALint) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffecti?(self.effect, efx.AL_VOCAL_MORPHER_PHONEMEA_COARSE_TUNING?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some(("AL_VOCAL_MORPHER_PHONEMEA_COARSE_TUNING", value).into())))
 	}
 
 
@@ -1779,14 +1783,14 @@ impl<'d: 'c, 'c> VocalMorpherEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { efx.alGetEffecti?(self.effect, efx.AL_VOCAL_MORPHER_PHONEMEB_COARSE_TUNING?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffecti", ObjectKind::Effect, Some("AL_VOCAL_MORPHER_PHONEMEB_COARSE_TUNING".into()))).map(|_| value)
 	}
 	/// `alEffecti(AL_VOCAL_MORPHER_PHONEMEB_COARSE_TUNING)`
 	pub fn set_phonemeb_coarse_tuning(&mut self, value: sys::ALint) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffecti?(self.effect, efx.AL_VOCAL_MORPHER_PHONEMEB_COARSE_TUNING?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some(("AL_VOCAL_MORPHER_PHONEMEB_COARSE_TUNING", value).into())))
 	}
 
 
@@ -1796,14 +1800,14 @@ impl<'d: 'c, 'c> VocalMorpherEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { efx.alGetEffecti?(self.effect, efx.AL_VOCAL_MORPHER_WAVEFORM?, &mut value); }
-		self.ctx.get_error().and_then(|_| VocalMorpherWaveform::from_i32(value as i32).ok_or(AltoError::AlInvalidValue))
+		self.ctx.get_error().map_err(|e| e.at("alGetEffecti", ObjectKind::Effect, Some("AL_VOCAL_MORPHER_WAVEFORM".into()))).and_then(|_| VocalMorpherWaveform::from_i32(value as i32).ok_or(AltoError::AlInvalidValue))
 	}
 	/// `alEffecti(AL_VOCAL_MORPHER_WAVEFORM)`
 	pub fn set_waveform(&mut self, value: VocalMorpherWaveform) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffecti?(self.effect, efx.AL_VOCAL_MORPHER_WAVEFORM?, value as sys::ALint); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some(("AL_VOCAL_MORPHER_WAVEFORM", value as sys::ALint).into())))
 	}
 
 
@@ -1813,14 +1817,14 @@ impl<'d: 'c, 'c> VocalMorpherEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_VOCAL_MORPHER_RATE?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_VOCAL_MORPHER_RATE".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_VOCAL_MORPHER_RATE)`
 	pub fn set_rate(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_VOCAL_MORPHER_RATE?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_VOCAL_MORPHER_RATE", value).into())))
 	}
 }
 
@@ -1828,7 +1832,7 @@ impl<'d: 'c, 'c> VocalMorpherEffect<'d, 'c> {
 impl<'d: 'c, 'c> Drop for VocalMorpherEffect<'d, 'c> {
 	fn drop(&mut self) {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX().unwrap();
-		if let Ok(_lock) = self.ctx.make_current(true) {
+		if let Ok(_lock) = self.ctx.make_current_unchecked(true) {
 			unsafe { efx.alDeleteEffects.unwrap()(1, &mut self.effect as *mut sys::ALuint); }
 			if let Err(_) = self.ctx.get_error() {
 				let _ = writeln!(io::stderr(), "ALTO ERROR: `alDeleteEffects` failed in VocalMorpherEffect drop");
@@ -1846,10 +1850,10 @@ unsafe impl<'d: 'c, 'c> EffectTrait<'d, 'c> for PitchShifterEffect<'d, 'c> {
 		let _lock = ctx.make_current(true)?;
 		let mut effect = 0;
 		unsafe { efx.alGenEffects?(1, &mut effect); }
-		ctx.get_error()?;
+		ctx.get_error().map_err(|e| e.at("alGenEffects", ObjectKind::Effect, None))?;
 		let effect = PitchShifterEffect{ctx: ctx, effect: effect};
 		unsafe { efx.alEffecti?(effect.as_raw(), efx.AL_EFFECT_TYPE?, efx.AL_EFFECT_PITCH_SHIFTER?); }
-		ctx.get_error().map(|_| effect)
+		ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some("AL_EFFECT_TYPE".into()))).map(|_| effect)
 	}
 
 
@@ -1867,14 +1871,14 @@ impl<'d: 'c, 'c> PitchShifterEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { efx.alGetEffecti?(self.effect, efx.AL_PITCH_SHIFTER_COARSE_TUNE?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffecti", ObjectKind::Effect, Some("AL_PITCH_SHIFTER_COARSE_TUNE".into()))).map(|_| value)
 	}
 	/// `alEffecti(AL_PITCH_SHIFTER_COARSE_TUNE)`
 	pub fn set_coarse_tune(&mut self, value: sys::ALint) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffecti?(self.effect, efx.AL_PITCH_SHIFTER_COARSE_TUNE?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some(("AL_PITCH_SHIFTER_COARSE_TUNE", value).into())))
 	}
 
 
@@ -1884,14 +1888,14 @@ impl<'d: 'c, 'c> PitchShifterEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { efx.alGetEffecti?(self.effect, efx.AL_PITCH_SHIFTER_FINE_TUNE?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffecti", ObjectKind::Effect, Some("AL_PITCH_SHIFTER_FINE_TUNE".into()))).map(|_| value)
 	}
 	/// `alEffecti(AL_PITCH_SHIFTER_FINE_TUNE)`
 	pub fn set_fine_tune(&mut self, value: sys::ALint) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffecti?(self.effect, efx.AL_PITCH_SHIFTER_FINE_TUNE?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some(("AL_PITCH_SHIFTER_FINE_TUNE", value).into())))
 	}
 }
 
@@ -1899,7 +1903,7 @@ impl<'d: 'c, 'c> PitchShifterEffect<'d, 'c> {
 impl<'d: 'c, 'c> Drop for PitchShifterEffect<'d, 'c> {
 	fn drop(&mut self) {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX().unwrap();
-		if let Ok(_lock) = self.ctx.make_current(true) {
+		if let Ok(_lock) = self.ctx.make_current_unchecked(true) {
 			unsafe { efx.alDeleteEffects.unwrap()(1, &mut self.effect as *mut sys::ALuint); }
 			if let Err(_) = self.ctx.get_error() {
 				let _ = writeln!(io::stderr(), "ALTO ERROR: `alDeleteEffects` failed in PitchShifterEffect drop");
@@ -1917,10 +1921,10 @@ unsafe impl<'d: 'c, 'c> EffectTrait<'d, 'c> for RingModulatorEffect<'d, 'c> {
 		let _lock = ctx.make_current(true)?;
 		let mut effect = 0;
 		unsafe { efx.alGenEffects?(1, &mut effect); }
-		ctx.get_error()?;
+		ctx.get_error().map_err(|e| e.at("alGenEffects", ObjectKind::Effect, None))?;
 		let effect = RingModulatorEffect{ctx: ctx, effect: effect};
 		unsafe { efx.alEffecti?(effect.as_raw(), efx.AL_EFFECT_TYPE?, efx.AL_EFFECT_RING_MODULATOR?); }
-		ctx.get_error().map(|_| effect)
+		ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some("AL_EFFECT_TYPE".into()))).map(|_| effect)
 	}
 
 
@@ -1938,14 +1942,14 @@ impl<'d: 'c, 'c> RingModulatorEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_RING_MODULATOR_FREQUENCY?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_RING_MODULATOR_FREQUENCY".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_RING_MODULATOR_FREQUENCY)`
 	pub fn set_frequency(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_RING_MODULATOR_FREQUENCY?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_RING_MODULATOR_FREQUENCY", value).into())))
 	}
 
 
@@ -1955,14 +1959,14 @@ impl<'d: 'c, 'c> RingModulatorEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_RING_MODULATOR_HIGHPASS_CUTOFF?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_RING_MODULATOR_HIGHPASS_CUTOFF".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_RING_MODULATOR_HIGHPASS_CUTOFF)`
 	pub fn set_highpass_cutoff(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_RING_MODULATOR_HIGHPASS_CUTOFF?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_RING_MODULATOR_HIGHPASS_CUTOFF", value).into())))
 	}
 
 
@@ -1972,14 +1976,14 @@ impl<'d: 'c, 'c> RingModulatorEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { efx.alGetEffecti?(self.effect, efx.AL_RING_MODULATOR_WAVEFORM?, &mut value); }
-		self.ctx.get_error().and_then(|_| RingModulatorWaveform::from_i32(value as i32).ok_or(AltoError::AlInvalidValue))
+		self.ctx.get_error().map_err(|e| e.at("alGetEffecti", ObjectKind::Effect, Some("AL_RING_MODULATOR_WAVEFORM".into()))).and_then(|_| RingModulatorWaveform::from_i32(value as i32).ok_or(AltoError::AlInvalidValue))
 	}
 	/// `alEffecti(AL_RING_MODULATOR_WAVEFORM)`
 	pub fn set_waveform(&mut self, value: ChorusWaveform) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffecti?(self.effect, efx.AL_RING_MODULATOR_WAVEFORM?, value as sys::ALint) };
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some(("AL_RING_MODULATOR_WAVEFORM", value as sys::ALint).into())))
 	}
 }
 
@@ -1987,7 +1991,7 @@ impl<'d: 'c, 'c> RingModulatorEffect<'d, 'c> {
 impl<'d: 'c, 'c> Drop for RingModulatorEffect<'d, 'c> {
 	fn drop(&mut self) {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX().unwrap();
-		if let Ok(_lock) = self.ctx.make_current(true) {
+		if let Ok(_lock) = self.ctx.make_current_unchecked(true) {
 			unsafe { efx.alDeleteEffects.unwrap()(1, &mut self.effect as *mut sys::ALuint); }
 			if let Err(_) = self.ctx.get_error() {
 				let _ = writeln!(io::stderr(), "ALTO ERROR: `alDeleteEffects` failed in RingModulatorEffect drop");
@@ -2005,10 +2009,10 @@ unsafe impl<'d: 'c, 'c> EffectTrait<'d, 'c> for AutowahEffect<'d, 'c> {
 		let _lock = ctx.make_current(true)?;
 		let mut effect = 0;
 		unsafe { efx.alGenEffects?(1, &mut effect); }
-		ctx.get_error()?;
+		ctx.get_error().map_err(|e| e.at("alGenEffects", ObjectKind::Effect, None))?;
 		let effect = AutowahEffect{ctx: ctx, effect: effect};
 		unsafe { efx.alEffecti?(effect.as_raw(), efx.AL_EFFECT_TYPE?, efx.AL_EFFECT_AUTOWAH?); }
-		ctx.get_error().map(|_| effect)
+		ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some("AL_EFFECT_TYPE".into()))).map(|_| effect)
 	}
 
 
@@ -2026,14 +2030,14 @@ impl<'d: 'c, 'c> AutowahEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_AUTOWAH_ATTACK_TIME?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_AUTOWAH_ATTACK_TIME".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_AUTOWAH_ATTACK_TIME)`
 	pub fn set_attack_time(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_AUTOWAH_ATTACK_TIME?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_AUTOWAH_ATTACK_TIME", value).into())))
 	}
 
 
@@ -2043,14 +2047,14 @@ impl<'d: 'c, 'c> AutowahEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_AUTOWAH_RELEASE_TIME?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_AUTOWAH_RELEASE_TIME".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_AUTOWAH_RELEASE_TIME)`
 	pub fn set_release_time(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_AUTOWAH_RELEASE_TIME?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_AUTOWAH_RELEASE_TIME", value).into())))
 	}
 
 
@@ -2060,14 +2064,14 @@ impl<'d: 'c, 'c> AutowahEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_AUTOWAH_RESONANCE?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_AUTOWAH_RESONANCE".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_AUTOWAH_RESONANCE)`
 	pub fn set_resonance(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_AUTOWAH_RESONANCE?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_AUTOWAH_RESONANCE", value).into())))
 	}
 
 
@@ -2077,14 +2081,14 @@ impl<'d: 'c, 'c> AutowahEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_AUTOWAH_PEAK_GAIN?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_AUTOWAH_PEAK_GAIN".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_AUTOWAH_PEAK_GAIN)`
 	pub fn set_peak_gain(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_AUTOWAH_PEAK_GAIN?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_AUTOWAH_PEAK_GAIN", value).into())))
 	}
 }
 
@@ -2092,7 +2096,7 @@ impl<'d: 'c, 'c> AutowahEffect<'d, 'c> {
 impl<'d: 'c, 'c> Drop for AutowahEffect<'d, 'c> {
 	fn drop(&mut self) {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX().unwrap();
-		if let Ok(_lock) = self.ctx.make_current(true) {
+		if let Ok(_lock) = self.ctx.make_current_unchecked(true) {
 			unsafe { efx.alDeleteEffects.unwrap()(1, &mut self.effect as *mut sys::ALuint); }
 			if let Err(_) = self.ctx.get_error() {
 				let _ = writeln!(io::stderr(), "ALTO ERROR: `alDeleteEffects` failed in AutowahEffect drop");
@@ -2110,10 +2114,10 @@ unsafe impl<'d: 'c, 'c> EffectTrait<'d, 'c> for CompressorEffect<'d, 'c> {
 		let _lock = ctx.make_current(true)?;
 		let mut effect = 0;
 		unsafe { efx.alGenEffects?(1, &mut effect); }
-		ctx.get_error()?;
+		ctx.get_error().map_err(|e| e.at("alGenEffects", ObjectKind::Effect, None))?;
 		let effect = CompressorEffect{ctx: ctx, effect: effect};
 		unsafe { efx.alEffecti?(effect.as_raw(), efx.AL_EFFECT_TYPE?, efx.AL_EFFECT_COMPRESSOR?); }
-		ctx.get_error().map(|_| effect)
+		ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some("AL_EFFECT_TYPE".into()))).map(|_| effect)
 	}
 
 
@@ -2131,14 +2135,14 @@ impl<'d: 'c, 'c> CompressorEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0;
 		unsafe { efx.alGetEffecti?(self.effect, efx.AL_COMPRESSOR_ONOFF?, &mut value); }
-		self.ctx.get_error().map(|_| value == 1 as sys::ALint)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffecti", ObjectKind::Effect, Some("AL_COMPRESSOR_ONOFF".into()))).map(|_| value == 1 as sys::ALint)
 	}
 	/// `alEffecti(AL_COMPRESSOR_ONOFF)`
 	pub fn set_onoff(&mut self, value: bool) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffecti?(self.effect, efx.AL_COMPRESSOR_ONOFF?, if value { 1 } else { 0 } as sys::ALint); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some(("AL_COMPRESSOR_ONOFF", value).into())))
 	}
 }
 
@@ -2146,7 +2150,7 @@ impl<'d: 'c, 'c> CompressorEffect<'d, 'c> {
 impl<'d: 'c, 'c> Drop for CompressorEffect<'d, 'c> {
 	fn drop(&mut self) {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX().unwrap();
-		if let Ok(_lock) = self.ctx.make_current(true) {
+		if let Ok(_lock) = self.ctx.make_current_unchecked(true) {
 			unsafe { efx.alDeleteEffects.unwrap()(1, &mut self.effect as *mut sys::ALuint); }
 			if let Err(_) = self.ctx.get_error() {
 				let _ = writeln!(io::stderr(), "ALTO ERROR: `alDeleteEffects` failed in CompressorEffect drop");
@@ -2164,10 +2168,10 @@ unsafe impl<'d: 'c, 'c> EffectTrait<'d, 'c> for EqualizerEffect<'d, 'c> {
 		let _lock = ctx.make_current(true)?;
 		let mut effect = 0;
 		unsafe { efx.alGenEffects?(1, &mut effect); }
-		ctx.get_error()?;
+		ctx.get_error().map_err(|e| e.at("alGenEffects", ObjectKind::Effect, None))?;
 		let effect = EqualizerEffect{ctx: ctx, effect: effect};
 		unsafe { efx.alEffecti?(effect.as_raw(), efx.AL_EFFECT_TYPE?, efx.AL_EFFECT_EQUALIZER?); }
-		ctx.get_error().map(|_| effect)
+		ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some("AL_EFFECT_TYPE".into()))).map(|_| effect)
 	}
 
 
@@ -2185,14 +2189,14 @@ impl<'d: 'c, 'c> EqualizerEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EQUALIZER_LOW_GAIN?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EQUALIZER_LOW_GAIN".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EQUALIZER_LOW_GAIN)`
 	pub fn set_low_gain(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EQUALIZER_LOW_GAIN?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EQUALIZER_LOW_GAIN", value).into())))
 	}
 
 
@@ -2202,14 +2206,14 @@ impl<'d: 'c, 'c> EqualizerEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EQUALIZER_LOW_CUTOFF?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EQUALIZER_LOW_CUTOFF".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EQUALIZER_LOW_CUTOFF)`
 	pub fn set_low_cutoff(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EQUALIZER_LOW_CUTOFF?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EQUALIZER_LOW_CUTOFF", value).into())))
 	}
 
 
@@ -2219,14 +2223,14 @@ impl<'d: 'c, 'c> EqualizerEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EQUALIZER_MID1_GAIN?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EQUALIZER_MID1_GAIN".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EQUALIZER_MID1_GAIN)`
 	pub fn set_mid1_gain(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EQUALIZER_MID1_GAIN?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EQUALIZER_MID1_GAIN", value).into())))
 	}
 
 
@@ -2236,14 +2240,14 @@ impl<'d: 'c, 'c> EqualizerEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EQUALIZER_MID1_CENTER?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EQUALIZER_MID1_CENTER".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EQUALIZER_MID1_CENTER)`
 	pub fn set_mid1_center(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EQUALIZER_MID1_CENTER?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EQUALIZER_MID1_CENTER", value).into())))
 	}
 
 
@@ -2253,14 +2257,14 @@ impl<'d: 'c, 'c> EqualizerEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EQUALIZER_MID1_WIDTH?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EQUALIZER_MID1_WIDTH".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EQUALIZER_MID1_WIDTH)`
 	pub fn set_mid1_width(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EQUALIZER_MID1_WIDTH?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EQUALIZER_MID1_WIDTH", value).into())))
 	}
 
 
@@ -2270,14 +2274,14 @@ impl<'d: 'c, 'c> EqualizerEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EQUALIZER_MID2_GAIN?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EQUALIZER_MID2_GAIN".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EQUALIZER_MID2_GAIN)`
 	pub fn set_mid2_gain(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EQUALIZER_MID2_GAIN?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EQUALIZER_MID2_GAIN", value).into())))
 	}
 
 
@@ -2287,14 +2291,14 @@ impl<'d: 'c, 'c> EqualizerEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EQUALIZER_MID2_CENTER?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EQUALIZER_MID2_CENTER".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EQUALIZER_MID2_CENTER)`
 	pub fn set_mid2_center(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EQUALIZER_MID2_CENTER?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EQUALIZER_MID2_CENTER", value).into())))
 	}
 
 
@@ -2304,14 +2308,14 @@ impl<'d: 'c, 'c> EqualizerEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EQUALIZER_MID2_WIDTH?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EQUALIZER_MID2_WIDTH".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EQUALIZER_MID2_WIDTH)`
 	pub fn set_mid2_width(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EQUALIZER_MID2_WIDTH?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EQUALIZER_MID2_WIDTH", value).into())))
 	}
 
 
@@ -2321,14 +2325,14 @@ impl<'d: 'c, 'c> EqualizerEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EQUALIZER_HIGH_GAIN?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EQUALIZER_HIGH_GAIN".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EQUALIZER_HIGH_GAIN)`
 	pub fn set_high_gain(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EQUALIZER_HIGH_GAIN?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EQUALIZER_HIGH_GAIN", value).into())))
 	}
 
 
@@ -2338,14 +2342,14 @@ impl<'d: 'c, 'c> EqualizerEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, efx.AL_EQUALIZER_HIGH_CUTOFF?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EQUALIZER_HIGH_CUTOFF".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EQUALIZER_HIGH_CUTOFF)`
 	pub fn set_high_cutoff(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, efx.AL_EQUALIZER_HIGH_CUTOFF?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EQUALIZER_HIGH_CUTOFF", value).into())))
 	}
 }
 
@@ -2353,7 +2357,7 @@ impl<'d: 'c, 'c> EqualizerEffect<'d, 'c> {
 impl<'d: 'c, 'c> Drop for EqualizerEffect<'d, 'c> {
 	fn drop(&mut self) {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX().unwrap();
-		if let Ok(_lock) = self.ctx.make_current(true) {
+		if let Ok(_lock) = self.ctx.make_current_unchecked(true) {
 			unsafe { efx.alDeleteEffects.unwrap()(1, &mut self.effect as *mut sys::ALuint); }
 			if let Err(_) = self.ctx.get_error() {
 				let _ = writeln!(io::stderr(), "ALTO ERROR: `alDeleteEffects` failed in EqualizerEffect drop");
@@ -2372,10 +2376,10 @@ unsafe impl<'d: 'c, 'c> EffectTrait<'d, 'c> for DedicatedLowFrequencyEffect<'d,
 		let _lock = ctx.make_current(true)?;
 		let mut effect = 0;
 		unsafe { efx.alGenEffects?(1, &mut effect); }
-		ctx.get_error()?;
+		ctx.get_error().map_err(|e| e.at("alGenEffects", ObjectKind::Effect, None))?;
 		let effect = DedicatedLowFrequencyEffect{ctx: ctx, effect: effect};
 		unsafe { efx.alEffecti?(effect.as_raw(), efx.AL_EFFECT_TYPE?, d.AL_EFFECT_DEDICATED_LOW_FREQUENCY_EFFECT?); }
-		ctx.get_error().map(|_| effect)
+		ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some("AL_EFFECT_TYPE".into()))).map(|_| effect)
 	}
 
 
@@ -2394,7 +2398,7 @@ impl<'d: 'c, 'c> DedicatedLowFrequencyEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, d.AL_EFFECT_DEDICATED_GAIN?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EFFECT_DEDICATED_GAIN".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EFFECT_DEDICATED_GAIN)`
 	pub fn set_gain(&mut self, value: f32) -> AltoResult<()> {
@@ -2402,7 +2406,7 @@ impl<'d: 'c, 'c> DedicatedLowFrequencyEffect<'d, 'c> {
 		let d = self.ctx.device().extensions().ALC_EXT_DEDICATED()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, d.AL_EFFECT_DEDICATED_GAIN?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EFFECT_DEDICATED_GAIN", value).into())))
 	}
 }
 
@@ -2410,7 +2414,7 @@ impl<'d: 'c, 'c> DedicatedLowFrequencyEffect<'d, 'c> {
 impl<'d: 'c, 'c> Drop for DedicatedLowFrequencyEffect<'d, 'c> {
 	fn drop(&mut self) {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX().unwrap();
-		if let Ok(_lock) = self.ctx.make_current(true) {
+		if let Ok(_lock) = self.ctx.make_current_unchecked(true) {
 			unsafe { efx.alDeleteEffects.unwrap()(1, &mut self.effect as *mut sys::ALuint); }
 			if let Err(_) = self.ctx.get_error() {
 				let _ = writeln!(io::stderr(), "ALTO ERROR: `alDeleteEffects` failed in DedicatedLowFrequencyEffect drop");
@@ -2429,10 +2433,10 @@ unsafe impl<'d: 'c, 'c> EffectTrait<'d, 'c> for DedicatedDialogueEffect<'d, 'c>
 		let _lock = ctx.make_current(true)?;
 		let mut effect = 0;
 		unsafe { efx.alGenEffects?(1, &mut effect); }
-		ctx.get_error()?;
+		ctx.get_error().map_err(|e| e.at("alGenEffects", ObjectKind::Effect, None))?;
 		let effect = DedicatedDialogueEffect{ctx: ctx, effect: effect};
 		unsafe { efx.alEffecti?(effect.as_raw(), efx.AL_EFFECT_TYPE?, d.AL_EFFECT_DEDICATED_DIALOGUE?); }
-		ctx.get_error().map(|_| effect)
+		ctx.get_error().map_err(|e| e.at("alEffecti", ObjectKind::Effect, Some("AL_EFFECT_TYPE".into()))).map(|_| effect)
 	}
 
 
@@ -2451,7 +2455,7 @@ impl<'d: 'c, 'c> DedicatedDialogueEffect<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetEffectf?(self.effect, d.AL_EFFECT_DEDICATED_GAIN?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetEffectf", ObjectKind::Effect, Some("AL_EFFECT_DEDICATED_GAIN".into()))).map(|_| value)
 	}
 	/// `alEffectf(AL_EFFECT_DEDICATED_GAIN)`
 	pub fn set_gain(&mut self, value: f32) -> AltoResult<()> {
@@ -2459,7 +2463,7 @@ impl<'d: 'c, 'c> DedicatedDialogueEffect<'d, 'c> {
 		let d = self.ctx.device().extensions().ALC_EXT_DEDICATED()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alEffectf?(self.effect, d.AL_EFFECT_DEDICATED_GAIN?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alEffectf", ObjectKind::Effect, Some(("AL_EFFECT_DEDICATED_GAIN", value).into())))
 	}
 }
 
@@ -2467,7 +2471,7 @@ impl<'d: 'c, 'c> DedicatedDialogueEffect<'d, 'c> {
 impl<'d: 'c, 'c> Drop for DedicatedDialogueEffect<'d, 'c> {
 	fn drop(&mut self) {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX().unwrap();
-		if let Ok(_lock) = self.ctx.make_current(true) {
+		if let Ok(_lock) = self.ctx.make_current_unchecked(true) {
 			unsafe { efx.alDeleteEffects.unwrap()(1, &mut self.effect as *mut sys::ALuint); }
 			if let Err(_) = self.ctx.get_error() {
 				let _ = writeln!(io::stderr(), "ALTO ERROR: `alDeleteEffects` failed in DedicatedDialogueEffect drop");
@@ -2485,10 +2489,10 @@ unsafe impl<'d: 'c, 'c> FilterTrait<'d, 'c> for LowpassFilter<'d, 'c> {
 		let _lock = ctx.make_current(true)?;
 		let mut filter = 0;
 		unsafe { efx.alGenFilters?(1, &mut filter); }
-		ctx.get_error()?;
+		ctx.get_error().map_err(|e| e.at("alGenFilters", ObjectKind::Filter, None))?;
 		let filter = LowpassFilter{ctx: ctx, filter: filter};
 		unsafe { efx.alFilteri?(filter.as_raw(), efx.AL_FILTER_TYPE?, efx.AL_FILTER_LOWPASS?); }
-		ctx.get_error().map(|_| filter)
+		ctx.get_error().map_err(|e| e.at("alFilteri", ObjectKind::Filter, Some("AL_FILTER_TYPE".into()))).map(|_| filter)
 	}
 
 
@@ -2506,14 +2510,14 @@ impl<'d: 'c, 'c> LowpassFilter<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetFilterf?(self.filter, efx.AL_LOWPASS_GAIN?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetFilterf", ObjectKind::Filter, Some("AL_LOWPASS_GAIN".into()))).map(|_| value)
 	}
 	/// `alFilterf(AL_LOWPASS_GAIN)`
 	pub fn set_gain(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alFilterf?(self.filter, efx.AL_LOWPASS_GAIN?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alFilterf", ObjectKind::Filter, Some(("AL_LOWPASS_GAIN", value).into())))
 	}
 
 
@@ -2523,14 +2527,14 @@ impl<'d: 'c, 'c> LowpassFilter<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetFilterf?(self.filter, efx.AL_LOWPASS_GAINHF?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetFilterf", ObjectKind::Filter, Some("AL_LOWPASS_GAINHF".into()))).map(|_| value)
 	}
 	/// `alFilterf(AL_LOWPASS_GAINHF)`
 	pub fn set_gainhf(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alFilterf?(self.filter, efx.AL_LOWPASS_GAINHF?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alFilterf", ObjectKind::Filter, Some(("AL_LOWPASS_GAINHF", value).into())))
 	}
 }
 
@@ -2538,7 +2542,7 @@ impl<'d: 'c, 'c> LowpassFilter<'d, 'c> {
 impl<'d: 'c, 'c> Drop for LowpassFilter<'d, 'c> {
 	fn drop(&mut self) {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX().unwrap();
-		if let Ok(_lock) = self.ctx.make_current(true) {
+		if let Ok(_lock) = self.ctx.make_current_unchecked(true) {
 			unsafe { efx.alDeleteFilters.unwrap()(1, &mut self.filter as *mut sys::ALuint); }
 			if let Err(_) = self.ctx.get_error() {
 				let _ = writeln!(io::stderr(), "ALTO ERROR: `alDeleteFilters` failed in LowpassFilter drop");
@@ -2556,10 +2560,10 @@ unsafe impl<'d: 'c, 'c> FilterTrait<'d, 'c> for HighpassFilter<'d, 'c> {
 		let _lock = ctx.make_current(true)?;
 		let mut filter = 0;
 		unsafe { efx.alGenFilters?(1, &mut filter); }
-		ctx.get_error()?;
+		ctx.get_error().map_err(|e| e.at("alGenFilters", ObjectKind::Filter, None))?;
 		let filter = HighpassFilter{ctx: ctx, filter: filter};
 		unsafe { efx.alFilteri?(filter.as_raw(), efx.AL_FILTER_TYPE?, efx.AL_FILTER_HIGHPASS?); }
-		ctx.get_error().map(|_| filter)
+		ctx.get_error().map_err(|e| e.at("alFilteri", ObjectKind::Filter, Some("AL_FILTER_TYPE".into()))).map(|_| filter)
 	}
 
 
@@ -2577,14 +2581,14 @@ impl<'d: 'c, 'c> HighpassFilter<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetFilterf?(self.filter, efx.AL_HIGHPASS_GAIN?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetFilterf", ObjectKind::Filter, Some("AL_HIGHPASS_GAIN".into()))).map(|_| value)
 	}
 	/// `alFilterf(AL_HIGHPASS_GAIN)`
 	pub fn set_gain(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alFilterf?(self.filter, efx.AL_HIGHPASS_GAIN?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alFilterf", ObjectKind::Filter, Some(("AL_HIGHPASS_GAIN", value).into())))
 	}
 
 
@@ -2594,14 +2598,14 @@ impl<'d: 'c, 'c> HighpassFilter<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetFilterf?(self.filter, efx.AL_HIGHPASS_GAINLF?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetFilterf", ObjectKind::Filter, Some("AL_HIGHPASS_GAINLF".into()))).map(|_| value)
 	}
 	/// `alFilterf(AL_HIGHPASS_GAINLF)`
 	pub fn set_gainlf(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alFilterf?(self.filter, efx.AL_HIGHPASS_GAINLF?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alFilterf", ObjectKind::Filter, Some(("AL_HIGHPASS_GAINLF", value).into())))
 	}
 }
 
@@ -2609,7 +2613,7 @@ impl<'d: 'c, 'c> HighpassFilter<'d, 'c> {
 impl<'d: 'c, 'c> Drop for HighpassFilter<'d, 'c> {
 	fn drop(&mut self) {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX().unwrap();
-		if let Ok(_lock) = self.ctx.make_current(true) {
+		if let Ok(_lock) = self.ctx.make_current_unchecked(true) {
 			unsafe { efx.alDeleteFilters.unwrap()(1, &mut self.filter as *mut sys::ALuint); }
 			if let Err(_) = self.ctx.get_error() {
 				let _ = writeln!(io::stderr(), "ALTO ERROR: `alDeleteFilters` failed in HighpassFilter drop");
@@ -2627,10 +2631,10 @@ unsafe impl<'d: 'c, 'c> FilterTrait<'d, 'c> for BandpassFilter<'d, 'c> {
 		let _lock = ctx.make_current(true)?;
 		let mut filter = 0;
 		unsafe { efx.alGenFilters?(1, &mut filter); }
-		ctx.get_error()?;
+		ctx.get_error().map_err(|e| e.at("alGenFilters", ObjectKind::Filter, None))?;
 		let filter = BandpassFilter{ctx: ctx, filter: filter};
 		unsafe { efx.alFilteri?(filter.as_raw(), efx.AL_FILTER_TYPE?, efx.AL_FILTER_BANDPASS?); }
-		ctx.get_error().map(|_| filter)
+		ctx.get_error().map_err(|e| e.at("alFilteri", ObjectKind::Filter, Some("AL_FILTER_TYPE".into()))).map(|_| filter)
 	}
 
 
@@ -2648,14 +2652,14 @@ impl<'d: 'c, 'c> BandpassFilter<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetFilterf?(self.filter, efx.AL_BANDPASS_GAIN?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetFilterf", ObjectKind::Filter, Some("AL_BANDPASS_GAIN".into()))).map(|_| value)
 	}
 	/// `alFilterf(AL_BANDPASS_GAIN)`
 	pub fn set_gain(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alFilterf?(self.filter, efx.AL_BANDPASS_GAIN?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alFilterf", ObjectKind::Filter, Some(("AL_BANDPASS_GAIN", value).into())))
 	}
 
 
@@ -2665,14 +2669,14 @@ impl<'d: 'c, 'c> BandpassFilter<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetFilterf?(self.filter, efx.AL_BANDPASS_GAINLF?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetFilterf", ObjectKind::Filter, Some("AL_BANDPASS_GAINLF".into()))).map(|_| value)
 	}
 	/// `alFilterf(AL_BANDPASS_GAINLF)`
 	pub fn set_gainlf(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alFilterf?(self.filter, efx.AL_BANDPASS_GAINLF?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alFilterf", ObjectKind::Filter, Some(("AL_BANDPASS_GAINLF", value).into())))
 	}
 
 
@@ -2682,14 +2686,14 @@ impl<'d: 'c, 'c> BandpassFilter<'d, 'c> {
 		let _lock = self.ctx.make_current(true)?;
 		let mut value = 0.0;
 		unsafe { efx.alGetFilterf?(self.filter, efx.AL_BANDPASS_GAINHF?, &mut value); }
-		self.ctx.get_error().map(|_| value)
+		self.ctx.get_error().map_err(|e| e.at("alGetFilterf", ObjectKind::Filter, Some("AL_BANDPASS_GAINHF".into()))).map(|_| value)
 	}
 	/// `alFilterf(AL_BANDPASS_GAINHF)`
 	pub fn set_gainhf(&mut self, value: f32) -> AltoResult<()> {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX()?;
 		let _lock = self.ctx.make_current(true)?;
 		unsafe { efx.alFilterf?(self.filter, efx.AL_BANDPASS_GAINHF?, value); }
-		self.ctx.get_error()
+		self.ctx.get_error().map_err(|e| e.at("alFilterf", ObjectKind::Filter, Some(("AL_BANDPASS_GAINHF", value).into())))
 	}
 }
 
@@ -2697,7 +2701,7 @@ impl<'d: 'c, 'c> BandpassFilter<'d, 'c> {
 impl<'d: 'c, 'c> Drop for BandpassFilter<'d, 'c> {
 	fn drop(&mut self) {
 		let efx = self.ctx.device().extensions().ALC_EXT_EFX().unwrap();
-		if let Ok(_lock) = self.ctx.make_current(true) {
+		if let Ok(_lock) = self.ctx.make_current_unchecked(true) {
 			unsafe { efx.alDeleteFilters.unwrap()(1, &mut self.filter as *mut sys::ALuint); }
 			if let Err(_) = self.ctx.get_error() {
 				let _ = writeln!(io::stderr(), "ALTO ERROR: `alDeleteFilters` failed in BandpassFilter drop");
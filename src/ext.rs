@@ -1,31 +1,12 @@
+use std::fmt;
 use std::mem;
-use std::sync::RwLock;
+use std::sync::OnceLock;
 use std::ptr;
+use std::marker::PhantomData;
 
 use sys::*;
 
 
-rental! {
-	mod rent {
-		use std::sync;
-
-
-		#[rental(deref_suffix)]
-		pub struct AlcExt<'a, T: 'static> {
-			lock: sync::RwLockReadGuard<'a, Option<super::AlcExtResult<T>>>,
-			ext: &'lock T,
-		}
-
-
-		#[rental(deref_suffix)]
-		pub struct AlExt<'a, T: 'static> {
-			lock: sync::RwLockReadGuard<'a, Option<super::AlExtResult<T>>>,
-			ext: &'lock T,
-		}
-	}
-}
-
-
 macro_rules! alc_ext {
 	{
 		pub cache $cache:ident;
@@ -39,37 +20,28 @@ macro_rules! alc_ext {
 		#[doc(hidden)]
 		#[allow(non_snake_case)]
 		pub struct $cache<'a> {
-			api: &'a AlApi,
-			dev: *mut ALCdevice,
-			$($ext: RwLock<Option<AlcExtResult<$ext>>>,)*
+			$($ext: AlcExtResult<$ext>,)*
+			marker: PhantomData<&'a AlApi>,
 		}
 
 
 		#[allow(non_snake_case)]
 		impl<'a> $cache<'a> {
+			/// Every extension is resolved up front, so lookups afterward are a field access.
 			pub unsafe fn new(api: &'a AlApi, dev: *mut ALCdevice) -> $cache<'a> {
 				$cache{
-					api: api,
-					dev: dev,
-					$($ext: RwLock::new(None),)*
+					$($ext: $ext::load(api, dev),)*
+					marker: PhantomData,
 				}
 			}
 
 
-			$(pub fn $ext(&self) -> AlcExtResult<rent::AlcExt<$ext>> {
-				if let Ok(mut ext) = self.$ext.try_write() {
-					if ext.is_none() {
-						*ext = Some($ext::load(&self.api, self.dev));
-					}
+			$(#[inline]
+			pub fn $ext(&self) -> AlcExtResult<&$ext> {
+				match self.$ext {
+					Ok(ref ext) => Ok(ext),
+					Err(e) => Err(e),
 				}
-
-				rent::AlcExt::try_new(
-					self.$ext.read().unwrap(),
-					|ext| match *ext.as_ref().unwrap() {
-						Ok(ref ext) => Ok(ext),
-						Err(e) => Err(e),
-					}
-				).map_err(|e| e.0)
 			})*
 		}
 
@@ -102,7 +74,7 @@ macro_rules! alc_ext {
 									"AL_EFFECTSLOT_EFFECT" => Ok(1),
 									"AL_EFFECTSLOT_GAIN" => Ok(2),
 									"AL_EFFECTSLOT_AUXILIARY_SEND_AUTO" => Ok(3),
-									_ => Err(AlcExtensionError),
+									_ => Err(AlcExtensionError::MissingSymbol(stringify!($ext), stringify!($const_))),
 								}
 							}
 						},)*
@@ -111,14 +83,23 @@ macro_rules! alc_ext {
 							if p != ptr::null_mut() && unsafe { api.alcGetError()(dev) } == ALC_NO_ERROR {
 								Ok(unsafe { mem::transmute(p) })
 							} else {
-								Err(AlcExtensionError)
+								Err(AlcExtensionError::MissingSymbol(stringify!($ext), stringify!($fn_)))
 							}
 						},)*
 					})
 				} else {
-					Err(AlcExtensionError)
+					Err(AlcExtensionError::NotPresent)
 				}
 			}
+
+
+			/// The enums and functions of the extension that didn't resolve.
+			pub fn missing_symbols(&self) -> MissingSymbols {
+				let mut symbols = Vec::new();
+				$(if self.$const_.is_err() { symbols.push(stringify!($const_)); })*
+				$(if self.$fn_.is_err() { symbols.push(stringify!($fn_)); })*
+				MissingSymbols{extension: stringify!($ext), symbols: symbols}
+			}
 		})*
 	};
 }
@@ -138,7 +119,7 @@ macro_rules! al_ext {
 		#[allow(non_snake_case)]
 		pub struct $cache<'a> {
 			api: &'a AlApi,
-			$($ext: RwLock<Option<AlExtResult<$ext>>>,)*
+			$($ext: OnceLock<AlExtResult<$ext>>,)*
 		}
 
 
@@ -147,25 +128,24 @@ macro_rules! al_ext {
 			pub unsafe fn new(api: &'a AlApi) -> $cache<'a> {
 				$cache{
 					api: api,
-					$($ext: RwLock::new(None),)*
+					$($ext: OnceLock::new(),)*
 				}
 			}
 
 
-			$(pub fn $ext(&self) -> AlExtResult<rent::AlExt<$ext>> {
-				if let Ok(mut ext) = self.$ext.try_write() {
-					if ext.is_none() {
-						*ext = Some($ext::load(&self.api));
-					}
-				}
+			/// Resolve every extension not yet loaded, so lookups afterward are a field access.
+			/// The context must be current.
+			pub fn preload(&self) {
+				$(let _ = self.$ext();)*
+			}
 
-				rent::AlExt::try_new(
-					self.$ext.read().unwrap(),
-					|ext| match *ext.as_ref().unwrap() {
-						Ok(ref ext) => Ok(ext),
-						Err(e) => Err(e),
-					}
-				).map_err(|e| e.0)
+
+			$(#[inline]
+			pub fn $ext(&self) -> AlExtResult<&$ext> {
+				match *self.$ext.get_or_init(|| $ext::load(self.api)) {
+					Ok(ref ext) => Ok(ext),
+					Err(e) => Err(e),
+				}
 			})*
 		}
 
@@ -192,7 +172,7 @@ macro_rules! al_ext {
 							if e != 0 && unsafe { api.alGetError()() } == AL_NO_ERROR {
 								Ok(e)
 							} else {
-								Err(AlExtensionError)
+								Err(AlExtensionError::MissingSymbol(stringify!($ext), stringify!($const_)))
 							}
 						},)*
 						$($fn_: {
@@ -200,14 +180,23 @@ macro_rules! al_ext {
 							if p != ptr::null_mut() && unsafe { api.alGetError()() } == AL_NO_ERROR {
 								Ok(unsafe { mem::transmute(p) })
 							} else {
-								Err(AlExtensionError)
+								Err(AlExtensionError::MissingSymbol(stringify!($ext), stringify!($fn_)))
 							}
 						},)*
 					})
 				} else {
-					Err(AlExtensionError)
+					Err(AlExtensionError::NotPresent)
 				}
 			}
+
+
+			/// The enums and functions of the extension that didn't resolve.
+			pub fn missing_symbols(&self) -> MissingSymbols {
+				let mut symbols = Vec::new();
+				$(if self.$const_.is_err() { symbols.push(stringify!($const_)); })*
+				$(if self.$fn_.is_err() { symbols.push(stringify!($fn_)); })*
+				MissingSymbols{extension: stringify!($ext), symbols: symbols}
+			}
 		})*
 	};
 }
@@ -215,10 +204,31 @@ macro_rules! al_ext {
 
 #[doc(hidden)]
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
-pub struct AlcExtensionError;
+pub enum AlcExtensionError {
+	/// The extension isn't advertised.
+	NotPresent,
+	/// The extension is advertised, but the named enum or function of it didn't resolve.
+	MissingSymbol(&'static str, &'static str),
+}
 #[doc(hidden)]
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
-pub struct AlExtensionError;
+pub enum AlExtensionError {
+	/// The extension isn't advertised.
+	NotPresent,
+	/// The extension is advertised, but the named enum or function of it didn't resolve.
+	MissingSymbol(&'static str, &'static str),
+}
+
+
+/// Enums and functions of an extension that the implementation advertises but doesn't export.
+/// This points at a broken or outdated OpenAL runtime rather than at the code using it.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct MissingSymbols {
+	/// Name of the extension, such as `AL_SOFT_events`.
+	pub extension: &'static str,
+	/// Names of the symbols that didn't resolve, enums first.
+	pub symbols: Vec<&'static str>,
+}
 
 
 #[doc(hidden)]
@@ -281,6 +291,8 @@ pub enum Al {
 	SoftDeferredUpdates,
 	/// `AL_SOFT_direct_channels`
 	SoftDirectChannels,
+	/// `AL_SOFT_events`
+	SoftEvents,
 	/// `AL_SOFT_loop_points`
 	SoftLoopPoints,
 	/// `AL_SOFT_MSADPCM`
@@ -294,6 +306,40 @@ pub enum Al {
 }
 
 
+impl MissingSymbols {
+	/// Whether every symbol of the extension resolved.
+	#[inline]
+	pub fn is_empty(&self) -> bool { self.symbols.is_empty() }
+}
+
+
+impl fmt::Display for MissingSymbols {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "`{}` is missing", self.extension)?;
+		for (i, sym) in self.symbols.iter().enumerate() {
+			write!(f, "{} `{}`", if i == 0 { "" } else { "," }, sym)?;
+		}
+		Ok(())
+	}
+}
+
+
+impl Alc {
+	/// Every extension, in declaration order.
+	pub const ALL: &'static [Alc] = &[Alc::Dedicated, Alc::Disconnect, Alc::Efx, Alc::SoftHrtf, Alc::SoftPauseDevice];
+}
+
+
+impl Al {
+	/// Every extension, in declaration order.
+	pub const ALL: &'static [Al] = &[
+		Al::ALaw, Al::BFormat, Al::Double, Al::Float32, Al::Ima4, Al::McFormats, Al::MuLaw, Al::MuLawBFormat, Al::MuLawMcFormats,
+		Al::SoftBlockAlignment, Al::SoftDeferredUpdates, Al::SoftDirectChannels, Al::SoftEvents, Al::SoftLoopPoints, Al::SoftMsadpcm,
+		Al::SoftSourceLatency, Al::SoftSourceLength, Al::SourceDistanceModel,
+	];
+}
+
+
 alc_ext! {
 	pub cache AlcNullCache;
 
@@ -551,6 +597,7 @@ alc_ext! {
 
 pub type ALint64SOFT = i64;
 pub type ALuint64SOFT = u64;
+pub type ALEVENTPROCSOFT = unsafe extern "C" fn(eventType: ALenum, object: ALuint, param: ALuint, length: ALsizei, message: *const ALchar, userParam: *mut ALvoid);
 
 
 al_ext! {
@@ -714,6 +761,18 @@ al_ext! {
 	}
 
 
+	pub ext AL_SOFT_events {
+		pub const AL_EVENT_CALLBACK_FUNCTION_SOFT,
+		pub const AL_EVENT_CALLBACK_USER_PARAM_SOFT,
+		pub const AL_EVENT_TYPE_BUFFER_COMPLETED_SOFT,
+		pub const AL_EVENT_TYPE_SOURCE_STATE_CHANGED_SOFT,
+		pub const AL_EVENT_TYPE_DISCONNECTED_SOFT,
+
+		pub fn alEventControlSOFT: unsafe extern "C" fn(count: ALsizei, types: *const ALenum, enable: ALboolean),
+		pub fn alEventCallbackSOFT: unsafe extern "C" fn(callback: Option<ALEVENTPROCSOFT>, userParam: *mut ALvoid),
+	}
+
+
 	pub ext AL_SOFT_loop_points {
 		pub const AL_LOOP_POINTS_SOFT,
 	}
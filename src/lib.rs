@@ -7,20 +7,66 @@
 //! The core of the API is the [`Alto`](struct.Alto.html) struct. It has no analog in raw OpenAL and
 //! represents an implementation of the API itself. From there, instances of familiar OpenAL objects
 //! can be instantiated.
+//!
+//! Much of the API is provided through traits such as `SourceTrait` and `DeviceTrait`, which must be
+//! in scope to be used. Importing [`prelude`](prelude/index.html) with `use alto::prelude::*;` brings in all of them.
 
 
 #[macro_use]
 extern crate lazy_static;
-#[macro_use]
+#[cfg_attr(feature = "efx", macro_use)]
 extern crate enum_primitive;
 #[macro_use]
 extern crate rental;
 extern crate tempdir;
 extern crate al_sys;
+#[cfg(feature = "cpal")]
+extern crate cpal;
+#[cfg(feature = "hound")]
+extern crate hound;
+#[cfg(feature = "lewton")]
+extern crate lewton;
+#[cfg(feature = "minimp3")]
+extern crate minimp3;
+#[cfg(feature = "symphonia")]
+extern crate symphonia;
+#[cfg(feature = "audiopus")]
+extern crate audiopus;
+#[cfg(feature = "tracing")]
+extern crate tracing;
+#[cfg(feature = "dasp")]
+extern crate dasp;
+#[cfg(feature = "ffmpeg-next")]
+extern crate ffmpeg_next as ffmpeg;
+#[cfg(feature = "bevy")]
+extern crate bevy;
 
 use std::error::Error as StdError;
 use std::fmt;
 use std::io;
+use std::panic::{self, AssertUnwindSafe};
+
+
+/// Emit a `tracing` event when the `tracing` feature is enabled, otherwise do nothing.
+#[cfg(feature = "tracing")]
+macro_rules! alto_event {
+	($lvl:ident, $($arg:tt)+) => { ::tracing::$lvl!(target: "alto", $($arg)+) };
+}
+#[cfg(not(feature = "tracing"))]
+macro_rules! alto_event {
+	($lvl:ident, $($arg:tt)+) => { () };
+}
+
+
+/// Enter a debug-level `tracing` span for the rest of the enclosing scope when the `tracing` feature is enabled.
+#[cfg(feature = "tracing")]
+macro_rules! alto_span {
+	($($arg:tt)+) => { ::tracing::debug_span!(target: "alto", $($arg)+).entered() };
+}
+#[cfg(not(feature = "tracing"))]
+macro_rules! alto_span {
+	($($arg:tt)+) => { () };
+}
 
 
 mod alc;
@@ -34,15 +80,59 @@ pub use al::*;
 pub mod ext;
 
 
+pub mod decode;
+
+
+pub mod convert;
+
+
+#[cfg(feature = "efx")]
 mod efx;
+#[cfg(feature = "efx")]
 pub use efx::*;
 
 
+mod metrics;
+pub use metrics::*;
+
+
+mod config;
+pub use config::*;
+
+
+pub mod owned;
+
+
+pub mod typestate;
+
+
+pub mod prelude;
+
+
 pub mod sys {
 	pub use al_sys::*;
 }
 
 
+#[cfg(feature = "cpal")]
+pub mod cpal_bridge;
+
+
+#[cfg(feature = "dasp")]
+pub mod dasp_bridge;
+
+
+#[cfg(feature = "mock")]
+pub mod mock;
+
+
+pub mod ambisonic_bridge;
+
+
+#[cfg(feature = "bevy-plugin")]
+pub mod bevy_plugin;
+
+
 /// An error as reported by `alcGetError` or `alGetError`.
 #[derive(Debug)]
 pub enum AltoError {
@@ -54,7 +144,23 @@ pub enum AltoError {
 
 	AlcUnsupportedVersion,
 	AlcExtensionNotPresent,
+	/// An ALC extension is advertised, but a symbol the call needed didn't resolve.
+	/// `DeviceTrait::missing_symbols()` lists every symbol of the extension that is missing.
+	AlcMissingSymbols(ext::MissingSymbols),
 	AlcUnknownError,
+	/// The device was found to be disconnected, such as by being unplugged.
+	/// Every later call on the device, its contexts, and their objects fails with this error.
+	AlcDeviceDisconnected,
+	/// A context attribute was rejected before the context was created, named by its ALC enum.
+	AlcInvalidAttribute(&'static str),
+	/// Opening an output or loopback device failed, with the specifier of the device.
+	AlcOpenDevice(Box<AltoError>, String),
+	/// Opening a capture device failed, with the specifier of the device.
+	AlcOpenCaptureDevice(Box<AltoError>, String),
+	/// Creating a context failed, with the specifier of its device.
+	AlcCreateContext(Box<AltoError>, String),
+	/// None of the devices in a preference list could be opened, with the reason for each in order.
+	AlcNoDevice(Vec<AltoError>),
 
 	AlInvalidName,
 	AlInvalidEnum,
@@ -63,10 +169,81 @@ pub enum AltoError {
 	AlOutOfMemory,
 
 	AlExtensionNotPresent,
+	/// An AL extension is advertised, but a symbol the call needed didn't resolve.
+	/// `Context::missing_symbols()` lists every symbol of the extension that is missing.
+	AlMissingSymbols(ext::MissingSymbols),
+	/// An object was used with an object of another device, such as a buffer attached to a source of another device.
 	AlWrongDevice,
+	/// An object was used with an object or handle of another context, such as an effect slot on a source of another context.
+	AlWrongContext,
+	/// A raw handle passed to `from_raw()` or `borrow_raw()` is already held by alto in the same context,
+	/// so adopting it again would delete it twice.
+	AlAlreadyAdopted,
 	AlUnknownError,
+	/// Properties or data were rejected before being passed to OpenAL, with the constraint they violated.
+	AlInvalidRange(&'static str),
 
 	Io(io::Error),
+	/// Code run by OpenAL or a background thread on the caller's behalf, such as a `MetricsSink` or a `Decoder`
+	/// wrapped by a `DecodePool`, panicked. The panic was caught so it wouldn't unwind into native code or
+	/// take the thread down, and is reported with its message.
+	CallbackPanicked(String),
+
+	/// An error reported by OpenAL, along with the call that raised it.
+	Call(&'static AltoError, CallInfo),
+}
+
+
+/// The kind of object an OpenAL call operated on.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum ObjectKind {
+	Device,
+	Context,
+	Listener,
+	Buffer,
+	Source,
+	EffectSlot,
+	Effect,
+	Filter,
+}
+
+
+/// The native call that raised an error.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct CallInfo {
+	/// Name of the native function, such as `alSourcef`.
+	pub function: &'static str,
+	pub object: ObjectKind,
+	pub param: Option<CallParam>,
+}
+
+
+/// The property a native call accessed and, for setters, the value it was given.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct CallParam {
+	/// Name of the property, such as `AL_GAIN`.
+	pub name: &'static str,
+	pub value: Option<ParamValue>,
+}
+
+
+/// A value passed to a native call, kept as is so that raising an error doesn't allocate.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ParamValue {
+	Bool(bool),
+	Int(i32),
+	/// A pair of integers, such as loop points.
+	IntPair([i32; 2]),
+	/// An object name, such as that of a buffer.
+	Name(u32),
+	/// An enum value, shown in hexadecimal.
+	Enum(i32),
+	/// The number of objects a call operated on.
+	Count(usize),
+	Float(f32),
+	Vector([f32; 3]),
+	/// An at and an up vector.
+	Orientation([[f32; 3]; 2]),
 }
 
 
@@ -96,12 +273,168 @@ impl AltoError {
 			_ => AltoError::AlUnknownError,
 		}
 	}
+
+
+	/// Attach the call that raised this error. Errors that already have one are left alone, so the innermost call is kept,
+	/// and so are errors carrying data, which alto raises itself rather than OpenAL.
+	#[doc(hidden)]
+	pub fn at(self, function: &'static str, object: ObjectKind, param: Option<CallParam>) -> AltoError {
+		match self.as_static() {
+			Some(kind) => AltoError::Call(kind, CallInfo{function: function, object: object, param: param}),
+			None => self,
+		}
+	}
+
+
+	/// The same error as a static, if it carries no data.
+	fn as_static(&self) -> Option<&'static AltoError> {
+		Some(match *self {
+			AltoError::AlcInvalidDevice => &AltoError::AlcInvalidDevice,
+			AltoError::AlcInvalidContext => &AltoError::AlcInvalidContext,
+			AltoError::AlcInvalidEnum => &AltoError::AlcInvalidEnum,
+			AltoError::AlcInvalidValue => &AltoError::AlcInvalidValue,
+			AltoError::AlcOutOfMemory => &AltoError::AlcOutOfMemory,
+			AltoError::AlcUnsupportedVersion => &AltoError::AlcUnsupportedVersion,
+			AltoError::AlcExtensionNotPresent => &AltoError::AlcExtensionNotPresent,
+			AltoError::AlcUnknownError => &AltoError::AlcUnknownError,
+			AltoError::AlcDeviceDisconnected => &AltoError::AlcDeviceDisconnected,
+			AltoError::AlInvalidName => &AltoError::AlInvalidName,
+			AltoError::AlInvalidEnum => &AltoError::AlInvalidEnum,
+			AltoError::AlInvalidValue => &AltoError::AlInvalidValue,
+			AltoError::AlInvalidOperation => &AltoError::AlInvalidOperation,
+			AltoError::AlOutOfMemory => &AltoError::AlOutOfMemory,
+			AltoError::AlExtensionNotPresent => &AltoError::AlExtensionNotPresent,
+			AltoError::AlWrongDevice => &AltoError::AlWrongDevice,
+			AltoError::AlWrongContext => &AltoError::AlWrongContext,
+			AltoError::AlAlreadyAdopted => &AltoError::AlAlreadyAdopted,
+			AltoError::AlUnknownError => &AltoError::AlUnknownError,
+			_ => return None,
+		})
+	}
+
+
+	/// The underlying error, without any call information.
+	pub fn kind(&self) -> &AltoError {
+		match *self {
+			AltoError::Call(e, _) => e,
+			AltoError::AlcOpenDevice(ref e, _)
+				| AltoError::AlcOpenCaptureDevice(ref e, _)
+				| AltoError::AlcCreateContext(ref e, _)
+				=> e.kind(),
+			ref e => e,
+		}
+	}
+
+
+	/// Specifier of the device that failed to open or to create a context.
+	pub fn device(&self) -> Option<&str> {
+		match *self {
+			AltoError::AlcOpenDevice(_, ref spec)
+				| AltoError::AlcOpenCaptureDevice(_, ref spec)
+				| AltoError::AlcCreateContext(_, ref spec)
+				=> Some(spec),
+			_ => None,
+		}
+	}
+
+
+	/// The native call that raised this error, if known.
+	pub fn call(&self) -> Option<&CallInfo> {
+		match *self {
+			AltoError::Call(_, ref call) => Some(call),
+			_ => None,
+		}
+	}
+
+
+	/// `AL_INVALID_NAME`
+	pub fn is_invalid_name(&self) -> bool {
+		match *self.kind() { AltoError::AlInvalidName => true, _ => false }
+	}
+	/// `AL_INVALID_ENUM` or `ALC_INVALID_ENUM`
+	pub fn is_invalid_enum(&self) -> bool {
+		match *self.kind() { AltoError::AlInvalidEnum | AltoError::AlcInvalidEnum => true, _ => false }
+	}
+	/// `AL_INVALID_VALUE` or `ALC_INVALID_VALUE`, including attributes and ranges rejected before they were set.
+	pub fn is_invalid_value(&self) -> bool {
+		match *self.kind() { AltoError::AlInvalidValue | AltoError::AlcInvalidValue | AltoError::AlcInvalidAttribute(_) | AltoError::AlInvalidRange(_) => true, _ => false }
+	}
+	/// `AL_INVALID_OPERATION`
+	pub fn is_invalid_operation(&self) -> bool {
+		match *self.kind() { AltoError::AlInvalidOperation => true, _ => false }
+	}
+	/// `AL_OUT_OF_MEMORY` or `ALC_OUT_OF_MEMORY`
+	pub fn is_out_of_memory(&self) -> bool {
+		match *self.kind() { AltoError::AlOutOfMemory | AltoError::AlcOutOfMemory => true, _ => false }
+	}
+	/// `ALC_INVALID_DEVICE`
+	pub fn is_invalid_device(&self) -> bool {
+		match *self.kind() { AltoError::AlcInvalidDevice => true, _ => false }
+	}
+	/// The device was disconnected. See `DeviceTrait::is_disconnected()`.
+	pub fn is_device_disconnected(&self) -> bool {
+		match *self.kind() { AltoError::AlcDeviceDisconnected => true, _ => false }
+	}
+	/// `ALC_INVALID_CONTEXT`
+	pub fn is_invalid_context(&self) -> bool {
+		match *self.kind() { AltoError::AlcInvalidContext => true, _ => false }
+	}
+	/// An object was used with one belonging to another device.
+	pub fn is_wrong_device(&self) -> bool {
+		match *self.kind() { AltoError::AlWrongDevice => true, _ => false }
+	}
+	/// An object was used with one belonging to another context.
+	pub fn is_wrong_context(&self) -> bool {
+		match *self.kind() { AltoError::AlWrongContext => true, _ => false }
+	}
+	/// A raw handle was adopted while alto already held it.
+	pub fn is_already_adopted(&self) -> bool {
+		match *self.kind() { AltoError::AlAlreadyAdopted => true, _ => false }
+	}
+	/// A required AL or ALC extension is not available, including one advertised without all of its symbols.
+	pub fn is_extension_not_present(&self) -> bool {
+		match *self.kind() {
+			AltoError::AlExtensionNotPresent | AltoError::AlcExtensionNotPresent
+				| AltoError::AlMissingSymbols(_) | AltoError::AlcMissingSymbols(_)
+				=> true,
+			_ => false,
+		}
+	}
+	/// A required extension is advertised, but the runtime doesn't export all of its symbols.
+	pub fn missing_symbols(&self) -> Option<&ext::MissingSymbols> {
+		match *self.kind() { AltoError::AlMissingSymbols(ref missing) | AltoError::AlcMissingSymbols(ref missing) => Some(missing), _ => None }
+	}
+	/// An I/O error, such as failing to load the OpenAL library.
+	pub fn is_io(&self) -> bool {
+		match *self.kind() { AltoError::Io(_) => true, _ => false }
+	}
+	/// A callback panicked.
+	pub fn is_callback_panicked(&self) -> bool {
+		match *self.kind() { AltoError::CallbackPanicked(_) => true, _ => false }
+	}
 }
 
 
 impl fmt::Display for AltoError {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(f, "{}", self.description())
+		match *self {
+			AltoError::AlcInvalidAttribute(attr) => write!(f, "{} `{}`", self.description(), attr),
+			AltoError::AlInvalidRange(reason) => write!(f, "{}: {}", self.description(), reason),
+			AltoError::CallbackPanicked(ref msg) => write!(f, "{}: {}", self.description(), msg),
+			AltoError::AlcMissingSymbols(ref missing) | AltoError::AlMissingSymbols(ref missing) => write!(f, "{}: {}", self.description(), missing),
+			AltoError::AlcOpenDevice(ref e, ref spec) => write!(f, "{} while opening device `{}`", e, spec),
+			AltoError::AlcOpenCaptureDevice(ref e, ref spec) => write!(f, "{} while opening capture device `{}`", e, spec),
+			AltoError::AlcCreateContext(ref e, ref spec) => write!(f, "{} while creating a context on device `{}`", e, spec),
+			AltoError::AlcNoDevice(ref errors) => {
+				write!(f, "{}", self.description())?;
+				for (i, e) in errors.iter().enumerate() {
+					write!(f, "{} {}", if i == 0 { ":" } else { ";" }, e)?;
+				}
+				Ok(())
+			},
+			AltoError::Call(ref e, ref call) => write!(f, "{} in {}", e, call),
+			_ => write!(f, "{}", self.description()),
+		}
 	}
 }
 
@@ -117,7 +450,14 @@ impl StdError for AltoError {
 
 			AltoError::AlcUnsupportedVersion => "ALC ERROR: Unsupported Version",
 			AltoError::AlcExtensionNotPresent => "ALC ERROR: Extension Not Present",
+			AltoError::AlcMissingSymbols(_) => "ALC ERROR: Extension Symbols Missing",
 			AltoError::AlcUnknownError => "ALC ERROR: Unknown Error",
+			AltoError::AlcDeviceDisconnected => "ALC ERROR: Device Disconnected",
+			AltoError::AlcInvalidAttribute(_) => "ALC ERROR: Invalid Attribute",
+			AltoError::AlcOpenDevice(ref e, _) => e.description(),
+			AltoError::AlcOpenCaptureDevice(ref e, _) => e.description(),
+			AltoError::AlcCreateContext(ref e, _) => e.description(),
+			AltoError::AlcNoDevice(_) => "ALC ERROR: No Device Available",
 
 			AltoError::AlInvalidName => "AL ERROR: Invalid Name",
 			AltoError::AlInvalidEnum => "AL ERROR: Invalid Enum",
@@ -126,15 +466,91 @@ impl StdError for AltoError {
 			AltoError::AlOutOfMemory => "AL ERROR: Invalid Memory",
 
 			AltoError::AlExtensionNotPresent => "AL ERROR: Extension Not Present",
+			AltoError::AlMissingSymbols(_) => "AL ERROR: Extension Symbols Missing",
 			AltoError::AlWrongDevice => "AL ERROR: Wrong Device",
+			AltoError::AlWrongContext => "AL ERROR: Wrong Context",
+			AltoError::AlAlreadyAdopted => "AL ERROR: Already Adopted",
 			AltoError::AlUnknownError => "AL ERROR: Unknown Error",
+			AltoError::AlInvalidRange(_) => "AL ERROR: Invalid Range",
 
 			AltoError::Io(ref io) => io.description(),
+			AltoError::CallbackPanicked(_) => "Callback Panicked",
+
+			AltoError::Call(ref e, _) => e.description(),
+		}
+	}
+
+
+	fn source(&self) -> Option<&(StdError + 'static)> {
+		match *self {
+			AltoError::Io(ref io) => Some(io),
+			AltoError::AlcOpenDevice(ref e, _)
+				| AltoError::AlcOpenCaptureDevice(ref e, _)
+				| AltoError::AlcCreateContext(ref e, _)
+				=> Some(&**e),
+			AltoError::Call(e, _) => Some(e),
+			_ => None,
+		}
+	}
+}
+
+
+impl fmt::Display for CallInfo {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "`{}()` on {:?}", self.function, self.object)?;
+		if let Some(ref param) = self.param {
+			write!(f, " ({})", param)?;
+		}
+		Ok(())
+	}
+}
+
+
+impl fmt::Display for CallParam {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.value {
+			Some(ref value) => write!(f, "{} = {}", self.name, value),
+			None => write!(f, "{}", self.name),
 		}
 	}
 }
 
 
+impl fmt::Display for ParamValue {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			ParamValue::Bool(v) => write!(f, "{}", v),
+			ParamValue::Int(v) => write!(f, "{}", v),
+			ParamValue::IntPair(v) => write!(f, "{:?}", v),
+			ParamValue::Name(v) => write!(f, "{}", v),
+			ParamValue::Enum(v) => write!(f, "{:#x}", v),
+			ParamValue::Count(v) => write!(f, "{}", v),
+			ParamValue::Float(v) => write!(f, "{:?}", v),
+			ParamValue::Vector(v) => write!(f, "{:?}", v),
+			ParamValue::Orientation(v) => write!(f, "{:?}", v),
+		}
+	}
+}
+
+
+impl From<&'static str> for CallParam {
+	fn from(name: &'static str) -> CallParam { CallParam{name: name, value: None} }
+}
+impl<V: Into<ParamValue>> From<(&'static str, V)> for CallParam {
+	fn from((name, value): (&'static str, V)) -> CallParam { CallParam{name: name, value: Some(value.into())} }
+}
+
+
+impl From<bool> for ParamValue { fn from(v: bool) -> ParamValue { ParamValue::Bool(v) } }
+impl From<i32> for ParamValue { fn from(v: i32) -> ParamValue { ParamValue::Int(v) } }
+impl From<(i32, i32)> for ParamValue { fn from(v: (i32, i32)) -> ParamValue { ParamValue::IntPair([v.0, v.1]) } }
+impl From<u32> for ParamValue { fn from(v: u32) -> ParamValue { ParamValue::Name(v) } }
+impl From<usize> for ParamValue { fn from(v: usize) -> ParamValue { ParamValue::Count(v) } }
+impl From<f32> for ParamValue { fn from(v: f32) -> ParamValue { ParamValue::Float(v) } }
+impl From<[f32; 3]> for ParamValue { fn from(v: [f32; 3]) -> ParamValue { ParamValue::Vector(v) } }
+impl From<[[f32; 3]; 2]> for ParamValue { fn from(v: [[f32; 3]; 2]) -> ParamValue { ParamValue::Orientation(v) } }
+
+
 impl From<io::Error> for AltoError {
 	fn from(io: io::Error) -> AltoError {
 		AltoError::Io(io)
@@ -143,12 +559,32 @@ impl From<io::Error> for AltoError {
 
 
 impl From<ext::AlcExtensionError> for AltoError {
-	fn from(_: ext::AlcExtensionError) -> AltoError {
-		AltoError::AlcExtensionNotPresent
+	fn from(e: ext::AlcExtensionError) -> AltoError {
+		match e {
+			ext::AlcExtensionError::NotPresent => AltoError::AlcExtensionNotPresent,
+			ext::AlcExtensionError::MissingSymbol(ext, sym) => AltoError::AlcMissingSymbols(ext::MissingSymbols{extension: ext, symbols: vec![sym]}),
+		}
 	}
 }
 impl From<ext::AlExtensionError> for AltoError {
-	fn from(_: ext::AlExtensionError) -> AltoError {
-		AltoError::AlExtensionNotPresent
+	fn from(e: ext::AlExtensionError) -> AltoError {
+		match e {
+			ext::AlExtensionError::NotPresent => AltoError::AlExtensionNotPresent,
+			ext::AlExtensionError::MissingSymbol(ext, sym) => AltoError::AlMissingSymbols(ext::MissingSymbols{extension: ext, symbols: vec![sym]}),
+		}
 	}
 }
+
+
+/// Run `f`, turning a panic into `CallbackPanicked` so it can't unwind into native code or out of a worker thread.
+/// Whatever `f` was working on may be left half updated, so callers only use this around code that leaves
+/// nothing inconsistent behind, or discard what it touched.
+fn catch_panic<R, F: FnOnce() -> R>(f: F) -> AltoResult<R> {
+	panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+		let msg = match payload.downcast_ref::<&str>() {
+			Some(msg) => msg.to_string(),
+			None => payload.downcast_ref::<String>().cloned().unwrap_or_else(|| "unknown panic".to_string()),
+		};
+		AltoError::CallbackPanicked(msg)
+	})
+}
@@ -0,0 +1,94 @@
+use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use sys;
+use ::AltoError;
+
+
+/// Receives measurements of audio health for production monitoring.
+/// All methods have empty default implementations, so a sink only needs to handle what it cares about.
+/// Methods may be called from the audio thread of a capture backend, and should return quickly.
+pub trait MetricsSink: Send + Sync {
+	/// A streaming source played through all of its queued buffers before they were refilled.
+	fn underrun(&self, _source: sys::ALuint) { }
+	/// Captured frames were discarded because they were not read in time.
+	fn overrun(&self, _frames: usize) { }
+	/// The number of sources currently allocated has changed.
+	fn active_voices(&self, _count: usize) { }
+	/// Sample data was uploaded into a buffer.
+	fn bytes_uploaded(&self, _bytes: usize) { }
+	/// A capture backend reported an error, which will also be returned by the next read.
+	fn capture_error(&self, _error: &AltoError) { }
+}
+
+
+#[doc(hidden)]
+#[derive(Default)]
+pub struct Metrics {
+	sink: RwLock<Option<Arc<dyn MetricsSink>>>,
+	voices: AtomicUsize,
+}
+
+
+impl Metrics {
+	pub fn set_sink(&self, sink: Option<Arc<dyn MetricsSink>>) {
+		*self.sink.write().unwrap_or_else(|e| e.into_inner()) = sink;
+	}
+
+
+	pub fn sink(&self) -> Option<Arc<dyn MetricsSink>> {
+		self.sink.read().unwrap_or_else(|e| e.into_inner()).clone()
+	}
+
+
+	#[inline]
+	pub fn report<F: FnOnce(&dyn MetricsSink)>(&self, f: F) {
+		if let Some(ref sink) = *self.sink.read().unwrap_or_else(|e| e.into_inner()) {
+			f(&**sink);
+		}
+	}
+
+
+	pub fn voice_created(&self) {
+		let count = self.voices.fetch_add(1, Ordering::SeqCst) + 1;
+		self.report(|m| m.active_voices(count));
+	}
+
+
+	pub fn voice_deleted(&self) {
+		// Saturating, so that a voice deleted without having been counted can't wrap the count around.
+		let count = self.voices.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| Some(n.saturating_sub(1))).unwrap_or_else(|n| n).saturating_sub(1);
+		self.report(|m| m.active_voices(count));
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Mutex;
+
+	use super::*;
+
+
+	#[derive(Default)]
+	struct Voices(Mutex<Vec<usize>>);
+
+	impl MetricsSink for Voices {
+		fn active_voices(&self, count: usize) { self.0.lock().unwrap().push(count); }
+	}
+
+
+	#[test]
+	fn voice_count_does_not_underflow() {
+		let metrics = Metrics::default();
+		let sink = Arc::new(Voices::default());
+		metrics.set_sink(Some(sink.clone()));
+		metrics.voice_deleted();
+		metrics.voice_created();
+		metrics.voice_created();
+		metrics.voice_deleted();
+		metrics.voice_deleted();
+		metrics.voice_deleted();
+		assert_eq!(*sink.0.lock().unwrap(), vec![0, 1, 2, 1, 0, 0]);
+	}
+}
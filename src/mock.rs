@@ -0,0 +1,1088 @@
+//! An in-memory fake of the OpenAL API, for testing audio logic on machines without an audio device.
+//! [`Alto::load_mock()`](../struct.Alto.html#method.load_mock) returns an instance backed by this fake.
+//! It records every call and keeps track of devices, contexts, buffers and sources, but never produces any sound.
+//!
+//! Playback does not advance by itself. Use [`finish_buffers`](fn.finish_buffers.html) and
+//! [`finish_playback`](fn.finish_playback.html) to simulate sources consuming their buffers.
+//!
+//! No extensions are reported, except those advertised with [`advertise_extension`](fn.advertise_extension.html).
+//! The current context and the call log are tracked per thread, so tests running in parallel do not observe each other.
+
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_void;
+use std::sync::Mutex;
+use std::{cmp, ptr, slice, f32};
+
+use sys;
+
+
+/// A call made into the fake implementation.
+#[derive(Clone, PartialEq, Debug)]
+pub struct MockCall {
+	/// Name of the OpenAL entry point, such as `"alSourcePlay"`.
+	pub name: &'static str,
+	/// Arguments formatted with `Debug`. Pointers are shown as addresses.
+	pub args: Vec<String>,
+}
+
+
+#[derive(Default)]
+struct State {
+	next_id: usize,
+	devices: HashMap<usize, MockDevice>,
+	contexts: HashMap<usize, MockContext>,
+	buffers: HashMap<sys::ALuint, MockBuffer>,
+	sources: HashMap<sys::ALuint, MockSource>,
+	null_error: sys::ALCenum,
+}
+
+
+struct MockDevice {
+	spec: CString,
+	error: sys::ALCenum,
+	attrs: Vec<sys::ALCint>,
+	capture: Option<MockCapture>,
+}
+
+
+struct MockCapture {
+	frame_size: usize,
+	running: bool,
+	data: Vec<u8>,
+}
+
+
+struct MockContext {
+	dev: usize,
+	error: sys::ALenum,
+	props: HashMap<sys::ALenum, Vec<f64>>,
+	listener: HashMap<sys::ALenum, Vec<f64>>,
+}
+
+
+struct MockBuffer {
+	format: sys::ALenum,
+	freq: sys::ALint,
+	data: Vec<u8>,
+	props: HashMap<sys::ALenum, Vec<f64>>,
+}
+
+
+struct MockSource {
+	ctx: usize,
+	ty: sys::ALint,
+	state: sys::ALint,
+	queue: Vec<sys::ALuint>,
+	processed: usize,
+	props: HashMap<sys::ALenum, Vec<f64>>,
+}
+
+
+lazy_static! {
+	static ref STATE: Mutex<State> = Mutex::new(State::default());
+}
+
+
+thread_local! {
+	static CURRENT: Cell<usize> = Cell::new(0);
+	static CALLS: RefCell<Vec<MockCall>> = RefCell::new(Vec::new());
+	static INJECTED_ALC: Cell<sys::ALCenum> = Cell::new(sys::ALC_NO_ERROR);
+	static INJECTED_AL: Cell<sys::ALenum> = Cell::new(sys::AL_NO_ERROR);
+	static FAILURES: RefCell<HashMap<String, i32>> = RefCell::new(HashMap::new());
+	static ADVERTISED: RefCell<Vec<CString>> = RefCell::new(Vec::new());
+}
+
+
+const DEVICE_NAME: &'static [u8] = b"Alto Mock Device\0";
+const DEVICE_LIST: &'static [u8] = b"Alto Mock Device\0\0";
+const CAPTURE_NAME: &'static [u8] = b"Alto Mock Capture\0";
+const CAPTURE_LIST: &'static [u8] = b"Alto Mock Capture\0\0";
+
+
+/// Calls made on this thread since the log was last taken.
+pub fn calls() -> Vec<MockCall> {
+	CALLS.with(|c| c.borrow().clone())
+}
+
+
+/// Take the calls made on this thread, leaving the log empty.
+pub fn take_calls() -> Vec<MockCall> {
+	CALLS.with(|c| c.borrow_mut().drain(..).collect())
+}
+
+
+/// Make the next call to the named entry point on this thread fail with `error` instead of taking effect.
+/// `error` is reported by the next `alcGetError()` for `alc*` functions, or by the next `alGetError()` otherwise.
+pub fn fail_next(name: &str, error: i32) {
+	FAILURES.with(|f| f.borrow_mut().insert(name.to_owned(), error));
+}
+
+
+/// Report the named AL or ALC extension as present on this thread, without resolving any of its enums or functions,
+/// like a runtime that advertises an extension it doesn't fully export.
+/// ALC extensions are looked up when a device is opened, and AL extensions when a context first uses them.
+pub fn advertise_extension(name: &str) {
+	let name = CString::new(name).expect("extension name contains a nul byte");
+	ADVERTISED.with(|a| a.borrow_mut().push(name));
+}
+
+
+fn advertised(name: *const sys::ALchar) -> bool {
+	let name = unsafe { CStr::from_ptr(name) };
+	ADVERTISED.with(|a| a.borrow().iter().any(|n| n.as_c_str() == name))
+}
+
+
+/// Mark up to `count` of the queued buffers of a source as processed.
+/// If every queued buffer has then been processed, a playing source stops.
+pub fn finish_buffers(src: sys::ALuint, count: usize) {
+	let mut state = STATE.lock().unwrap();
+	if let Some(src) = state.sources.get_mut(&src) {
+		if src.state != sys::AL_PLAYING { return; }
+
+		src.processed = cmp::min(src.processed + count, src.queue.len());
+		if src.processed == src.queue.len() {
+			src.state = sys::AL_STOPPED;
+		}
+	}
+}
+
+
+/// Simulate a source reaching the end of its data: all queued buffers are processed and it stops.
+pub fn finish_playback(src: sys::ALuint) {
+	let mut state = STATE.lock().unwrap();
+	if let Some(src) = state.sources.get_mut(&src) {
+		src.processed = src.queue.len();
+		src.state = sys::AL_STOPPED;
+	}
+}
+
+
+/// The raw bytes last uploaded into a buffer, or `None` if it does not exist.
+pub fn buffer_data(buf: sys::ALuint) -> Option<Vec<u8>> {
+	STATE.lock().unwrap().buffers.get(&buf).map(|b| b.data.clone())
+}
+
+
+/// Make sample data available to a capture device. It is only taken while capture is running.
+pub fn feed_capture(dev: *mut sys::ALCdevice, data: &[u8]) {
+	let mut state = STATE.lock().unwrap();
+	if let Some(&mut MockDevice{capture: Some(ref mut cap), ..}) = state.devices.get_mut(&(dev as usize)) {
+		if cap.running {
+			cap.data.extend_from_slice(data);
+		}
+	}
+}
+
+
+/// A return value for calls that fail before taking effect.
+trait Fallback {
+	fn fallback() -> Self;
+}
+impl Fallback for () { fn fallback() { } }
+impl Fallback for i8 { fn fallback() -> i8 { 0 } }
+impl Fallback for i32 { fn fallback() -> i32 { 0 } }
+impl Fallback for f32 { fn fallback() -> f32 { 0.0 } }
+impl Fallback for f64 { fn fallback() -> f64 { 0.0 } }
+impl<T> Fallback for *mut T { fn fallback() -> *mut T { ptr::null_mut() } }
+impl<T> Fallback for *const T { fn fallback() -> *const T { ptr::null() } }
+impl<T> Fallback for Option<T> { fn fallback() -> Option<T> { None } }
+
+
+fn begin(name: &'static str, args: Vec<String>) -> bool {
+	CALLS.with(|c| c.borrow_mut().push(MockCall{name: name, args: args}));
+
+	match FAILURES.with(|f| f.borrow_mut().remove(name)) {
+		Some(e) if name.starts_with("alc") => { INJECTED_ALC.with(|i| i.set(e)); false },
+		Some(e) => { INJECTED_AL.with(|i| i.set(e)); false },
+		None => true,
+	}
+}
+
+
+macro_rules! mock_fns {
+	{
+		$(fn $name:ident($($arg:ident: $ty:ty),*) $(-> $ret:ty)* $body:block)*
+	} => {
+		$(#[allow(non_snake_case)]
+		unsafe extern "C" fn $name($($arg: $ty),*) $(-> $ret)* {
+			if !begin(stringify!($name), vec![$(format!("{:?}", $arg)),*]) {
+				return Fallback::fallback();
+			}
+			$body
+		})*
+
+
+		#[doc(hidden)]
+		pub fn fns() -> sys::AlFns {
+			sys::AlFns{$($name: $name,)*}
+		}
+	};
+}
+
+
+fn alc_error(state: &mut State, dev: *mut sys::ALCdevice, err: sys::ALCenum) {
+	let slot = match state.devices.get_mut(&(dev as usize)) {
+		Some(dev) => &mut dev.error,
+		None => &mut state.null_error,
+	};
+	if *slot == sys::ALC_NO_ERROR {
+		*slot = err;
+	}
+}
+
+
+fn al_error(state: &mut State, ctx: usize, err: sys::ALenum) {
+	if let Some(ctx) = state.contexts.get_mut(&ctx) {
+		if ctx.error == sys::AL_NO_ERROR {
+			ctx.error = err;
+		}
+	}
+}
+
+
+/// Run `f` against the current context, or do nothing if there isn't one.
+fn with_ctx<T: Fallback, F: FnOnce(&mut State, usize) -> T>(f: F) -> T {
+	let mut state = STATE.lock().unwrap();
+	let ctx = CURRENT.with(|c| c.get());
+	if !state.contexts.contains_key(&ctx) {
+		return T::fallback();
+	}
+	f(&mut state, ctx)
+}
+
+
+fn gen_id(state: &mut State) -> usize {
+	state.next_id += 1;
+	state.next_id
+}
+
+
+/// Number of values taken by a vector parameter.
+fn param_len(param: sys::ALenum) -> usize {
+	match param {
+		sys::AL_POSITION | sys::AL_VELOCITY | sys::AL_DIRECTION => 3,
+		sys::AL_ORIENTATION => 6,
+		_ => 1,
+	}
+}
+
+
+fn default_value(param: sys::ALenum) -> Vec<f64> {
+	match param {
+		sys::AL_GAIN | sys::AL_PITCH | sys::AL_MAX_GAIN | sys::AL_REFERENCE_DISTANCE | sys::AL_ROLLOFF_FACTOR => vec![1.0],
+		sys::AL_DOPPLER_FACTOR | sys::AL_DOPPLER_VELOCITY => vec![1.0],
+		sys::AL_CONE_INNER_ANGLE | sys::AL_CONE_OUTER_ANGLE => vec![360.0],
+		sys::AL_MAX_DISTANCE => vec![f32::MAX as f64],
+		sys::AL_SPEED_OF_SOUND => vec![343.3],
+		sys::AL_DISTANCE_MODEL => vec![sys::AL_INVERSE_DISTANCE_CLAMPED as f64],
+		sys::AL_ORIENTATION => vec![0.0, 0.0, -1.0, 0.0, 1.0, 0.0],
+		p => vec![0.0; param_len(p)],
+	}
+}
+
+
+fn get_prop(props: &HashMap<sys::ALenum, Vec<f64>>, param: sys::ALenum) -> Vec<f64> {
+	props.get(&param).cloned().unwrap_or_else(|| default_value(param))
+}
+
+
+unsafe fn read<T: Copy + Into<f64>>(values: *const T, param: sys::ALenum) -> Vec<f64> {
+	slice::from_raw_parts(values, param_len(param)).iter().map(|&v| v.into()).collect()
+}
+
+
+unsafe fn write_f32(out: *mut sys::ALfloat, values: &[f64]) {
+	for (i, v) in values.iter().enumerate() { *out.offset(i as isize) = *v as sys::ALfloat; }
+}
+unsafe fn write_i32(out: *mut sys::ALint, values: &[f64]) {
+	for (i, v) in values.iter().enumerate() { *out.offset(i as isize) = *v as sys::ALint; }
+}
+
+
+fn format_frame_size(format: sys::ALenum) -> Option<usize> {
+	match format {
+		sys::AL_FORMAT_MONO8 => Some(1),
+		sys::AL_FORMAT_MONO16 | sys::AL_FORMAT_STEREO8 => Some(2),
+		sys::AL_FORMAT_STEREO16 => Some(4),
+		_ => None,
+	}
+}
+
+
+fn buffer_in_use(state: &State, buf: sys::ALuint) -> bool {
+	state.sources.values().any(|s| s.queue.contains(&buf))
+}
+
+
+fn set_context_prop(param: sys::ALenum, value: f64) {
+	with_ctx(|state, ctx| {
+		let valid = match param {
+			sys::AL_DISTANCE_MODEL => true,
+			_ => value >= 0.0,
+		};
+		if valid {
+			state.contexts.get_mut(&ctx).unwrap().props.insert(param, vec![value]);
+		} else {
+			al_error(state, ctx, sys::AL_INVALID_VALUE);
+		}
+	})
+}
+
+
+fn get_context_prop(param: sys::ALenum) -> f64 {
+	with_ctx(|state, ctx| match param {
+		sys::AL_DOPPLER_FACTOR | sys::AL_DOPPLER_VELOCITY | sys::AL_SPEED_OF_SOUND | sys::AL_DISTANCE_MODEL => {
+			get_prop(&state.contexts[&ctx].props, param)[0]
+		},
+		_ => {
+			al_error(state, ctx, sys::AL_INVALID_ENUM);
+			0.0
+		},
+	})
+}
+
+
+fn set_listener(param: sys::ALenum, values: Vec<f64>) {
+	with_ctx(|state, ctx| {
+		match param {
+			sys::AL_GAIN if values[0] < 0.0 => al_error(state, ctx, sys::AL_INVALID_VALUE),
+			sys::AL_GAIN | sys::AL_POSITION | sys::AL_VELOCITY | sys::AL_ORIENTATION => {
+				state.contexts.get_mut(&ctx).unwrap().listener.insert(param, values);
+			},
+			_ => al_error(state, ctx, sys::AL_INVALID_ENUM),
+		}
+	})
+}
+
+
+fn get_listener(param: sys::ALenum) -> Option<Vec<f64>> {
+	with_ctx(|state, ctx| {
+		match param {
+			sys::AL_GAIN | sys::AL_POSITION | sys::AL_VELOCITY | sys::AL_ORIENTATION => Some(get_prop(&state.contexts[&ctx].listener, param)),
+			_ => {
+				al_error(state, ctx, sys::AL_INVALID_ENUM);
+				Some(Vec::new())
+			},
+		}
+	})
+}
+
+
+/// Look up a source belonging to the current context.
+fn source_ok(state: &mut State, ctx: usize, src: sys::ALuint) -> bool {
+	match state.sources.get(&src) {
+		Some(s) if s.ctx == ctx => true,
+		_ => {
+			al_error(state, ctx, sys::AL_INVALID_NAME);
+			false
+		},
+	}
+}
+
+
+fn set_source(src: sys::ALuint, param: sys::ALenum, values: Vec<f64>) {
+	with_ctx(|state, ctx| {
+		if !source_ok(state, ctx, src) { return; }
+
+		match param {
+			sys::AL_BUFFER => {
+				let buf = values[0] as sys::ALuint;
+				let playing = match state.sources[&src].state {
+					sys::AL_PLAYING | sys::AL_PAUSED => true,
+					_ => false,
+				};
+				if playing {
+					al_error(state, ctx, sys::AL_INVALID_OPERATION);
+				} else if buf != 0 && !state.buffers.contains_key(&buf) {
+					al_error(state, ctx, sys::AL_INVALID_VALUE);
+				} else {
+					let src = state.sources.get_mut(&src).unwrap();
+					src.processed = 0;
+					if buf == 0 {
+						src.queue.clear();
+						src.ty = sys::AL_UNDETERMINED;
+					} else {
+						src.queue = vec![buf];
+						src.ty = sys::AL_STATIC;
+					}
+				}
+			},
+			sys::AL_SOURCE_STATE | sys::AL_SOURCE_TYPE | sys::AL_BUFFERS_QUEUED | sys::AL_BUFFERS_PROCESSED => al_error(state, ctx, sys::AL_INVALID_OPERATION),
+			sys::AL_GAIN | sys::AL_PITCH | sys::AL_MIN_GAIN | sys::AL_MAX_GAIN | sys::AL_REFERENCE_DISTANCE
+				| sys::AL_ROLLOFF_FACTOR | sys::AL_MAX_DISTANCE if values[0] < 0.0
+			=> al_error(state, ctx, sys::AL_INVALID_VALUE),
+			_ => {
+				state.sources.get_mut(&src).unwrap().props.insert(param, values);
+			},
+		}
+	})
+}
+
+
+fn get_source(src: sys::ALuint, param: sys::ALenum) -> Option<Vec<f64>> {
+	with_ctx(|state, ctx| {
+		if !source_ok(state, ctx, src) { return None; }
+
+		let src = &state.sources[&src];
+		Some(match param {
+			sys::AL_SOURCE_STATE => vec![src.state as f64],
+			sys::AL_SOURCE_TYPE => vec![src.ty as f64],
+			sys::AL_BUFFERS_QUEUED => vec![src.queue.len() as f64],
+			sys::AL_BUFFERS_PROCESSED => vec![if src.ty == sys::AL_STREAMING { src.processed as f64 } else { 0.0 }],
+			sys::AL_BUFFER => vec![src.queue.get(src.processed).or(src.queue.last()).cloned().unwrap_or(0) as f64],
+			_ => get_prop(&src.props, param),
+		})
+	})
+}
+
+
+fn set_buffer(buf: sys::ALuint, param: sys::ALenum, values: Vec<f64>) {
+	with_ctx(|state, ctx| {
+		match state.buffers.get_mut(&buf) {
+			Some(buf) => { buf.props.insert(param, values); },
+			None => al_error(state, ctx, sys::AL_INVALID_NAME),
+		}
+	})
+}
+
+
+fn get_buffer(buf: sys::ALuint, param: sys::ALenum) -> Option<Vec<f64>> {
+	with_ctx(|state, ctx| {
+		let buf = match state.buffers.get(&buf) {
+			Some(buf) => buf,
+			None => {
+				al_error(state, ctx, sys::AL_INVALID_NAME);
+				return None;
+			},
+		};
+
+		Some(match param {
+			sys::AL_FREQUENCY => vec![buf.freq as f64],
+			sys::AL_SIZE => vec![buf.data.len() as f64],
+			sys::AL_BITS => vec![match buf.format { sys::AL_FORMAT_MONO8 | sys::AL_FORMAT_STEREO8 => 8.0, _ => 16.0 }],
+			sys::AL_CHANNELS => vec![match buf.format { sys::AL_FORMAT_STEREO8 | sys::AL_FORMAT_STEREO16 => 2.0, _ => 1.0 }],
+			_ => get_prop(&buf.props, param),
+		})
+	})
+}
+
+
+/// Apply a state change to a batch of sources. Nothing changes if any name is invalid.
+unsafe fn transition(n: sys::ALsizei, sources: *const sys::ALuint, f: fn(&mut MockSource)) {
+	with_ctx(|state, ctx| {
+		if n < 0 {
+			return al_error(state, ctx, sys::AL_INVALID_VALUE);
+		}
+		let sources = slice::from_raw_parts(sources, n as usize);
+		if !sources.iter().all(|&s| state.sources.get(&s).map(|s| s.ctx == ctx).unwrap_or(false)) {
+			return al_error(state, ctx, sys::AL_INVALID_NAME);
+		}
+		for s in sources {
+			f(state.sources.get_mut(s).unwrap());
+		}
+	})
+}
+
+
+fn play(src: &mut MockSource) {
+	if src.state != sys::AL_PAUSED {
+		src.processed = 0;
+	}
+	src.state = if src.queue.is_empty() { sys::AL_STOPPED } else { sys::AL_PLAYING };
+}
+fn pause(src: &mut MockSource) {
+	if src.state == sys::AL_PLAYING {
+		src.state = sys::AL_PAUSED;
+	}
+}
+fn stop(src: &mut MockSource) {
+	if src.state != sys::AL_INITIAL {
+		src.state = sys::AL_STOPPED;
+		src.processed = src.queue.len();
+	}
+}
+fn rewind(src: &mut MockSource) {
+	src.state = sys::AL_INITIAL;
+	src.processed = 0;
+}
+
+
+mock_fns! {
+	fn alcCreateContext(device: *mut sys::ALCdevice, attrlist: *const sys::ALCint) -> *mut sys::ALCcontext {
+		let mut state = STATE.lock().unwrap();
+		let dev = device as usize;
+		match state.devices.get(&dev) {
+			Some(&MockDevice{capture: None, ..}) => (),
+			_ => {
+				alc_error(&mut state, device, sys::ALC_INVALID_DEVICE);
+				return ptr::null_mut();
+			},
+		}
+
+		let mut attrs = vec![sys::ALC_FREQUENCY, 44100, sys::ALC_REFRESH, 50, sys::ALC_SYNC, 0, sys::ALC_MONO_SOURCES, 255, sys::ALC_STEREO_SOURCES, 1];
+		if attrlist != ptr::null() {
+			let mut i = 0;
+			while *attrlist.offset(i) != 0 {
+				let (key, value) = (*attrlist.offset(i), *attrlist.offset(i + 1));
+				match attrs.chunks_mut(2).find(|a| a[0] == key) {
+					Some(a) => a[1] = value,
+					None => attrs.extend(&[key, value]),
+				}
+				i += 2;
+			}
+		}
+		attrs.push(0);
+		state.devices.get_mut(&dev).unwrap().attrs = attrs;
+
+		let id = gen_id(&mut state);
+		state.contexts.insert(id, MockContext{dev: dev, error: sys::AL_NO_ERROR, props: HashMap::new(), listener: HashMap::new()});
+		id as *mut sys::ALCcontext
+	}
+	fn alcMakeContextCurrent(context: *mut sys::ALCcontext) -> sys::ALCboolean {
+		let mut state = STATE.lock().unwrap();
+		let ctx = context as usize;
+		if ctx != 0 && !state.contexts.contains_key(&ctx) {
+			alc_error(&mut state, ptr::null_mut(), sys::ALC_INVALID_CONTEXT);
+			return sys::ALC_FALSE;
+		}
+		CURRENT.with(|c| c.set(ctx));
+		sys::ALC_TRUE
+	}
+	fn alcProcessContext(context: *mut sys::ALCcontext) {
+		let mut state = STATE.lock().unwrap();
+		if !state.contexts.contains_key(&(context as usize)) {
+			alc_error(&mut state, ptr::null_mut(), sys::ALC_INVALID_CONTEXT);
+		}
+	}
+	fn alcSuspendContext(context: *mut sys::ALCcontext) {
+		let mut state = STATE.lock().unwrap();
+		if !state.contexts.contains_key(&(context as usize)) {
+			alc_error(&mut state, ptr::null_mut(), sys::ALC_INVALID_CONTEXT);
+		}
+	}
+	fn alcDestroyContext(context: *mut sys::ALCcontext) {
+		let mut state = STATE.lock().unwrap();
+		let ctx = context as usize;
+		match state.contexts.remove(&ctx) {
+			Some(_) => {
+				state.sources.retain(|_, s| s.ctx != ctx);
+				CURRENT.with(|c| if c.get() == ctx { c.set(0) });
+			},
+			None => alc_error(&mut state, ptr::null_mut(), sys::ALC_INVALID_CONTEXT),
+		}
+	}
+	fn alcGetCurrentContext() -> *mut sys::ALCcontext {
+		CURRENT.with(|c| c.get()) as *mut sys::ALCcontext
+	}
+	fn alcGetContextsDevice(context: *mut sys::ALCcontext) -> *mut sys::ALCdevice {
+		let mut state = STATE.lock().unwrap();
+		match state.contexts.get(&(context as usize)).map(|c| c.dev) {
+			Some(dev) => dev as *mut sys::ALCdevice,
+			None => {
+				alc_error(&mut state, ptr::null_mut(), sys::ALC_INVALID_CONTEXT);
+				ptr::null_mut()
+			},
+		}
+	}
+	fn alcOpenDevice(devicename: *const sys::ALCchar) -> *mut sys::ALCdevice {
+		let mut state = STATE.lock().unwrap();
+		if devicename != ptr::null() && CStr::from_ptr(devicename).to_bytes_with_nul() != DEVICE_NAME {
+			alc_error(&mut state, ptr::null_mut(), sys::ALC_INVALID_VALUE);
+			return ptr::null_mut();
+		}
+
+		let id = gen_id(&mut state);
+		state.devices.insert(id, MockDevice{
+			spec: CStr::from_bytes_with_nul(DEVICE_NAME).unwrap().to_owned(),
+			error: sys::ALC_NO_ERROR,
+			attrs: vec![0],
+			capture: None,
+		});
+		id as *mut sys::ALCdevice
+	}
+	fn alcCloseDevice(device: *mut sys::ALCdevice) -> sys::ALCboolean {
+		let mut state = STATE.lock().unwrap();
+		let dev = device as usize;
+		let busy = state.contexts.values().any(|c| c.dev == dev);
+		match state.devices.get(&dev) {
+			Some(&MockDevice{capture: None, ..}) if !busy => {
+				state.devices.remove(&dev);
+				sys::ALC_TRUE
+			},
+			_ => {
+				alc_error(&mut state, device, sys::ALC_INVALID_DEVICE);
+				sys::ALC_FALSE
+			},
+		}
+	}
+	fn alcGetError(device: *mut sys::ALCdevice) -> sys::ALCenum {
+		let injected = INJECTED_ALC.with(|i| i.replace(sys::ALC_NO_ERROR));
+		if injected != sys::ALC_NO_ERROR {
+			return injected;
+		}
+
+		let mut state = STATE.lock().unwrap();
+		let state = &mut *state;
+		match state.devices.get_mut(&(device as usize)) {
+			Some(dev) => ::std::mem::replace(&mut dev.error, sys::ALC_NO_ERROR),
+			// Like openal-soft, errors for a device that no longer exists are reported through the null device.
+			None => ::std::mem::replace(&mut state.null_error, sys::ALC_NO_ERROR),
+		}
+	}
+	fn alcIsExtensionPresent(device: *mut sys::ALCdevice, extname: *const sys::ALCchar) -> sys::ALCboolean {
+		let _ = device;
+		if advertised(extname) { sys::ALC_TRUE } else { sys::ALC_FALSE }
+	}
+	fn alcGetProcAddress(device: *mut sys::ALCdevice, funcname: *const sys::ALCchar) -> *mut c_void {
+		let _ = (device, funcname);
+		ptr::null_mut()
+	}
+	fn alcGetEnumValue(device: *mut sys::ALCdevice, enumname: *const sys::ALCchar) -> sys::ALCenum {
+		let _ = (device, enumname);
+		0
+	}
+	fn alcGetString(device: *mut sys::ALCdevice, param: sys::ALCenum) -> *const sys::ALCchar {
+		let mut state = STATE.lock().unwrap();
+		let s: &[u8] = match param {
+			sys::ALC_NO_ERROR => b"No Error\0",
+			sys::ALC_INVALID_DEVICE => b"Invalid Device\0",
+			sys::ALC_INVALID_CONTEXT => b"Invalid Context\0",
+			sys::ALC_INVALID_ENUM => b"Invalid Enum\0",
+			sys::ALC_INVALID_VALUE => b"Invalid Value\0",
+			sys::ALC_OUT_OF_MEMORY => b"Out of Memory\0",
+			sys::ALC_EXTENSIONS => b"\0",
+			sys::ALC_DEFAULT_DEVICE_SPECIFIER => DEVICE_NAME,
+			sys::ALC_CAPTURE_DEFAULT_DEVICE_SPECIFIER => CAPTURE_NAME,
+			sys::ALC_DEVICE_SPECIFIER | sys::ALC_CAPTURE_DEVICE_SPECIFIER if device != ptr::null_mut() => {
+				return match state.devices.get(&(device as usize)) {
+					Some(dev) => dev.spec.as_ptr(),
+					None => {
+						alc_error(&mut state, device, sys::ALC_INVALID_DEVICE);
+						ptr::null()
+					},
+				};
+			},
+			sys::ALC_DEVICE_SPECIFIER => DEVICE_LIST,
+			sys::ALC_CAPTURE_DEVICE_SPECIFIER => CAPTURE_LIST,
+			_ => {
+				alc_error(&mut state, device, sys::ALC_INVALID_ENUM);
+				return ptr::null();
+			},
+		};
+		s.as_ptr() as *const sys::ALCchar
+	}
+	fn alcGetIntegerv(device: *mut sys::ALCdevice, param: sys::ALCenum, size: sys::ALCsizei, values: *mut sys::ALCint) {
+		let mut state = STATE.lock().unwrap();
+		if size <= 0 || values == ptr::null_mut() {
+			return alc_error(&mut state, device, sys::ALC_INVALID_VALUE);
+		}
+
+		let result = match (param, state.devices.get(&(device as usize))) {
+			(sys::ALC_MAJOR_VERSION, _) | (sys::ALC_MINOR_VERSION, _) => vec![1],
+			(sys::ALC_CAPTURE_SAMPLES, Some(&MockDevice{capture: Some(ref cap), ..})) => vec![(cap.data.len() / cap.frame_size) as sys::ALCint],
+			(sys::ALC_ATTRIBUTES_SIZE, Some(dev)) => vec![dev.attrs.len() as sys::ALCint],
+			(sys::ALC_ALL_ATTRIBUTES, Some(dev)) => dev.attrs.clone(),
+			(_, Some(dev)) => match dev.attrs.chunks(2).find(|a| a[0] == param) {
+				Some(a) => vec![a[1]],
+				None => {
+					alc_error(&mut state, device, sys::ALC_INVALID_ENUM);
+					return;
+				},
+			},
+			(_, None) => {
+				alc_error(&mut state, device, sys::ALC_INVALID_DEVICE);
+				return;
+			},
+		};
+		if result.len() > size as usize {
+			return alc_error(&mut state, device, sys::ALC_INVALID_VALUE);
+		}
+		ptr::copy_nonoverlapping(result.as_ptr(), values, result.len());
+	}
+	fn alcCaptureOpenDevice(devicename: *const sys::ALCchar, frequency: sys::ALCuint, format: sys::ALCenum, buffersize: sys::ALCsizei) -> *mut sys::ALCdevice {
+		let mut state = STATE.lock().unwrap();
+		if devicename != ptr::null() && CStr::from_ptr(devicename).to_bytes_with_nul() != CAPTURE_NAME {
+			alc_error(&mut state, ptr::null_mut(), sys::ALC_INVALID_VALUE);
+			return ptr::null_mut();
+		}
+		let frame_size = match format_frame_size(format) {
+			Some(frame_size) if buffersize > 0 => frame_size,
+			_ => {
+				alc_error(&mut state, ptr::null_mut(), sys::ALC_INVALID_VALUE);
+				return ptr::null_mut();
+			},
+		};
+
+		let id = gen_id(&mut state);
+		state.devices.insert(id, MockDevice{
+			spec: CStr::from_bytes_with_nul(CAPTURE_NAME).unwrap().to_owned(),
+			error: sys::ALC_NO_ERROR,
+			attrs: vec![sys::ALC_FREQUENCY, frequency as sys::ALCint, 0],
+			capture: Some(MockCapture{frame_size: frame_size, running: false, data: Vec::new()}),
+		});
+		id as *mut sys::ALCdevice
+	}
+	fn alcCaptureCloseDevice(device: *mut sys::ALCdevice) -> sys::ALCboolean {
+		let mut state = STATE.lock().unwrap();
+		match state.devices.get(&(device as usize)) {
+			Some(&MockDevice{capture: Some(_), ..}) => {
+				state.devices.remove(&(device as usize));
+				sys::ALC_TRUE
+			},
+			_ => {
+				alc_error(&mut state, device, sys::ALC_INVALID_DEVICE);
+				sys::ALC_FALSE
+			},
+		}
+	}
+	fn alcCaptureStart(device: *mut sys::ALCdevice) {
+		let mut state = STATE.lock().unwrap();
+		match state.devices.get_mut(&(device as usize)) {
+			Some(&mut MockDevice{capture: Some(ref mut cap), ..}) => cap.running = true,
+			_ => alc_error(&mut state, device, sys::ALC_INVALID_DEVICE),
+		}
+	}
+	fn alcCaptureStop(device: *mut sys::ALCdevice) {
+		let mut state = STATE.lock().unwrap();
+		match state.devices.get_mut(&(device as usize)) {
+			Some(&mut MockDevice{capture: Some(ref mut cap), ..}) => cap.running = false,
+			_ => alc_error(&mut state, device, sys::ALC_INVALID_DEVICE),
+		}
+	}
+	fn alcCaptureSamples(device: *mut sys::ALCdevice, buffer: *mut sys::ALCvoid, samples: sys::ALCsizei) {
+		let mut state = STATE.lock().unwrap();
+		let taken = match state.devices.get_mut(&(device as usize)) {
+			Some(&mut MockDevice{capture: Some(ref mut cap), ..}) => {
+				let len = samples as usize * cap.frame_size;
+				if samples < 0 || len > cap.data.len() {
+					None
+				} else {
+					ptr::copy_nonoverlapping(cap.data.as_ptr(), buffer as *mut u8, len);
+					cap.data.drain(.. len);
+					Some(())
+				}
+			},
+			_ => {
+				alc_error(&mut state, device, sys::ALC_INVALID_DEVICE);
+				return;
+			},
+		};
+		if taken.is_none() {
+			alc_error(&mut state, device, sys::ALC_INVALID_VALUE);
+		}
+	}
+
+	fn alDopplerFactor(value: sys::ALfloat) { set_context_prop(sys::AL_DOPPLER_FACTOR, value as f64) }
+	fn alDopplerVelocity(value: sys::ALfloat) { set_context_prop(sys::AL_DOPPLER_VELOCITY, value as f64) }
+	fn alSpeedOfSound(value: sys::ALfloat) { set_context_prop(sys::AL_SPEED_OF_SOUND, value as f64) }
+	fn alDistanceModel(distanceModel: sys::ALenum) {
+		match distanceModel {
+			sys::AL_NONE | sys::AL_INVERSE_DISTANCE | sys::AL_INVERSE_DISTANCE_CLAMPED | sys::AL_LINEAR_DISTANCE
+				| sys::AL_LINEAR_DISTANCE_CLAMPED | sys::AL_EXPONENT_DISTANCE | sys::AL_EXPONENT_DISTANCE_CLAMPED
+			=> set_context_prop(sys::AL_DISTANCE_MODEL, distanceModel as f64),
+			_ => with_ctx(|state, ctx| al_error(state, ctx, sys::AL_INVALID_VALUE)),
+		}
+	}
+	fn alEnable(capability: sys::ALenum) {
+		let _ = capability;
+		with_ctx(|state, ctx| al_error(state, ctx, sys::AL_INVALID_ENUM))
+	}
+	fn alDisable(capability: sys::ALenum) {
+		let _ = capability;
+		with_ctx(|state, ctx| al_error(state, ctx, sys::AL_INVALID_ENUM))
+	}
+	fn alIsEnabled(capability: sys::ALenum) -> sys::ALboolean {
+		let _ = capability;
+		with_ctx(|state, ctx| { al_error(state, ctx, sys::AL_INVALID_ENUM); sys::AL_FALSE })
+	}
+	fn alGetString(param: sys::ALenum) -> *const sys::ALchar {
+		let s: &[u8] = match param {
+			sys::AL_VENDOR => b"alto\0",
+			sys::AL_VERSION => b"1.1 mock\0",
+			sys::AL_RENDERER => b"Alto Mock\0",
+			sys::AL_EXTENSIONS => b"\0",
+			sys::AL_NO_ERROR => b"No Error\0",
+			sys::AL_INVALID_NAME => b"Invalid Name\0",
+			sys::AL_INVALID_ENUM => b"Invalid Enum\0",
+			sys::AL_INVALID_VALUE => b"Invalid Value\0",
+			sys::AL_INVALID_OPERATION => b"Invalid Operation\0",
+			sys::AL_OUT_OF_MEMORY => b"Out of Memory\0",
+			_ => return with_ctx(|state, ctx| { al_error(state, ctx, sys::AL_INVALID_ENUM); ptr::null() }),
+		};
+		s.as_ptr() as *const sys::ALchar
+	}
+	fn alGetBooleanv(param: sys::ALenum, values: *mut sys::ALboolean) {
+		*values = (get_context_prop(param) != 0.0) as sys::ALboolean;
+	}
+	fn alGetIntegerv(param: sys::ALenum, values: *mut sys::ALint) {
+		*values = get_context_prop(param) as sys::ALint;
+	}
+	fn alGetFloatv(param: sys::ALenum, values: *mut sys::ALfloat) {
+		*values = get_context_prop(param) as sys::ALfloat;
+	}
+	fn alGetDoublev(param: sys::ALenum, values: *mut sys::ALdouble) {
+		*values = get_context_prop(param);
+	}
+	fn alGetBoolean(param: sys::ALenum) -> sys::ALboolean { (get_context_prop(param) != 0.0) as sys::ALboolean }
+	fn alGetInteger(param: sys::ALenum) -> sys::ALint { get_context_prop(param) as sys::ALint }
+	fn alGetFloat(param: sys::ALenum) -> sys::ALfloat { get_context_prop(param) as sys::ALfloat }
+	fn alGetDouble(param: sys::ALenum) -> sys::ALdouble { get_context_prop(param) }
+	fn alGetError() -> sys::ALenum {
+		let injected = INJECTED_AL.with(|i| i.replace(sys::AL_NO_ERROR));
+		if injected != sys::AL_NO_ERROR {
+			return injected;
+		}
+
+		with_ctx(|state, ctx| ::std::mem::replace(&mut state.contexts.get_mut(&ctx).unwrap().error, sys::AL_NO_ERROR))
+	}
+	fn alIsExtensionPresent(extname: *const sys::ALchar) -> sys::ALboolean {
+		if advertised(extname) { sys::AL_TRUE } else { sys::AL_FALSE }
+	}
+	fn alGetProcAddress(fname: *const sys::ALchar) -> *mut c_void {
+		let _ = fname;
+		ptr::null_mut()
+	}
+	fn alGetEnumValue(ename: *const sys::ALchar) -> sys::ALenum {
+		let _ = ename;
+		0
+	}
+	fn alListenerf(param: sys::ALenum, value: sys::ALfloat) { set_listener(param, vec![value as f64]) }
+	fn alListener3f(param: sys::ALenum, value1: sys::ALfloat, value2: sys::ALfloat, value3: sys::ALfloat) {
+		set_listener(param, vec![value1 as f64, value2 as f64, value3 as f64])
+	}
+	fn alListenerfv(param: sys::ALenum, values: *const sys::ALfloat) { set_listener(param, read(values, param)) }
+	fn alListeneri(param: sys::ALenum, value: sys::ALint) { set_listener(param, vec![value as f64]) }
+	fn alListener3i(param: sys::ALenum, value1: sys::ALint, value2: sys::ALint, value3: sys::ALint) {
+		set_listener(param, vec![value1 as f64, value2 as f64, value3 as f64])
+	}
+	fn alListeneriv(param: sys::ALenum, values: *const sys::ALint) { set_listener(param, read(values, param)) }
+	fn alGetListenerf(param: sys::ALenum, value: *mut sys::ALfloat) {
+		if let Some(v) = get_listener(param) { write_f32(value, &v[.. cmp::min(v.len(), 1)]) }
+	}
+	fn alGetListener3f(param: sys::ALenum, value1: *mut sys::ALfloat, value2: *mut sys::ALfloat, value3: *mut sys::ALfloat) {
+		if let Some(v) = get_listener(param) {
+			if v.len() >= 3 { *value1 = v[0] as sys::ALfloat; *value2 = v[1] as sys::ALfloat; *value3 = v[2] as sys::ALfloat; }
+		}
+	}
+	fn alGetListenerfv(param: sys::ALenum, values: *mut sys::ALfloat) {
+		if let Some(v) = get_listener(param) { write_f32(values, &v) }
+	}
+	fn alGetListeneri(param: sys::ALenum, value: *mut sys::ALint) {
+		if let Some(v) = get_listener(param) { write_i32(value, &v[.. cmp::min(v.len(), 1)]) }
+	}
+	fn alGetListener3i(param: sys::ALenum, value1: *mut sys::ALint, value2: *mut sys::ALint, value3: *mut sys::ALint) {
+		if let Some(v) = get_listener(param) {
+			if v.len() >= 3 { *value1 = v[0] as sys::ALint; *value2 = v[1] as sys::ALint; *value3 = v[2] as sys::ALint; }
+		}
+	}
+	fn alGetListeneriv(param: sys::ALenum, values: *mut sys::ALint) {
+		if let Some(v) = get_listener(param) { write_i32(values, &v) }
+	}
+	fn alGenSources(n: sys::ALsizei, sources: *mut sys::ALuint) {
+		with_ctx(|state, ctx| {
+			if n < 0 {
+				return al_error(state, ctx, sys::AL_INVALID_VALUE);
+			}
+			for i in 0 .. n as isize {
+				let id = gen_id(state) as sys::ALuint;
+				state.sources.insert(id, MockSource{
+					ctx: ctx,
+					ty: sys::AL_UNDETERMINED,
+					state: sys::AL_INITIAL,
+					queue: Vec::new(),
+					processed: 0,
+					props: HashMap::new(),
+				});
+				*sources.offset(i) = id;
+			}
+		})
+	}
+	fn alDeleteSources(n: sys::ALsizei, sources: *const sys::ALuint) {
+		with_ctx(|state, ctx| {
+			if n < 0 {
+				return al_error(state, ctx, sys::AL_INVALID_VALUE);
+			}
+			let sources = slice::from_raw_parts(sources, n as usize);
+			if !sources.iter().all(|&s| state.sources.get(&s).map(|s| s.ctx == ctx).unwrap_or(false)) {
+				return al_error(state, ctx, sys::AL_INVALID_NAME);
+			}
+			for s in sources {
+				state.sources.remove(s);
+			}
+		})
+	}
+	fn alIsSource(source: sys::ALuint) -> sys::ALboolean {
+		with_ctx(|state, ctx| match state.sources.get(&source) {
+			Some(s) if s.ctx == ctx => sys::AL_TRUE,
+			_ => sys::AL_FALSE,
+		})
+	}
+	fn alSourcef(source: sys::ALuint, param: sys::ALenum, value: sys::ALfloat) { set_source(source, param, vec![value as f64]) }
+	fn alSource3f(source: sys::ALuint, param: sys::ALenum, value1: sys::ALfloat, value2: sys::ALfloat, value3: sys::ALfloat) {
+		set_source(source, param, vec![value1 as f64, value2 as f64, value3 as f64])
+	}
+	fn alSourcefv(source: sys::ALuint, param: sys::ALenum, values: *const sys::ALfloat) { set_source(source, param, read(values, param)) }
+	fn alSourcei(source: sys::ALuint, param: sys::ALenum, value: sys::ALint) { set_source(source, param, vec![value as f64]) }
+	fn alSource3i(source: sys::ALuint, param: sys::ALenum, value1: sys::ALint, value2: sys::ALint, value3: sys::ALint) {
+		set_source(source, param, vec![value1 as f64, value2 as f64, value3 as f64])
+	}
+	fn alSourceiv(source: sys::ALuint, param: sys::ALenum, values: *const sys::ALint) { set_source(source, param, read(values, param)) }
+	fn alGetSourcef(source: sys::ALuint, param: sys::ALenum, value: *mut sys::ALfloat) {
+		if let Some(v) = get_source(source, param) { write_f32(value, &v[.. cmp::min(v.len(), 1)]) }
+	}
+	fn alGetSource3f(source: sys::ALuint, param: sys::ALenum, value1: *mut sys::ALfloat, value2: *mut sys::ALfloat, value3: *mut sys::ALfloat) {
+		if let Some(v) = get_source(source, param) {
+			if v.len() >= 3 { *value1 = v[0] as sys::ALfloat; *value2 = v[1] as sys::ALfloat; *value3 = v[2] as sys::ALfloat; }
+		}
+	}
+	fn alGetSourcefv(source: sys::ALuint, param: sys::ALenum, values: *mut sys::ALfloat) {
+		if let Some(v) = get_source(source, param) { write_f32(values, &v) }
+	}
+	fn alGetSourcei(source: sys::ALuint, param: sys::ALenum, value: *mut sys::ALint) {
+		if let Some(v) = get_source(source, param) { write_i32(value, &v[.. cmp::min(v.len(), 1)]) }
+	}
+	fn alGetSource3i(source: sys::ALuint, param: sys::ALenum, value1: *mut sys::ALint, value2: *mut sys::ALint, value3: *mut sys::ALint) {
+		if let Some(v) = get_source(source, param) {
+			if v.len() >= 3 { *value1 = v[0] as sys::ALint; *value2 = v[1] as sys::ALint; *value3 = v[2] as sys::ALint; }
+		}
+	}
+	fn alGetSourceiv(source: sys::ALuint, param: sys::ALenum, values: *mut sys::ALint) {
+		if let Some(v) = get_source(source, param) { write_i32(values, &v) }
+	}
+	fn alSourcePlayv(n: sys::ALsizei, sources: *const sys::ALuint) { transition(n, sources, play) }
+	fn alSourceStopv(n: sys::ALsizei, sources: *const sys::ALuint) { transition(n, sources, stop) }
+	fn alSourceRewindv(n: sys::ALsizei, sources: *const sys::ALuint) { transition(n, sources, rewind) }
+	fn alSourcePausev(n: sys::ALsizei, sources: *const sys::ALuint) { transition(n, sources, pause) }
+	fn alSourcePlay(source: sys::ALuint) { transition(1, &source, play) }
+	fn alSourceStop(source: sys::ALuint) { transition(1, &source, stop) }
+	fn alSourceRewind(source: sys::ALuint) { transition(1, &source, rewind) }
+	fn alSourcePause(source: sys::ALuint) { transition(1, &source, pause) }
+	fn alSourceQueueBuffers(source: sys::ALuint, nb: sys::ALsizei, buffers: *const sys::ALuint) {
+		with_ctx(|state, ctx| {
+			if !source_ok(state, ctx, source) { return; }
+			if nb < 0 {
+				return al_error(state, ctx, sys::AL_INVALID_VALUE);
+			}
+			let buffers = slice::from_raw_parts(buffers, nb as usize);
+			if !buffers.iter().all(|b| state.buffers.contains_key(b)) {
+				return al_error(state, ctx, sys::AL_INVALID_NAME);
+			}
+
+			if state.sources[&source].ty == sys::AL_STATIC {
+				return al_error(state, ctx, sys::AL_INVALID_OPERATION);
+			}
+			let src = state.sources.get_mut(&source).unwrap();
+			src.ty = sys::AL_STREAMING;
+			src.queue.extend_from_slice(buffers);
+		})
+	}
+	fn alSourceUnqueueBuffers(source: sys::ALuint, nb: sys::ALsizei, buffers: *mut sys::ALuint) {
+		with_ctx(|state, ctx| {
+			if !source_ok(state, ctx, source) { return; }
+
+			let valid = {
+				let src = &state.sources[&source];
+				src.ty == sys::AL_STREAMING && nb >= 0 && nb as usize <= src.processed
+			};
+			if !valid {
+				return al_error(state, ctx, sys::AL_INVALID_VALUE);
+			}
+
+			let src = state.sources.get_mut(&source).unwrap();
+			for (i, buf) in src.queue.drain(.. nb as usize).enumerate() {
+				*buffers.offset(i as isize) = buf;
+			}
+			src.processed -= nb as usize;
+		})
+	}
+	fn alGenBuffers(n: sys::ALsizei, buffers: *mut sys::ALuint) {
+		with_ctx(|state, ctx| {
+			if n < 0 {
+				return al_error(state, ctx, sys::AL_INVALID_VALUE);
+			}
+			for i in 0 .. n as isize {
+				let id = gen_id(state) as sys::ALuint;
+				state.buffers.insert(id, MockBuffer{format: sys::AL_FORMAT_MONO8, freq: 0, data: Vec::new(), props: HashMap::new()});
+				*buffers.offset(i) = id;
+			}
+		})
+	}
+	fn alDeleteBuffers(n: sys::ALsizei, buffers: *const sys::ALuint) {
+		with_ctx(|state, ctx| {
+			if n < 0 {
+				return al_error(state, ctx, sys::AL_INVALID_VALUE);
+			}
+			let buffers = slice::from_raw_parts(buffers, n as usize);
+			if !buffers.iter().all(|b| *b == 0 || state.buffers.contains_key(b)) {
+				return al_error(state, ctx, sys::AL_INVALID_NAME);
+			}
+			if buffers.iter().any(|&b| buffer_in_use(state, b)) {
+				return al_error(state, ctx, sys::AL_INVALID_OPERATION);
+			}
+			for b in buffers {
+				state.buffers.remove(b);
+			}
+		})
+	}
+	fn alIsBuffer(buffer: sys::ALuint) -> sys::ALboolean {
+		with_ctx(|state, _| if buffer == 0 || state.buffers.contains_key(&buffer) { sys::AL_TRUE } else { sys::AL_FALSE })
+	}
+	fn alBufferData(buffer: sys::ALuint, format: sys::ALenum, data: *const sys::ALvoid, size: sys::ALsizei, freq: sys::ALsizei) {
+		with_ctx(|state, ctx| {
+			if !state.buffers.contains_key(&buffer) {
+				return al_error(state, ctx, sys::AL_INVALID_NAME);
+			}
+			let frame_size = match format_frame_size(format) {
+				Some(frame_size) => frame_size,
+				None => return al_error(state, ctx, sys::AL_INVALID_ENUM),
+			};
+			if size < 0 || size as usize % frame_size != 0 || freq <= 0 {
+				return al_error(state, ctx, sys::AL_INVALID_VALUE);
+			}
+			if buffer_in_use(state, buffer) {
+				return al_error(state, ctx, sys::AL_INVALID_OPERATION);
+			}
+
+			let buf = state.buffers.get_mut(&buffer).unwrap();
+			buf.format = format;
+			buf.freq = freq;
+			buf.data = slice::from_raw_parts(data as *const u8, size as usize).to_vec();
+		})
+	}
+	fn alBufferf(buffer: sys::ALuint, param: sys::ALenum, value: sys::ALfloat) { set_buffer(buffer, param, vec![value as f64]) }
+	fn alBuffer3f(buffer: sys::ALuint, param: sys::ALenum, value1: sys::ALfloat, value2: sys::ALfloat, value3: sys::ALfloat) {
+		set_buffer(buffer, param, vec![value1 as f64, value2 as f64, value3 as f64])
+	}
+	fn alBufferfv(buffer: sys::ALuint, param: sys::ALenum, values: *const sys::ALfloat) { set_buffer(buffer, param, read(values, param)) }
+	fn alBufferi(buffer: sys::ALuint, param: sys::ALenum, value: sys::ALint) { set_buffer(buffer, param, vec![value as f64]) }
+	fn alBuffer3i(buffer: sys::ALuint, param: sys::ALenum, value1: sys::ALint, value2: sys::ALint, value3: sys::ALint) {
+		set_buffer(buffer, param, vec![value1 as f64, value2 as f64, value3 as f64])
+	}
+	fn alBufferiv(buffer: sys::ALuint, param: sys::ALenum, values: *const sys::ALint) { set_buffer(buffer, param, read(values, param)) }
+	fn alGetBufferf(buffer: sys::ALuint, param: sys::ALenum, value: *mut sys::ALfloat) {
+		if let Some(v) = get_buffer(buffer, param) { write_f32(value, &v[.. cmp::min(v.len(), 1)]) }
+	}
+	fn alGetBuffer3f(buffer: sys::ALuint, param: sys::ALenum, value1: *mut sys::ALfloat, value2: *mut sys::ALfloat, value3: *mut sys::ALfloat) {
+		if let Some(v) = get_buffer(buffer, param) {
+			if v.len() >= 3 { *value1 = v[0] as sys::ALfloat; *value2 = v[1] as sys::ALfloat; *value3 = v[2] as sys::ALfloat; }
+		}
+	}
+	fn alGetBufferfv(buffer: sys::ALuint, param: sys::ALenum, values: *mut sys::ALfloat) {
+		if let Some(v) = get_buffer(buffer, param) { write_f32(values, &v) }
+	}
+	fn alGetBufferi(buffer: sys::ALuint, param: sys::ALenum, value: *mut sys::ALint) {
+		if let Some(v) = get_buffer(buffer, param) { write_i32(value, &v[.. cmp::min(v.len(), 1)]) }
+	}
+	fn alGetBuffer3i(buffer: sys::ALuint, param: sys::ALenum, value1: *mut sys::ALint, value2: *mut sys::ALint, value3: *mut sys::ALint) {
+		if let Some(v) = get_buffer(buffer, param) {
+			if v.len() >= 3 { *value1 = v[0] as sys::ALint; *value2 = v[1] as sys::ALint; *value3 = v[2] as sys::ALint; }
+		}
+	}
+	fn alGetBufferiv(buffer: sys::ALuint, param: sys::ALenum, values: *mut sys::ALint) {
+		if let Some(v) = get_buffer(buffer, param) { write_i32(values, &v) }
+	}
+}
@@ -0,0 +1,545 @@
+//! Reference-counted handles that keep their parent objects alive, as an alternative to the borrowing API.
+//! The types in the crate root borrow from one another, so a `Context` can't outlive the `Device` it was
+//! created from. That is zero-cost, but makes it hard to store audio objects in long-lived structs, move
+//! them across threads, or hold them in ECS components. The handles in this module instead hold an `Arc`
+//! of their parent, so they carry no lifetimes and can be stored anywhere.
+//!
+//! Each handle dereferences to its borrowing counterpart for read-only access. Sources implement
+//! `SourceTrait` directly, and buffers and sources can only be combined if they belong to the same device.
+
+
+use std::ffi::CStr;
+use std::ops::Deref;
+use std::sync::Arc;
+use std::collections::VecDeque;
+
+use ::{AltoError, AltoResult};
+use sys;
+use al::{self, SampleFrame, AsBufferData, ResolvedFormat, SourceTrait, SourceState, SourceProps, SourceProp, SourceCone, PropValues, DistanceModel};
+use alc::{self, Alto, ContextAttrs, ContextBuilder, DeviceTrait};
+#[cfg(feature = "efx")]
+use efx::{AuxEffectSlot, FilterTrait};
+use decode::{self, Decoder};
+
+
+// Fields are dropped in declaration order, so each object goes before the parent handle it keeps alive.
+struct DeviceInner {
+	dev: alc::Device<'static>,
+	_alto: Arc<Alto>,
+}
+
+
+/// An output device that keeps its `Alto` instance alive. Cloning the handle shares the same device.
+#[derive(Clone)]
+pub struct Device(Arc<DeviceInner>);
+
+
+struct ContextInner {
+	ctx: al::Context<'static>,
+	_dev: Device,
+}
+
+
+/// A context that keeps its device alive. Cloning the handle shares the same context.
+#[derive(Clone)]
+pub struct Context(Arc<ContextInner>);
+
+
+/// A buffer that keeps its context alive.
+/// Its data can only be replaced while it isn't attached to a source.
+pub struct Buffer {
+	buf: Arc<al::Buffer<'static, 'static>>,
+	ctx: Context,
+}
+
+
+/// A source that plays a single buffer, and keeps its context alive.
+pub struct StaticSource {
+	src: al::StaticSource<'static, 'static>,
+	buf_ctx: Option<Context>,
+	_ctx: Context,
+}
+
+
+/// A source that plays a queue of buffers, and keeps its context alive.
+pub struct StreamingSource {
+	src: al::StreamingSource<'static, 'static>,
+	buf_ctxs: VecDeque<Context>,
+	_ctx: Context,
+}
+
+
+impl Device {
+	/// `alcOpenDevice()`
+	pub fn open<'s, S: Into<Option<&'s CStr>>>(alto: Arc<Alto>, spec: S) -> AltoResult<Device> {
+		// The device borrows the `Alto` inside the `Arc`, which it keeps alive and which never moves.
+		let dev = unsafe { &*(&*alto as *const Alto) }.open(spec)?;
+		Ok(Device(Arc::new(DeviceInner{dev: dev, _alto: alto})))
+	}
+
+
+	/// `alcCreateContext()`
+	pub fn new_context<A: Into<Option<ContextAttrs>>>(&self, attrs: A) -> AltoResult<Context> {
+		let ctx = unsafe { &*(&self.0.dev as *const alc::Device<'static>) }.new_context(attrs)?;
+		Ok(Context(Arc::new(ContextInner{ctx: ctx, _dev: self.clone()})))
+	}
+}
+
+
+impl Deref for Device {
+	type Target = alc::Device<'static>;
+
+	fn deref(&self) -> &alc::Device<'static> { &self.0.dev }
+}
+
+
+impl Context {
+	/// `alcOpenDevice()` and `alcCreateContext()` in one call.
+	/// `configure` sets the attributes of the context on a builder, such as `|b| b.frequency(48000)`,
+	/// and they are validated against the device. Errors name the device and whether it failed to open
+	/// or the context couldn't be created on it.
+	pub fn open<'s, S, F>(alto: Arc<Alto>, spec: S, configure: F) -> AltoResult<Context> where
+		S: Into<Option<&'s CStr>>,
+		F: for<'d> FnOnce(ContextBuilder<'static, 'd>) -> ContextBuilder<'static, 'd>,
+	{
+		let dev = Device::open(alto, spec)?;
+		let attrs = configure(dev.context_builder()).validate()
+			.map_err(|e| AltoError::AlcCreateContext(Box::new(e), dev.specifier().to_string_lossy().into_owned()))?;
+		dev.new_context(attrs)
+	}
+
+
+	fn raw(&self) -> &'static al::Context<'static> {
+		// Everything created from the context holds a clone of this handle, so it outlives them.
+		unsafe { &*(&self.0.ctx as *const al::Context<'static>) }
+	}
+
+
+	/// The device this context was created from.
+	pub fn device(&self) -> &Device { &self.0._dev }
+
+
+	/// `alGenBuffers()`
+	pub fn new_buffer(&self) -> AltoResult<Buffer> {
+		Ok(Buffer{buf: Arc::new(self.raw().new_buffer()?), ctx: self.clone()})
+	}
+
+
+	/// Create a buffer holding the entire remaining output of a decoder.
+	pub fn new_buffer_from_decoder<D: Decoder + ?Sized>(&self, dec: &mut D) -> AltoResult<Buffer> {
+		let mut buf = self.raw().new_buffer()?;
+		if let Some(samples) = decode::decode_all(dec)? {
+			samples.upload(&mut buf, dec.channels(), dec.frequency())?;
+		}
+		Ok(Buffer{buf: Arc::new(buf), ctx: self.clone()})
+	}
+
+
+	/// `alGenSources()`
+	pub fn new_static_source(&self) -> AltoResult<StaticSource> {
+		Ok(StaticSource{src: self.raw().new_static_source()?, buf_ctx: None, _ctx: self.clone()})
+	}
+
+
+	/// `alGenSources()`
+	pub fn new_streaming_source(&self) -> AltoResult<StreamingSource> {
+		Ok(StreamingSource{src: self.raw().new_streaming_source()?, buf_ctxs: VecDeque::new(), _ctx: self.clone()})
+	}
+
+
+	/// `alGenSources()`
+	/// Generates `n` sources in a single call. Either every source is created or none are.
+	pub fn new_static_sources(&self, n: usize) -> AltoResult<Vec<StaticSource>> {
+		Ok(self.raw().new_static_sources(n)?.into_iter().map(|src| StaticSource{src: src, buf_ctx: None, _ctx: self.clone()}).collect())
+	}
+
+
+	/// `alGenSources()`
+	/// Generates `n` sources in a single call. Either every source is created or none are.
+	pub fn new_streaming_sources(&self, n: usize) -> AltoResult<Vec<StreamingSource>> {
+		Ok(self.raw().new_streaming_sources(n)?.into_iter().map(|src| StreamingSource{src: src, buf_ctxs: VecDeque::new(), _ctx: self.clone()}).collect())
+	}
+}
+
+
+impl Deref for Context {
+	type Target = al::Context<'static>;
+
+	fn deref(&self) -> &al::Context<'static> { &self.0.ctx }
+}
+
+
+impl PartialEq for Context {
+	fn eq(&self, other: &Context) -> bool {
+		Arc::ptr_eq(&self.0, &other.0)
+	}
+}
+impl Eq for Context { }
+
+
+impl Buffer {
+	/// `alBufferData()`
+	/// Fails with `AlInvalidOperation` while the buffer is attached to a source.
+	pub fn set_data<F: SampleFrame, R: AsBufferData<F>>(&mut self, data: R, freq: i32) -> AltoResult<()> {
+		Arc::get_mut(&mut self.buf).ok_or(AltoError::AlInvalidOperation)?.set_data(data, freq)
+	}
+	/// `alBufferData()` with a format resolved by `Context::resolve_format()`.
+	/// Fails with `AlInvalidOperation` while the buffer is attached to a source.
+	pub fn set_data_raw(&mut self, format: ResolvedFormat, data: &[u8], freq: i32) -> AltoResult<()> {
+		Arc::get_mut(&mut self.buf).ok_or(AltoError::AlInvalidOperation)?.set_data_raw(format, data, freq)
+	}
+}
+
+
+impl Deref for Buffer {
+	type Target = al::Buffer<'static, 'static>;
+
+	fn deref(&self) -> &al::Buffer<'static, 'static> { &self.buf }
+}
+
+
+impl StaticSource {
+	/// `alSourcei(AL_BUFFER)`
+	pub fn set_buffer(&mut self, buf: &Buffer) -> AltoResult<()> {
+		self.src.set_buffer(buf.buf.clone())?;
+		self.buf_ctx = Some(buf.ctx.clone());
+		Ok(())
+	}
+	/// `alSourcei(AL_BUFFER)`
+	pub fn clear_buffer(&mut self) -> AltoResult<()> {
+		self.src.clear_buffer()?;
+		self.buf_ctx = None;
+		Ok(())
+	}
+
+
+	/// Play only the sample frames `[start, end)` of the attached buffer, stopped at `end` by `Context::maintain()`.
+	pub fn play_range(&mut self, start: sys::ALint, end: sys::ALint) -> AltoResult<()> { self.src.play_range(start, end) }
+
+
+	/// `alGetSourcei(AL_LOOPING)`
+	pub fn looping(&self) -> AltoResult<bool> { self.src.looping() }
+	/// `alSourcei(AL_LOOPING)`
+	pub fn set_looping(&mut self, value: bool) -> AltoResult<()> { self.src.set_looping(value) }
+}
+
+
+unsafe impl SourceTrait<'static, 'static> for StaticSource {
+	fn context(&self) -> &al::Context<'static> { self.src.context() }
+	fn as_raw(&self) -> sys::ALuint { self.src.as_raw() }
+
+	fn state(&self) -> AltoResult<SourceState> { self.src.state() }
+	fn play(&mut self) -> AltoResult<()> { self.src.play() }
+	fn pause(&mut self) -> AltoResult<()> { self.src.pause() }
+	fn stop(&mut self) -> AltoResult<()> { self.src.stop() }
+	fn rewind(&mut self) -> AltoResult<()> { self.src.rewind() }
+
+	fn relative(&self) -> AltoResult<bool> { self.src.relative() }
+	fn set_relative(&mut self, value: bool) -> AltoResult<()> { self.src.set_relative(value) }
+
+	fn gain(&self) -> AltoResult<f32> { self.src.gain() }
+	fn set_gain(&mut self, value: f32) -> AltoResult<()> { self.src.set_gain(value) }
+
+	fn min_gain(&self) -> AltoResult<f32> { self.src.min_gain() }
+	fn set_min_gain(&mut self, value: f32) -> AltoResult<()> { self.src.set_min_gain(value) }
+
+	fn max_gain(&self) -> AltoResult<f32> { self.src.max_gain() }
+	fn set_max_gain(&mut self, value: f32) -> AltoResult<()> { self.src.set_max_gain(value) }
+
+	fn reference_distance(&self) -> AltoResult<f32> { self.src.reference_distance() }
+	fn set_reference_distance(&mut self, value: f32) -> AltoResult<()> { self.src.set_reference_distance(value) }
+
+	fn rolloff_factor(&self) -> AltoResult<f32> { self.src.rolloff_factor() }
+	fn set_rolloff_factor(&mut self, value: f32) -> AltoResult<()> { self.src.set_rolloff_factor(value) }
+
+	fn max_distance(&self) -> AltoResult<f32> { self.src.max_distance() }
+	fn set_max_distance(&mut self, value: f32) -> AltoResult<()> { self.src.set_max_distance(value) }
+
+	fn pitch(&self) -> AltoResult<f32> { self.src.pitch() }
+	fn set_pitch(&mut self, value: f32) -> AltoResult<()> { self.src.set_pitch(value) }
+
+	fn position<V: From<[f32; 3]>>(&self) -> AltoResult<V> { self.src.position() }
+	fn set_position<V: Into<[f32; 3]>>(&mut self, value: V) -> AltoResult<()> { self.src.set_position(value) }
+
+	fn velocity<V: From<[f32; 3]>>(&self) -> AltoResult<V> { self.src.velocity() }
+	fn set_velocity<V: Into<[f32; 3]>>(&mut self, value: V) -> AltoResult<()> { self.src.set_velocity(value) }
+	fn auto_velocity(&self) -> Option<f32> { self.src.auto_velocity() }
+	fn set_auto_velocity(&mut self, smoothing: Option<f32>) -> AltoResult<()> { self.src.set_auto_velocity(smoothing) }
+
+	fn direction<V: From<[f32; 3]>>(&self) -> AltoResult<V> { self.src.direction() }
+	fn set_direction<V: Into<[f32; 3]>>(&mut self, value: V) -> AltoResult<()> { self.src.set_direction(value) }
+
+	fn orientation<V: From<[f32; 3]>>(&self) -> AltoResult<(V, V)> { self.src.orientation() }
+	fn set_orientation<V: Into<[f32; 3]>>(&mut self, value: (V, V)) -> AltoResult<()> { self.src.set_orientation(value) }
+
+	fn cone_inner_angle(&self) -> AltoResult<f32> { self.src.cone_inner_angle() }
+	fn set_cone_inner_angle(&mut self, value: f32) -> AltoResult<()> { self.src.set_cone_inner_angle(value) }
+
+	fn cone_outer_angle(&self) -> AltoResult<f32> { self.src.cone_outer_angle() }
+	fn set_cone_outer_angle(&mut self, value: f32) -> AltoResult<()> { self.src.set_cone_outer_angle(value) }
+
+	fn cone_outer_gain(&self) -> AltoResult<f32> { self.src.cone_outer_gain() }
+	fn set_cone_outer_gain(&mut self, value: f32) -> AltoResult<()> { self.src.set_cone_outer_gain(value) }
+	fn set_cone(&mut self, cone: SourceCone) -> AltoResult<()> { self.src.set_cone(cone) }
+
+	fn sec_offset(&self) -> AltoResult<f32> { self.src.sec_offset() }
+	fn set_sec_offset(&mut self, value: f32) -> AltoResult<()> { self.src.set_sec_offset(value) }
+
+	fn sample_offset(&self) -> AltoResult<sys::ALint> { self.src.sample_offset() }
+	fn set_sample_offset(&mut self, value: sys::ALint) -> AltoResult<()> { self.src.set_sample_offset(value) }
+
+	fn byte_offset(&self) -> AltoResult<sys::ALint> { self.src.byte_offset() }
+	fn set_byte_offset(&mut self, value: sys::ALint) -> AltoResult<()> { self.src.set_byte_offset(value) }
+
+	fn soft_sec_offset_latency(&self) -> AltoResult<(f64, f64)> { self.src.soft_sec_offset_latency() }
+
+	fn soft_sample_frac_offset_latency(&self) -> AltoResult<(i32, i32, i64)> { self.src.soft_sample_frac_offset_latency() }
+
+	fn soft_sec_length(&self) -> AltoResult<f32> { self.src.soft_sec_length() }
+
+	fn soft_sample_length(&self) -> AltoResult<sys::ALint> { self.src.soft_sample_length() }
+
+	fn soft_byte_length(&self) -> AltoResult<sys::ALint> { self.src.soft_byte_length() }
+
+	fn soft_direct_channels(&self) -> AltoResult<bool> { self.src.soft_direct_channels() }
+	fn set_soft_direct_channels(&mut self, value: bool) -> AltoResult<()> { self.src.set_soft_direct_channels(value) }
+
+	fn distance_model(&self) -> AltoResult<DistanceModel> { self.src.distance_model() }
+	fn set_distance_model(&mut self, value: DistanceModel) -> AltoResult<()> { self.src.set_distance_model(value) }
+
+	fn state_snapshot(&self) -> AltoResult<SourceProps> { self.src.state_snapshot() }
+	fn apply(&mut self, props: &SourceProps) -> AltoResult<()> { self.src.apply(props) }
+	fn apply_changed(&mut self, old: &SourceProps, new: &SourceProps) -> AltoResult<()> { self.src.apply_changed(old, new) }
+	fn get_many(&self, props: &[SourceProp]) -> AltoResult<PropValues> { self.src.get_many(props) }
+
+	#[cfg(feature = "efx")]
+	fn set_direct_filter<F: FilterTrait<'static, 'static>>(&mut self, value: &F) -> AltoResult<()> { self.src.set_direct_filter(value) }
+	#[cfg(feature = "efx")]
+	fn clear_direct_filter(&mut self) -> AltoResult<()> { self.src.clear_direct_filter() }
+
+	#[cfg(feature = "efx")]
+	fn set_auxiliary_send(&mut self, send: sys::ALint, slot: &mut AuxEffectSlot<'static, 'static>) -> AltoResult<()> { self.src.set_auxiliary_send(send, slot) }
+	#[cfg(feature = "efx")]
+	fn set_auxiliary_send_filter<F: FilterTrait<'static, 'static>>(&mut self, send: sys::ALint, slot: &mut AuxEffectSlot<'static, 'static>, filter: &F) -> AltoResult<()> { self.src.set_auxiliary_send_filter(send, slot, filter) }
+	#[cfg(feature = "efx")]
+	fn clear_auxiliary_send(&mut self, send: sys::ALint) -> AltoResult<()> { self.src.clear_auxiliary_send(send) }
+
+	#[cfg(feature = "efx")]
+	fn air_absorption_factor(&self) -> AltoResult<f32> { self.src.air_absorption_factor() }
+	#[cfg(feature = "efx")]
+	fn set_air_absorption_factor(&mut self, value: f32) -> AltoResult<()> { self.src.set_air_absorption_factor(value) }
+
+	#[cfg(feature = "efx")]
+	fn room_rolloff_factor(&self) -> AltoResult<f32> { self.src.room_rolloff_factor() }
+	#[cfg(feature = "efx")]
+	fn set_room_rolloff_factor(&mut self, value: f32) -> AltoResult<()> { self.src.set_room_rolloff_factor(value) }
+
+	#[cfg(feature = "efx")]
+	fn cone_outer_gainhf(&self) -> AltoResult<f32> { self.src.cone_outer_gainhf() }
+	#[cfg(feature = "efx")]
+	fn set_cone_outer_gainhf(&mut self, value: f32) -> AltoResult<()> { self.src.set_cone_outer_gainhf(value) }
+
+	#[cfg(feature = "efx")]
+	fn direct_filter_gainhf_auto(&self) -> AltoResult<bool> { self.src.direct_filter_gainhf_auto() }
+	#[cfg(feature = "efx")]
+	fn set_direct_filter_gainhf_auto(&mut self, value: bool) -> AltoResult<()> { self.src.set_direct_filter_gainhf_auto(value) }
+}
+
+
+impl StreamingSource {
+	/// `alGetSourcei(AL_BUFFERS_QUEUED)`
+	pub fn buffers_queued(&self) -> AltoResult<sys::ALint> { self.src.buffers_queued() }
+	/// `alGetSourcei(AL_BUFFERS_PROCESSED)`
+	pub fn buffers_processed(&self) -> AltoResult<sys::ALint> { self.src.buffers_processed() }
+
+
+	/// `alSourceQueueBuffers()`
+	/// Fails with `AlInvalidOperation` if the buffer is also attached to a static source.
+	pub fn queue_buffer(&mut self, buf: Buffer) -> Result<(), (AltoError, Buffer)> {
+		let Buffer{buf, ctx} = buf;
+		match Arc::try_unwrap(buf) {
+			Ok(buf) => match self.src.queue_buffer(buf) {
+				Ok(()) => {
+					self.buf_ctxs.push_back(ctx);
+					Ok(())
+				},
+				Err((e, buf)) => Err((e, Buffer{buf: Arc::new(buf), ctx: ctx})),
+			},
+			Err(buf) => Err((AltoError::AlInvalidOperation, Buffer{buf: buf, ctx: ctx})),
+		}
+	}
+
+
+	/// `alSourceUnqueueBuffers()`
+	pub fn unqueue_buffer(&mut self) -> AltoResult<Buffer> {
+		let buf = self.src.unqueue_buffer()?;
+		Ok(Buffer{buf: Arc::new(buf), ctx: self.buf_ctxs.pop_front().unwrap()})
+	}
+
+
+	/// `alSourceQueueBuffers()`
+	/// Queue several buffers in order, taking the context lock once for all of them.
+	/// Fails with `AlInvalidOperation` if any buffer is also attached to a static source.
+	/// If this fails, none of the buffers are queued, and they are returned.
+	pub fn queue_buffers(&mut self, bufs: Vec<Buffer>) -> Result<(), (AltoError, Vec<Buffer>)> {
+		if bufs.iter().any(|buf| Arc::strong_count(&buf.buf) > 1) {
+			return Err((AltoError::AlInvalidOperation, bufs));
+		}
+
+		let (bufs, ctxs): (Vec<_>, Vec<_>) = bufs.into_iter().map(|Buffer{buf, ctx}| match Arc::try_unwrap(buf) {
+			Ok(buf) => (buf, ctx),
+			Err(_) => unreachable!(),
+		}).unzip();
+		match self.src.queue_buffers(bufs) {
+			Ok(()) => {
+				self.buf_ctxs.extend(ctxs);
+				Ok(())
+			},
+			Err((e, bufs)) => Err((e, bufs.into_iter().zip(ctxs).map(|(buf, ctx)| Buffer{buf: Arc::new(buf), ctx: ctx}).collect())),
+		}
+	}
+
+
+	/// `alSourceUnqueueBuffers()`
+	/// Unqueue the `n` oldest buffers, taking the context lock once for all of them.
+	pub fn unqueue_buffers(&mut self, n: usize) -> AltoResult<Vec<Buffer>> {
+		let bufs = self.src.unqueue_buffers(n)?;
+		Ok(self.wrap_unqueued(bufs))
+	}
+
+
+	/// Unqueue every buffer that has finished playing, taking the context lock once.
+	pub fn unqueue_processed(&mut self) -> AltoResult<Vec<Buffer>> {
+		let bufs = self.src.unqueue_processed()?;
+		Ok(self.wrap_unqueued(bufs))
+	}
+
+
+	fn wrap_unqueued(&mut self, bufs: Vec<al::Buffer<'static, 'static>>) -> Vec<Buffer> {
+		let n = bufs.len();
+		bufs.into_iter().zip(self.buf_ctxs.drain(.. n)).map(|(buf, ctx)| Buffer{buf: Arc::new(buf), ctx: ctx}).collect()
+	}
+}
+
+
+unsafe impl SourceTrait<'static, 'static> for StreamingSource {
+	fn context(&self) -> &al::Context<'static> { self.src.context() }
+	fn as_raw(&self) -> sys::ALuint { self.src.as_raw() }
+
+	fn state(&self) -> AltoResult<SourceState> { self.src.state() }
+	fn play(&mut self) -> AltoResult<()> { self.src.play() }
+	fn pause(&mut self) -> AltoResult<()> { self.src.pause() }
+	fn stop(&mut self) -> AltoResult<()> { self.src.stop() }
+	fn rewind(&mut self) -> AltoResult<()> { self.src.rewind() }
+
+	fn relative(&self) -> AltoResult<bool> { self.src.relative() }
+	fn set_relative(&mut self, value: bool) -> AltoResult<()> { self.src.set_relative(value) }
+
+	fn gain(&self) -> AltoResult<f32> { self.src.gain() }
+	fn set_gain(&mut self, value: f32) -> AltoResult<()> { self.src.set_gain(value) }
+
+	fn min_gain(&self) -> AltoResult<f32> { self.src.min_gain() }
+	fn set_min_gain(&mut self, value: f32) -> AltoResult<()> { self.src.set_min_gain(value) }
+
+	fn max_gain(&self) -> AltoResult<f32> { self.src.max_gain() }
+	fn set_max_gain(&mut self, value: f32) -> AltoResult<()> { self.src.set_max_gain(value) }
+
+	fn reference_distance(&self) -> AltoResult<f32> { self.src.reference_distance() }
+	fn set_reference_distance(&mut self, value: f32) -> AltoResult<()> { self.src.set_reference_distance(value) }
+
+	fn rolloff_factor(&self) -> AltoResult<f32> { self.src.rolloff_factor() }
+	fn set_rolloff_factor(&mut self, value: f32) -> AltoResult<()> { self.src.set_rolloff_factor(value) }
+
+	fn max_distance(&self) -> AltoResult<f32> { self.src.max_distance() }
+	fn set_max_distance(&mut self, value: f32) -> AltoResult<()> { self.src.set_max_distance(value) }
+
+	fn pitch(&self) -> AltoResult<f32> { self.src.pitch() }
+	fn set_pitch(&mut self, value: f32) -> AltoResult<()> { self.src.set_pitch(value) }
+
+	fn position<V: From<[f32; 3]>>(&self) -> AltoResult<V> { self.src.position() }
+	fn set_position<V: Into<[f32; 3]>>(&mut self, value: V) -> AltoResult<()> { self.src.set_position(value) }
+
+	fn velocity<V: From<[f32; 3]>>(&self) -> AltoResult<V> { self.src.velocity() }
+	fn set_velocity<V: Into<[f32; 3]>>(&mut self, value: V) -> AltoResult<()> { self.src.set_velocity(value) }
+	fn auto_velocity(&self) -> Option<f32> { self.src.auto_velocity() }
+	fn set_auto_velocity(&mut self, smoothing: Option<f32>) -> AltoResult<()> { self.src.set_auto_velocity(smoothing) }
+
+	fn direction<V: From<[f32; 3]>>(&self) -> AltoResult<V> { self.src.direction() }
+	fn set_direction<V: Into<[f32; 3]>>(&mut self, value: V) -> AltoResult<()> { self.src.set_direction(value) }
+
+	fn orientation<V: From<[f32; 3]>>(&self) -> AltoResult<(V, V)> { self.src.orientation() }
+	fn set_orientation<V: Into<[f32; 3]>>(&mut self, value: (V, V)) -> AltoResult<()> { self.src.set_orientation(value) }
+
+	fn cone_inner_angle(&self) -> AltoResult<f32> { self.src.cone_inner_angle() }
+	fn set_cone_inner_angle(&mut self, value: f32) -> AltoResult<()> { self.src.set_cone_inner_angle(value) }
+
+	fn cone_outer_angle(&self) -> AltoResult<f32> { self.src.cone_outer_angle() }
+	fn set_cone_outer_angle(&mut self, value: f32) -> AltoResult<()> { self.src.set_cone_outer_angle(value) }
+
+	fn cone_outer_gain(&self) -> AltoResult<f32> { self.src.cone_outer_gain() }
+	fn set_cone_outer_gain(&mut self, value: f32) -> AltoResult<()> { self.src.set_cone_outer_gain(value) }
+	fn set_cone(&mut self, cone: SourceCone) -> AltoResult<()> { self.src.set_cone(cone) }
+
+	fn sec_offset(&self) -> AltoResult<f32> { self.src.sec_offset() }
+	fn set_sec_offset(&mut self, value: f32) -> AltoResult<()> { self.src.set_sec_offset(value) }
+
+	fn sample_offset(&self) -> AltoResult<sys::ALint> { self.src.sample_offset() }
+	fn set_sample_offset(&mut self, value: sys::ALint) -> AltoResult<()> { self.src.set_sample_offset(value) }
+
+	fn byte_offset(&self) -> AltoResult<sys::ALint> { self.src.byte_offset() }
+	fn set_byte_offset(&mut self, value: sys::ALint) -> AltoResult<()> { self.src.set_byte_offset(value) }
+
+	fn soft_sec_offset_latency(&self) -> AltoResult<(f64, f64)> { self.src.soft_sec_offset_latency() }
+
+	fn soft_sample_frac_offset_latency(&self) -> AltoResult<(i32, i32, i64)> { self.src.soft_sample_frac_offset_latency() }
+
+	fn soft_sec_length(&self) -> AltoResult<f32> { self.src.soft_sec_length() }
+
+	fn soft_sample_length(&self) -> AltoResult<sys::ALint> { self.src.soft_sample_length() }
+
+	fn soft_byte_length(&self) -> AltoResult<sys::ALint> { self.src.soft_byte_length() }
+
+	fn soft_direct_channels(&self) -> AltoResult<bool> { self.src.soft_direct_channels() }
+	fn set_soft_direct_channels(&mut self, value: bool) -> AltoResult<()> { self.src.set_soft_direct_channels(value) }
+
+	fn distance_model(&self) -> AltoResult<DistanceModel> { self.src.distance_model() }
+	fn set_distance_model(&mut self, value: DistanceModel) -> AltoResult<()> { self.src.set_distance_model(value) }
+
+	fn state_snapshot(&self) -> AltoResult<SourceProps> { self.src.state_snapshot() }
+	fn apply(&mut self, props: &SourceProps) -> AltoResult<()> { self.src.apply(props) }
+	fn apply_changed(&mut self, old: &SourceProps, new: &SourceProps) -> AltoResult<()> { self.src.apply_changed(old, new) }
+	fn get_many(&self, props: &[SourceProp]) -> AltoResult<PropValues> { self.src.get_many(props) }
+
+	#[cfg(feature = "efx")]
+	fn set_direct_filter<F: FilterTrait<'static, 'static>>(&mut self, value: &F) -> AltoResult<()> { self.src.set_direct_filter(value) }
+	#[cfg(feature = "efx")]
+	fn clear_direct_filter(&mut self) -> AltoResult<()> { self.src.clear_direct_filter() }
+
+	#[cfg(feature = "efx")]
+	fn set_auxiliary_send(&mut self, send: sys::ALint, slot: &mut AuxEffectSlot<'static, 'static>) -> AltoResult<()> { self.src.set_auxiliary_send(send, slot) }
+	#[cfg(feature = "efx")]
+	fn set_auxiliary_send_filter<F: FilterTrait<'static, 'static>>(&mut self, send: sys::ALint, slot: &mut AuxEffectSlot<'static, 'static>, filter: &F) -> AltoResult<()> { self.src.set_auxiliary_send_filter(send, slot, filter) }
+	#[cfg(feature = "efx")]
+	fn clear_auxiliary_send(&mut self, send: sys::ALint) -> AltoResult<()> { self.src.clear_auxiliary_send(send) }
+
+	#[cfg(feature = "efx")]
+	fn air_absorption_factor(&self) -> AltoResult<f32> { self.src.air_absorption_factor() }
+	#[cfg(feature = "efx")]
+	fn set_air_absorption_factor(&mut self, value: f32) -> AltoResult<()> { self.src.set_air_absorption_factor(value) }
+
+	#[cfg(feature = "efx")]
+	fn room_rolloff_factor(&self) -> AltoResult<f32> { self.src.room_rolloff_factor() }
+	#[cfg(feature = "efx")]
+	fn set_room_rolloff_factor(&mut self, value: f32) -> AltoResult<()> { self.src.set_room_rolloff_factor(value) }
+
+	#[cfg(feature = "efx")]
+	fn cone_outer_gainhf(&self) -> AltoResult<f32> { self.src.cone_outer_gainhf() }
+	#[cfg(feature = "efx")]
+	fn set_cone_outer_gainhf(&mut self, value: f32) -> AltoResult<()> { self.src.set_cone_outer_gainhf(value) }
+
+	#[cfg(feature = "efx")]
+	fn direct_filter_gainhf_auto(&self) -> AltoResult<bool> { self.src.direct_filter_gainhf_auto() }
+	#[cfg(feature = "efx")]
+	fn set_direct_filter_gainhf_auto(&mut self, value: bool) -> AltoResult<()> { self.src.set_direct_filter_gainhf_auto(value) }
+}
+
@@ -0,0 +1,13 @@
+//! The traits needed for common operations, for glob import.
+//! Most methods on sources, devices, and sample data are provided by traits, which must be in scope
+//! to be called. `use alto::prelude::*;` brings all of them in at once.
+
+
+pub use al::{SourceTrait, SampleFrame, StandardFrame, AsBufferData, AsBufferDataMut};
+pub use alc::DeviceTrait;
+#[cfg(feature = "loopback")]
+pub use alc::LoopbackFrame;
+#[cfg(feature = "efx")]
+pub use efx::{EffectTrait, FilterTrait};
+pub use decode::Decoder;
+pub use metrics::MetricsSink;
@@ -0,0 +1,110 @@
+//! Typed playback states, as an optional layer over `SourceTrait`.
+//! Starting a source with [`Playing::play()`](struct.Playing.html#method.play) consumes it and returns a
+//! token that only offers what makes sense during playback. Stopping the token gives the idle source back,
+//! so calls such as pausing a source that was never started are rejected by the compiler.
+//!
+//! Tokens track what the application requested. A source that isn't looping still stops by itself at the
+//! end of its data, which `state()` or `is_finished()` will report.
+//!
+//! Transitions that fail return the error together with the value they were called on, so the source is never lost.
+
+
+use ::{AltoError, AltoResult};
+use sys;
+use al::{SourceTrait, SourceState};
+
+
+/// A source that has been started with `alSourcePlay()`.
+pub struct Playing<S>(S);
+
+
+/// A source that has been paused with `alSourcePause()`.
+pub struct Paused<S>(S);
+
+
+impl<'d: 'c, 'c, S: SourceTrait<'d, 'c>> Playing<S> {
+	/// `alSourcePlay()`
+	pub fn play(mut src: S) -> Result<Playing<S>, (AltoError, S)> {
+		match src.play() {
+			Ok(()) => Ok(Playing(src)),
+			Err(e) => Err((e, src)),
+		}
+	}
+
+
+	#[inline]
+	pub fn source(&self) -> &S { &self.0 }
+	/// Give up the typed state and return the source as is.
+	#[inline]
+	pub fn into_inner(self) -> S { self.0 }
+
+
+	/// `alGetSourcei(AL_SOURCE_STATE)`
+	pub fn state(&self) -> AltoResult<SourceState> { self.0.state() }
+	/// Whether the source has reached the end of its data and stopped by itself.
+	pub fn is_finished(&self) -> AltoResult<bool> { self.0.state().map(|s| s == SourceState::Stopped) }
+
+
+	/// `alGetSourcef(AL_SEC_OFFSET)`
+	pub fn sec_offset(&self) -> AltoResult<f32> { self.0.sec_offset() }
+	/// `alGetSourcei(AL_SAMPLE_OFFSET)`
+	pub fn sample_offset(&self) -> AltoResult<sys::ALint> { self.0.sample_offset() }
+	/// `alGetSourcei(AL_BYTE_OFFSET)`
+	pub fn byte_offset(&self) -> AltoResult<sys::ALint> { self.0.byte_offset() }
+	/// `alGetSourcedvSOFT(AL_SEC_OFFSET_LATENCY_SOFT)`
+	/// Requires `AL_SOFT_source_latency`
+	pub fn soft_sec_offset_latency(&self) -> AltoResult<(f64, f64)> { self.0.soft_sec_offset_latency() }
+
+
+	/// `alSourcePause()`
+	pub fn pause(mut self) -> Result<Paused<S>, (AltoError, Playing<S>)> {
+		match self.0.pause() {
+			Ok(()) => Ok(Paused(self.0)),
+			Err(e) => Err((e, self)),
+		}
+	}
+
+
+	/// `alSourceStop()`
+	pub fn stop(mut self) -> Result<S, (AltoError, Playing<S>)> {
+		match self.0.stop() {
+			Ok(()) => Ok(self.0),
+			Err(e) => Err((e, self)),
+		}
+	}
+}
+
+
+impl<'d: 'c, 'c, S: SourceTrait<'d, 'c>> Paused<S> {
+	#[inline]
+	pub fn source(&self) -> &S { &self.0 }
+	/// Give up the typed state and return the source as is.
+	#[inline]
+	pub fn into_inner(self) -> S { self.0 }
+
+
+	/// `alGetSourcef(AL_SEC_OFFSET)`
+	pub fn sec_offset(&self) -> AltoResult<f32> { self.0.sec_offset() }
+	/// `alGetSourcei(AL_SAMPLE_OFFSET)`
+	pub fn sample_offset(&self) -> AltoResult<sys::ALint> { self.0.sample_offset() }
+	/// `alGetSourcei(AL_BYTE_OFFSET)`
+	pub fn byte_offset(&self) -> AltoResult<sys::ALint> { self.0.byte_offset() }
+
+
+	/// `alSourcePlay()`
+	pub fn resume(mut self) -> Result<Playing<S>, (AltoError, Paused<S>)> {
+		match self.0.play() {
+			Ok(()) => Ok(Playing(self.0)),
+			Err(e) => Err((e, self)),
+		}
+	}
+
+
+	/// `alSourceStop()`
+	pub fn stop(mut self) -> Result<S, (AltoError, Paused<S>)> {
+		match self.0.stop() {
+			Ok(()) => Ok(self.0),
+			Err(e) => Err((e, self)),
+		}
+	}
+}
@@ -0,0 +1,204 @@
+#![cfg(feature = "mock")]
+
+extern crate alto;
+
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+use alto::*;
+
+
+fn names(calls: Vec<mock::MockCall>) -> Vec<&'static str> {
+	calls.into_iter().map(|c| c.name).collect()
+}
+
+
+fn filled<'d, 'c>(ctx: &'c Context<'d>, frames: usize) -> Buffer<'d, 'c> {
+	let mut buf = ctx.new_buffer().unwrap();
+	buf.set_data(&vec![Mono{center: 0i16}; frames][..], 44100).unwrap();
+	buf
+}
+
+
+#[test]
+fn source_and_buffer_lifecycle() {
+	let alto = Alto::load_mock().unwrap();
+	let dev = alto.open(None).unwrap();
+	let ctx = dev.new_context(None).unwrap();
+
+	let buf = Arc::new(filled(&ctx, 32));
+	assert_eq!(buf.frequency().unwrap(), 44100);
+	assert_eq!(buf.size().unwrap(), 64);
+
+	let mut src = ctx.new_static_source().unwrap();
+	src.set_buffer(buf.clone()).unwrap();
+	assert_eq!(src.state().unwrap(), SourceState::Initial);
+	src.play().unwrap();
+	assert_eq!(src.state().unwrap(), SourceState::Playing);
+	mock::finish_playback(src.as_raw());
+	assert_eq!(src.state().unwrap(), SourceState::Stopped);
+
+	mock::take_calls();
+	drop(src);
+	assert!(names(mock::take_calls()).contains(&"alDeleteSources"));
+
+	// Buffers are only deleted by the next `maintain()`.
+	drop(buf);
+	assert!(!names(mock::take_calls()).contains(&"alDeleteBuffers"));
+	ctx.maintain().unwrap();
+	assert!(names(mock::take_calls()).contains(&"alDeleteBuffers"));
+
+	drop(ctx);
+	assert!(names(mock::take_calls()).contains(&"alcDestroyContext"));
+}
+
+
+#[test]
+fn queue_and_unqueue() {
+	let alto = Alto::load_mock().unwrap();
+	let dev = alto.open(None).unwrap();
+	let ctx = dev.new_context(None).unwrap();
+
+	let mut src = ctx.new_streaming_source().unwrap();
+	let bufs: Vec<_> = (0..4).map(|i| filled(&ctx, 8 * (i + 1))).collect();
+	let raw: Vec<_> = bufs.iter().map(|b| b.as_raw()).collect();
+	src.queue_buffers(bufs).map_err(|(e, _)| e).unwrap();
+	assert_eq!(src.buffers_queued().unwrap(), 4);
+	assert_eq!(src.buffers_processed().unwrap(), 0);
+
+	// Nothing is processed until the source plays.
+	mock::finish_buffers(src.as_raw(), 2);
+	assert_eq!(src.buffers_processed().unwrap(), 0);
+	assert!(src.unqueue_buffer().is_err());
+
+	src.play().unwrap();
+	mock::finish_buffers(src.as_raw(), 2);
+	assert_eq!(src.buffers_processed().unwrap(), 2);
+	assert_eq!(src.state().unwrap(), SourceState::Playing);
+
+	let done = src.unqueue_processed().unwrap();
+	assert_eq!(done.iter().map(|b| b.as_raw()).collect::<Vec<_>>(), &raw[..2]);
+	assert_eq!(src.buffers_queued().unwrap(), 2);
+	assert!(src.unqueue_buffers(1).is_err());
+
+	// Refilled buffers go to the back of the queue.
+	src.queue_buffers(done).map_err(|(e, _)| e).unwrap();
+	mock::finish_buffers(src.as_raw(), 4);
+	assert_eq!(src.state().unwrap(), SourceState::Stopped);
+	let order: Vec<_> = src.unqueue_buffers(4).unwrap().iter().map(|b| b.as_raw()).collect();
+	assert_eq!(order, vec![raw[2], raw[3], raw[0], raw[1]]);
+	assert_eq!(src.buffers_queued().unwrap(), 0);
+}
+
+
+#[test]
+fn into_raw_releases_without_destroying() {
+	let alto = Alto::load_mock().unwrap();
+	let dev = alto.open(None).unwrap();
+	let ctx = dev.new_context(None).unwrap();
+
+	mock::take_calls();
+	let raw = ctx.into_raw();
+	assert!(!names(mock::take_calls()).contains(&"alcDestroyContext"));
+	let ctx = unsafe { Context::from_raw(&dev, raw) }.unwrap();
+	drop(ctx);
+	assert!(names(mock::take_calls()).contains(&"alcDestroyContext"));
+
+	let raw = dev.into_raw();
+	assert!(!names(mock::take_calls()).contains(&"alcCloseDevice"));
+	drop(unsafe { Device::from_raw(&alto, raw) }.unwrap());
+	assert!(names(mock::take_calls()).contains(&"alcCloseDevice"));
+}
+
+
+#[test]
+fn injected_errors() {
+	let alto = Alto::load_mock().unwrap();
+	let dev = alto.open(None).unwrap();
+	let ctx = dev.new_context(None).unwrap();
+
+	mock::fail_next("alGenSources", sys::AL_OUT_OF_MEMORY);
+	assert!(ctx.new_static_source().unwrap_err().is_out_of_memory());
+	assert!(ctx.new_static_source().is_ok());
+
+	// A failed upload leaves the old data in place.
+	let mut buf = filled(&ctx, 8);
+	mock::fail_next("alBufferData", sys::AL_INVALID_VALUE);
+	assert!(buf.set_data(&[Mono{center: 0i16}; 32][..], 22050).unwrap_err().is_invalid_value());
+	assert_eq!(buf.frequency().unwrap(), 44100);
+	assert_eq!(buf.size().unwrap(), 16);
+
+	// A buffer that fails to queue is handed back.
+	let mut src = ctx.new_streaming_source().unwrap();
+	mock::fail_next("alSourceQueueBuffers", sys::AL_INVALID_OPERATION);
+	let (e, buf) = src.queue_buffer(buf).unwrap_err();
+	assert!(e.is_invalid_operation());
+	assert_eq!(src.buffers_queued().unwrap(), 0);
+	src.queue_buffer(buf).map_err(|(e, _)| e).unwrap();
+
+	mock::fail_next("alSourcePlay", sys::AL_INVALID_OPERATION);
+	assert!(src.play().is_err());
+	assert_eq!(src.state().unwrap(), SourceState::Initial);
+
+	mock::fail_next("alcCreateContext", sys::ALC_INVALID_VALUE);
+	assert!(dev.new_context(None).is_err());
+	assert!(dev.new_context(None).is_ok());
+}
+
+
+#[test]
+fn cone_is_validated_before_writing() {
+	let alto = Alto::load_mock().unwrap();
+	let dev = alto.open(None).unwrap();
+	let ctx = dev.new_context(None).unwrap();
+	let mut src = ctx.new_static_source().unwrap();
+
+	match src.set_cone(SourceCone{inner_angle: 90.0, outer_angle: 45.0, outer_gain: 0.5, outer_gain_hf: None}) {
+		Err(AltoError::AlInvalidRange(reason)) => assert_eq!(reason, "inner_angle must not exceed outer_angle"),
+		r => panic!("{:?}", r),
+	}
+	assert_eq!(src.cone_inner_angle().unwrap(), 360.0);
+	assert_eq!(src.cone_outer_gain().unwrap(), 0.0);
+
+	src.set_cone(SourceCone{inner_angle: 30.0, outer_angle: 90.0, outer_gain: 0.25, outer_gain_hf: None}).unwrap();
+	assert_eq!(src.cone_inner_angle().unwrap(), 30.0);
+	assert_eq!(src.cone_outer_angle().unwrap(), 90.0);
+	assert_eq!(src.cone_outer_gain().unwrap(), 0.25);
+}
+
+
+#[test]
+fn nested_contexts_on_one_thread() {
+	let (tx, rx) = mpsc::channel();
+	thread::spawn(move || {
+		let alto = Alto::load_mock().unwrap();
+		let dev = alto.open(None).unwrap();
+		let a = dev.new_context(None).unwrap();
+		let b = dev.new_context(None).unwrap();
+
+		let model = a.exclusive(|a| {
+			let _src = b.new_static_source().unwrap();
+			b.exclusive(|_| a.distance_model().unwrap()).unwrap()
+		}).unwrap();
+		tx.send(model).unwrap();
+	});
+	rx.recv_timeout(Duration::from_secs(10)).expect("nested calls on two contexts deadlocked");
+}
+
+
+#[test]
+fn context_set_nests() {
+	let (tx, rx) = mpsc::channel();
+	thread::spawn(move || {
+		let alto = Alto::load_mock().unwrap();
+		let dev = alto.open(None).unwrap();
+		let mut set = ContextSet::new();
+		set.insert("world", dev.new_context(None).unwrap());
+		set.insert("ui", dev.new_context(None).unwrap());
+
+		let ok = set.with("world", |_| set.with("ui", |ui| ui.new_static_source().is_ok()).unwrap()).unwrap();
+		tx.send(ok).unwrap();
+	});
+	assert!(rx.recv_timeout(Duration::from_secs(10)).expect("nested calls on a context set deadlocked"));
+}